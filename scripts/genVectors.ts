@@ -0,0 +1,177 @@
+import { mkdir, writeFile } from 'node:fs/promises';
+import { join } from 'node:path';
+import { pathToFileURL } from 'node:url';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { KeyManager } from '../src/crypto/keyManager';
+import { Poseidon2, Poseidon2Domain } from '../src/crypto/poseidon2';
+import { getZeroHash, TREE_DEPTH_DEFAULT } from '../src/merkle/zeroHashes';
+import type { FreezePolicy, Hex } from '../src/types';
+
+/**
+ * Cross-implementation test vectors generated from the TypeScript implementation.
+ * All field elements are serialized as decimal strings, hashes as 32-byte hex.
+ * The hashing they rely on is pinned against `scripts/refVectors.py`, an independent reference, in tests/vectors.test.ts.
+ *
+ * Usage: `tsx scripts/genVectors.ts [outDir]` (defaults to `./vectors`).
+ */
+
+export type CommitmentVector = {
+  asset_id: string;
+  asset_amount: string;
+  user_pk: [string, string];
+  blinding_factor: string;
+  is_frozen: boolean;
+  commitment: Hex;
+};
+
+export type NullifierVector = {
+  secret_key: string;
+  commitment: Hex;
+  freezer_pk: [string, string] | null;
+  nullifier: Hex;
+};
+
+export type PolicyNullifierVector = {
+  secret_key: string;
+  commitment: Hex;
+  policy: { kind: 'none' } | { kind: 'single'; freezer_pk: [string, string] } | { kind: 'set'; freezers: Array<[string, string]>; freezer_pk: [string, string] };
+  freezer_pk: [string, string];
+  nullifier: Hex;
+};
+
+export type MerkleVector = {
+  depth: number;
+  leaves: Hex[];
+  root: Hex;
+  proofs: Array<{ leaf_index: number; path: Hex[] }>;
+};
+
+const SEEDS = ['ocash-vectors-seed-0000', 'ocash-vectors-seed-0001', 'ocash-vectors-seed-0002'];
+const AMOUNTS = [0n, 1n, 10n ** 18n, (1n << 128n) - 1n];
+
+const toHex32 = (value: bigint): Hex => `0x${value.toString(16).padStart(64, '0')}`;
+
+export function generateCommitmentVectors(): CommitmentVector[] {
+  const out: CommitmentVector[] = [];
+  SEEDS.forEach((seed, i) => {
+    const [x, y] = KeyManager.getPublicKeyBySeed(seed).user_pk.user_address;
+    AMOUNTS.forEach((amount, j) => {
+      const record = CryptoToolkit.createRecordOpening({
+        asset_id: BigInt(i + 1),
+        asset_amount: amount,
+        user_pk: { user_address: [x, y] },
+        blinding_factor: BigInt(1000 * i + j + 1),
+        is_frozen: j % 2 === 1,
+      });
+      out.push({
+        asset_id: record.asset_id.toString(),
+        asset_amount: record.asset_amount.toString(),
+        user_pk: [x.toString(), y.toString()],
+        blinding_factor: record.blinding_factor.toString(),
+        is_frozen: record.is_frozen,
+        commitment: CryptoToolkit.commitment(record, 'hex'),
+      });
+    });
+  });
+  return out;
+}
+
+export function generateNullifierVectors(): NullifierVector[] {
+  const freezer = KeyManager.getPublicKeyBySeed('ocash-vectors-freezer').user_pk.user_address;
+  const commitments = generateCommitmentVectors().slice(0, 4).map((v) => v.commitment);
+  const out: NullifierVector[] = [];
+  for (const seed of SEEDS) {
+    const secretKey = BigInt(KeyManager.getSecretKeyBySeed(seed).user_sk.address_sk);
+    for (const commitment of commitments) {
+      out.push({ secret_key: secretKey.toString(), commitment, freezer_pk: null, nullifier: CryptoToolkit.nullifier(secretKey, commitment) });
+      out.push({
+        secret_key: secretKey.toString(),
+        commitment,
+        freezer_pk: [freezer[0].toString(), freezer[1].toString()],
+        nullifier: CryptoToolkit.nullifier(secretKey, commitment, freezer),
+      });
+    }
+  }
+  return out;
+}
+
+export function generatePolicyNullifierVectors(): PolicyNullifierVector[] {
+  const freezers = ['ocash-vectors-freezer', 'ocash-vectors-freezer-1'].map((seed) => KeyManager.getPublicKeyBySeed(seed).user_pk.user_address);
+  const pair = (p: [bigint, bigint]): [string, string] => [p[0].toString(), p[1].toString()];
+  const policies: Array<{ policy: FreezePolicy; json: PolicyNullifierVector['policy'] }> = [
+    { policy: { kind: 'none' }, json: { kind: 'none' } },
+    { policy: { kind: 'single', freezerPk: freezers[0]! }, json: { kind: 'single', freezer_pk: pair(freezers[0]!) } },
+    { policy: { kind: 'set', freezers, freezerPk: freezers[1]! }, json: { kind: 'set', freezers: freezers.map(pair), freezer_pk: pair(freezers[1]!) } },
+  ];
+  const commitment = generateCommitmentVectors()[0]!.commitment;
+  const out: PolicyNullifierVector[] = [];
+  for (const seed of SEEDS) {
+    const secretKey = BigInt(KeyManager.getSecretKeyBySeed(seed).user_sk.address_sk);
+    for (const { policy, json } of policies) {
+      out.push({
+        secret_key: secretKey.toString(),
+        commitment,
+        policy: json,
+        freezer_pk: pair(CryptoToolkit.resolveFreezerPk(policy)),
+        nullifier: CryptoToolkit.nullifierWithPolicy(secretKey, commitment, policy),
+      });
+    }
+  }
+  return out;
+}
+
+export function generateMerkleVectors(depth = TREE_DEPTH_DEFAULT): MerkleVector[] {
+  return [1, 2, 5].map((count) =>
+    merkleVector(
+      generateCommitmentVectors()
+        .slice(0, count)
+        .map((v) => v.commitment),
+      depth,
+    ),
+  );
+}
+
+/**
+ * Build the root and every leaf's proof path for `leaves` packed from index 0 of a `depth`-level tree.
+ */
+export function merkleVector(leaves: Hex[], depth = TREE_DEPTH_DEFAULT): MerkleVector {
+  const levels: bigint[][] = [leaves.map((l) => BigInt(l))];
+  for (let level = 0; level < depth; level++) {
+    const current = levels[level]!;
+    const next: bigint[] = [];
+    for (let i = 0; i < current.length; i += 2) {
+      const right = i + 1 < current.length ? current[i + 1]! : BigInt(getZeroHash(level));
+      next.push(Poseidon2.hashDomain(current[i]!, right, Poseidon2Domain.Merkle));
+    }
+    levels.push(next);
+  }
+  const proofs = leaves.map((leaf, leafIndex) => {
+    const path: Hex[] = [leaf];
+    for (let level = 0; level < depth; level++) {
+      const sibling = levels[level]![(leafIndex >> level) ^ 1];
+      path.push(sibling != null ? toHex32(sibling) : getZeroHash(level));
+    }
+    return { leaf_index: leafIndex, path };
+  });
+  return { depth, leaves, root: toHex32(levels[depth]![0]!), proofs };
+}
+
+export async function writeVectors(outDir: string) {
+  await mkdir(outDir, { recursive: true });
+  const files: Record<string, unknown> = {
+    'commitment.json': generateCommitmentVectors(),
+    'nullifier.json': generateNullifierVectors(),
+    'nullifier_policy.json': generatePolicyNullifierVectors(),
+    'merkle.json': generateMerkleVectors(),
+  };
+  for (const [name, data] of Object.entries(files)) {
+    await writeFile(join(outDir, name), `${JSON.stringify(data, null, 2)}\n`, 'utf8');
+  }
+  return Object.keys(files);
+}
+
+if (process.argv[1] && import.meta.url === pathToFileURL(process.argv[1]).href) {
+  const outDir = process.argv[2] ?? 'vectors';
+  const written = await writeVectors(outDir);
+  console.log(`wrote ${written.join(', ')} to ${outDir}`);
+}
@@ -0,0 +1,178 @@
+"""
+Independent reference for the known-answer values pinned in tests/vectors.test.ts.
+
+Written from the Poseidon2 paper (t = 3, x^5 S-box, circ(2, 1, 1) external and diag(1, 1, 2) internal matrices)
+and the textbook twisted Edwards addition law, sharing no code with the TypeScript SDK. Only the published round
+constants are read from src/crypto/poseidon2.ts.
+
+Usage: `python3 scripts/refVectors.py`
+"""
+
+import hashlib
+import hmac
+import os
+import re
+
+P = 0x30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000001
+
+DOMAIN = {
+    'record': 0x5245434F52440000,
+    'nullifier': 0x4E554C4C49464945,
+    'merkle': 0x4D45524B4C450000,
+    'keyder': 0x4B45594445520000,
+    'blinding': 0x424C494E44494E47,
+    'signature': 0x5349474E41545552,
+}
+
+_SOURCE = open(os.path.join(os.path.dirname(__file__), '..', 'src', 'crypto', 'poseidon2.ts')).read()
+
+
+def _constants(name):
+    start = _SOURCE.index(name)
+    end = _SOURCE.index('];', start)
+    return [int(v, 16) for v in re.findall(r'(0x[0-9a-fA-F]+)n', _SOURCE[start:end])]
+
+
+_FULL_START = _constants('FULL_ROUND_KEYS_START')
+_PARTIAL = _constants('PARTIAL_ROUND_KEYS:')
+_FULL_END = _constants('FULL_ROUND_KEYS_END')
+
+
+def _external(s):
+    t = sum(s) % P
+    return [(v + t) % P for v in s]
+
+
+def _internal(s):
+    t = sum(s) % P
+    return [(s[0] + t) % P, (s[1] + t) % P, (2 * s[2] + t) % P]
+
+
+def _full_rounds(s, keys):
+    for r in range(0, len(keys), 3):
+        s = _external([pow((s[i] + keys[r + i]) % P, 5, P) for i in range(3)])
+    return s
+
+
+def permute(s):
+    s = _external([v % P for v in s])
+    s = _full_rounds(s, _FULL_START)
+    for k in _PARTIAL:
+        s = _internal([pow((s[0] + k) % P, 5, P), s[1], s[2]])
+    return _full_rounds(s, _FULL_END)
+
+
+def hash_domain(a, b, domain):
+    return permute([a, b, domain])[0]
+
+
+def fold(inputs, domain):
+    acc = hash_domain(inputs[0], inputs[1], domain)
+    for v in inputs[2:]:
+        acc = hash_domain(acc, v, domain)
+    return acc
+
+
+def sponge(inputs, domain):
+    s = [0, 0, domain]
+    block = list(inputs) + [0] * (len(inputs) % 2)
+    for i in range(0, len(block), 2):
+        s = permute([(s[0] + block[i]) % P, (s[1] + block[i + 1]) % P, s[2]])
+    return s[0]
+
+
+# BabyJubjub in reduced twisted Edwards form: -x^2 + y^2 = 1 + d x^2 y^2.
+D = 12181644023421730124874158521699555681764249180949974110617291017600649128846
+BASE = (
+    9671717474070082183213120605117400219616337014328744928644933853176787189663,
+    16950150798460657717958625567821834550301663161624707787222815936182638968203,
+)
+
+
+def ed_add(p1, p2):
+    (x1, y1), (x2, y2) = p1, p2
+    k = D * x1 * x2 * y1 * y2 % P
+    x3 = (x1 * y2 + y1 * x2) * pow(1 + k, -1, P) % P
+    y3 = (y1 * y2 + x1 * x2) * pow(1 - k, -1, P) % P
+    return (x3, y3)
+
+
+def ed_mul(point, scalar):
+    acc = (0, 1)
+    while scalar:
+        if scalar & 1:
+            acc = ed_add(acc, point)
+        point = ed_add(point, point)
+        scalar >>= 1
+    return acc
+
+
+ORDER = 2736030358979909402780800718157159386076813972158567259200215660948447373041
+
+
+def eddsa_sign(sk, message):
+    # Nonce: HMAC-SHA512(key = sk as 32 bytes BE, data = "OCash.EdDSA" || each message element as 32 bytes BE) mod ORDER.
+    data = b'OCash.EdDSA' + b''.join(m.to_bytes(32, 'big') for m in message)
+    nonce = int.from_bytes(hmac.new(sk.to_bytes(32, 'big'), data, hashlib.sha512).digest(), 'big') % ORDER
+    a = ed_mul(BASE, sk)
+    r = ed_mul(BASE, nonce)
+    c = fold([r[0], r[1], a[0], a[1]] + list(message), DOMAIN['signature'])
+    return r, (nonce + c * sk) % ORDER
+
+
+def pack_signature(r, s):
+    # Compressed R: y little-endian, top bit set when x > -x as integers; then s little-endian.
+    y = bytearray(r[1].to_bytes(32, 'little'))
+    if r[0] > (P - r[0]) % P:
+        y[31] |= 0x80
+    return bytes(y) + s.to_bytes(32, 'little')
+
+
+def commitment(x, y, blinding, asset_id, amount, frozen):
+    return fold([x, y, blinding, asset_id, amount | (1 << 128) if frozen else amount], DOMAIN['record'])
+
+
+def nullifier(secret_key, commitment_value, freezer=None):
+    key = secret_key
+    if freezer is not None:
+        shared = ed_mul(freezer, secret_key)
+        key = hash_domain(shared[0], shared[1], DOMAIN['keyder'])
+    return hash_domain(key, commitment_value, DOMAIN['nullifier'])
+
+
+def merkle_root(leaves, depth=32):
+    zero = 0
+    level = list(leaves)
+    for _ in range(depth):
+        if len(level) % 2:
+            level.append(zero)
+        level = [hash_domain(level[i], level[i + 1], DOMAIN['merkle']) for i in range(0, len(level), 2)]
+        zero = hash_domain(zero, zero, DOMAIN['merkle'])
+    return level[0]
+
+
+def hex32(v):
+    return '0x%064x' % v
+
+
+if __name__ == '__main__':
+    owner = ed_mul(BASE, 7)
+    freezer = ed_mul(BASE, 11)
+    plain = commitment(owner[0], owner[1], 42, 1, 10**18, False)
+    frozen = commitment(owner[0], owner[1], 42, 1, 10**18, True)
+    print('owner', owner)
+    print('freezer', freezer)
+    print('commitment', hex32(plain))
+    print('commitment_frozen', hex32(frozen))
+    print('nullifier', hex32(nullifier(5, plain)))
+    print('nullifier_freezer', hex32(nullifier(5, plain, freezer)))
+    print('merkle_root_1', hex32(merkle_root([plain])))
+    print('merkle_root_3', hex32(merkle_root([plain, frozen, 3])))
+    print('zero_1', hex32(hash_domain(0, 0, DOMAIN['merkle'])))
+    print('blinding_5_0', hex32(fold([5, 0], DOMAIN['blinding'])))
+    print('blinding_5_3', hex32(fold([5, 3], DOMAIN['blinding'])))
+    for sk, message in ((0x2A, [1, 2, 3]), (0x2A, []), (987654321987654321, [P - 1])):
+        r, s = eddsa_sign(sk, message)
+        print('eddsa', hex(sk), message, hex(r[0]), hex(r[1]), hex(s), '0x' + pack_signature(r, s).hex())
+    for n in (1, 2, 3, 4, 5):
+        print('sponge_record_%d' % n, hex32(sponge(range(1, n + 1), DOMAIN['record'])))
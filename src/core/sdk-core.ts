@@ -82,9 +82,15 @@ export class SdkCore {
 
   /**
    * Emit an SDK event to local listeners and the global onEvent callback.
+   * Returns a promise for the `onEvent` result, so an async handler can be awaited (the sync event queue does).
+   * A rejected handler promise is reported as an `error` event instead of escaping as an unhandled rejection;
+   * a rejection while handling an `error` event is dropped so it cannot loop.
    */
-  emit(event: SdkEvent) {
+  emit(event: SdkEvent): Promise<void> {
     this.eventBus.emit(event);
-    this.config.onEvent?.(event);
+    return Promise.resolve(this.config.onEvent?.(event)).catch((error) => {
+      if (event.type === 'error') return;
+      void this.emit({ type: 'error', payload: { code: 'CONFIG', message: 'onEvent handler failed', detail: { event: event.type }, cause: error } });
+    });
   }
 }
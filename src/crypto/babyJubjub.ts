@@ -184,6 +184,33 @@ export function mulPoint(base: [bigint, bigint], scalar: bigint): [bigint, bigin
   return result;
 }
 
+/**
+ * 从坐标对解析曲线点（十进制或 0x 十六进制字符串、bigint 均可）
+ * 校验两个坐标都在有限域内且点在曲线上，否则抛出错误
+ */
+export function pointFromPair(pair: readonly [string | bigint, string | bigint]): [bigint, bigint] {
+  let point: [bigint, bigint];
+  try {
+    point = [BigInt(pair[0]), BigInt(pair[1])];
+  } catch {
+    throw new Error(`Invalid BabyJubjub point coordinates: ${String(pair[0])}, ${String(pair[1])}`);
+  }
+  if (point.some((c) => c < 0n || c >= BABYJUBJUB_SCALAR_FIELD)) {
+    throw new Error('BabyJubjub point coordinate is outside the scalar field');
+  }
+  if (!isPointOnCurve(point)) {
+    throw new Error('BabyJubjub point is not on curve');
+  }
+  return point;
+}
+
+/**
+ * 将曲线点转换为 32 字节十六进制坐标对
+ */
+export function pointToHexPair(point: readonly [bigint, bigint]): [`0x${string}`, `0x${string}`] {
+  return [`0x${point[0].toString(16).padStart(64, '0')}`, `0x${point[1].toString(16).padStart(64, '0')}`];
+}
+
 /**
  * 验证密钥对是否有效
  */
@@ -300,9 +327,17 @@ function decompressPoint(compressed: Uint8Array): [bigint, bigint] {
   yBytes[31] &= 0x7f; // 清除MSB
   const y = fixedBytesToBigInt(yBytes, 32);
 
+  // 拒绝非规范编码：y 必须小于模数，x = 0 时符号位必须为 0（否则重新压缩得到不同的字节）
+  if (y >= BABYJUBJUB_SCALAR_FIELD) {
+    throw new Error('Failed to decompress elliptic curve point: y coordinate is not a canonical field element');
+  }
+
   try {
     // 从Y坐标计算X坐标
     const x = recoverXCoordinate(y, isXLexLargest);
+    if (x === 0n && isXLexLargest) {
+      throw new Error('Sign bit set for x = 0');
+    }
 
     const point: [bigint, bigint] = [x, y];
 
@@ -317,6 +352,20 @@ function decompressPoint(compressed: Uint8Array): [bigint, bigint] {
   }
 }
 
+/**
+ * 尝试解压缩椭圆曲线点，任何失败都返回 null 而不是抛出异常
+ * 适用于批量扫描（例如备忘录试解密），外部或损坏的数据不应中断整个批次
+ * @param compressed 压缩的32字节数组
+ * @returns [x, y] 坐标对，失败时为 null
+ */
+function tryDecompressPoint(compressed: Uint8Array): [bigint, bigint] | null {
+  try {
+    return decompressPoint(compressed);
+  } catch {
+    return null;
+  }
+}
+
 /**
  * 从Y坐标恢复X坐标
  * BabyJubjub 曲线方程: ax^2 + y^2 = 1 + dx^2y^2
@@ -521,8 +570,11 @@ export const BabyJubjub = {
   addPoint: babyJubjubPointAdd,
   mulPoint: mulPoint,
   isOnCurve: isPointOnCurve,
+  pointFromPair: pointFromPair,
+  pointToHexPair: pointToHexPair,
   isInPrimeSubgroup: isInPrimeSubgroup,
   scalarMult: babyJubjubScalarMult,
   compressPoint: compressPoint,
   decompressPoint: decompressPoint,
+  tryDecompressPoint: tryDecompressPoint,
 };
@@ -1,31 +1,112 @@
-import { toHex } from 'viem';
 import { BabyJubjub, BABYJUBJUB_ORDER } from './babyJubjub';
-import type { CommitmentData, Hex } from '../types';
+import type { CommitmentData, FreezePolicy, Hex, UserPublicKey } from '../types';
 import { Poseidon2, Poseidon2Domain } from './poseidon2';
-import { randomBytes32Bigint } from '../utils/random';
+import { randomBytes32Bigint, type RandomSource } from '../utils/random';
+import { assertCanonicalRecord, toCommitmentData } from './records';
+import { BN254_FIELD_MODULUS, fieldToHex } from './field';
+import { SdkError } from '../errors';
+import { KeyManager } from './keyManager';
+
+type FieldInput = bigint | number | string;
+
+// Frozen records set bit 128 of the committed amount (matches the contract's encoding).
+const FROZEN_BIT = 1n << 128n;
 
 /**
  * Cryptographic helpers for commitments, nullifiers, and record openings.
  */
 export class CryptoToolkit {
-  static commitment(record: CommitmentData, format: 'hex'): Hex;
-  static commitment(record: CommitmentData, format: 'bigint'): bigint;
-  static commitment(record: CommitmentData, format?: 'hex' | 'bigint'): Hex | bigint {
-    let amount = BigInt(record.asset_amount);
-    if (record.is_frozen) {
-      amount |= 1n << 128n;
-    }
-    const elements = [BigInt(record.user_pk.user_address[0]), BigInt(record.user_pk.user_address[1]), BigInt(record.blinding_factor), BigInt(record.asset_id), amount];
-    const h = Poseidon2.hashSequenceWithDomain(elements, Poseidon2Domain.Record);
-    const hex = toHex(h, { size: 32 });
+  /**
+   * Poseidon2 record commitment. Fields are reduced modulo the BN254 prime unless `strict` is set, in which case a
+   * non-canonical record (field out of range, amount at or above 2^128) throws SdkError(CRYPTO).
+   */
+  static commitment(record: CommitmentData, format: 'hex', options?: { strict?: boolean }): Hex;
+  static commitment(record: CommitmentData, format: 'bigint', options?: { strict?: boolean }): bigint;
+  static commitment(record: CommitmentData, format?: 'hex' | 'bigint', options?: { strict?: boolean }): Hex | bigint {
+    if (options?.strict) assertCanonicalRecord(record);
+    const h = Poseidon2.hashSequenceWithDomain(CryptoToolkit.commitmentInputs(record), Poseidon2Domain.Record);
+    const hex = fieldToHex(h);
     return format === 'bigint' ? BigInt(hex) : hex;
   }
 
+  /**
+   * Hex commitment from loosely typed parts, so callers holding an owner key or address and string scalars need not
+   * convert them by hand. `owner` is a `UserPublicKey`, its `user_pk`, or an OCash address; scalars are decimal or
+   * 0x-hex. The record is checked as with `{ strict: true }`: malformed or out-of-range input throws SdkError(CRYPTO).
+   */
+  static commitmentFromParts(input: {
+    owner: UserPublicKey | { user_address: [FieldInput, FieldInput] } | Hex;
+    assetId: FieldInput;
+    amount: FieldInput;
+    blindingFactor: FieldInput;
+    isFrozen?: boolean;
+  }): Hex {
+    const { owner } = input;
+    let userPk: { user_address: [FieldInput, FieldInput] };
+    if (typeof owner === 'string') {
+      try {
+        userPk = KeyManager.addressToUserPk(owner);
+      } catch (error) {
+        throw new SdkError('CRYPTO', 'Invalid record opening field', { field: 'owner', value: owner }, error);
+      }
+    } else {
+      userPk = 'user_pk' in owner ? owner.user_pk : owner;
+    }
+    const record = toCommitmentData({
+      asset_id: input.assetId,
+      asset_amount: input.amount,
+      user_pk: userPk,
+      blinding_factor: input.blindingFactor,
+      is_frozen: input.isFrozen ?? false,
+    });
+    return CryptoToolkit.commitment(record, 'hex', { strict: true });
+  }
+
+  /**
+   * Compute a commitment together with the intermediate accumulator after each Poseidon2 fold step,
+   * for comparing against circuit witnesses. The last intermediate equals the commitment.
+   */
+  static commitmentTrace(record: CommitmentData): { commitment: bigint; intermediates: bigint[] } {
+    const intermediates = Poseidon2.hashSequenceTrace(CryptoToolkit.commitmentInputs(record), Poseidon2Domain.Record);
+    return { commitment: intermediates[intermediates.length - 1]!, intermediates };
+  }
+
+  private static commitmentInputs(record: CommitmentData): bigint[] {
+    const amount = record.is_frozen ? CryptoToolkit.setFrozenBit(BigInt(record.asset_amount)) : BigInt(record.asset_amount);
+    return [BigInt(record.user_pk.user_address[0]), BigInt(record.user_pk.user_address[1]), BigInt(record.blinding_factor), BigInt(record.asset_id), amount];
+  }
+
+  /**
+   * Set the frozen flag bit on a committed amount.
+   */
+  static setFrozenBit(amount: bigint): bigint {
+    return amount | FROZEN_BIT;
+  }
+
+  /**
+   * Clear the frozen flag bit from a committed amount.
+   */
+  static clearFrozenBit(amount: bigint): bigint {
+    return amount & ~FROZEN_BIT;
+  }
+
+  /**
+   * Whether a committed amount carries the frozen flag bit.
+   */
+  static hasFrozenBit(amount: bigint): boolean {
+    return (amount & FROZEN_BIT) !== 0n;
+  }
+
   /**
    * Compute nullifier for a commitment using secret key and optional freezer PK.
    * If freezer PK is default (0,1), the secret key is used directly.
+   * `strict` rejects a secret key outside the curve order or a commitment outside the field with SdkError(CRYPTO).
    */
-  static nullifier(secretKey: bigint, commitment: `0x${string}`, freezerPk?: [bigint, bigint]): `0x${string}` {
+  static nullifier(secretKey: bigint, commitment: `0x${string}`, freezerPk?: [bigint, bigint], options?: { strict?: boolean }): `0x${string}` {
+    if (options?.strict) {
+      if (secretKey < 0n || secretKey >= BABYJUBJUB_ORDER) throw new SdkError('CRYPTO', 'Invalid nullifier input', { field: 'secretKey' });
+      if (BigInt(commitment) >= BN254_FIELD_MODULUS) throw new SdkError('CRYPTO', 'Invalid nullifier input', { field: 'commitment', value: commitment });
+    }
     let nullifierKey: bigint;
     const defaultFreezer = !freezerPk || (freezerPk[0] === 0n && freezerPk[1] === 1n);
     if (defaultFreezer) {
@@ -39,12 +120,43 @@ export class CryptoToolkit {
     }
 
     const n = Poseidon2.hashDomain(nullifierKey, BigInt(commitment), Poseidon2Domain.Nullifier);
-    return toHex(n, { size: 32 });
+    return fieldToHex(n);
+  }
+
+  /**
+   * Resolve a freezer policy to the freezer public key used for nullifier derivation.
+   * A set policy names the member that governs the record; it must be one of `freezers`.
+   */
+  static resolveFreezerPk(policy: FreezePolicy): [bigint, bigint] {
+    switch (policy.kind) {
+      case 'none':
+        return [0n, 1n];
+      case 'single':
+        return policy.freezerPk;
+      case 'set': {
+        const [x, y] = policy.freezerPk;
+        if (!policy.freezers.some((member) => member[0] === x && member[1] === y)) {
+          throw new SdkError('CRYPTO', 'Freezer public key is not a member of the policy set', { freezerPk: [x.toString(), y.toString()] });
+        }
+        return policy.freezerPk;
+      }
+    }
+  }
+
+  /**
+   * Compute nullifier for a commitment under a freezer policy.
+   * Equivalent to `nullifier(secretKey, commitment, resolveFreezerPk(policy))`.
+   */
+  static nullifierWithPolicy(secretKey: bigint, commitment: `0x${string}`, policy: FreezePolicy): `0x${string}` {
+    return CryptoToolkit.nullifier(secretKey, commitment, CryptoToolkit.resolveFreezerPk(policy));
   }
 
   /**
    * Create a record opening with normalized fields and a random blinding factor.
    * Ensures non-zero commitment when auto-generating the blinding factor.
+   * `rng` overrides the secure random source (e.g. a seeded source in tests).
+   * Amounts must stay below 2^128 (bit 128 is the frozen flag); larger amounts throw SdkError(CONFIG).
+   * Outputs are never split: the circuit has a fixed output layout.
    */
   static createRecordOpening(input: {
     asset_id: bigint | number | string;
@@ -52,20 +164,16 @@ export class CryptoToolkit {
     user_pk: { user_address: [bigint | number | string, bigint | number | string] };
     blinding_factor?: bigint | number | string;
     is_frozen?: boolean;
+    rng?: RandomSource;
   }): CommitmentData {
     const hasCustomBlinding = input.blinding_factor !== undefined;
     const attempts = hasCustomBlinding ? 1 : 5;
     for (let attempt = 0; attempt < attempts; attempt++) {
-      const blinding = hasCustomBlinding && attempt === 0 ? BigInt(input.blinding_factor!) : randomBytes32Bigint(true);
-      const record: CommitmentData = {
-        asset_id: BigInt(input.asset_id),
-        asset_amount: BigInt(input.asset_amount),
-        user_pk: {
-          user_address: [BigInt(input.user_pk.user_address[0]), BigInt(input.user_pk.user_address[1])],
-        },
-        blinding_factor: blinding,
-        is_frozen: Boolean(input.is_frozen),
-      };
+      const blinding = hasCustomBlinding && attempt === 0 ? BigInt(input.blinding_factor!) : randomBytes32Bigint(true, input.rng);
+      const record = toCommitmentData({ ...input, blinding_factor: blinding, is_frozen: Boolean(input.is_frozen) });
+      if (record.asset_amount >= FROZEN_BIT) {
+        throw new SdkError('CONFIG', 'Output amount exceeds the 128-bit record limit', { amount: record.asset_amount.toString() });
+      }
       if (hasCustomBlinding) return record;
       const commitment = Poseidon2.hashSequenceWithDomain(
         [record.user_pk.user_address[0], record.user_pk.user_address[1], record.blinding_factor, record.asset_id, record.asset_amount],
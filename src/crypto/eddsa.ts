@@ -0,0 +1,111 @@
+import { hmac } from '@noble/hashes/hmac';
+import { sha512 } from '@noble/hashes/sha512';
+import { bytesToHex, concatBytes, hexToBytes, utf8ToBytes } from '@noble/hashes/utils';
+import { SdkError } from '../errors';
+import type { Hex } from '../types';
+import { BabyJubjub, BABYJUBJUB_ORDER, BABYJUBJUB_SCALAR_FIELD } from './babyJubjub';
+import { Poseidon2, Poseidon2Domain } from './poseidon2';
+
+const NONCE_TAG = utf8ToBytes('OCash.EdDSA');
+const SIGNATURE_BYTES = 64;
+
+/**
+ * BabyJubjub EdDSA signature: nonce point `r` and scalar `s` (< curve order).
+ */
+export interface EdDSASignature {
+  r: [bigint, bigint];
+  s: bigint;
+}
+
+const toBytes32BE = (value: bigint) => hexToBytes(value.toString(16).padStart(64, '0'));
+const bytesToBigintBE = (bytes: Uint8Array) => BigInt(`0x${bytesToHex(bytes)}`);
+const bytesToBigintLE = (bytes: Uint8Array) => bytesToBigintBE(Uint8Array.from(bytes).reverse());
+
+const requireMessage = (message: readonly bigint[]) => {
+  for (const m of message) {
+    if (m < 0n || m >= BABYJUBJUB_SCALAR_FIELD) throw new SdkError('CRYPTO', 'EdDSA message element is outside the scalar field', { value: m.toString() });
+  }
+};
+
+/**
+ * Challenge `c = Poseidon2([R.x, R.y, A.x, A.y, ...message], Signature)`, under its own domain so it cannot collide with key derivation.
+ */
+const challenge = (r: readonly [bigint, bigint], publicKey: readonly [bigint, bigint], message: readonly bigint[]) =>
+  Poseidon2.hashSequenceWithDomain([r[0], r[1], publicKey[0], publicKey[1], ...message], Poseidon2Domain.Signature);
+
+/**
+ * Deterministic nonce (RFC 6979 style): HMAC-SHA512 keyed by the secret key over the tagged message, reduced mod the curve order.
+ */
+const deriveNonce = (secretKey: bigint, message: readonly bigint[]) =>
+  bytesToBigintBE(hmac(sha512, toBytes32BE(secretKey), concatBytes(NONCE_TAG, ...message.map(toBytes32BE)))) % BABYJUBJUB_ORDER;
+
+/**
+ * Sign field elements with a BabyJubjub secret key (`address_sk`); same key and message always give the same signature.
+ */
+const sign = (secretKey: bigint, message: readonly bigint[]): EdDSASignature => {
+  const sk = ((secretKey % BABYJUBJUB_ORDER) + BABYJUBJUB_ORDER) % BABYJUBJUB_ORDER;
+  if (sk === 0n) throw new SdkError('CRYPTO', 'EdDSA secret key must be non-zero');
+  requireMessage(message);
+  const publicKey = BabyJubjub.scalarMult(sk);
+  const nonce = deriveNonce(sk, message);
+  const r = BabyJubjub.scalarMult(nonce);
+  const s = (nonce + challenge(r, publicKey, message) * sk) % BABYJUBJUB_ORDER;
+  return { r, s };
+};
+
+/**
+ * Check `s·G == R + c·A`. Malformed input (identity or low-order key, off-curve R, s out of range) returns false.
+ */
+const verify = (publicKey: readonly [bigint, bigint], message: readonly bigint[], signature: EdDSASignature): boolean => {
+  try {
+    const a: [bigint, bigint] = [publicKey[0], publicKey[1]];
+    const r: [bigint, bigint] = [signature.r[0], signature.r[1]];
+    if (a[0] === 0n && a[1] === 1n) return false;
+    if (!BabyJubjub.isInPrimeSubgroup(a) || !BabyJubjub.isOnCurve(r)) return false;
+    if (signature.s < 0n || signature.s >= BABYJUBJUB_ORDER) return false;
+    requireMessage(message);
+    const left = BabyJubjub.scalarMult(signature.s);
+    const right = BabyJubjub.addPoint(r, BabyJubjub.mulPoint(a, challenge(r, a, message)));
+    return left[0] === right[0] && left[1] === right[1];
+  } catch {
+    return false;
+  }
+};
+
+/**
+ * Serialize to 64 bytes: compressed `R` followed by `s` little-endian.
+ */
+const packSignature = (signature: EdDSASignature): Hex => {
+  if (signature.s < 0n || signature.s >= BABYJUBJUB_ORDER) throw new SdkError('CRYPTO', 'EdDSA signature scalar out of range');
+  const s = toBytes32BE(signature.s).reverse();
+  return `0x${bytesToHex(concatBytes(BabyJubjub.compressPoint(signature.r), s))}`;
+};
+
+/**
+ * Parse a 64-byte packed signature; throws on a malformed `R` or an out-of-range `s`.
+ */
+const unpackSignature = (packed: Hex | string): EdDSASignature => {
+  const bytes = hexToBytes(packed.startsWith('0x') ? packed.slice(2) : packed);
+  if (bytes.length !== SIGNATURE_BYTES) throw new SdkError('CRYPTO', `Invalid EdDSA signature length: expected ${SIGNATURE_BYTES} bytes, got ${bytes.length}`);
+  let r: [bigint, bigint];
+  try {
+    r = BabyJubjub.decompressPoint(bytes.slice(0, 32));
+  } catch (error) {
+    throw new SdkError('CRYPTO', 'Invalid EdDSA signature point', undefined, error);
+  }
+  const s = bytesToBigintLE(bytes.slice(32));
+  if (s >= BABYJUBJUB_ORDER) throw new SdkError('CRYPTO', 'EdDSA signature scalar out of range');
+  return { r, s };
+};
+
+/**
+ * EdDSA over BabyJubjub with a Poseidon2 challenge, for signing field-element messages with wallet keys.
+ * This is the SDK's own scheme, not circomlib's EdDSA-Poseidon: the challenge, nonce derivation and encoding differ,
+ * so signatures do not verify with circomlib (or vice versa).
+ */
+export const EdDSA = {
+  sign,
+  verify,
+  packSignature,
+  unpackSignature,
+};
@@ -1,3 +1,10 @@
 // BN254 / alt_bn128 prime field modulus.
 // Used for Poseidon hash and BabyJubjub curve arithmetic.
 export const BN254_FIELD_MODULUS = 21888242871839275222246405745257275088548364400416034343698204186575808495617n;
+
+// Left-padded 32-byte big-endian hex encoding of a field element (no viem dependency).
+// Values outside [0, 2^256) would not fit 64 hex digits and are rejected instead of widening the output.
+export const fieldToHex = (value: bigint): `0x${string}` => {
+  if (value < 0n || value >> 256n !== 0n) throw new RangeError(`fieldToHex: value does not fit in 32 bytes: ${value}`);
+  return `0x${value.toString(16).padStart(64, '0')}`;
+};
@@ -1,27 +1,35 @@
 import { hkdf } from '@noble/hashes/hkdf';
+import { hmac } from '@noble/hashes/hmac';
 import { sha256 } from '@noble/hashes/sha256';
-import { bytesToHex, hexToBytes, utf8ToBytes } from '@noble/hashes/utils';
+import { bytesToHex, hexToBytes, randomBytes, utf8ToBytes } from '@noble/hashes/utils';
 import { BabyJubjub, createKeyPairFromSeed, validateKeyPair } from './babyJubjub';
+import { Poseidon2, Poseidon2Domain } from './poseidon2';
 import type { Hex, UserKeyPair, UserPublicKey, UserSecretKey } from '../types';
-import { toHex } from 'viem';
 
 const HKDF_INFO = 'OCash.KeyGen';
 
 /**
- * Derive a 32-byte seed from a human string using HKDF-SHA256.
+ * HKDF-SHA256 expand output (32 bytes) for a human seed; `info` is `OCash.KeyGen` or `OCash.KeyGen:<nonce>`.
  */
-const deriveSeed = (seed: string, nonce?: string): `0x${string}` => {
+const deriveOkm = (seed: string, nonce?: string): Uint8Array => {
   if (seed.length < 16) throw new Error('Seed must be at least 16 characters. Any passphrase, hex string, or random bytes will work — it is run through HKDF-SHA256 internally.');
   const ikm = utf8ToBytes(seed);
   const info = utf8ToBytes(nonce ? `${HKDF_INFO}:${nonce}` : HKDF_INFO);
-  const okm = hkdf(sha256, ikm, undefined, info, 32);
-  return `0x${bytesToHex(okm)}`;
+  return hkdf(sha256, ikm, undefined, info, 32);
 };
 
+/**
+ * Derive a 32-byte seed from a human string using HKDF-SHA256.
+ */
+const deriveSeed = (seed: string, nonce?: string): `0x${string}` => `0x${bytesToHex(deriveOkm(seed, nonce))}`;
+
+let derivationCount = 0;
+
 /**
  * Derive a BabyJubjub keypair from a seed and optional nonce.
  */
 const seedToKeyPair = (seed: string, nonce?: string): UserKeyPair => {
+  derivationCount++;
   const derivedSeed = deriveSeed(seed, nonce);
   const keyPair = createKeyPairFromSeed(derivedSeed);
   if (!validateKeyPair(keyPair)) {
@@ -30,6 +38,51 @@ const seedToKeyPair = (seed: string, nonce?: string): UserKeyPair => {
   return keyPair;
 };
 
+type CachedKeyPair = { user_address: [bigint, bigint]; address_sk: bigint };
+
+// Opt-in LRU cache. Entries hold the secret key until evicted or cleared; bigints cannot be wiped, so only
+// disabling the cache keeps derived secrets out of it.
+const keyPairCache = new Map<string, CachedKeyPair>();
+let keyPairCacheLimit = 0;
+// Random per-process HMAC key, so cache keys cannot be matched against a hash of a guessed seed.
+let keyPairCacheSalt: Uint8Array | undefined;
+
+const keyPairCacheKey = (seed: string, nonce?: string) => {
+  if (!keyPairCacheSalt) keyPairCacheSalt = randomBytes(32);
+  return bytesToHex(hmac(sha256, keyPairCacheSalt, utf8ToBytes(JSON.stringify([seed, nonce ?? null]))));
+};
+
+const evictKeyPairCache = (limit: number) => {
+  for (const key of keyPairCache.keys()) {
+    if (keyPairCache.size <= limit) break;
+    keyPairCache.delete(key);
+  }
+};
+
+/**
+ * Derive a keypair, serving repeated seed/nonce pairs from the cache when enabled.
+ */
+const cachedSeedToKeyPair = (seed: string, nonce?: string): UserKeyPair => {
+  if (keyPairCacheLimit <= 0) return seedToKeyPair(seed, nonce);
+  const key = keyPairCacheKey(seed, nonce);
+  const hit = keyPairCache.get(key);
+  if (hit) {
+    keyPairCache.delete(key);
+    keyPairCache.set(key, hit);
+    return {
+      user_pk: { user_address: [hit.user_address[0], hit.user_address[1]] },
+      user_sk: { address_sk: hit.address_sk },
+    };
+  }
+  const keyPair = seedToKeyPair(seed, nonce);
+  keyPairCache.set(key, {
+    user_address: [keyPair.user_pk.user_address[0], keyPair.user_pk.user_address[1]],
+    address_sk: keyPair.user_sk.address_sk,
+  });
+  evictKeyPairCache(keyPairCacheLimit);
+  return keyPair;
+};
+
 /**
  * Key derivation and address conversion utilities.
  */
@@ -38,14 +91,14 @@ export class KeyManager {
    * Derive a full keypair from seed and optional nonce.
    */
   static deriveKeyPair(seed: string, nonce?: string): UserKeyPair {
-    return seedToKeyPair(seed, nonce);
+    return cachedSeedToKeyPair(seed, nonce);
   }
 
   /**
    * Derive public key only from seed (no secret exposure).
    */
   static getPublicKeyBySeed(seed: string, nonce?: string): UserPublicKey {
-    const keyPair = seedToKeyPair(seed, nonce);
+    const keyPair = cachedSeedToKeyPair(seed, nonce);
     return { user_pk: keyPair.user_pk };
   }
 
@@ -53,7 +106,67 @@ export class KeyManager {
    * Derive secret key object from seed (includes public key).
    */
   static getSecretKeyBySeed(seed: string, nonce?: string): UserSecretKey {
-    return seedToKeyPair(seed, nonce);
+    return cachedSeedToKeyPair(seed, nonce);
+  }
+
+  /**
+   * Raw HKDF output behind `deriveKeyPair(seed, nonce)`, before it is hashed into the secret key
+   * (`address_sk = sha256(okm) mod order`). For cross-implementation tests; treat it as secret.
+   */
+  static deriveRawOkm(seed: string, nonce?: string): Uint8Array {
+    return deriveOkm(seed, nonce);
+  }
+
+  /**
+   * Next nonce in a deterministic address walk: no nonce → `'1'`; a trailing decimal number is
+   * incremented (`'7'` → `'8'`, `'acct-7'` → `'acct-8'`); any other label gets `'-1'` appended.
+   * The number keeps at least its original width, so zero-padded nonces stay padded (`'acct-007'` → `'acct-008'`).
+   */
+  static nextAccountNonce(current?: string): string {
+    if (!current) return '1';
+    const match = /^(.*?)(\d+)$/.exec(current);
+    if (!match) return `${current}-1`;
+    const digits = match[2]!;
+    return `${match[1]}${(BigInt(digits) + 1n).toString().padStart(digits.length, '0')}`;
+  }
+
+  /**
+   * Advance from `currentNonce` and derive the keypair for the new nonce.
+   */
+  static deriveNext(seed: string, currentNonce?: string): { nonce: string; keyPair: UserKeyPair } {
+    const nonce = KeyManager.nextAccountNonce(currentNonce);
+    return { nonce, keyPair: cachedSeedToKeyPair(seed, nonce) };
+  }
+
+  /**
+   * Deterministic record blinding: `Poseidon2([secretKey, ...context], Blinding)`.
+   * The same key and context always give the same blinding, so notes can be rebuilt from the key alone.
+   */
+  static deriveBlindingFactor(secretKey: bigint, context: readonly (bigint | number)[]): bigint {
+    return Poseidon2.hashSequenceWithDomain([secretKey, ...context.map((c) => BigInt(c))], Poseidon2Domain.Blinding);
+  }
+
+  /**
+   * Enable (maxEntries > 0) or disable (0) the derived keypair cache.
+   * Cached entries hold secret keys in memory until evicted; leave the cache disabled where that matters.
+   */
+  static configureKeyPairCache(options: { maxEntries: number }) {
+    keyPairCacheLimit = Number.isFinite(options.maxEntries) ? Math.max(0, Math.floor(options.maxEntries)) : 0;
+    evictKeyPairCache(keyPairCacheLimit);
+  }
+
+  /**
+   * Drop all cached keypairs (cache stays enabled).
+   */
+  static clearKeyPairCache() {
+    evictKeyPairCache(0);
+  }
+
+  /**
+   * Cache size and number of full derivations performed so far.
+   */
+  static getKeyPairCacheStats() {
+    return { size: keyPairCache.size, derivations: derivationCount };
   }
 
   /**
@@ -66,7 +179,7 @@ export class KeyManager {
       throw new Error('Invalid elliptic curve point');
     }
     const compressed = BabyJubjub.compressPoint([x, y]);
-    return toHex(compressed);
+    return `0x${bytesToHex(compressed)}`;
   }
 
   /**
@@ -4,7 +4,7 @@
  * domain-separated hashing.
  */
 
-import { BN254_FIELD_MODULUS } from './field';
+import { BN254_FIELD_MODULUS, fieldToHex } from './field';
 
 export const Poseidon2Domain = {
   None: 0x0000000000000000n,
@@ -16,9 +16,21 @@ export const Poseidon2Domain = {
   Memo: 0x4d454d4f00000000n, // "MEMO"
   Asset: 0x4153534554000000n, // "ASSET"
   KeyDerivation: 0x4b45594445520000n, // "KEYDER"
+  Blinding: 0x424c494e44494e47n, // "BLINDING"
+  Signature: 0x5349474e41545552n, // "SIGNATUR"
 } as const;
 
 type DomainValue = (typeof Poseidon2Domain)[keyof typeof Poseidon2Domain];
+export type Poseidon2DomainName = keyof typeof Poseidon2Domain;
+
+/**
+ * Resolve a domain by name (case-insensitive, e.g. `record` or `Record`). Throws on unknown names.
+ */
+export const poseidon2DomainFromName = (name: string): DomainValue => {
+  const key = (Object.keys(Poseidon2Domain) as Poseidon2DomainName[]).find((k) => k.toLowerCase() === name.toLowerCase());
+  if (!key) throw new Error(`Unknown Poseidon2 domain: ${name}`);
+  return Poseidon2Domain[key];
+};
 type HashInput = bigint | number | string;
 
 /**
@@ -153,6 +165,31 @@ export class Poseidon2 {
     return state0;
   }
 
+  /**
+   * Hash many pairs under one domain, e.g. a level of tree nodes; `result[i]` is `hashDomain(...pairs[i], domain)`.
+   * The domain is normalized once for the whole batch.
+   */
+  public static hashMany(pairs: readonly (readonly [HashInput, HashInput])[], domain: HashInput | DomainValue = Poseidon2Domain.None): bigint[] {
+    const capacity = this.normalize(domain);
+    return pairs.map(([a, b]) => this.permutation(this.normalize(a), this.normalize(b), capacity)[0]);
+  }
+
+  /**
+   * Three-input compression: the first state element after permuting `(a, b, c)`.
+   * Same value as `hashDomain(a, b, c)`, with `c` read as a plain input rather than a domain.
+   */
+  public static hash3(a: HashInput, b: HashInput, c: HashInput): bigint {
+    return this.permute(a, b, c)[0];
+  }
+
+  /**
+   * Apply the raw permutation to `(a, b, c)` and return the full output state, for integrations that
+   * need more than the first element. Inputs are reduced into the field first.
+   */
+  public static permute(a: HashInput, b: HashInput, c: HashInput): [bigint, bigint, bigint] {
+    return this.permutation(this.normalize(a), this.normalize(b), this.normalize(c));
+  }
+
   /**
    * Convenience helper that accepts generic inputs (numbers / strings / bigint)
    * and allows passing an optional domain (defaults to Poseidon2Domain.None).
@@ -168,15 +205,24 @@ export class Poseidon2 {
    * constructions such as array hashes that start from zero.
    */
   public static hashSequenceWithDomain(inputs: HashInput[], domain: HashInput | DomainValue, seed?: HashInput): bigint {
+    const trace = this.hashSequenceTrace(inputs, domain, seed);
+    return trace[trace.length - 1]!;
+  }
+
+  /**
+   * Same folding as `hashSequenceWithDomain`, returning the accumulator after every fold step
+   * (the last element is the final hash). Useful for comparing against circuit witnesses.
+   */
+  public static hashSequenceTrace(inputs: HashInput[], domain: HashInput | DomainValue, seed?: HashInput): bigint[] {
     if (inputs.length === 0) {
       if (seed === undefined) {
         throw new Error('Poseidon2.hashSequenceWithDomain requires at least one input or a seed.');
       }
-      return this.normalize(seed);
+      return [this.normalize(seed)];
     }
 
     if (inputs.length === 1 && seed === undefined) {
-      return this.hashDomain(0n, inputs[0], domain);
+      return [this.hashDomain(0n, inputs[0], domain)];
     }
 
     let acc: bigint;
@@ -189,19 +235,59 @@ export class Poseidon2 {
       startIndex = 2;
     }
 
+    const trace = [acc];
     for (let i = startIndex; i < inputs.length; i++) {
       acc = this.hashDomain(acc, inputs[i], domain);
+      trace.push(acc);
     }
 
-    return acc;
+    return trace;
+  }
+
+  /**
+   * Canonical hash of any number of field elements under a domain; prefer this when the arity varies.
+   * One input hashes as `hashDomain(0, x)`, two as `hashDomain(a, b)`, and longer inputs fold left:
+   * `hashDomain(hashDomain(a, b), c)`, ... (the unseeded `hashSequenceWithDomain`). Empty input throws.
+   * The domain is required so callers cannot silently hash under `Poseidon2Domain.None`; pass it explicitly when that is intended.
+   */
+  public static hashFields(inputs: HashInput[], domain: HashInput | DomainValue): bigint {
+    return this.hashSequenceWithDomain(inputs, domain);
+  }
+
+  /**
+   * Rate-2 Poseidon2 sponge over any number of inputs, as the circuits hash more than two elements.
+   * The state starts as `(0, 0, domain)` (the same capacity layout as `hashDomain`), inputs are added into the rate two
+   * per permutation with an odd last block padded by `0`, and the first state element is squeezed out.
+   * Two inputs therefore hash exactly like `hashDomain(a, b, domain)`. The input count is not absorbed, so `[x]` and
+   * `[x, 0]` collide: use a fixed arity per domain. Empty input throws.
+   * Not interchangeable with the folding helpers (`hashFields`, `hashSequenceWithDomain`), which stay as the wallet's hash.
+   */
+  public static sponge(inputs: HashInput[], domain: HashInput | DomainValue = Poseidon2Domain.None): bigint {
+    if (inputs.length === 0) {
+      throw new Error('Poseidon2.sponge requires at least one input.');
+    }
+    const block = inputs.map((input) => this.normalize(input));
+    if (block.length % 2 === 1) block.push(0n);
+
+    let state: [bigint, bigint, bigint] = [0n, 0n, this.normalize(domain)];
+    for (let i = 0; i < block.length; i += 2) {
+      state = this.permutation(this.addMod(state[0], block[i]!), this.addMod(state[1], block[i + 1]!), state[2]);
+    }
+    return state[0];
+  }
+
+  /**
+   * Same folding as `hashSequenceWithDomain`, with the domain given by name.
+   */
+  public static hashSequenceNamed(inputs: HashInput[], domainName: string, seed?: HashInput): bigint {
+    return this.hashSequenceWithDomain(inputs, poseidon2DomainFromName(domainName), seed);
   }
 
   /**
    * Hash two inputs and return a 0x-prefixed hex string.
    */
   public static hashToHex(a: HashInput, b: HashInput, domain: HashInput | DomainValue = Poseidon2Domain.None): `0x${string}` {
-    const result = this.hashInputs(a, b, domain);
-    return `0x${result.toString(16).padStart(64, '0')}`;
+    return fieldToHex(this.hashInputs(a, b, domain));
   }
 
   /**
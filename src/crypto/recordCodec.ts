@@ -1,8 +1,12 @@
 import { Hash, encodeAbiParameters, decodeAbiParameters, parseAbiParameters, toHex, toBytes } from 'viem';
 import { CommitmentData } from '../types';
 import { BabyJubjub } from './babyJubjub';
+import { SdkError } from '../errors';
+import { assertCanonicalRecord } from './records';
 
 const ABI_PARAMETERS = parseAbiParameters('uint256, uint256, uint256, uint256, bool');
+// 5 个 32 字节的 ABI 槽位
+const RECORD_BYTES = 160;
 
 /**
  * Encode/decode record openings to ABI-compatible hex payloads.
@@ -10,8 +14,10 @@ const ABI_PARAMETERS = parseAbiParameters('uint256, uint256, uint256, uint256, b
 export class RecordCodec {
   /**
    * Encode a record opening into ABI-packed bytes.
+   * `strict` rejects non-canonical records (see `assertCanonicalRecord`) instead of encoding them as given.
    */
-  static encode(ro: CommitmentData): Hash {
+  static encode(ro: CommitmentData, options?: { strict?: boolean }): Hash {
+    if (options?.strict) assertCanonicalRecord(ro);
     const userAddressX = BigInt(ro.user_pk.user_address[0]);
     const userAddressY = BigInt(ro.user_pk.user_address[1]);
 
@@ -27,21 +33,34 @@ export class RecordCodec {
 
   /**
    * Decode an ABI-packed record opening back into CommitmentData.
+   * Rejects anything that is not exactly 160 bytes or does not re-encode to the same bytes
+   * (e.g. undecompressable or non-canonical key slots, bool slots other than 0/1) with SdkError(CRYPTO).
    */
   static decode(hexData: string): CommitmentData {
     if (!hexData) throw new Error('Missing record payload');
-    const normalized = hexData.startsWith('0x') ? (hexData as Hash) : (`0x${hexData}` as Hash);
-    const decoded = decodeAbiParameters(ABI_PARAMETERS, normalized) as readonly [bigint, bigint, bigint, bigint, boolean];
-    // `decoded[2]` 是压缩后的 BabyJubjub 公钥，ABI 解析会去掉前导 0，需要重新补齐 32 字节
-    const compressed = toBytes(decoded[2], { size: 32 });
-    const [userX, userY] = BabyJubjub.decompressPoint(compressed);
-
-    return {
-      asset_id: decoded[0],
-      asset_amount: decoded[1],
-      user_pk: { user_address: [userX, userY] },
-      blinding_factor: decoded[3],
-      is_frozen: decoded[4],
-    };
+    const normalized = (hexData.startsWith('0x') ? hexData : `0x${hexData}`).toLowerCase() as Hash;
+    if (normalized.length !== 2 + RECORD_BYTES * 2) {
+      throw new SdkError('CRYPTO', 'Invalid record payload length', { bytes: (normalized.length - 2) / 2 });
+    }
+    let record: CommitmentData;
+    try {
+      const decoded = decodeAbiParameters(ABI_PARAMETERS, normalized) as readonly [bigint, bigint, bigint, bigint, boolean];
+      // `decoded[2]` 是压缩后的 BabyJubjub 公钥，ABI 解析会去掉前导 0，需要重新补齐 32 字节
+      const compressed = toBytes(decoded[2], { size: 32 });
+      const [userX, userY] = BabyJubjub.decompressPoint(compressed);
+      record = {
+        asset_id: decoded[0],
+        asset_amount: decoded[1],
+        user_pk: { user_address: [userX, userY] },
+        blinding_factor: decoded[3],
+        is_frozen: decoded[4],
+      };
+    } catch (error) {
+      throw new SdkError('CRYPTO', 'Invalid record payload', { reason: error instanceof Error ? error.message : String(error) }, error);
+    }
+    if (RecordCodec.encode(record) !== normalized) {
+      throw new SdkError('CRYPTO', 'Non-canonical record payload');
+    }
+    return record;
   }
 }
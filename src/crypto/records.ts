@@ -7,31 +7,64 @@ export interface AppRecordOpening<T = bigint> {
 }
 
 import type { CommitmentData } from '../types';
+import { SdkError } from '../errors';
+import { BN254_FIELD_MODULUS, fieldToHex } from './field';
 
 export interface CommitmentDataJSON extends AppRecordOpening<string> {}
 
+/**
+ * Parse a decimal/hex string, safe integer, or bigint into a BN254 field element.
+ * Throws SdkError(CRYPTO) naming the offending field on malformed or out-of-range input.
+ */
+const toFieldElement = (value: number | bigint | string, field: string): bigint => {
+  let parsed: bigint | undefined;
+  if (typeof value === 'bigint') parsed = value;
+  else if (typeof value === 'number' && Number.isSafeInteger(value)) parsed = BigInt(value);
+  else if (typeof value === 'string' && /^(0x[0-9a-fA-F]+|[0-9]+)$/.test(value.trim())) parsed = BigInt(value.trim());
+  if (parsed === undefined || parsed < 0n || parsed >= BN254_FIELD_MODULUS) {
+    throw new SdkError('CRYPTO', 'Invalid record opening field', { field, value: String(value) });
+  }
+  return parsed;
+};
+
 /**
  * Normalize a generic record opening into CommitmentData (bigint fields).
+ * Accepts decimal or 0x-hex strings; every numeric field must be a BN254 field element.
  */
 export const toCommitmentData = (ro: AppRecordOpening<number | bigint | string>): CommitmentData => ({
-  asset_id: BigInt(ro.asset_id),
-  asset_amount: BigInt(ro.asset_amount),
+  asset_id: toFieldElement(ro.asset_id, 'asset_id'),
+  asset_amount: toFieldElement(ro.asset_amount, 'asset_amount'),
   user_pk: {
-    user_address: [BigInt(ro.user_pk.user_address[0]), BigInt(ro.user_pk.user_address[1])],
+    user_address: [toFieldElement(ro.user_pk.user_address[0], 'user_pk.user_address[0]'), toFieldElement(ro.user_pk.user_address[1], 'user_pk.user_address[1]')],
   },
-  blinding_factor: BigInt(ro.blinding_factor),
+  blinding_factor: toFieldElement(ro.blinding_factor, 'blinding_factor'),
   is_frozen: Boolean(ro.is_frozen),
 });
 
+/**
+ * Reject a record opening that hashing or encoding would silently reduce: every field must be a BN254 field element
+ * and the amount must stay below 2^128 (bit 128 is the frozen flag). Throws SdkError(CRYPTO) naming the field.
+ */
+export const assertCanonicalRecord = (ro: CommitmentData): void => {
+  toCommitmentData(ro);
+  if (BigInt(ro.asset_amount) >= 1n << 128n) {
+    throw new SdkError('CRYPTO', 'Invalid record opening field', { field: 'asset_amount', value: String(ro.asset_amount) });
+  }
+};
+
 /**
  * Convert CommitmentData back into a JSON-friendly record opening.
+ * `encoding: 'hex'` emits 32-byte 0x-hex fields instead of decimal strings.
  */
-export const toRecordOpeningJson = (ro: CommitmentData): AppRecordOpening<string> => ({
-  asset_id: ro.asset_id.toString(),
-  asset_amount: ro.asset_amount.toString(),
-  user_pk: {
-    user_address: [ro.user_pk.user_address[0].toString(), ro.user_pk.user_address[1].toString()],
-  },
-  blinding_factor: ro.blinding_factor.toString(),
-  is_frozen: ro.is_frozen,
-});
+export const toRecordOpeningJson = (ro: CommitmentData, options?: { encoding?: 'decimal' | 'hex' }): AppRecordOpening<string> => {
+  const encode = options?.encoding === 'hex' ? fieldToHex : (v: bigint) => v.toString();
+  return {
+    asset_id: encode(ro.asset_id),
+    asset_amount: encode(ro.asset_amount),
+    user_pk: {
+      user_address: [encode(ro.user_pk.user_address[0]), encode(ro.user_pk.user_address[1])],
+    },
+    blinding_factor: encode(ro.blinding_factor),
+    is_frozen: ro.is_frozen,
+  };
+};
@@ -0,0 +1,32 @@
+// Crypto-only entrypoint: Poseidon2, BabyJubjub, commitments, nullifiers and merkle proofs.
+// Must not import network, storage, sync or runtime (WASM) modules so it stays usable on
+// constrained hosts such as hardware-wallet bridges.
+export type { CommitmentData, FreezePolicy, Hex, UserKeyPair, UserPublicKey, UserSecretKey } from './types';
+export { BN254_FIELD_MODULUS, fieldToHex } from './crypto/field';
+export { Poseidon2, Poseidon2Domain, poseidon2DomainFromName, type Poseidon2DomainName } from './crypto/poseidon2';
+export { BabyJubjub, BABYJUBJUB_ORDER, BABYJUBJUB_SCALAR_FIELD, BABYJUBJUB_COFACTOR } from './crypto/babyJubjub';
+export { EdDSA, type EdDSASignature } from './crypto/eddsa';
+export { CryptoToolkit } from './crypto/cryptoToolkit';
+export { assertCanonicalRecord, toCommitmentData, toRecordOpeningJson, type CommitmentDataJSON } from './crypto/records';
+export {
+  computeMerkleRoot,
+  computeMerkleRootFromLeaves,
+  verifyMerkleProof,
+  verifyMerkleProofs,
+  verifyMerkleProofTruncated,
+  buildMerkleConsistencyProof,
+  verifyMerkleConsistency,
+  buildMerkleMultiProof,
+  verifyMerkleMultiProof,
+  verifyMerkleProofAtDepth,
+  merkleProofToJson,
+  merkleProofFromJson,
+  encodeMerkleProof,
+  decodeMerkleProof,
+  type MerkleProof,
+  type MerkleProofJson,
+  type MerkleConsistencyProof,
+  type MerkleMultiProof,
+} from './merkle/merkleProof';
+export { getZeroHash } from './merkle/zeroHashes';
+export { randomBytes32Bigint, type RandomSource } from './utils/random';
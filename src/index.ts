@@ -5,8 +5,12 @@ export type {
   SdkEvent,
   ChainConfigInput,
   Hex,
+  ChainId,
+  ContractAddress,
+  AmountFormat,
   TokenMetadata,
   CommitmentData,
+  FreezePolicy,
   ProofResult,
   TransferWitnessInput,
   WithdrawWitnessInput,
@@ -25,19 +29,37 @@ export type {
   ChairmanMerkleNodeRecord,
   ChairmanMerkleVersionRecord,
   SyncChainStatus,
+  SyncChainBacklog,
+  SyncMetricsSnapshot,
+  SyncStreamEvent,
+  StoreStats,
+  AssetBalance,
+  StoreEvent,
   SyncCursor,
+  CursorHistoryEntry,
+  SyncCheckpoint,
+  MerkleProofSource,
+  RemoteMerkleProofResponse,
   PlannerEstimateTransferResult,
   PlannerEstimateWithdrawResult,
+  PlannerFeeConfig,
+  PlannerFeeSchedule,
+  PlannerSelectionStrategy,
+  PlannerOutputOrder,
   UtxoRecord,
   WalletSessionInput,
   OpsApi,
+  RelayerAction,
   RelayerRequest,
+  RelayerSubmissionStatus,
 } from './types';
 // Default runtime asset overrides for mainnet/testnet.
 export { defaultAssetsOverrideMainnet, defaultAssetsOverrideTestnet } from './assets/defaultAssetsOverride';
 // High-level helpers re-exported for advanced usage.
 export { MemoKit } from './memo/memoKit';
+export type { MemoCipherName } from './memo/memoCipher';
 export { CryptoToolkit } from './crypto/cryptoToolkit';
+export { assertCanonicalRecord, toCommitmentData, toRecordOpeningJson } from './crypto/records';
 export { KeyManager } from './crypto/keyManager';
 export { LedgerInfo } from './ledger/ledgerInfo';
 export { normalizeTokenMetadata } from './ledger/tokenNormalize';
@@ -45,10 +67,40 @@ export { assertTokenMetadata, assertTokenList, assertChainConfigInput } from './
 export { fetchPoolTokensFromContract } from './ledger/poolsFromContract';
 export { DummyFactory } from './dummy/dummyFactory';
 export { Utils } from './utils';
+export type { RandomSource } from './utils/random';
 export { BABYJUBJUB_SCALAR_FIELD } from './crypto/babyJubjub';
+export { EdDSA, type EdDSASignature } from './crypto/eddsa';
 export { calcTransferProofBinding, calcWithdrawProofBinding } from './utils/ocashBindings';
+export { canonicalRelayerRequestBytes, relayerRequestDigest } from './tx/canonicalRequest';
+export { RELAYER_ACTION_PATHS, RelayerRequestBuilder } from './tx/relayerRequest';
+export { validateUtxo } from './ops/validateUtxo';
+export { planConsolidation, planSplit } from './planner/planner';
 export { App_ABI } from './abi/app';
 export { MemoryStore } from './store/memoryStore';
+export type { StoreCall } from './store/memoryStore';
+export { getStoreStats } from './store/storeStats';
+export { getStoreBalances } from './store/storeBalances';
+export { EncryptedStore } from './store/encryptedStore';
+export {
+  computeMerkleRoot,
+  computeMerkleRootFromLeaves,
+  verifyMerkleProof,
+  verifyMerkleProofs,
+  verifyMerkleProofTruncated,
+  buildMerkleConsistencyProof,
+  verifyMerkleConsistency,
+  buildMerkleMultiProof,
+  verifyMerkleMultiProof,
+  verifyMerkleProofAtDepth,
+  merkleProofToJson,
+  merkleProofFromJson,
+  encodeMerkleProof,
+  decodeMerkleProof,
+  type MerkleProof,
+  type MerkleProofJson,
+  type MerkleConsistencyProof,
+  type MerkleMultiProof,
+} from './merkle/merkleProof';
 export { ETH_MAINNET, BSC_MAINNET, BASE_MAINNET, SEPOLIA_TESTNET, BSC_TESTNET, ETH_DEV, BSC_DEV, BASE_DEV, SEPOLIA_DEV, BSC_TESTNET_DEV } from './deployments';
 export { KeyValueStore, type KeyValueStoreOptions, type KeyValueClient } from './store/keyValueStore';
 export { RedisStore, type RedisStoreOptions } from './store/redisStore';
@@ -140,16 +192,16 @@ export const createSdk = (config: OCashSdkConfig): OCashSdk => {
     syncAllRelayerConfigs: () => ledger.syncAllRelayerConfigs(),
   };
 
-  // Centralized event emitter for submodules.
+  // Centralized event emitter for submodules; returns the `onEvent` result so the sync event queue can await it.
   const emit = (evt: SdkEvent) => core.emit(evt);
 
   // Module wiring for wallet/sync/merkle/planner/tx/ops.
-  const walletService = new WalletService(assetsApi, store, emit);
+  const walletService = new WalletService(assetsApi, store, emit, { foreignMemoCacheSize: normalizedConfig.wallet?.foreignMemoCacheSize });
   const merkle = new MerkleEngine((chainId) => assetsApi.getChain(chainId), bridge, normalizedConfig.merkle, store);
   const syncEngine = new SyncEngine(assetsApi, store, walletService, emit, merkle, normalizedConfig.sync);
-  const planner = new Planner(assetsApi, walletService, bridge);
+  const planner = new Planner(assetsApi, walletService, bridge, { fees: normalizedConfig.planner?.fees, selection: normalizedConfig.planner?.selection, outputOrder: normalizedConfig.planner?.outputOrder });
   const tx = new TxBuilder();
-  const ops = new Ops(assetsApi, planner, merkle, zkp, tx, walletService, store, emit);
+  const ops = new Ops(assetsApi, planner, merkle, zkp, tx, walletService, store, emit, { proofSource: normalizedConfig.merkle?.proofSource });
 
   // Public SDK surface: expose APIs with stable shapes and minimal coupling.
   return {
@@ -168,7 +220,7 @@ export const createSdk = (config: OCashSdkConfig): OCashSdk => {
       memo: {
         createMemo: (ro) => MemoKit.createMemo(ro),
         memoNonce: (ephemeral, user) => MemoKit.memoNonce(ephemeral, user),
-        decryptMemo: (secret, memo) => MemoKit.decryptMemo(secret, memo),
+        decryptMemo: (secret, memo) => MemoKit.decryptMemo(secret, memo, { maxMemoBytes: normalizedConfig.memoWorker?.maxMemoBytes }),
         decryptBatch: (requests) => memoWorker.decryptBatch(requests),
       },
       dummy: {
@@ -0,0 +1,54 @@
+import nacl from 'tweetnacl';
+import { gcm } from '@noble/ciphers/aes';
+import { SdkError } from '../errors';
+
+/**
+ * Authenticated cipher used to seal memo payloads.
+ * `key` is the 32-byte compressed ECDH shared point; `nonce` is the 24-byte memo nonce
+ * (ciphers with shorter nonces use its prefix).
+ */
+export interface MemoCipher {
+  name: MemoCipherName;
+  /** Wire version byte. Version 0 is the legacy unprefixed format. */
+  version: number;
+  seal(key: Uint8Array, nonce: Uint8Array, plaintext: Uint8Array): Uint8Array;
+  /** Returns null when authentication fails. */
+  open(key: Uint8Array, nonce: Uint8Array, ciphertext: Uint8Array): Uint8Array | null;
+}
+
+export type MemoCipherName = 'xsalsa20-poly1305' | 'aes-256-gcm';
+
+const xsalsa20Poly1305: MemoCipher = {
+  name: 'xsalsa20-poly1305',
+  version: 0,
+  seal: (key, nonce, plaintext) => nacl.secretbox(plaintext, nonce, key),
+  open: (key, nonce, ciphertext) => nacl.secretbox.open(ciphertext, nonce, key),
+};
+
+const AES_GCM_NONCE_BYTES = 12;
+
+const aes256Gcm: MemoCipher = {
+  name: 'aes-256-gcm',
+  version: 1,
+  seal: (key, nonce, plaintext) => gcm(key, nonce.slice(0, AES_GCM_NONCE_BYTES)).encrypt(plaintext),
+  open: (key, nonce, ciphertext) => {
+    try {
+      return gcm(key, nonce.slice(0, AES_GCM_NONCE_BYTES)).decrypt(ciphertext);
+    } catch {
+      return null;
+    }
+  },
+};
+
+const CIPHERS: readonly MemoCipher[] = [xsalsa20Poly1305, aes256Gcm];
+
+/** Default memo cipher (the only one other OCash clients are guaranteed to read). */
+export const DEFAULT_MEMO_CIPHER = xsalsa20Poly1305;
+
+export const memoCipherByName = (name: MemoCipherName): MemoCipher => {
+  const cipher = CIPHERS.find((c) => c.name === name);
+  if (!cipher) throw new SdkError('CRYPTO', `Unknown memo cipher: ${name}`, { name });
+  return cipher;
+};
+
+export const memoCipherByVersion = (version: number): MemoCipher | undefined => CIPHERS.find((c) => c.version === version);
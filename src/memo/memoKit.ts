@@ -1,11 +1,16 @@
 import nacl from 'tweetnacl';
-import { bytesToHex, hexToBytes } from '@noble/hashes/utils';
+import { hmac } from '@noble/hashes/hmac';
+import { sha512 } from '@noble/hashes/sha512';
+import { bytesToHex, concatBytes, hexToBytes, utf8ToBytes } from '@noble/hashes/utils';
 import { keccak256, toBytes } from 'viem';
 import { BabyJubjub, BABYJUBJUB_ORDER } from '../crypto/babyJubjub';
 import type { CommitmentData, Hex } from '../types';
 import { RecordCodec } from '../crypto/recordCodec';
 import { KeyManager } from '../crypto/keyManager';
-import { randomBytes32Bigint } from '../utils/random';
+import { CryptoToolkit } from '../crypto/cryptoToolkit';
+import { randomBytes32Bigint, type RandomSource } from '../utils/random';
+import { SdkError } from '../errors';
+import { DEFAULT_MEMO_CIPHER, memoCipherByName, memoCipherByVersion, type MemoCipher, type MemoCipherName } from './memoCipher';
 
 /**
  * Derive memo nonce from ephemeral and user public keys (keccak256).
@@ -18,6 +23,42 @@ const memoNonce = (ephemeralPublicKey: [bigint, bigint], userPublicKey: [bigint,
   return toBytes(hex).slice(0, 24);
 };
 
+const EPHEMERAL_TAG = utf8ToBytes('OCash.MemoEphemeral');
+
+export const MAX_MEMO_BYTES_DEFAULT = 4096;
+
+/** Per-call memo size limit; memos longer than `maxMemoBytes` (default 4 KB) are rejected without decrypting. */
+export type MemoLimitOptions = { maxMemoBytes?: number };
+
+const memoLimit = (options?: MemoLimitOptions) => {
+  const value = options?.maxMemoBytes;
+  return value != null && Number.isFinite(value) ? Math.max(0, Math.floor(value)) : MAX_MEMO_BYTES_DEFAULT;
+};
+
+const memoByteLength = (encoded: string) => Math.ceil(encoded.replace(/^0x/, '').length / 2);
+
+/**
+ * Throw SdkError(MEMO_TOO_LARGE) when a memo of `length` bytes exceeds the limit.
+ */
+const assertMemoSize = (length: number, options?: MemoLimitOptions) => {
+  const maxMemoBytes = memoLimit(options);
+  if (length > maxMemoBytes) throw new SdkError('MEMO_TOO_LARGE', 'Memo exceeds maximum length', { length, maxMemoBytes });
+};
+
+// Legacy (version 0) memos are unprefixed: ephemeral PK (32) + secretbox(ABI record, 5 words).
+const LEGACY_MEMO_BYTES = 32 + 5 * 32 + nacl.secretbox.overheadLength;
+
+/**
+ * Split a memo into its cipher and `ephemeral PK || ciphertext` body.
+ * Legacy memos are recognized by length; every other memo starts with a version byte.
+ */
+const splitMemo = (payload: Uint8Array): { cipher: MemoCipher; body: Uint8Array } | null => {
+  if (payload.length === LEGACY_MEMO_BYTES) return { cipher: DEFAULT_MEMO_CIPHER, body: payload };
+  const cipher = payload.length > 0 ? memoCipherByVersion(payload[0]!) : undefined;
+  if (!cipher || cipher.version === DEFAULT_MEMO_CIPHER.version) return null;
+  return { cipher, body: payload.slice(1) };
+};
+
 /**
  * Memo helpers for encrypting/decrypting record openings.
  */
@@ -25,39 +66,114 @@ export class MemoKit {
   /**
    * Encrypt a record opening into a memo payload.
    * Payload = ephemeral PK (32 bytes) + NaCl secretbox ciphertext.
+   * Other ciphers (e.g. `aes-256-gcm` for targets with AES hardware) prepend their version byte;
+   * only the default cipher is readable by every OCash client.
+   * `rng` overrides the secure random source for the ephemeral key.
    */
-  static createMemo(ro: CommitmentData): `0x${string}` {
+  static createMemo(ro: CommitmentData, rng?: RandomSource, options?: { cipher?: MemoCipherName }): `0x${string}` {
+    return `0x${bytesToHex(MemoKit.createMemoBytes(ro, rng, options))}`;
+  }
+
+  /**
+   * Same as `createMemo`, returning the raw payload bytes for binary transports.
+   */
+  static createMemoBytes(ro: CommitmentData, rng?: RandomSource, options?: { cipher?: MemoCipherName }): Uint8Array {
+    return MemoKit.sealMemo(ro, randomBytes32Bigint(true, rng) % BABYJUBJUB_ORDER, options);
+  }
+
+  /**
+   * Same as `createMemo` with a caller-chosen ephemeral scalar (e.g. from `deriveMemoEphemeral`), so the memo is
+   * reproducible: the same record, key and cipher always give identical bytes. Throws SdkError(CRYPTO) unless
+   * `ephemeralSecretKey` is in `[1, BABYJUBJUB_ORDER)`. Never reuse one ephemeral key for different records.
+   */
+  static createMemoDeterministic(ro: CommitmentData, ephemeralSecretKey: bigint, options?: { cipher?: MemoCipherName }): `0x${string}` {
+    if (ephemeralSecretKey <= 0n || ephemeralSecretKey >= BABYJUBJUB_ORDER) {
+      throw new SdkError('CRYPTO', 'Memo ephemeral secret key out of range');
+    }
+    return `0x${bytesToHex(MemoKit.sealMemo(ro, ephemeralSecretKey, options))}`;
+  }
+
+  /**
+   * Derive a memo ephemeral scalar the way EdDSA derives its nonce: HMAC-SHA512 keyed by the sender's secret key
+   * over the record commitment, reduced mod the curve order. One scalar per (sender, record) pair.
+   */
+  static deriveMemoEphemeral(senderSecretKey: bigint, ro: CommitmentData): bigint {
+    const commitment = hexToBytes(CryptoToolkit.commitment(ro, 'hex').slice(2));
+    const key = hexToBytes(senderSecretKey.toString(16).padStart(64, '0'));
+    return BigInt(`0x${bytesToHex(hmac(sha512, key, concatBytes(EPHEMERAL_TAG, commitment)))}`) % BABYJUBJUB_ORDER;
+  }
+
+  private static sealMemo(ro: CommitmentData, ephemeralSecretKey: bigint, options?: { cipher?: MemoCipherName }): Uint8Array {
+    const cipher = options?.cipher ? memoCipherByName(options.cipher) : DEFAULT_MEMO_CIPHER;
     const messageHex = RecordCodec.encode(ro).slice(2);
     const message = hexToBytes(messageHex);
 
-    const ephemeralSecretKey = randomBytes32Bigint(true) % BABYJUBJUB_ORDER;
     const ephemeralPublicKey = BabyJubjub.scalarMult(ephemeralSecretKey);
     const sharedPoint = BabyJubjub.mulPoint(ro.user_pk.user_address, ephemeralSecretKey);
     const sharedKey = BabyJubjub.compressPoint(sharedPoint);
     const nonce = memoNonce(ephemeralPublicKey, ro.user_pk.user_address);
-    const ciphertext = nacl.secretbox(message, nonce, sharedKey);
+    const ciphertext = cipher.seal(sharedKey, nonce, message);
     if (!ciphertext) throw new Error('Failed to encrypt memo');
 
-    const sealed = new Uint8Array(32 + ciphertext.length);
-    sealed.set(BabyJubjub.compressPoint(ephemeralPublicKey), 0);
-    sealed.set(ciphertext, 32);
-    return `0x${bytesToHex(sealed)}`;
+    const prefix = cipher === DEFAULT_MEMO_CIPHER ? 0 : 1;
+    const sealed = new Uint8Array(prefix + 32 + ciphertext.length);
+    if (prefix) sealed[0] = cipher.version;
+    sealed.set(BabyJubjub.compressPoint(ephemeralPublicKey), prefix);
+    sealed.set(ciphertext, prefix + 32);
+    return sealed;
+  }
+
+  /**
+   * Decrypt a memo with the owner's secret key, dispatching on the memo's cipher version.
+   * Returns null if decryption fails, the version is unknown, or the payload is invalid.
+   * Throws SdkError(MEMO_TOO_LARGE) if it exceeds `maxMemoBytes` (checked before any decryption work).
+   * With `strict`, an unknown version byte throws SdkError(CRYPTO) `Unsupported memo version` instead of returning null.
+   */
+  static decryptMemo(secretKey: bigint, encoded: `0x${string}`, options?: { strict?: boolean } & MemoLimitOptions): CommitmentData | null {
+    // Checked before hex decoding so oversized strings are never materialized as bytes.
+    assertMemoSize(memoByteLength(encoded), options);
+    return MemoKit.decryptMemoBytes(secretKey, hexToBytes(encoded.replace(/^0x/, '')), options);
+  }
+
+  /**
+   * Same as `decryptMemo`, taking the raw payload bytes (e.g. from a binary transport) instead of hex.
+   */
+  static decryptMemoBytes(secretKey: bigint, payload: Uint8Array, options?: { strict?: boolean } & MemoLimitOptions): CommitmentData | null {
+    assertMemoSize(payload.length, options);
+    const split = splitMemo(payload);
+    if (!split) {
+      if (options?.strict && payload.length > 0 && !memoCipherByVersion(payload[0]!)) {
+        throw new SdkError('CRYPTO', 'Unsupported memo version', { version: payload[0], length: payload.length });
+      }
+      return null;
+    }
+    return MemoKit.openMemo(secretKey, split.cipher, split.body);
+  }
+
+  /**
+   * Decrypt assuming the unversioned layout (`ephemeral PK || XSalsa20-Poly1305 ciphertext`) whatever the length,
+   * for memos that predate version bytes. Returns null when decryption fails; throws SdkError(MEMO_TOO_LARGE) when the
+   * memo exceeds `maxMemoBytes`.
+   */
+  static decryptMemoLegacy(secretKey: bigint, encoded: `0x${string}`, options?: MemoLimitOptions): CommitmentData | null {
+    assertMemoSize(memoByteLength(encoded), options);
+    return MemoKit.openMemo(secretKey, DEFAULT_MEMO_CIPHER, hexToBytes(encoded.replace(/^0x/, '')));
   }
 
   /**
-   * Decrypt a memo with the owner's secret key.
-   * Returns null if decryption fails or payload is invalid.
+   * Open an `ephemeral PK || ciphertext` body with the given cipher.
    */
-  static decryptMemo(secretKey: bigint, encoded: `0x${string}`): CommitmentData | null {
-    const payload = hexToBytes(encoded.replace(/^0x/, ''));
+  private static openMemo(secretKey: bigint, cipher: MemoCipher, body: Uint8Array): CommitmentData | null {
     const bobPublicKey = BabyJubjub.scalarMult(secretKey);
-    const ephemeralPublicKey = BabyJubjub.decompressPoint(payload.slice(0, 32));
-    const ciphertext = payload.slice(32);
+    // A foreign or corrupt ephemeral key means the memo is not ours, not that the scan failed.
+    const ephemeralPublicKey = BabyJubjub.tryDecompressPoint(body.slice(0, 32));
+    if (!ephemeralPublicKey) return null;
+    const ciphertext = body.slice(32);
     const sharedPoint = BabyJubjub.mulPoint(ephemeralPublicKey, secretKey);
     const sharedKey = BabyJubjub.compressPoint(sharedPoint);
     const nonce = memoNonce(ephemeralPublicKey, bobPublicKey);
     try {
-      const decrypted = nacl.secretbox.open(ciphertext, nonce, sharedKey);
+      const decrypted = cipher.open(sharedKey, nonce, ciphertext);
       if (!decrypted) return null;
       const hexResult = bytesToHex(decrypted);
       return RecordCodec.decode(`0x${hexResult}`);
@@ -66,11 +182,68 @@ export class MemoKit {
     }
   }
 
+  /**
+   * Decrypt a memo and check that the decoded record hashes to the expected on-chain commitment.
+   * Returns null if decryption fails or the commitment does not match; throws SdkError(MEMO_TOO_LARGE) like `decryptMemo`.
+   */
+  static decryptAndVerify(secretKey: bigint, encoded: `0x${string}`, expectedCommitment: Hex | bigint, options?: MemoLimitOptions): CommitmentData | null {
+    const ro = MemoKit.decryptMemo(secretKey, encoded, options);
+    if (!ro) return null;
+    return CryptoToolkit.commitment(ro, 'bigint') === BigInt(expectedCommitment) ? ro : null;
+  }
+
+  /**
+   * Trial-decrypt memos as they arrive from a (possibly unbounded) source such as a push subscription.
+   * Yields one result per memo in source order; memos that fail to decrypt or exceed the size limit yield null,
+   * so one oversized memo does not end the stream.
+   * At most `concurrency` memos (default 4) are read ahead of the consumer.
+   */
+  static async *decryptStream(
+    secretKey: bigint,
+    memos: AsyncIterable<Hex> | Iterable<Hex>,
+    options: { concurrency?: number } & MemoLimitOptions = {},
+  ): AsyncGenerator<CommitmentData | null, void, undefined> {
+    const concurrency = Math.max(1, Math.floor(options.concurrency ?? 4));
+    const iterator = (async function* () {
+      yield* memos;
+    })();
+    const decrypt = (memo: Hex) => {
+      try {
+        return MemoKit.decryptMemo(secretKey, memo, options);
+      } catch {
+        return null;
+      }
+    };
+    const pull = () => iterator.next().then((r) => (r.done ? ({ done: true } as const) : ({ done: false, value: decrypt(r.value) } as const)));
+
+    const window: ReturnType<typeof pull>[] = [];
+    let exhausted = false;
+    try {
+      while (true) {
+        while (!exhausted && window.length < concurrency) window.push(pull());
+        const next = window.shift();
+        if (!next) return;
+        const slot = await next;
+        if (slot.done) {
+          exhausted = true;
+          continue;
+        }
+        yield slot.value;
+      }
+    } finally {
+      // Do not await: pending reads queue ahead of return() and may never settle on a live feed.
+      if (!exhausted) void iterator.return(undefined).catch(() => undefined);
+    }
+  }
+
   /**
    * Decode memo for owner with transparent fallback.
    * If isTransparent=true, treat memo as plaintext record opening.
+   * Throws SdkError(MEMO_TOO_LARGE) for memos over `maxMemoBytes`, transparent or not.
    */
-  static decodeMemoForOwner(input: { secretKey: bigint; memo: Hex; expectedAddress?: Hex | null; isTransparent?: boolean }): CommitmentData | null {
+  static decodeMemoForOwner(
+    input: { secretKey: bigint; memo: Hex; expectedAddress?: Hex | null; isTransparent?: boolean } & MemoLimitOptions,
+  ): CommitmentData | null {
     const tryTransparent = () => {
       try {
         const decoded = RecordCodec.decode(input.memo);
@@ -83,11 +256,12 @@ export class MemoKit {
       }
     };
 
+    assertMemoSize(memoByteLength(input.memo), input);
     if (input.isTransparent) {
       return tryTransparent();
     }
 
-    const decrypted = MemoKit.decryptMemo(input.secretKey, input.memo);
+    const decrypted = MemoKit.decryptMemo(input.secretKey, input.memo, input);
     if (decrypted) return decrypted;
     return tryTransparent();
   }
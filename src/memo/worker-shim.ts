@@ -4,6 +4,7 @@ import type { MemoDecryptRequest, MemoDecryptResult } from '../types';
 export interface WorkerBatchRequest {
   secretKey: string;
   memos: { index: number; memo: string }[];
+  maxMemoBytes?: number;
 }
 
 export interface WorkerMessage {
@@ -49,9 +50,10 @@ export const createMessage = (id: string, payload: WorkerBatchRequest): WorkerMe
  */
 export const handleDecrypt = (payload: WorkerBatchRequest): WorkerResponse['data'] => {
   const secretKey = BigInt(payload.secretKey);
+  const options = { maxMemoBytes: payload.maxMemoBytes };
   return payload.memos.map((entry) => {
     try {
-      const record = MemoKit.decryptMemo(secretKey, entry.memo as `0x${string}`);
+      const record = MemoKit.decryptMemo(secretKey, entry.memo as `0x${string}`, options);
       return { index: entry.index, record };
     } catch (error) {
       return {
@@ -101,6 +101,7 @@ export class MemoWorker {
       data: {
         secretKey: secretKey.toString(),
         memos: chunk.map((entry, index) => ({ index, memo: entry.memo })),
+        maxMemoBytes: this.config.maxMemoBytes,
       },
     };
     return new Promise<MemoDecryptResult[]>((resolve, reject) => {
@@ -173,7 +174,7 @@ export class MemoWorker {
    */
   private decryptSingle(secretKey: bigint, request: MemoDecryptRequest): MemoDecryptResult {
     try {
-      const record = MemoKit.decryptMemo(secretKey, request.memo);
+      const record = MemoKit.decryptMemo(secretKey, request.memo, { maxMemoBytes: this.config.maxMemoBytes });
       return { memo: request.memo, record, metadata: request.metadata };
     } catch (error) {
       return {
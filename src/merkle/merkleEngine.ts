@@ -1,7 +1,20 @@
-import type { MerkleApi, ProofBridge, RemoteMerkleProofResponse, Hex, AccMemberWitness, InputSecret, ChairmanMerkleNodeRecord, ChairmanMerkleVersionRecord, StorageAdapter, UserKeyPair } from '../types';
+import type {
+  MerkleApi,
+  ProofBridge,
+  RemoteMerkleProofResponse,
+  Hex,
+  AccMemberWitness,
+  InputSecret,
+  ChairmanMerkleNodeRecord,
+  ChairmanMerkleVersionRecord,
+  StorageAdapter,
+  SyncCheckpoint,
+  UserKeyPair,
+} from '../types';
 import { SdkError } from '../errors';
 import { MerkleClient } from './merkleClient';
 import { getZeroHash, TREE_DEPTH_DEFAULT } from './zeroHashes';
+import { computeMerkleRootFromLeaves } from './merkleProof';
 import { Poseidon2, Poseidon2Domain } from '../crypto/poseidon2';
 import { MemoKit } from '../memo/memoKit';
 import { KeyManager } from '../crypto/keyManager';
@@ -9,6 +22,8 @@ import { KeyManager } from '../crypto/keyManager';
 const TEMP_ARRAY_SIZE_DEFAULT = 32;
 const SUBTREE_SIZE = 32;
 const SUBTREE_DEPTH = 5;
+// Bound on leaves `setLeaf` holds past the tip per chain, so a stray far-ahead cid stream cannot grow memory unchecked.
+const MAX_PARKED_LEAVES = 4096;
 
 /**
  * Convert bigint-like values to decimal string without throwing.
@@ -29,9 +44,13 @@ const toDecString = (value: string | bigint) => {
  * Rollback to any previous version is O(1) — just switch the root pointer.
  */
 export class MerkleEngine implements MerkleApi {
-  private readonly mode: 'remote' | 'local' | 'hybrid';
+  readonly mode: 'remote' | 'local' | 'hybrid';
   private readonly treeDepth: number;
+  /** Poseidon2 domain for internal nodes and zero hashes (`Merkle` for the on-chain tree). */
+  private readonly hashDomain: bigint;
   private readonly pendingLeavesByChain = new Map<number, Hex[]>();
+  /** Leaves set past the tree tip by `setLeaf`, held until the gap before them is filled. */
+  private readonly parkedLeavesByChain = new Map<number, Map<number, Hex>>();
   private readonly chainStateByChain = new Map<number, { mergedElements: number; root: Hex }>();
   private readonly hydratedChains = new Set<number>();
   private readonly hydrateInFlight = new Map<number, Promise<void>>();
@@ -41,16 +60,29 @@ export class MerkleEngine implements MerkleApi {
    * When provided, each batch merge is verified against the contract root.
    */
   private readonly readContractRoot?: (chainId: number, rootIndex: number) => Promise<Hex | null>;
+  /**
+   * Maps a commitment to the leaf value stored in the tree, for contracts that insert `H(commitment)` instead of the
+   * commitment itself. Applied once on ingest; stored leaves and proof paths hold the transformed value.
+   */
+  private readonly leafTransform?: (commitment: bigint) => bigint;
 
   constructor(
     private readonly getChain: (chainId: number) => { merkleProofUrl?: string },
     private readonly bridge: ProofBridge,
-    options?: { mode?: 'remote' | 'local' | 'hybrid'; treeDepth?: number; readContractRoot?: (chainId: number, rootIndex: number) => Promise<Hex | null> },
+    options?: {
+      mode?: 'remote' | 'local' | 'hybrid';
+      treeDepth?: number;
+      hashDomain?: bigint;
+      readContractRoot?: (chainId: number, rootIndex: number) => Promise<Hex | null>;
+      leafTransform?: (commitment: bigint) => bigint;
+    },
     private readonly storage?: StorageAdapter,
   ) {
     this.mode = options?.mode ?? 'hybrid';
     this.treeDepth = Math.max(1, Math.floor(options?.treeDepth ?? TREE_DEPTH_DEFAULT));
+    this.hashDomain = options?.hashDomain ?? Poseidon2Domain.Merkle;
     this.readContractRoot = options?.readContractRoot;
+    this.leafTransform = options?.leafTransform;
   }
 
   /**
@@ -73,7 +105,7 @@ export class MerkleEngine implements MerkleApi {
   private ensureChainState(chainId: number) {
     let state = this.chainStateByChain.get(chainId);
     if (!state) {
-      state = { mergedElements: 0, root: getZeroHash(this.treeDepth) };
+      state = { mergedElements: 0, root: this.zeroHash(this.treeDepth) };
       this.chainStateByChain.set(chainId, state);
     }
     return state;
@@ -81,8 +113,21 @@ export class MerkleEngine implements MerkleApi {
 
   // ── Hashing ──
 
-  private static hashPair(left: Hex, right: Hex): Hex {
-    return Poseidon2.hashToHex(BigInt(left), BigInt(right), Poseidon2Domain.Merkle);
+  private hashPair(left: Hex, right: Hex): Hex {
+    return Poseidon2.hashToHex(BigInt(left), BigInt(right), this.hashDomain);
+  }
+
+  private zeroHash(level: number): Hex {
+    return getZeroHash(level, this.hashDomain);
+  }
+
+  /**
+   * Normalize a commitment and apply `leafTransform` (identity by default) to get the stored leaf.
+   */
+  private toLeaf(commitment: unknown, name: string): Hex {
+    const normalized = MerkleEngine.normalizeHex32(commitment, name);
+    if (!this.leafTransform) return normalized;
+    return MerkleEngine.normalizeHex32(this.leafTransform(BigInt(normalized)), `${name} (transformed)`);
   }
 
   static normalizeHex32(value: unknown, name: string): Hex {
@@ -112,7 +157,7 @@ export class MerkleEngine implements MerkleApi {
    * Build a fixed-depth subtree from 32 contiguous leaves.
    * Returns the subtree root hash and all intermediate nodes for storage.
    */
-  private static buildSubtree(leafCommitments: Hex[], baseIndex: number): { subtreeRoot: Hex; nodesToStore: ChairmanMerkleNodeRecord[] } {
+  private buildSubtree(leafCommitments: Hex[], baseIndex: number): { subtreeRoot: Hex; nodesToStore: ChairmanMerkleNodeRecord[] } {
     if (leafCommitments.length !== SUBTREE_SIZE) {
       throw new SdkError('MERKLE', 'Subtree must have exactly 32 leaf nodes', { got: leafCommitments.length });
     }
@@ -124,7 +169,7 @@ export class MerkleEngine implements MerkleApi {
     for (let level = 1; level <= SUBTREE_DEPTH; level++) {
       const next: Hex[] = [];
       for (let i = 0; i < currentLevel.length; i += 2) {
-        next.push(MerkleEngine.hashPair(currentLevel[i]!, currentLevel[i + 1]!));
+        next.push(this.hashPair(currentLevel[i]!, currentLevel[i + 1]!));
       }
       const basePos = baseIndex >> level;
       for (let i = 0; i < next.length; i++) {
@@ -195,8 +240,8 @@ export class MerkleEngine implements MerkleApi {
       if (goRight) {
         // Left child: shared from previous version
         const leftHash = prevLeftId
-          ? (await this.storage?.getChairmanMerkleNode?.(chainId, prevLeftId))?.hash ?? getZeroHash(childLevel)
-          : getZeroHash(childLevel);
+          ? (await this.storage?.getChairmanMerkleNode?.(chainId, prevLeftId))?.hash ?? this.zeroHash(childLevel)
+          : this.zeroHash(childLevel);
         leftResult = { id: prevLeftId, hash: leftHash };
         // Right child: recurse
         const right = await descend(prevRightId, depth + 1);
@@ -207,12 +252,12 @@ export class MerkleEngine implements MerkleApi {
         leftResult = { id: left.id, hash: left.hash };
         // Right child: shared from previous version
         const rightHash = prevRightId
-          ? (await this.storage?.getChairmanMerkleNode?.(chainId, prevRightId))?.hash ?? getZeroHash(childLevel)
-          : getZeroHash(childLevel);
+          ? (await this.storage?.getChairmanMerkleNode?.(chainId, prevRightId))?.hash ?? this.zeroHash(childLevel)
+          : this.zeroHash(childLevel);
         rightResult = { id: prevRightId, hash: rightHash };
       }
 
-      const hash = MerkleEngine.hashPair(leftResult.hash, rightResult.hash);
+      const hash = this.hashPair(leftResult.hash, rightResult.hash);
       const newId = `cm-${version}-${originalLevel}`;
       nodes.push({ chainId, id: newId, hash, leftId: leftResult.id, rightId: rightResult.id });
       return { id: newId, hash };
@@ -266,6 +311,68 @@ export class MerkleEngine implements MerkleApi {
 
   // ── Ingestion ──
 
+  /**
+   * Merge every full 32-leaf batch in the pending buffer into the chairmanMerkle tree.
+   */
+  private async mergeFullBatches(chainId: number) {
+    const state = this.ensureChainState(chainId);
+    const pending = this.ensurePendingLeaves(chainId);
+    while (pending.length >= SUBTREE_SIZE) {
+      const batch = pending.splice(0, SUBTREE_SIZE);
+      const batchIndex = state.mergedElements / SUBTREE_SIZE;
+
+      // Build subtree (levels 0-5)
+      const subtree = this.buildSubtree(batch, state.mergedElements);
+      const subtreeNodes: ChairmanMerkleNodeRecord[] = subtree.nodesToStore.map((n) => ({ ...n, chainId }));
+
+      // Get previous version root
+      const prevVersion = await this.storage?.getLatestChairmanMerkleVersion?.(chainId);
+      const prevRootId = prevVersion?.rootId ?? null;
+
+      // Insert subtree root into chairmanMerkle tree (levels 5-32)
+      const newVersion = state.mergedElements + SUBTREE_SIZE;
+      const result = await this.insertSubtreeRoot(chainId, prevRootId, subtree.subtreeRoot, batchIndex, newVersion);
+
+      // Verify against on-chain root before persisting (fail-fast).
+      // rootIndex = newVersion / 32, matching contract's _currentMerkleRootIndex.
+      if (this.readContractRoot) {
+        const rootIndex = newVersion / SUBTREE_SIZE;
+        const onChainRoot = await this.readContractRoot(chainId, rootIndex).catch(() => null);
+        if (onChainRoot !== null) {
+          const onChainNorm = MerkleEngine.normalizeHex32(onChainRoot, 'onChainRoot');
+          const isZero = BigInt(onChainNorm) === 0n;
+          if (!isZero && onChainNorm !== result.rootHash) {
+            // Mismatch: rollback to previous batch boundary (state.mergedElements).
+            // Resets tree + sync cursor; next sync re-ingests from there.
+            // If that position is also wrong, the next merge will detect it and step back again.
+            const target = state.mergedElements; // previous batch end, not yet updated
+            await this._rollback(chainId, target);
+            throw new SdkError('MERKLE', 'Local merkle root mismatch with on-chain root — rolled back', {
+              chainId,
+              rootIndex,
+              localRoot: result.rootHash,
+              onChainRoot: onChainNorm,
+              version: newVersion,
+              rollbackTarget: target,
+            });
+          }
+        }
+      }
+
+      // Persist all nodes + new version
+      await this.storage?.putChairmanMerkleNodes?.(chainId, [...subtreeNodes, ...result.nodes]);
+      await this.storage?.putChairmanMerkleVersion?.(chainId, {
+        chainId,
+        version: newVersion,
+        rootId: result.rootId,
+        rootHash: result.rootHash,
+      });
+
+      state.mergedElements = newVersion;
+      state.root = result.rootHash;
+    }
+  }
+
   async ingestEntryMemos(chainId: number, memos: Array<{ cid: number | null; commitment: Hex | string | bigint }>) {
     if (this.mode === 'remote') return;
     await this.hydrateFromStorage(chainId);
@@ -277,20 +384,22 @@ export class MerkleEngine implements MerkleApi {
       .filter((m): m is typeof m & { cid: number } => typeof m.cid === 'number' && Number.isInteger(m.cid) && m.cid >= 0)
       .map((m) => ({
         index: m.cid,
-        commitment: MerkleEngine.normalizeHex32(m.commitment, 'memo.commitment'),
+        commitment: this.toLeaf(m.commitment, 'memo.commitment'),
       }));
     if (!leaves.length) return;
 
     const sorted = [...leaves].sort((a, b) => a.index - b.index);
-    const persistLeaves = sorted.map((l) => ({ cid: l.index, commitment: l.commitment }));
+    // Only leaves past the tree tip are new; replays after a crash re-send already-built ranges.
+    const tip = state.mergedElements + pending.length;
+    const persistLeaves = sorted.filter((l) => l.index >= tip).map((l) => ({ cid: l.index, commitment: l.commitment }));
     try {
-      await this.storage?.appendMerkleLeaves?.(chainId, persistLeaves);
+      if (persistLeaves.length) await this.storage?.appendMerkleLeaves?.(chainId, persistLeaves);
     } catch {
       // Storage failure is non-fatal in hybrid mode
     }
 
     try {
-      let expected = state.mergedElements + pending.length;
+      let expected = tip;
       for (const leaf of sorted) {
         if (leaf.index < expected) continue;
         if (leaf.index !== expected) {
@@ -299,60 +408,7 @@ export class MerkleEngine implements MerkleApi {
         pending.push(leaf.commitment);
         expected++;
 
-        while (pending.length >= SUBTREE_SIZE) {
-          const batch = pending.splice(0, SUBTREE_SIZE);
-          const batchIndex = state.mergedElements / SUBTREE_SIZE;
-
-          // Build subtree (levels 0-5)
-          const subtree = MerkleEngine.buildSubtree(batch, state.mergedElements);
-          const subtreeNodes: ChairmanMerkleNodeRecord[] = subtree.nodesToStore.map((n) => ({ ...n, chainId }));
-
-          // Get previous version root
-          const prevVersion = await this.storage?.getLatestChairmanMerkleVersion?.(chainId);
-          const prevRootId = prevVersion?.rootId ?? null;
-
-          // Insert subtree root into chairmanMerkle tree (levels 5-32)
-          const newVersion = state.mergedElements + SUBTREE_SIZE;
-          const result = await this.insertSubtreeRoot(chainId, prevRootId, subtree.subtreeRoot, batchIndex, newVersion);
-
-          // Verify against on-chain root before persisting (fail-fast).
-          // rootIndex = newVersion / 32, matching contract's _currentMerkleRootIndex.
-          if (this.readContractRoot) {
-            const rootIndex = newVersion / SUBTREE_SIZE;
-            const onChainRoot = await this.readContractRoot(chainId, rootIndex).catch(() => null);
-            if (onChainRoot !== null) {
-              const onChainNorm = MerkleEngine.normalizeHex32(onChainRoot, 'onChainRoot');
-              const isZero = BigInt(onChainNorm) === 0n;
-              if (!isZero && onChainNorm !== result.rootHash) {
-                // Mismatch: rollback to previous batch boundary (state.mergedElements).
-                // Resets tree + sync cursor; next sync re-ingests from there.
-                // If that position is also wrong, the next merge will detect it and step back again.
-                const target = state.mergedElements; // previous batch end, not yet updated
-                await this._rollback(chainId, target);
-                throw new SdkError('MERKLE', 'Local merkle root mismatch with on-chain root — rolled back', {
-                  chainId,
-                  rootIndex,
-                  localRoot: result.rootHash,
-                  onChainRoot: onChainNorm,
-                  version: newVersion,
-                  rollbackTarget: target,
-                });
-              }
-            }
-          }
-
-          // Persist all nodes + new version
-          await this.storage?.putChairmanMerkleNodes?.(chainId, [...subtreeNodes, ...result.nodes]);
-          await this.storage?.putChairmanMerkleVersion?.(chainId, {
-            chainId,
-            version: newVersion,
-            rootId: result.rootId,
-            rootHash: result.rootHash,
-          });
-
-          state.mergedElements = newVersion;
-          state.root = result.rootHash;
-        }
+        await this.mergeFullBatches(chainId);
       }
       this.hydratedChains.add(chainId);
     } catch (error) {
@@ -363,6 +419,154 @@ export class MerkleEngine implements MerkleApi {
     }
   }
 
+  /**
+   * Bring the local tree up to the memo cursor from cached entry memos.
+   *
+   * The tree may lag the memo cursor (`leaves <= memo`) when a previous pass stopped between
+   * downloading memos and building the tree. Only the missing `[leaves, memoCursor)` range is
+   * ingested. Returns the number of leaves appended (0 when the tree is current or no cache exists).
+   */
+  async backfillFromEntryMemos(chainId: number, memoCursor: number): Promise<number> {
+    if (this.mode === 'remote' || !this.storage?.listEntryMemos) return 0;
+    await this.hydrateFromStorage(chainId);
+
+    const state = this.ensureChainState(chainId);
+    const pending = this.ensurePendingLeaves(chainId);
+    const before = state.mergedElements + pending.length;
+    if (before >= memoCursor) return 0;
+
+    const { rows } = await this.storage.listEntryMemos({ chainId, cidFrom: before, cidTo: memoCursor - 1, orderBy: 'cid', order: 'asc' });
+    await this.ingestEntryMemos(
+      chainId,
+      rows.map((r) => ({ cid: r.cid, commitment: r.commitment })),
+    );
+    return state.mergedElements + pending.length - before;
+  }
+
+  /**
+   * Seed an empty local tree with the leaves of a trusted checkpoint.
+   * The leaves (commitments, before any `leafTransform`) must fold to `checkpoint.merkleRoot` at the configured depth;
+   * remote mode only validates.
+   */
+  async seedFromCheckpoint(chainId: number, checkpoint: Pick<SyncCheckpoint, 'leaves' | 'merkleRoot'>) {
+    const commitments = checkpoint.leaves.map((leaf) => MerkleEngine.normalizeHex32(leaf, 'checkpoint.leaf'));
+    const leaves = this.leafTransform ? commitments.map((commitment) => this.toLeaf(commitment, 'checkpoint.leaf')) : commitments;
+    const expected = MerkleEngine.normalizeHex32(checkpoint.merkleRoot, 'checkpoint.merkleRoot');
+    const actual = MerkleEngine.normalizeHex32(computeMerkleRootFromLeaves(leaves, this.treeDepth, this.hashDomain), 'checkpoint root');
+    if (actual !== expected) {
+      throw new SdkError('MERKLE', 'Checkpoint merkle root mismatch', { chainId, expected, actual, leaves: leaves.length });
+    }
+    if (this.mode === 'remote') return;
+    await this.hydrateFromStorage(chainId);
+
+    const state = this.ensureChainState(chainId);
+    const pending = this.ensurePendingLeaves(chainId);
+    if (state.mergedElements + pending.length > 0) {
+      throw new SdkError('MERKLE', 'Checkpoint can only seed an empty local merkle tree', { chainId, leaves: state.mergedElements + pending.length });
+    }
+    await this.ingestEntryMemos(chainId, commitments.map((commitment, cid) => ({ cid, commitment })));
+  }
+
+  /**
+   * Append a single leaf at the current tree tip, skipping the sort/filter work of `ingestEntryMemos`.
+   * `cid` must equal the next leaf index; already-ingested indices are ignored.
+   * Only the rightmost path is touched when a batch fills, so the root matches a batch ingest.
+   */
+  async ingestLeaf(chainId: number, cid: number, commitment: Hex | string | bigint) {
+    if (this.mode === 'remote') return;
+    await this.hydrateFromStorage(chainId);
+
+    const state = this.ensureChainState(chainId);
+    const pending = this.ensurePendingLeaves(chainId);
+    const expected = state.mergedElements + pending.length;
+    if (cid < expected) return;
+    if (cid !== expected) {
+      if (this.mode === 'hybrid') return;
+      throw new SdkError('MERKLE', 'Non-contiguous merkle leaf', { chainId, expected, cid });
+    }
+
+    const normalized = this.toLeaf(commitment, 'leaf.commitment');
+    try {
+      await this.storage?.appendMerkleLeaves?.(chainId, [{ cid, commitment: normalized }]);
+    } catch {
+      // Storage failure is non-fatal in hybrid mode
+    }
+
+    pending.push(normalized);
+    if (pending.length < SUBTREE_SIZE) return;
+    try {
+      await this.mergeFullBatches(chainId);
+    } catch (error) {
+      if (error instanceof SdkError) throw error;
+      throw new SdkError('MERKLE', 'Failed to ingest local merkle leaf', { chainId, cid }, error);
+    }
+  }
+
+  /**
+   * Set the leaf at `cid`, tolerating gaps (e.g. Entry pages arriving out of order, or backfilling a hole).
+   * Leaves past the tip are held until every index before them is present, then appended in order,
+   * so batches are only merged once complete and the root matches an in-order ingest.
+   * Re-setting a leaf with the same commitment is a no-op; a different commitment for a cid that is already merged,
+   * pending or held throws `MERKLE`. At most 4096 leaves are held past the tip per chain.
+   */
+  async setLeaf(chainId: number, cid: number, commitment: Hex | string | bigint) {
+    if (this.mode === 'remote') return;
+    if (!Number.isInteger(cid) || cid < 0) {
+      throw new SdkError('MERKLE', 'Invalid merkle leaf index', { chainId, cid });
+    }
+    await this.hydrateFromStorage(chainId);
+
+    const state = this.ensureChainState(chainId);
+    const pending = this.ensurePendingLeaves(chainId);
+    const normalized = this.toLeaf(commitment, 'leaf.commitment');
+    const tip = state.mergedElements + pending.length;
+    if (cid < state.mergedElements) {
+      const merged = await this.storage?.getMerkleLeaf?.(chainId, cid);
+      if (merged?.commitment === normalized) return;
+      throw new SdkError('MERKLE', 'Conflicting merkle leaf for a merged index', { chainId, cid, existing: merged?.commitment, commitment: normalized });
+    }
+    if (cid < tip) {
+      const existing = pending[cid - state.mergedElements];
+      if (existing === normalized) return;
+      throw new SdkError('MERKLE', 'Conflicting merkle leaf for a pending index', { chainId, cid, existing, commitment: normalized });
+    }
+
+    let parked = this.parkedLeavesByChain.get(chainId);
+    if (!parked) {
+      parked = new Map();
+      this.parkedLeavesByChain.set(chainId, parked);
+    }
+    const held = parked.get(cid);
+    if (held === normalized) return;
+    if (held !== undefined) {
+      throw new SdkError('MERKLE', 'Conflicting merkle leaf for a held index', { chainId, cid, existing: held, commitment: normalized });
+    }
+    if (parked.size >= MAX_PARKED_LEAVES) {
+      throw new SdkError('MERKLE', 'Too many merkle leaves waiting for an earlier gap', { chainId, cid, tip, parked: parked.size });
+    }
+    parked.set(cid, normalized);
+
+    const run: Array<{ cid: number; commitment: Hex }> = [];
+    for (let next = tip; parked.has(next); next++) {
+      run.push({ cid: next, commitment: parked.get(next)! });
+      parked.delete(next);
+    }
+    if (!run.length) return;
+
+    try {
+      await this.storage?.appendMerkleLeaves?.(chainId, run);
+    } catch {
+      // Storage failure is non-fatal in hybrid mode
+    }
+    pending.push(...run.map((l) => l.commitment));
+    try {
+      await this.mergeFullBatches(chainId);
+    } catch (error) {
+      if (error instanceof SdkError) throw error;
+      throw new SdkError('MERKLE', 'Failed to set local merkle leaf', { chainId, cid }, error);
+    }
+  }
+
   // ── Rollback (tree O(1) + sync cursor reset) ──
 
   /**
@@ -399,8 +603,9 @@ export class MerkleEngine implements MerkleApi {
 
     if (targetMergedElements === 0) {
       state.mergedElements = 0;
-      state.root = getZeroHash(this.treeDepth);
+      state.root = this.zeroHash(this.treeDepth);
       pending.length = 0;
+      this.parkedLeavesByChain.delete(chainId);
       await this.resetSyncCursor(chainId, 0);
       return true;
     }
@@ -411,6 +616,7 @@ export class MerkleEngine implements MerkleApi {
     state.mergedElements = targetMergedElements;
     state.root = MerkleEngine.normalizeHex32(version.rootHash, 'version.rootHash');
     pending.length = 0;
+    this.parkedLeavesByChain.delete(chainId);
     this.hydratedChains.add(chainId);
     await this.resetSyncCursor(chainId, targetMergedElements);
     return true;
@@ -484,7 +690,7 @@ export class MerkleEngine implements MerkleApi {
 
             const effectiveRoot = contractTreeElements > 0
               ? MerkleEngine.normalizeHex32(version!.rootHash, 'version.rootHash')
-              : getZeroHash(this.treeDepth);
+              : this.zeroHash(this.treeDepth);
 
             return {
               proof,
@@ -505,7 +711,7 @@ export class MerkleEngine implements MerkleApi {
 
     // Remote fallback
     if (needsTreeProof.length === 0) {
-      const root = contractTreeElements === 0 ? getZeroHash(this.treeDepth) : await this.fetchRemoteRootOnly(input.chainId);
+      const root = contractTreeElements === 0 ? this.zeroHash(this.treeDepth) : await this.fetchRemoteRootOnly(input.chainId);
       return {
         proof: cids.map((cid) => ({ leaf_index: cid, path: new Array(this.treeDepth + 1).fill('0') })),
         merkle_root: root,
@@ -543,11 +749,11 @@ export class MerkleEngine implements MerkleApi {
       const siblingPos = (cid >> (level - 1)) ^ 1;
       if (level === 1) {
         const siblingLeaf = await this.storage!.getMerkleLeaf!(chainId, siblingPos);
-        path.push(siblingLeaf?.commitment ?? getZeroHash(0));
+        path.push(siblingLeaf?.commitment ?? this.zeroHash(0));
       } else {
         const targetLevel = level - 1;
         const node = await this.storage!.getChairmanMerkleNode!(chainId, `st-${targetLevel}-${siblingPos}`);
-        path.push(node?.hash ?? getZeroHash(targetLevel));
+        path.push(node?.hash ?? this.zeroHash(targetLevel));
       }
     }
 
@@ -563,13 +769,13 @@ export class MerkleEngine implements MerkleApi {
       const childLevel = this.treeDepth - depth - 1;
 
       if (!nodeId) {
-        mainSiblings.push(getZeroHash(childLevel));
+        mainSiblings.push(this.zeroHash(childLevel));
         continue;
       }
 
       const node = await this.storage!.getChairmanMerkleNode!(chainId, nodeId);
       if (!node) {
-        mainSiblings.push(getZeroHash(childLevel));
+        mainSiblings.push(this.zeroHash(childLevel));
         nodeId = null;
         continue;
       }
@@ -579,11 +785,11 @@ export class MerkleEngine implements MerkleApi {
 
       if (goRight) {
         const leftNode = node.leftId ? await this.storage!.getChairmanMerkleNode!(chainId, node.leftId) : null;
-        mainSiblings.push(leftNode?.hash ?? getZeroHash(childLevel));
+        mainSiblings.push(leftNode?.hash ?? this.zeroHash(childLevel));
         nodeId = node.rightId;
       } else {
         const rightNode = node.rightId ? await this.storage!.getChairmanMerkleNode!(chainId, node.rightId) : null;
-        mainSiblings.push(rightNode?.hash ?? getZeroHash(childLevel));
+        mainSiblings.push(rightNode?.hash ?? this.zeroHash(childLevel));
         nodeId = node.leftId;
       }
     }
@@ -0,0 +1,313 @@
+import { bytesToHex, hexToBytes } from '@noble/hashes/utils';
+import type { Hex } from '../types';
+import { fieldToHex } from '../crypto/field';
+import { Poseidon2, Poseidon2Domain } from '../crypto/poseidon2';
+import { getZeroHash, TREE_DEPTH_DEFAULT } from './zeroHashes';
+
+type ProofNode = Hex | string | bigint;
+
+/**
+ * Merkle membership proof in the path layout used by the Merkle service and MerkleEngine:
+ * `path[0]` is the leaf, `path[i + 1]` is the sibling at level `i`.
+ */
+export interface MerkleProof {
+  path: readonly ProofNode[];
+  leafIndex: number | bigint;
+}
+
+const toBigint = (value: ProofNode | number) => (typeof value === 'bigint' ? value : BigInt(value));
+
+/**
+ * JSON form of a `MerkleProof`: 32-byte hex path nodes and a numeric leaf index.
+ */
+export interface MerkleProofJson {
+  leafIndex: number;
+  path: Hex[];
+}
+
+const toLeafIndex = (value: number | bigint) => {
+  const index = Number(value);
+  if (!Number.isSafeInteger(index) || index < 0 || index > 0xffffffff) throw new Error(`invalid merkle leaf index ${value}`);
+  return index;
+};
+
+/**
+ * Serialize a proof for storage or transport (e.g. to a prover service).
+ */
+export function merkleProofToJson(proof: MerkleProof): MerkleProofJson {
+  return { leafIndex: toLeafIndex(proof.leafIndex), path: proof.path.map((node) => fieldToHex(toBigint(node))) };
+}
+
+/**
+ * Parse a proof from its JSON form; nodes come back as bigints.
+ */
+export function merkleProofFromJson(json: MerkleProofJson): MerkleProof {
+  if (!Array.isArray(json?.path) || !json.path.length) throw new Error('invalid merkle proof json: path must be a non-empty array');
+  return { leafIndex: toLeafIndex(json.leafIndex), path: json.path.map((node) => BigInt(fieldToHex(toBigint(node)))) };
+}
+
+/**
+ * Compact binary form: leaf index as a 4-byte big-endian prefix, then each path node as 32 bytes big-endian.
+ */
+export function encodeMerkleProof(proof: MerkleProof): Uint8Array {
+  const out = new Uint8Array(4 + 32 * proof.path.length);
+  new DataView(out.buffer).setUint32(0, toLeafIndex(proof.leafIndex));
+  proof.path.forEach((node, i) => out.set(hexToBytes(fieldToHex(toBigint(node)).slice(2)), 4 + 32 * i));
+  return out;
+}
+
+/**
+ * Decode `encodeMerkleProof` output; the length must be exactly `4 + 32 * (depth + 1)`.
+ */
+export function decodeMerkleProof(bytes: Uint8Array, depth: number = TREE_DEPTH_DEFAULT): MerkleProof {
+  const expected = 4 + 32 * (depth + 1);
+  if (!Number.isInteger(depth) || depth < 0 || bytes.length !== expected) {
+    throw new Error(`invalid merkle proof encoding: expected ${expected} bytes, got ${bytes.length}`);
+  }
+  const leafIndex = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength).getUint32(0);
+  const path = Array.from({ length: depth + 1 }, (_, i) => BigInt(`0x${bytesToHex(bytes.subarray(4 + 32 * i, 36 + 32 * i))}`));
+  return { leafIndex, path };
+}
+
+/**
+ * Fold the first `depth` levels of a proof path into a root.
+ */
+export function computeMerkleRoot(proof: MerkleProof, depth: number = proof.path.length - 1, domain: bigint = Poseidon2Domain.Merkle): bigint {
+  if (!Number.isInteger(depth) || depth < 0 || depth > proof.path.length - 1) {
+    throw new Error(`invalid merkle depth ${depth} for path of length ${proof.path.length}`);
+  }
+  const index = toBigint(proof.leafIndex);
+  let acc = toBigint(proof.path[0]!);
+  for (let level = 0; level < depth; level++) {
+    const sibling = toBigint(proof.path[level + 1]!);
+    acc = (index >> BigInt(level)) & 1n ? Poseidon2.hashDomain(sibling, acc, domain) : Poseidon2.hashDomain(acc, sibling, domain);
+  }
+  return acc;
+}
+
+/**
+ * Verify a proof by folding every level of its path under `domain` (the on-chain tree uses `Merkle`).
+ */
+export function verifyMerkleProof(proof: MerkleProof, root: ProofNode, domain: bigint = Poseidon2Domain.Merkle): boolean {
+  try {
+    return computeMerkleRoot(proof, proof.path.length - 1, domain) === toBigint(root);
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Verify a proof against a tree of an explicit `depth`, independent of whoever produced it.
+ * Unlike `verifyMerkleProof`, a proof whose shape disagrees with `depth` (path length other than
+ * `depth + 1`, leaf index outside `2^depth`) throws instead of quietly returning false.
+ */
+export function verifyMerkleProofAtDepth(proof: MerkleProof, root: ProofNode, depth: number, domain: bigint = Poseidon2Domain.Merkle): boolean {
+  if (!Number.isInteger(depth) || depth < 0) throw new Error(`invalid merkle depth ${depth}`);
+  if (proof.path.length !== depth + 1) {
+    throw new Error(`merkle proof path has ${proof.path.length} nodes but depth ${depth} needs ${depth + 1}`);
+  }
+  const index = toBigint(proof.leafIndex);
+  if (index < 0n || index >> BigInt(depth) !== 0n) throw new Error(`merkle leaf index ${index} does not fit in a depth-${depth} tree`);
+  return computeMerkleRoot(proof, depth, domain) === toBigint(root);
+}
+
+/**
+ * Verify a batch of proofs (e.g. every input of a transfer) against one root.
+ * Returns one result per proof, in order, so callers can report which input is stale.
+ */
+export function verifyMerkleProofs(proofs: readonly MerkleProof[], root: ProofNode, domain: bigint = Poseidon2Domain.Merkle): boolean[] {
+  return proofs.map((proof) => verifyMerkleProof(proof, root, domain));
+}
+
+/**
+ * Verify a full-depth proof against the root of a shallower tree.
+ *
+ * Only the lowest `effectiveDepth` levels are folded. This is sound only when the tree never
+ * grew past `2^effectiveDepth` leaves: the leaf index must fit in `effectiveDepth` bits and every
+ * sibling above that level must be the zero hash of its level, so the full-depth root is a
+ * deterministic function of the reduced root. Proofs violating either condition are rejected.
+ */
+export function verifyMerkleProofTruncated(proof: MerkleProof, root: ProofNode, effectiveDepth: number, domain: bigint = Poseidon2Domain.Merkle): boolean {
+  try {
+    const fullDepth = proof.path.length - 1;
+    if (!Number.isInteger(effectiveDepth) || effectiveDepth < 0 || effectiveDepth > fullDepth) return false;
+    if (toBigint(proof.leafIndex) >> BigInt(effectiveDepth) !== 0n) return false;
+    for (let level = effectiveDepth; level < fullDepth; level++) {
+      if (toBigint(proof.path[level + 1]!) !== BigInt(getZeroHash(level, domain))) return false;
+    }
+    return computeMerkleRoot(proof, effectiveDepth, domain) === toBigint(root);
+  } catch {
+    return false;
+  }
+}
+
+const hashPair = (left: bigint, right: bigint, domain: bigint) => Poseidon2.hashDomain(left, right, domain);
+
+/**
+ * Hash of the subtree at (`level`, `index`) over `leaves`, with missing leaves treated as zero.
+ */
+const subtreeRoot = (leaves: readonly bigint[], level: number, index: number, domain: bigint): bigint => {
+  if (index * 2 ** level >= leaves.length) return BigInt(getZeroHash(level, domain));
+  if (level === 0) return leaves[index]!;
+  return hashPair(subtreeRoot(leaves, level - 1, 2 * index, domain), subtreeRoot(leaves, level - 1, 2 * index + 1, domain), domain);
+};
+
+/**
+ * Root of the fixed-depth tree holding `leaves` at indices `0..leaves.length - 1` (the rest zero).
+ */
+export function computeMerkleRootFromLeaves(leaves: readonly ProofNode[], depth: number = TREE_DEPTH_DEFAULT, domain: bigint = Poseidon2Domain.Merkle): bigint {
+  if (!Number.isInteger(depth) || depth < 0 || leaves.length > 2 ** depth) {
+    throw new Error(`invalid merkle depth ${depth} for ${leaves.length} leaves`);
+  }
+  return subtreeRoot(leaves.map(toBigint), depth, 0, domain);
+}
+
+/**
+ * Proof that a tree of `oldSize` leaves is a prefix of a larger tree (append-only extension).
+ *
+ * Both roots are folded along the path of leaf index `oldSize`: left siblings are complete
+ * subtrees of old leaves (`frontier`, shared by both trees); right siblings are zero in the old
+ * tree and the hashes in `appended` (leaf `oldSize` first, then right siblings by level) in the new one.
+ */
+export interface MerkleConsistencyProof {
+  oldSize: number;
+  frontier: bigint[];
+  appended: bigint[];
+}
+
+/**
+ * Fold the path of leaf `oldSize` up to the root, taking left siblings from `frontier`.
+ */
+const foldConsistencyPath = (oldSize: number, depth: number, leaf: bigint, frontier: readonly bigint[], right: (level: number, i: number) => bigint, domain: bigint) => {
+  let acc = leaf;
+  let fi = 0;
+  let ri = 0;
+  for (let level = 0; level < depth; level++) {
+    acc = Math.floor(oldSize / 2 ** level) % 2 === 1 ? hashPair(frontier[fi++]!, acc, domain) : hashPair(acc, right(level, ri++), domain);
+  }
+  return { root: acc, frontierUsed: fi, rightUsed: ri };
+};
+
+/**
+ * Build a consistency proof from the first `oldSize` leaves to all of `leaves`.
+ */
+export function buildMerkleConsistencyProof(leaves: readonly ProofNode[], oldSize: number, depth: number = TREE_DEPTH_DEFAULT, domain: bigint = Poseidon2Domain.Merkle): MerkleConsistencyProof {
+  if (!Number.isInteger(oldSize) || oldSize < 0 || oldSize > leaves.length || oldSize >= 2 ** depth) {
+    throw new Error(`invalid old size ${oldSize} for ${leaves.length} leaves`);
+  }
+  const values = leaves.map(toBigint);
+  const frontier: bigint[] = [];
+  const appended: bigint[] = [subtreeRoot(values, 0, oldSize, domain)];
+  for (let level = 0; level < depth; level++) {
+    const index = Math.floor(oldSize / 2 ** level);
+    if (index % 2 === 1) frontier.push(subtreeRoot(values, level, index - 1, domain));
+    else appended.push(subtreeRoot(values, level, index + 1, domain));
+  }
+  return { oldSize, frontier, appended };
+}
+
+/**
+ * Verify that `newRoot` extends `oldRoot` without rewriting any of its first `proof.oldSize` leaves.
+ */
+export function verifyMerkleConsistency(proof: MerkleConsistencyProof, oldRoot: ProofNode, newRoot: ProofNode, depth: number = TREE_DEPTH_DEFAULT, domain: bigint = Poseidon2Domain.Merkle): boolean {
+  try {
+    const { oldSize, frontier, appended } = proof;
+    if (!Number.isInteger(depth) || depth < 0 || !Number.isInteger(oldSize) || oldSize < 0 || oldSize >= 2 ** depth) return false;
+    const old = foldConsistencyPath(oldSize, depth, BigInt(getZeroHash(0, domain)), frontier, (level) => BigInt(getZeroHash(level, domain)), domain);
+    if (old.frontierUsed !== frontier.length || old.rightUsed + 1 !== appended.length) return false;
+    const next = foldConsistencyPath(oldSize, depth, appended[0]!, frontier, (_level, i) => appended[i + 1]!, domain);
+    return old.root === toBigint(oldRoot) && next.root === toBigint(newRoot);
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Membership proof for several leaves of one tree. `indices` are sorted and unique with `leaves`
+ * in the same order; `siblings` lists, level by level from the leaves up and left to right within
+ * a level, every sibling hash that cannot be derived from the proven leaves, each exactly once.
+ */
+export interface MerkleMultiProof {
+  depth: number;
+  indices: number[];
+  leaves: bigint[];
+  siblings: bigint[];
+}
+
+/**
+ * Fold the known nodes up to the root, pairing nodes whose sibling is also known and taking every
+ * other sibling from `sibling` in proof order.
+ */
+const foldMultiProof = (indices: readonly number[], leaves: readonly bigint[], depth: number, sibling: (level: number, position: number) => bigint, domain: bigint) => {
+  let nodes = new Map(indices.map((index, i) => [index, leaves[i]!]));
+  for (let level = 0; level < depth; level++) {
+    const next = new Map<number, bigint>();
+    for (const [position, hash] of nodes) {
+      const parent = Math.floor(position / 2);
+      if (next.has(parent)) continue;
+      const isRight = position % 2 === 1;
+      const siblingPosition = isRight ? position - 1 : position + 1;
+      const other = nodes.get(siblingPosition) ?? sibling(level, siblingPosition);
+      next.set(parent, isRight ? hashPair(other, hash, domain) : hashPair(hash, other, domain));
+    }
+    nodes = next;
+  }
+  return nodes.get(0)!;
+};
+
+/**
+ * Build a multiproof for `indices` over `leaves` (the rest zero), sharing siblings between paths.
+ */
+export function buildMerkleMultiProof(leaves: readonly ProofNode[], indices: readonly number[], depth: number = TREE_DEPTH_DEFAULT, domain: bigint = Poseidon2Domain.Merkle): MerkleMultiProof {
+  if (!Number.isInteger(depth) || depth < 0 || leaves.length > 2 ** depth) {
+    throw new Error(`invalid merkle depth ${depth} for ${leaves.length} leaves`);
+  }
+  const sorted = [...new Set(indices)].sort((a, b) => a - b);
+  if (!sorted.length) throw new Error('multiproof requires at least one leaf index');
+  for (const index of sorted) {
+    if (!Number.isInteger(index) || index < 0 || index >= leaves.length) throw new Error(`invalid leaf index ${index} for ${leaves.length} leaves`);
+  }
+  const values = leaves.map(toBigint);
+  const proven = sorted.map((index) => values[index]!);
+  const siblings: bigint[] = [];
+  foldMultiProof(
+    sorted,
+    proven,
+    depth,
+    (level, position) => {
+      const hash = subtreeRoot(values, level, position, domain);
+      siblings.push(hash);
+      return hash;
+    },
+    domain,
+  );
+  return { depth, indices: sorted, leaves: proven, siblings };
+}
+
+/**
+ * Verify a multiproof against `root`. Malformed proofs (unsorted indices, wrong sibling count) return false.
+ */
+export function verifyMerkleMultiProof(proof: MerkleMultiProof, root: ProofNode, domain: bigint = Poseidon2Domain.Merkle): boolean {
+  try {
+    const { depth, indices, leaves, siblings } = proof;
+    if (!Number.isInteger(depth) || depth < 0 || !indices.length || indices.length !== leaves.length) return false;
+    for (let i = 0; i < indices.length; i++) {
+      const index = indices[i]!;
+      if (!Number.isInteger(index) || index < 0 || index >= 2 ** depth || (i > 0 && index <= indices[i - 1]!)) return false;
+    }
+    let used = 0;
+    const computed = foldMultiProof(
+      indices,
+      leaves.map(toBigint),
+      depth,
+      () => {
+        if (used >= siblings.length) throw new Error('multiproof is missing siblings');
+        return toBigint(siblings[used++]!);
+      },
+      domain,
+    );
+    return used === siblings.length && computed === toBigint(root);
+  } catch {
+    return false;
+  }
+}
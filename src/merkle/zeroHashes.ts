@@ -1,10 +1,33 @@
+import { Poseidon2, Poseidon2Domain } from '../crypto/poseidon2';
+
 export const TREE_DEPTH_DEFAULT = 32;
 
+const customZeroHashes = new Map<bigint, `0x${string}`[]>();
+
+/**
+ * Zero hashes for a tree hashed under a non-default domain: `zero[0] = 0`, `zero[i + 1] = H(zero[i], zero[i])`.
+ * Computed on demand and cached per domain.
+ */
+const customZeroHash = (level: number, domain: bigint): `0x${string}` => {
+  let zeros = customZeroHashes.get(domain);
+  if (!zeros) {
+    zeros = [getZeroHash(0)];
+    customZeroHashes.set(domain, zeros);
+  }
+  while (zeros.length <= level) {
+    const below = zeros[zeros.length - 1]!;
+    zeros.push(Poseidon2.hashToHex(below, below, domain));
+  }
+  return zeros[level]!;
+};
+
 /**
  * Zero hashes for the on-chain Merkle tree.
  * Must match the Solidity contract's `getZerotree()` output.
+ * Pass `domain` for trees hashed under another Poseidon2 domain (e.g. `Poseidon2Domain.None`).
  */
-export function getZeroHash(level: number): `0x${string}` {
+export function getZeroHash(level: number, domain: bigint = Poseidon2Domain.Merkle): `0x${string}` {
+  if (domain !== Poseidon2Domain.Merkle) return customZeroHash(level, domain);
   const zeroHashes: Record<number, `0x${string}`> = {
     0: '0x0000000000000000000000000000000000000000000000000000000000000000',
     1: '0x0c740b5d5661a25942f5aec3aa675b31c36714fa5026100b49af73236c2a265b',
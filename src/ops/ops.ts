@@ -5,6 +5,7 @@ import type {
   CommitmentData,
   Hex,
   MerkleApi,
+  MerkleProofSource,
   OpsApi,
   OperationCreateInput,
   PlannerApi,
@@ -18,12 +19,14 @@ import type {
   TxBuilderApi,
   UserKeyPair,
   UserPublicKey,
+  UserSecretKey,
   WithdrawPlan,
   WithdrawWitnessInput,
   WalletApi,
   ZkpApi,
 } from '../types';
 import { CryptoToolkit } from '../crypto/cryptoToolkit';
+import { KeyManager } from '../crypto/keyManager';
 import { Utils } from '../utils';
 import { MemoKit } from '../memo/memoKit';
 import { SdkError } from '../errors';
@@ -32,6 +35,7 @@ import type { StorageAdapter } from '../types';
 import { pickMerkleRootIndex } from './pickMerkleRootIndex';
 import { isHexStrict } from '../utils/hex';
 import { toBigintOrThrow } from '../utils/bigint';
+import type { RandomSource } from '../utils/random';
 
 const ARRAY_HASH_SIZE = 2048n;
 const NATIVE_ADDRESS = '0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE' as const;
@@ -114,10 +118,18 @@ export class Ops implements OpsApi {
     private readonly zkp: ZkpApi,
     private readonly tx: TxBuilderApi,
     private readonly wallet: Pick<WalletApi, 'markSpent'>,
-    private readonly store?: Pick<StorageAdapter, 'createOperation' | 'updateOperation'>,
+    private readonly store?: Pick<StorageAdapter, 'createOperation' | 'updateOperation' | 'reserveDepositCounter'>,
     private readonly emit?: (evt: SdkEvent) => void,
+    private readonly options: { rng?: RandomSource; proofSource?: MerkleProofSource } = {},
   ) {}
 
+  /**
+   * Membership proof source for spent inputs (the merkle engine unless overridden).
+   */
+  private get proofSource(): MerkleProofSource {
+    return this.options.proofSource ?? this.merkle;
+  }
+
   /**
    * Emit a debug event with a scoped message.
    */
@@ -238,7 +250,7 @@ export class Ops implements OpsApi {
 
     const remote = await this.timed(scope, 'merkle.getProofByCids', { chainId: input.plan.chainId, cids: selected.map((u) => u.mkIndex) }, () =>
       this.stage('MERKLE', 'prepareTransfer merkle proof fetch failed', { chainId: input.plan.chainId, cids: selected.map((u) => u.mkIndex) }, () =>
-        this.merkle.getProofByCids({ chainId: input.plan.chainId, cids: selected.map((u) => u.mkIndex), totalElements: totalElementsBig }),
+        this.proofSource.getProofByCids({ chainId: input.plan.chainId, cids: selected.map((u) => u.mkIndex), totalElements: totalElementsBig }),
       ),
     );
     const totalElementsInProof = (remote.latest_cid ?? 0) + 1;
@@ -449,7 +461,7 @@ export class Ops implements OpsApi {
 
     const remote = await this.timed(scope, 'merkle.getProofByCids', { chainId: input.chainId, cids: [utxo.mkIndex] }, () =>
       this.stage('MERKLE', 'prepareWithdraw merkle proof fetch failed', { chainId: input.chainId, cids: [utxo.mkIndex] }, () =>
-        this.merkle.getProofByCids({ chainId: input.chainId, cids: [utxo.mkIndex], totalElements: totalElementsBig }),
+        this.proofSource.getProofByCids({ chainId: input.chainId, cids: [utxo.mkIndex], totalElements: totalElementsBig }),
       ),
     );
     const totalElementsInProof = (remote.latest_cid ?? 0) + 1;
@@ -738,6 +750,95 @@ export class Ops implements OpsApi {
       value: bigint;
     };
   }> {
+    return this.prepareDepositOutput(input);
+  }
+
+  /**
+   * Prepare several deposits for one owner, returned in submission order.
+   *
+   * The contract has no batch entrypoint: every `deposit` call queues its own commitment, so commitments (and the memos
+   * scanned for them) land in the order the requests are submitted, which is the order of `outputs`. ERC20 allowance is
+   * tracked across the batch so each entry's `approveNeeded` holds when the entries are submitted one after another.
+   *
+   * With `blindingDerivation`, every blinding is derived from the owner's secret key and a counter reserved per entry
+   * (as in `prepareDepositDeterministic`), so counters increase in submission order and each entry reports its own.
+   */
+  async prepareDepositBatch(input: {
+    chainId: number;
+    outputs: Array<{ assetId: string; amount: bigint }>;
+    ownerPublicKey: UserPublicKey;
+    account: Address;
+    publicClient: PublicClient;
+    blindingDerivation?: { ownerSecretKey: UserSecretKey };
+  }): Promise<Array<Awaited<ReturnType<Ops['prepareDeposit']>> & { counter?: number }>> {
+    const { outputs, blindingDerivation, ...common } = input;
+    if (!outputs.length) {
+      throw new SdkError('CONFIG', 'prepareDepositBatch requires at least one output', { chainId: input.chainId });
+    }
+    if (blindingDerivation) {
+      const [x, y] = blindingDerivation.ownerSecretKey.user_pk.user_address;
+      const [ownerX, ownerY] = input.ownerPublicKey.user_pk.user_address;
+      if (x !== ownerX || y !== ownerY) {
+        throw new SdkError('CONFIG', 'blindingDerivation key does not match ownerPublicKey', { chainId: input.chainId });
+      }
+    }
+    const allowances = new Map<string, bigint>();
+    const prepared: Array<Awaited<ReturnType<Ops['prepareDeposit']>> & { counter?: number }> = [];
+    for (const output of outputs) {
+      const derived = blindingDerivation ? await this.reserveDerivedBlinding(input.chainId, blindingDerivation.ownerSecretKey) : undefined;
+      const entry = await this.prepareDepositOutput({ ...common, assetId: output.assetId, amount: output.amount }, allowances, derived?.blindingFactor);
+      prepared.push(derived ? { ...entry, counter: derived.counter } : entry);
+    }
+    return prepared;
+  }
+
+  /**
+   * Prepare a deposit whose blinding factor is derived from the owner's secret key and a store-managed counter
+   * (`KeyManager.deriveBlindingFactor(address_sk, [counter])`) instead of drawn at random.
+   *
+   * The counter is reserved through `StorageAdapter.reserveDepositCounter`, which advances it atomically, so two
+   * deposits never share a blinding even when prepared concurrently. A reserved counter is spent even if the deposit is
+   * never submitted. Lost notes can be recovered by re-deriving blindings for counters 0, 1, 2, ... and matching the
+   * resulting commitments.
+   */
+  async prepareDepositDeterministic(input: {
+    chainId: number;
+    assetId: string;
+    amount: bigint;
+    ownerSecretKey: UserSecretKey;
+    account: Address;
+    publicClient: PublicClient;
+  }): Promise<Awaited<ReturnType<Ops['prepareDeposit']>> & { counter: number }> {
+    const { ownerSecretKey, ...common } = input;
+    const { counter, blindingFactor } = await this.reserveDerivedBlinding(input.chainId, ownerSecretKey);
+    const prepared = await this.prepareDepositOutput({ ...common, ownerPublicKey: { user_pk: ownerSecretKey.user_pk } }, undefined, blindingFactor);
+    return { ...prepared, counter };
+  }
+
+  /**
+   * Reserve the owner's next deposit counter from the store and derive the blinding factor for it.
+   */
+  private async reserveDerivedBlinding(chainId: number, ownerSecretKey: UserSecretKey): Promise<{ counter: number; blindingFactor: bigint }> {
+    if (!this.store?.reserveDepositCounter) {
+      throw new SdkError('STORAGE', 'Storage adapter does not support deposit counters', { chainId });
+    }
+    const owner = KeyManager.userPkToAddress(ownerSecretKey.user_pk);
+    const counter = await this.store.reserveDepositCounter(owner);
+    if (!Number.isSafeInteger(counter) || counter < 0) {
+      throw new SdkError('STORAGE', 'Storage adapter returned an invalid deposit counter', { owner, counter });
+    }
+    return { counter, blindingFactor: KeyManager.deriveBlindingFactor(ownerSecretKey.user_sk.address_sk, [BigInt(counter)]) };
+  }
+
+  /**
+   * Build one deposit. `allowances` carries the remaining ERC20 allowance (by token address) between batch entries;
+   * `blindingFactor` replaces the random blinding when set.
+   */
+  private async prepareDepositOutput(
+    input: { chainId: number; assetId: string; amount: bigint; ownerPublicKey: UserPublicKey; account: Address; publicClient: PublicClient },
+    allowances?: Map<string, bigint>,
+    blindingFactor?: bigint,
+  ): Promise<Awaited<ReturnType<Ops['prepareDeposit']>>> {
     const chain = this.assets.getChain(input.chainId);
     if (!chain.ocashContractAddress) {
       throw new SdkError('CONFIG', `chain ${input.chainId} missing ocashContractAddress`, { chainId: input.chainId });
@@ -766,9 +867,11 @@ export class Ops implements OpsApi {
       asset_id: BigInt(token.id),
       asset_amount: input.amount,
       user_pk: { user_address: userPK },
+      blinding_factor: blindingFactor,
+      rng: this.options.rng,
     });
 
-    const memo = MemoKit.createMemo(recordOpening);
+    const memo = MemoKit.createMemo(recordOpening, this.options.rng);
 
     const isNative = token.wrappedErc20.toLowerCase() === NATIVE_ADDRESS.toLowerCase();
     const value = isNative ? payAmount + depositRelayerFee : depositRelayerFee;
@@ -801,14 +904,19 @@ export class Ops implements OpsApi {
       };
     }
 
-    const allowance = await this.stage(
-      'CONFIG',
-      'prepareDeposit failed to read ERC20 allowance',
-      { chainId: input.chainId, token: token.wrappedErc20, account: input.account, spender: contractAddress },
-      () => input.publicClient.readContract({ address: token.wrappedErc20, abi: erc20Abi, functionName: 'allowance', args: [input.account, contractAddress] }),
-    );
+    const allowanceKey = token.wrappedErc20.toLowerCase();
+    const allowance =
+      allowances?.get(allowanceKey) ??
+      (await this.stage(
+        'CONFIG',
+        'prepareDeposit failed to read ERC20 allowance',
+        { chainId: input.chainId, token: token.wrappedErc20, account: input.account, spender: contractAddress },
+        () => input.publicClient.readContract({ address: token.wrappedErc20, abi: erc20Abi, functionName: 'allowance', args: [input.account, contractAddress] }),
+      ));
 
     const approveNeeded = allowance < payAmount;
+    // An approve sets the allowance to exactly `payAmount`, which this deposit then consumes.
+    allowances?.set(allowanceKey, approveNeeded ? 0n : allowance - payAmount);
     const approveRequest = approveNeeded
       ? {
           chainId: input.chainId,
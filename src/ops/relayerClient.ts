@@ -1,12 +1,51 @@
-import type { Hex, RelayerRequest } from '../types';
+import type { Hex, RelayerRequest, RelayerSubmissionStatus } from '../types';
 import { SdkError } from '../errors';
 import { isHexStrict } from '../utils/hex';
-import { signalTimeout, signalAny } from '../utils/signal';
+import { isTimeoutError, signalTimeout, signalAny } from '../utils/signal';
 import { joinUrl } from '../utils/url';
 
 type ApiResponse<T> = { code?: number; message?: string; user_message?: string; data?: T };
 
 const DEFAULT_RELAYER_REQUEST_TIMEOUT_MS = 60_000;
+const SUBMISSION_STATUSES: readonly RelayerSubmissionStatus['status'][] = ['pending', 'submitted', 'confirmed', 'failed'];
+
+type RawSubmissionStatus = { status?: unknown; relayer_txhash?: unknown; txhash?: unknown; block_number?: unknown };
+
+/**
+ * Parse the relayer's snake_case status payload, rejecting unknown states and malformed hashes.
+ */
+const parseSubmissionStatus = (nullifier: Hex, raw: RawSubmissionStatus, url: string): RelayerSubmissionStatus => {
+  const status = raw.status as RelayerSubmissionStatus['status'];
+  if (!SUBMISSION_STATUSES.includes(status)) {
+    throw new SdkError('RELAYER', 'Invalid relayer submission status', { status: raw.status, url });
+  }
+  const optionalHash = (value: unknown, name: string) => {
+    if (value == null || value === '') return undefined;
+    if (!isHexStrict(value, { minBytes: 1 })) throw new SdkError('RELAYER', `Invalid relayer ${name}`, { [name]: value, url });
+    return value;
+  };
+  const blockNumber = raw.block_number == null ? undefined : Number(raw.block_number);
+  if (blockNumber !== undefined && (!Number.isSafeInteger(blockNumber) || blockNumber < 0)) {
+    throw new SdkError('RELAYER', 'Invalid relayer block number', { block_number: raw.block_number, url });
+  }
+  return { nullifier, status, relayerTxHash: optionalHash(raw.relayer_txhash, 'relayer_txhash'), txHash: optionalHash(raw.txhash, 'txhash'), blockNumber };
+};
+
+/**
+ * `fetch` that reports an expired request timeout as SdkError(RELAYER) with `reason: 'timeout'`, so callers can
+ * retry it; other failures are rethrown unchanged.
+ */
+const fetchWithTimeout = async (url: string, init: RequestInit & { signal?: AbortSignal }, timeoutMs: number): Promise<Response> => {
+  try {
+    return await fetch(url, init);
+  } catch (error) {
+    // Some runtimes reject with a generic AbortError; the signal's reason still says why it aborted.
+    if (isTimeoutError(error) || (init.signal?.aborted && isTimeoutError(init.signal.reason))) {
+      throw new SdkError('RELAYER', 'Relayer request timed out', { reason: 'timeout', url, timeoutMs }, error);
+    }
+    throw error;
+  }
+};
 
 /**
  * Lightweight HTTP client for relayer endpoints.
@@ -21,12 +60,16 @@ export class RelayerClient {
     const url = joinUrl(this.baseUrl, request.path);
     const requestTimeoutMs = options?.requestTimeoutMs ?? DEFAULT_RELAYER_REQUEST_TIMEOUT_MS;
     const signal = signalAny([options?.signal, signalTimeout(requestTimeoutMs)]);
-    const res = await fetch(url, {
-      method: 'POST',
-      headers: { 'content-type': 'application/json' },
-      body: JSON.stringify(request.body),
-      signal,
-    });
+    const res = await fetchWithTimeout(
+      url,
+      {
+        method: 'POST',
+        headers: { 'content-type': 'application/json' },
+        body: JSON.stringify(request.body),
+        signal,
+      },
+      requestTimeoutMs,
+    );
     if (!res.ok) {
       throw new SdkError('RELAYER', 'Relayer request failed', { status: res.status, method: 'POST', url });
     }
@@ -45,7 +88,7 @@ export class RelayerClient {
     url.searchParams.set('txhash', input.relayerTxHash);
     const requestTimeoutMs = input.requestTimeoutMs ?? DEFAULT_RELAYER_REQUEST_TIMEOUT_MS;
     const signal = signalAny([input.signal, signalTimeout(requestTimeoutMs)]);
-    const res = await fetch(url.toString(), { signal });
+    const res = await fetchWithTimeout(url.toString(), { signal }, requestTimeoutMs);
     if (!res.ok) {
       throw new SdkError('RELAYER', 'Relayer txhash request failed', { status: res.status, method: 'GET', url: url.toString() });
     }
@@ -59,4 +102,27 @@ export class RelayerClient {
     }
     return payload.data as Hex;
   }
+
+  /**
+   * Look up a submission by one of the nullifiers it spent, e.g. to recover the tx hash after a restart.
+   * Returns null when the relayer has no submission for the nullifier.
+   * The endpoint is not part of the published relayer API, so callers pass the deployment's `path`; there is no default.
+   */
+  async getStatusByNullifier(input: { chainId: number; nullifier: Hex; path: string; signal?: AbortSignal; requestTimeoutMs?: number }): Promise<RelayerSubmissionStatus | null> {
+    const url = new URL(joinUrl(this.baseUrl, input.path));
+    url.searchParams.set('chain_id', String(input.chainId));
+    url.searchParams.set('nullifier', input.nullifier);
+    const requestTimeoutMs = input.requestTimeoutMs ?? DEFAULT_RELAYER_REQUEST_TIMEOUT_MS;
+    const signal = signalAny([input.signal, signalTimeout(requestTimeoutMs)]);
+    const res = await fetchWithTimeout(url.toString(), { signal }, requestTimeoutMs);
+    if (!res.ok) {
+      throw new SdkError('RELAYER', 'Relayer status request failed', { status: res.status, method: 'GET', url: url.toString() });
+    }
+    const payload = (await res.json()) as ApiResponse<RawSubmissionStatus | null>;
+    if (payload?.code) {
+      throw new SdkError('RELAYER', payload.user_message || payload.message || 'Relayer request failed', payload);
+    }
+    if (payload.data == null) return null;
+    return parseSubmissionStatus(input.nullifier, payload.data, url.toString());
+  }
 }
@@ -0,0 +1,22 @@
+import type { UtxoRecord } from '../types';
+import { SdkError } from '../errors';
+import { MemoKit } from '../memo/memoKit';
+import { CryptoToolkit } from '../crypto/cryptoToolkit';
+
+/**
+ * Check that a UTXO's memo belongs to its commitment: the memo must open (for `ownerSecretKey`) to a record
+ * whose recomputed commitment equals `utxo.commitment`. UTXOs without a memo pass unchecked.
+ * The stored amount is used for the recomputation, as sync does for entries that carry their own amount.
+ * Throws SdkError(CRYPTO) with reason `memo_unreadable` or `commitment_mismatch`, or SdkError(MEMO_TOO_LARGE).
+ */
+export const validateUtxo = (utxo: UtxoRecord, ownerSecretKey: bigint): void => {
+  if (!utxo.memo) return;
+  const ro = MemoKit.decodeMemoForOwner({ secretKey: ownerSecretKey, memo: utxo.memo });
+  if (!ro) {
+    throw new SdkError('CRYPTO', 'UTXO memo cannot be opened with this key', { reason: 'memo_unreadable', chainId: utxo.chainId, commitment: utxo.commitment });
+  }
+  const memoCommitment = CryptoToolkit.commitment({ ...ro, asset_amount: utxo.amount }, 'hex');
+  if (memoCommitment.toLowerCase() !== utxo.commitment.toLowerCase()) {
+    throw new SdkError('CRYPTO', 'UTXO memo does not match its commitment', { reason: 'commitment_mismatch', chainId: utxo.chainId, commitment: utxo.commitment, memoCommitment });
+  }
+};
@@ -1,5 +1,19 @@
 import { maxUint256, toHex } from 'viem';
-import type { AssetsApi, CommitmentData, PlannerApi, PlannerFeeSummary, PlannerMaxEstimateResult, TransferPlan, RelayerConfig, TokenMetadata, UtxoRecord } from '../types';
+import type {
+  AssetsApi,
+  CommitmentData,
+  Hex,
+  PlannerApi,
+  PlannerFeeConfig,
+  PlannerFeeSummary,
+  PlannerMaxEstimateResult,
+  PlannerOutputOrder,
+  PlannerSelectionStrategy,
+  TransferPlan,
+  RelayerConfig,
+  TokenMetadata,
+  UtxoRecord,
+} from '../types';
 import { SdkError } from '../errors';
 import { KeyManager } from '../crypto/keyManager';
 import { CryptoToolkit } from '../crypto/cryptoToolkit';
@@ -9,6 +23,7 @@ import { MemoKit } from '../memo/memoKit';
 import { calcTransferProofBinding, calcWithdrawProofBinding } from '../utils/ocashBindings';
 import { fetchRelayerConfigFromRelayerUrl } from '../ledger/relayerConfig';
 import { requireHex } from '../utils/validators';
+import type { RandomSource } from '../utils/random';
 
 type PlanTransferInput = {
   action: 'transfer';
@@ -19,6 +34,8 @@ type PlanTransferInput = {
   payIncludesFee?: boolean;
   relayerUrl?: string;
   autoMerge?: boolean;
+  selection?: PlannerSelectionStrategy;
+  includeFrozen?: boolean;
 };
 
 type PlanWithdrawInput = {
@@ -30,6 +47,7 @@ type PlanWithdrawInput = {
   gasDropValue?: bigint;
   payIncludesFee?: boolean;
   relayerUrl?: string;
+  includeFrozen?: boolean;
 };
 
 type PlanInput = PlanTransferInput | PlanWithdrawInput;
@@ -56,27 +74,39 @@ const parsePlanInput = (input: Record<string, unknown>): PlanInput => {
   }
   const autoMerge = input.autoMerge === null ? undefined : input.autoMerge;
   if (autoMerge != null && typeof autoMerge !== 'boolean') throw new SdkError('CONFIG', 'autoMerge must be boolean');
+  const selection = input.selection === null ? undefined : input.selection;
+  if (selection != null && selection !== 'greedy' && selection !== 'privacy' && selection !== 'optimal') throw new SdkError('CONFIG', 'selection must be greedy|privacy|optimal');
+  const includeFrozen = input.includeFrozen === null ? undefined : input.includeFrozen;
+  if (includeFrozen != null && typeof includeFrozen !== 'boolean') throw new SdkError('CONFIG', 'includeFrozen must be boolean');
 
   if (action === 'transfer') {
     const to = requireHex(input.to, 'Planner.plan(transfer).to');
-    return { action, chainId, assetId, amount, to, payIncludesFee, relayerUrl: relayerUrl ?? undefined, autoMerge };
+    return { action, chainId, assetId, amount, to, payIncludesFee, relayerUrl: relayerUrl ?? undefined, autoMerge, selection, includeFrozen };
   }
 
   const recipient = requireHex(input.recipient, 'Planner.plan(withdraw).recipient');
   const gasDropValue = input.gasDropValue === null ? undefined : input.gasDropValue;
   if (gasDropValue != null && typeof gasDropValue !== 'bigint') throw new SdkError('CONFIG', 'gasDropValue must be bigint');
-  return { action, chainId, assetId, amount, recipient, gasDropValue, payIncludesFee, relayerUrl: relayerUrl ?? undefined };
+  return { action, chainId, assetId, amount, recipient, gasDropValue, payIncludesFee, relayerUrl: relayerUrl ?? undefined, includeFrozen };
 };
 
 // Relayer config fee map keys are serialized as 32-byte B256 hex strings.
 // Use `size: 32` to match the relayer's canonical key format (leading zeros included).
 const tokenFeeKey = (token: TokenMetadata) => toHex(BigInt(token.id), { size: 32 }).toLowerCase();
 
+/**
+ * Sort UTXOs by amount without mutating the input. The sort is stable: equal amounts keep their input order.
+ */
+export const sortUtxosByAmount = (utxos: readonly UtxoRecord[], order: 'asc' | 'desc'): UtxoRecord[] => {
+  const sign = order === 'asc' ? 1 : -1;
+  return [...utxos].sort((a, b) => (a.amount === b.amount ? 0 : a.amount > b.amount ? sign : -sign));
+};
+
 /**
  * Select up to maxInputs UTXOs that can cover required amount (greedy by amount).
  */
 const selectTransferInputs = (utxos: UtxoRecord[], required: bigint, maxInputs = 3) => {
-  const sorted = [...utxos].sort((a, b) => (b.amount > a.amount ? 1 : b.amount < a.amount ? -1 : 0));
+  const sorted = sortUtxosByAmount(utxos, 'desc');
   const selected: UtxoRecord[] = [];
   let sum = 0n;
   for (const utxo of sorted) {
@@ -88,14 +118,201 @@ const selectTransferInputs = (utxos: UtxoRecord[], required: bigint, maxInputs =
 };
 
 /**
- * Select a single UTXO that can cover the required amount (largest-first).
+ * Privacy-preferring selection: spending several inputs together links them on-chain, so
+ * 1. use the smallest single UTXO that covers the amount (accepting larger change);
+ * 2. otherwise combine UTXOs from one source (same `createdAt`), fewest inputs first;
+ * 3. otherwise fall back to greedy selection.
+ */
+const selectTransferInputsPrivacy = (utxos: UtxoRecord[], required: bigint, maxInputs = 3) => {
+  const single = sortUtxosByAmount(utxos, 'asc').find((u) => u.amount >= required);
+  if (single) return { selected: [single], sum: single.amount };
+
+  const sources = new Map<number, UtxoRecord[]>();
+  for (const utxo of utxos) {
+    if (utxo.createdAt == null) continue;
+    const group = sources.get(utxo.createdAt);
+    if (group) group.push(utxo);
+    else sources.set(utxo.createdAt, [utxo]);
+  }
+  let best: { selected: UtxoRecord[]; sum: bigint } | null = null;
+  for (const group of sources.values()) {
+    const candidate = selectTransferInputs(group, required, maxInputs);
+    if (candidate.sum < required) continue;
+    if (!best || candidate.selected.length < best.selected.length || (candidate.selected.length === best.selected.length && candidate.sum < best.sum)) {
+      best = candidate;
+    }
+  }
+  return best ?? selectTransferInputs(utxos, required, maxInputs);
+};
+
+/** Subsets visited by `selectTransferInputsOptimal` before it settles for the best one found so far. */
+const OPTIMAL_SEARCH_BUDGET = 100_000;
+
+/**
+ * Change-minimizing selection: search subsets of at most maxInputs UTXOs for the one that covers
+ * the amount with the least change (fewest inputs on ties), stopping early on an exact match.
+ * Falls back to greedy selection when nothing within the input cap covers the amount.
  */
-const selectWithdrawInput = (utxos: UtxoRecord[], required: bigint) => {
-  const sorted = [...utxos].sort((a, b) => (b.amount > a.amount ? 1 : b.amount < a.amount ? -1 : 0));
+const selectTransferInputsOptimal = (utxos: UtxoRecord[], required: bigint, maxInputs = 3) => {
+  const sorted = sortUtxosByAmount(utxos, 'desc');
+  // Largest sum `slots` more inputs from sorted[start..] can add; prunes branches that can no longer cover.
+  const reach = (start: number, slots: number) => {
+    let sum = 0n;
+    for (let i = start; i < Math.min(sorted.length, start + slots); i++) sum += sorted[i]!.amount;
+    return sum;
+  };
+  let best: { selected: UtxoRecord[]; sum: bigint } | null = null;
+  let visited = 0;
+  const picked: UtxoRecord[] = [];
+  const search = (start: number, sum: bigint): boolean => {
+    if (++visited > OPTIMAL_SEARCH_BUDGET) return true;
+    if (sum >= required) {
+      if (!best || sum < best.sum || (sum === best.sum && picked.length < best.selected.length)) best = { selected: [...picked], sum };
+      return sum === required;
+    }
+    if (picked.length >= maxInputs || sum + reach(start, maxInputs - picked.length) < required) return false;
+    for (let i = start; i < sorted.length; i++) {
+      picked.push(sorted[i]!);
+      const done = search(i + 1, sum + sorted[i]!.amount);
+      picked.pop();
+      if (done) return true;
+    }
+    return false;
+  };
+  search(0, 0n);
+  return best ?? selectTransferInputs(utxos, required, maxInputs);
+};
+
+/**
+ * Drop inputs that are not needed to cover `required`, smallest first, keeping the selection order.
+ * Fewer inputs means a cheaper proof and fewer linked coins. Selections that do not cover are returned unchanged.
+ */
+export const minimizeSelectedInputs = (selection: { selected: UtxoRecord[]; sum: bigint }, required: bigint) => {
+  if (selection.sum < required) return selection;
+  const dropped = new Set<UtxoRecord>();
+  let sum = selection.sum;
+  for (const utxo of sortUtxosByAmount(selection.selected, 'asc')) {
+    if (sum - utxo.amount < required) continue;
+    dropped.add(utxo);
+    sum -= utxo.amount;
+  }
+  if (dropped.size === 0) return selection;
+  return { selected: selection.selected.filter((u) => !dropped.has(u)), sum };
+};
+
+/**
+ * Drop frozen UTXOs unless the caller spends them deliberately (e.g. with the freezer key);
+ * the contract rejects frozen inputs otherwise. Guards against adapters that ignore `includeFrozen` queries.
+ */
+const spendableUtxos = (utxos: UtxoRecord[], includeFrozen: boolean) => (includeFrozen ? utxos : utxos.filter((u) => !u.isFrozen));
+
+/**
+ * Select transfer inputs with the given strategy, then drop any input the selection does not need.
+ * Frozen UTXOs are skipped unless `includeFrozen` is set.
+ */
+export const selectInputsWithStrategy = (utxos: UtxoRecord[], required: bigint, strategy: PlannerSelectionStrategy = 'greedy', maxInputs = 3, includeFrozen = false) => {
+  const candidates = spendableUtxos(utxos, includeFrozen);
+  const selection =
+    strategy === 'privacy'
+      ? selectTransferInputsPrivacy(candidates, required, maxInputs)
+      : strategy === 'optimal'
+        ? selectTransferInputsOptimal(candidates, required, maxInputs)
+        : selectTransferInputs(candidates, required, maxInputs);
+  return minimizeSelectedInputs(selection, required);
+};
+
+/**
+ * Like `selectInputsWithStrategy`, but throws instead of returning a selection that does not cover `required`.
+ * `detail.reason` is `insufficient_funds` when the spendable UTXOs together fall short, or `too_many_inputs`
+ * when they would cover it but not within `maxInputs` (merge first). Amounts in `detail` are decimal strings.
+ */
+export const requireInputsWithStrategy = (utxos: UtxoRecord[], required: bigint, strategy: PlannerSelectionStrategy = 'greedy', maxInputs = 3, includeFrozen = false) => {
+  const selection = selectInputsWithStrategy(utxos, required, strategy, maxInputs, includeFrozen);
+  if (selection.sum >= required) return selection;
+  const spendable = spendableUtxos(utxos, includeFrozen);
+  const available = spendable.reduce((acc, cur) => acc + cur.amount, 0n);
+  if (available < required) {
+    throw new SdkError('CONFIG', 'insufficient shielded balance', { reason: 'insufficient_funds', available: available.toString(), required: required.toString() });
+  }
+  const reachable = sortUtxosByAmount(spendable, 'desc')
+    .slice(0, maxInputs)
+    .reduce((acc, cur) => acc + cur.amount, 0n);
+  throw new SdkError('CONFIG', `amount needs more than ${maxInputs} inputs; merge UTXOs first`, {
+    reason: 'too_many_inputs',
+    maxInputs,
+    reachable: reachable.toString(),
+    available: available.toString(),
+    required: required.toString(),
+  });
+};
+
+/**
+ * Reorder outputs by ascending commitment, keeping each memo paired with its output.
+ */
+export const sortOutputsByCommitment = <TOutputs extends readonly CommitmentData[], TMemos extends readonly Hex[]>(outputs: TOutputs, memos: TMemos): { outputs: TOutputs; memos: TMemos } => {
+  if (outputs.length !== memos.length) {
+    throw new SdkError('CONFIG', 'outputs and memos must have the same length', { outputs: outputs.length, memos: memos.length });
+  }
+  const pairs = outputs.map((output, i) => ({ output, memo: memos[i]!, commitment: CryptoToolkit.commitment(output, 'bigint') }));
+  pairs.sort((a, b) => (a.commitment === b.commitment ? 0 : a.commitment < b.commitment ? -1 : 1));
+  return { outputs: pairs.map((p) => p.output) as unknown as TOutputs, memos: pairs.map((p) => p.memo) as unknown as TMemos };
+};
+
+/**
+ * Select a single UTXO that can cover the required amount (largest-first), skipping frozen ones unless `includeFrozen`.
+ */
+export const selectWithdrawInput = (utxos: UtxoRecord[], required: bigint, includeFrozen = false) => {
+  const sorted = sortUtxosByAmount(spendableUtxos(utxos, includeFrozen), 'desc');
   return sorted.find((u) => u.amount >= required) ?? null;
 };
 
 const INPUT_NUMBER = 3;
+const OUTPUT_NUMBER = 3;
+
+/**
+ * Split one UTXO into `parts` self-owned outputs of nearly equal value after paying `fee`, the inverse of
+ * `planConsolidation`. The first `(amount - fee) % parts` outputs carry one extra unit, so the split is deterministic.
+ * `parts` is bounded by the transfer circuit's output count, and every output must be non-zero.
+ */
+export const planSplit = (utxo: UtxoRecord, parts: number, fee: bigint): { input: UtxoRecord; fee: bigint; outputAmounts: bigint[] } => {
+  if (!Number.isInteger(parts) || parts < 2 || parts > OUTPUT_NUMBER) {
+    throw new SdkError('CONFIG', `parts must be an integer between 2 and ${OUTPUT_NUMBER}`, { parts });
+  }
+  if (fee < 0n) throw new SdkError('CONFIG', 'fee must be non-negative', { fee: fee.toString() });
+  const net = utxo.amount - fee;
+  if (net < BigInt(parts)) {
+    throw new SdkError('CONFIG', 'UTXO is too small to split', { amount: utxo.amount.toString(), fee: fee.toString(), parts });
+  }
+  const base = net / BigInt(parts);
+  const remainder = Number(net % BigInt(parts));
+  return { input: utxo, fee, outputAmounts: Array.from({ length: parts }, (_, i) => (i < remainder ? base + 1n : base)) };
+};
+
+/**
+ * Group small UTXOs of one asset into self-transfer batches of at most `maxInputs` inputs, smallest first,
+ * so dust can be merged before a transfer needs more inputs than the circuit allows.
+ * UTXOs above `dustThreshold` (when set) and frozen ones are left alone; a lone leftover is not a batch.
+ * Batches are ordered by how many UTXOs they remove (`inputs - 1`), most first.
+ * Each call plans one round; spending the batches and planning again merges the results further.
+ */
+export const planConsolidation = (
+  utxos: UtxoRecord[],
+  assetId: string,
+  options?: { maxInputs?: number; dustThreshold?: bigint; includeFrozen?: boolean },
+): Array<{ selected: UtxoRecord[]; sum: bigint }> => {
+  const maxInputs = Math.min(INPUT_NUMBER, Math.max(2, Math.floor(options?.maxInputs ?? INPUT_NUMBER)));
+  const threshold = options?.dustThreshold;
+  const candidates = sortUtxosByAmount(
+    spendableUtxos(utxos, options?.includeFrozen ?? false).filter((u) => u.assetId === assetId && !u.isSpent && u.amount > 0n && (threshold == null || u.amount <= threshold)),
+    'asc',
+  );
+  const batches: Array<{ selected: UtxoRecord[]; sum: bigint }> = [];
+  for (let i = 0; i + 1 < candidates.length; i += maxInputs) {
+    const selected = candidates.slice(i, i + maxInputs);
+    batches.push({ selected, sum: selected.reduce((acc, cur) => acc + cur.amount, 0n) });
+  }
+  return batches.sort((a, b) => b.selected.length - a.selected.length);
+};
 
 /**
  * Compute total fees and outputs for a sequence of records.
@@ -271,13 +488,14 @@ export class Planner implements PlannerApi {
     private readonly assets: AssetsApi,
     private readonly wallet: WalletService,
     private readonly bridge: ProofBridge,
+    private readonly options: { fees?: PlannerFeeConfig; rng?: RandomSource; selection?: PlannerSelectionStrategy; outputOrder?: PlannerOutputOrder } = {},
   ) {}
 
   /**
    * Select N inputs for a merge operation (smallest-first to minimize change).
    */
   private selectMergeInputs(utxos: UtxoRecord[], count = INPUT_NUMBER) {
-    const sorted = [...utxos].sort((a, b) => (a.amount > b.amount ? 1 : a.amount < b.amount ? -1 : 0));
+    const sorted = sortUtxosByAmount(utxos, 'asc');
     return sorted.slice(0, count);
   }
 
@@ -315,6 +533,7 @@ export class Planner implements PlannerApi {
       asset_id: BigInt(input.token.id),
       asset_amount: sendAmount,
       user_pk: { user_address: recipientPk.user_address },
+      rng: this.options.rng,
     });
     const change = selectedSum - required;
     const output1 =
@@ -323,12 +542,16 @@ export class Planner implements PlannerApi {
             asset_id: BigInt(input.token.id),
             asset_amount: change,
             user_pk: { user_address: input.ownerPk.user_address },
+            rng: this.options.rng,
           })
         : await this.bridge.createDummyRecordOpening();
+    // Unused slots get fresh zero-amount dummy records from the circuits, not a fixed sentinel: every output
+    // commitment is inserted into the tree, and a shared value would mark which slots are padding.
     const output2 = await this.bridge.createDummyRecordOpening();
 
-    const outputs = [output0, output1, output2] as const;
-    const extraData = [MemoKit.createMemo(output0), MemoKit.createMemo(output1), MemoKit.createMemo(output2)] as const;
+    const memos = [MemoKit.createMemo(output0, this.options.rng), MemoKit.createMemo(output1, this.options.rng), MemoKit.createMemo(output2, this.options.rng)] as const;
+    const { outputs, memos: extraData } =
+      this.options.outputOrder === 'commitment' ? sortOutputsByCommitment([output0, output1, output2] as const, memos) : { outputs: [output0, output1, output2] as const, memos };
     const proofBinding = calcTransferProofBinding({ relayer: input.relayer, extraData });
 
     return {
@@ -357,7 +580,7 @@ export class Planner implements PlannerApi {
   /**
    * Estimate fees and balance sufficiency for transfer/withdraw without building a plan.
    */
-  async estimate(input: { chainId: number; assetId: string; action: 'transfer' | 'withdraw'; amount: bigint; payIncludesFee?: boolean }) {
+  async estimate(input: { chainId: number; assetId: string; action: 'transfer' | 'withdraw'; amount: bigint; payIncludesFee?: boolean; selection?: PlannerSelectionStrategy }) {
     const token = this.assets.getPoolInfo(input.chainId, input.assetId);
     if (!token) {
       throw new SdkError('CONFIG', `Token ${input.assetId} not found in chain ${input.chainId}`);
@@ -373,14 +596,14 @@ export class Planner implements PlannerApi {
         throw new SdkError('CONFIG', 'amount is too small to cover relayer fee', { relayerFee: relayerFee.toString() });
       }
       const utxos = (await this.wallet.getUtxos({ chainId: input.chainId, assetId: input.assetId, includeSpent: false, includeFrozen: false })).rows;
-      const { selected, sum } = selectTransferInputs(utxos, required, 3);
+      const { selected, sum } = selectInputsWithStrategy(utxos, required, input.selection ?? this.options.selection, 3);
       const records = utxos.map((u) => u.amount).filter((v) => v > 0n);
       const estimates = estimateRecords({
         records,
         expectedOutput: input.amount,
         action: 'transfer',
         relayerFee: { transfer: relayerFee, withdraw: 0n },
-        withdrawFeeBps: token.withdrawFeeBps,
+        withdrawFeeBps: this.getWithdrawFeeBps(token),
         expectedIsWithFee: input.payIncludesFee,
       });
       const feeSummary = buildFeeSummary(estimates.payInfo, estimates.payRecords.length);
@@ -404,7 +627,7 @@ export class Planner implements PlannerApi {
     }
 
     const withdrawBase = input.payIncludesFee ? input.amount : input.amount + relayerFee;
-    const protocolFee = (withdrawBase * BigInt(token.withdrawFeeBps ?? 0)) / 10000n;
+    const protocolFee = (withdrawBase * BigInt(this.getWithdrawFeeBps(token) ?? 0)) / 10000n;
     const burnAmount = input.payIncludesFee ? input.amount : input.amount + relayerFee + protocolFee;
 
     const utxos = (await this.wallet.getUtxos({ chainId: input.chainId, assetId: input.assetId, includeSpent: false, includeFrozen: false })).rows;
@@ -415,7 +638,7 @@ export class Planner implements PlannerApi {
       expectedOutput: input.amount,
       action: 'withdraw',
       relayerFee: { transfer: this.getRelayerFee(relayerConfig, token, 'transfer'), withdraw: relayerFee },
-      withdrawFeeBps: token.withdrawFeeBps,
+      withdrawFeeBps: this.getWithdrawFeeBps(token),
       expectedIsWithFee: input.payIncludesFee,
     });
     const feeSummary = buildFeeSummary(estimates.payInfo, estimates.payRecords.length);
@@ -458,7 +681,7 @@ export class Planner implements PlannerApi {
       expectedOutput: maxUint256,
       action: input.action,
       relayerFee: { transfer: transferFee, withdraw: relayerFee },
-      withdrawFeeBps: token.withdrawFeeBps,
+      withdrawFeeBps: this.getWithdrawFeeBps(token),
       expectedIsWithFee: input.payIncludesFee,
     });
     const maxSummary = buildFeeSummary(estimates.maxInfo, estimates.maxRecords.length);
@@ -473,11 +696,71 @@ export class Planner implements PlannerApi {
     };
   }
 
+  /**
+   * Plan a transfer of everything the `maxInputs` largest UTXOs hold, minus the relayer fee.
+   * The whole selection is spent (no change output); fails when the selection does not exceed the fee.
+   */
+  async planSendMax(input: { chainId: number; assetId: string; to: `0x${string}`; relayerUrl?: string; maxInputs?: number }): Promise<TransferPlan> {
+    const to = requireHex(input.to, 'to');
+    const maxInputs = Math.min(INPUT_NUMBER, Math.max(1, Math.floor(input.maxInputs ?? INPUT_NUMBER)));
+    const token = this.assets.getPoolInfo(input.chainId, input.assetId);
+    if (!token) {
+      throw new SdkError('CONFIG', `Token ${input.assetId} not found in chain ${input.chainId}`);
+    }
+
+    const relayerConfig = await this.getRelayerConfig(input.chainId, input.relayerUrl);
+    const relayerFee = this.getRelayerFee(relayerConfig, token, 'transfer');
+    const ownerViewingAddress = this.wallet.getViewingAddress();
+
+    const utxos = (await this.wallet.getUtxos({ chainId: input.chainId, assetId: input.assetId, includeSpent: false, includeFrozen: false })).rows;
+    const selected = sortUtxosByAmount(utxos.filter((u) => u.amount > 0n), 'desc').slice(0, maxInputs);
+    const sum = selected.reduce((acc, cur) => acc + cur.amount, 0n);
+    if (sum <= relayerFee) {
+      throw new SdkError('CONFIG', 'insufficient shielded balance to cover relayer fee', { relayerFee: relayerFee.toString(), selectedSum: sum.toString() });
+    }
+
+    const estimates = estimateRecords({
+      records: selected.map((u) => u.amount),
+      expectedOutput: sum,
+      action: 'transfer',
+      relayerFee: { transfer: relayerFee, withdraw: 0n },
+      withdrawFeeBps: this.getWithdrawFeeBps(token),
+      expectedIsWithFee: true,
+    });
+    const feeSummary = buildFeeSummary(estimates.payInfo, estimates.payRecords.length);
+    return this.buildTransferPlan({
+      chainId: input.chainId,
+      assetId: input.assetId,
+      token,
+      requestedAmount: sum,
+      to,
+      relayer: relayerConfig.config.relayer_address,
+      relayerUrl: input.relayerUrl ?? this.assets.getChain(input.chainId).relayerUrl ?? undefined,
+      relayerFee,
+      payIncludesFee: true,
+      selectedInputs: selected,
+      ownerPk: KeyManager.addressToUserPk(ownerViewingAddress),
+      feeSummary,
+      maxSummary: feeSummary,
+      okWithMerge: feeSummary.cost > 0n,
+    });
+  }
+
+  /**
+   * Plan one round of dust consolidation for the open wallet's UTXOs of an asset; see `planConsolidation`.
+   * Each returned batch is meant to be spent as a self-transfer.
+   */
+  async planConsolidation(input: { chainId: number; assetId: string; maxInputs?: number; dustThreshold?: bigint }): Promise<Array<{ selected: UtxoRecord[]; sum: bigint }>> {
+    const utxos = (await this.wallet.getUtxos({ chainId: input.chainId, assetId: input.assetId, includeSpent: false, includeFrozen: false })).rows;
+    return planConsolidation(utxos, input.assetId, { maxInputs: input.maxInputs, dustThreshold: input.dustThreshold });
+  }
+
   /**
    * Build a full plan (transfer, withdraw, or transfer-merge).
    */
   async plan(input: Record<string, unknown>) {
     const parsed = parsePlanInput(input);
+    const includeFrozen = parsed.includeFrozen ?? false;
     const token = this.assets.getPoolInfo(parsed.chainId, parsed.assetId);
     if (!token) {
       throw new SdkError('CONFIG', `Token ${parsed.assetId} not found in chain ${parsed.chainId}`);
@@ -503,16 +786,17 @@ export class Planner implements PlannerApi {
           chainId: parsed.chainId,
           assetId: parsed.assetId,
           includeSpent: false,
-          includeFrozen: false,
+          includeFrozen,
         })
       ).rows;
-      const { selected, sum } = selectTransferInputs(utxos, required, 3);
+      const selection = parsed.selection ?? this.options.selection;
+      const { selected, sum } = selectInputsWithStrategy(utxos, required, selection, 3, includeFrozen);
       const estimates = estimateRecords({
         records: utxos.map((u) => u.amount).filter((v) => v > 0n),
         expectedOutput: parsed.amount,
         action: 'transfer',
         relayerFee: { transfer: relayerFee, withdraw: 0n },
-        withdrawFeeBps: token.withdrawFeeBps,
+        withdrawFeeBps: this.getWithdrawFeeBps(token),
         expectedIsWithFee: parsed.payIncludesFee,
       });
       const feeSummary = buildFeeSummary(estimates.payInfo, estimates.payRecords.length);
@@ -521,10 +805,9 @@ export class Planner implements PlannerApi {
 
       if (sum < required) {
         const total = utxos.reduce((acc, cur) => acc + cur.amount, 0n);
-        if (!okWithMerge || total < required) {
-          throw new SdkError('CONFIG', 'insufficient shielded balance', { required: required.toString(), selectedSum: sum.toString() });
-        }
-        if (!parsed.autoMerge) {
+        if (!okWithMerge || total < required || !parsed.autoMerge) {
+          // No merge will be planned: rerun the selection strictly so the error says why it fell short.
+          requireInputsWithStrategy(utxos, required, selection, 3, includeFrozen);
           throw new SdkError('CONFIG', 'insufficient shielded balance', { required: required.toString(), selectedSum: sum.toString() });
         }
 
@@ -534,7 +817,7 @@ export class Planner implements PlannerApi {
         }
         let mergeSum = mergeInputs.reduce((acc, cur) => acc + cur.amount, 0n);
         if (mergeSum <= relayerFee) {
-          const largest = [...utxos].sort((a, b) => (b.amount > a.amount ? 1 : b.amount < a.amount ? -1 : 0)).slice(0, INPUT_NUMBER);
+          const largest = sortUtxosByAmount(utxos, 'desc').slice(0, INPUT_NUMBER);
           const largestSum = largest.reduce((acc, cur) => acc + cur.amount, 0n);
           if (largestSum <= relayerFee) {
             throw new SdkError('CONFIG', 'insufficient shielded balance for merge fee', {
@@ -551,7 +834,7 @@ export class Planner implements PlannerApi {
           expectedOutput: mergeAmount,
           action: 'transfer',
           relayerFee: { transfer: relayerFee, withdraw: 0n },
-          withdrawFeeBps: token.withdrawFeeBps,
+          withdrawFeeBps: this.getWithdrawFeeBps(token),
           expectedIsWithFee: false,
         });
         const mergeFeeSummary = buildFeeSummary(mergeEstimates.payInfo, mergeEstimates.payRecords.length);
@@ -613,7 +896,7 @@ export class Planner implements PlannerApi {
 
     const gasDropValue = parsed.gasDropValue ?? 0n;
     const withdrawBase = parsed.payIncludesFee ? parsed.amount : parsed.amount + relayerFee;
-    const protocolFee = (withdrawBase * BigInt(token.withdrawFeeBps ?? 0)) / 10000n;
+    const protocolFee = (withdrawBase * BigInt(this.getWithdrawFeeBps(token) ?? 0)) / 10000n;
     const burnAmount = parsed.payIncludesFee ? parsed.amount : parsed.amount + relayerFee + protocolFee;
 
     const utxos = (
@@ -621,19 +904,23 @@ export class Planner implements PlannerApi {
         chainId: parsed.chainId,
         assetId: parsed.assetId,
         includeSpent: false,
-        includeFrozen: false,
+        includeFrozen,
       })
     ).rows;
-    const chosen = selectWithdrawInput(utxos, burnAmount);
+    const chosen = selectWithdrawInput(utxos, burnAmount, includeFrozen);
     if (!chosen) {
-      throw new SdkError('CONFIG', 'no single utxo can cover burn amount', { burnAmount: burnAmount.toString() });
+      const available = spendableUtxos(utxos, includeFrozen).reduce((acc, cur) => acc + cur.amount, 0n);
+      if (available < burnAmount) {
+        throw new SdkError('CONFIG', 'insufficient shielded balance', { reason: 'insufficient_funds', available: available.toString(), required: burnAmount.toString() });
+      }
+      throw new SdkError('CONFIG', 'no single utxo can cover burn amount', { reason: 'no_single_input', burnAmount: burnAmount.toString() });
     }
     const estimates = estimateRecords({
       records: utxos.map((u) => u.amount).filter((v) => v > 0n),
       expectedOutput: parsed.amount,
       action: 'withdraw',
       relayerFee: { transfer: this.getRelayerFee(relayerConfig, token, 'transfer'), withdraw: relayerFee },
-      withdrawFeeBps: token.withdrawFeeBps,
+      withdrawFeeBps: this.getWithdrawFeeBps(token),
       expectedIsWithFee: parsed.payIncludesFee,
     });
     const feeSummary = buildFeeSummary(estimates.payInfo, estimates.payRecords.length);
@@ -647,10 +934,11 @@ export class Planner implements PlannerApi {
             asset_id: BigInt(token.id),
             asset_amount: change,
             user_pk: { user_address: ownerPk.user_address },
+            rng: this.options.rng,
           })
         : await this.bridge.createDummyRecordOpening();
 
-    const extraData = MemoKit.createMemo(outputRo);
+    const extraData = MemoKit.createMemo(outputRo, this.options.rng);
     const proofBinding = calcWithdrawProofBinding({
       recipient: parsed.recipient,
       amount: parsed.amount,
@@ -700,13 +988,24 @@ export class Planner implements PlannerApi {
   }
 
   /**
-   * Lookup relayer fee for a token/action; defaults to 0 when missing.
+   * Lookup relayer fee for a token/action.
+   * The relayer's fee table is authoritative (it rejects requests that pay less); the fee config only fills in
+   * tokens the table does not list: per-asset entry, then default, then 0.
    */
   private getRelayerFee(config: RelayerConfig, token: TokenMetadata, action: 'transfer' | 'withdraw'): bigint {
     const key = tokenFeeKey(token);
     const table = action === 'transfer' ? config.fee_configure.transfer : config.fee_configure.withdraw;
     const fee = table?.[key]?.fee;
-    // No fee entry → default to zero (favorable to user, no charge)
-    return fee != null ? BigInt(fee) : 0n;
+    if (fee != null) return BigInt(fee);
+    const field = action === 'transfer' ? 'transferRelayerFee' : 'withdrawRelayerFee';
+    // No fee entry or config → default to zero (favorable to user, no charge)
+    return this.options.fees?.assets?.[token.id]?.[field] ?? this.options.fees?.default?.[field] ?? 0n;
+  }
+
+  /**
+   * Lookup protocol withdraw fee bps: the contract's value from token metadata, else per-asset then default fee config.
+   */
+  private getWithdrawFeeBps(token: TokenMetadata): number | undefined {
+    return token.withdrawFeeBps ?? this.options.fees?.assets?.[token.id]?.withdrawFeeBps ?? this.options.fees?.default?.withdrawFeeBps;
   }
 }
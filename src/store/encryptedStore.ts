@@ -0,0 +1,181 @@
+import nacl from 'tweetnacl';
+import { hkdf } from '@noble/hashes/hkdf';
+import { sha256 } from '@noble/hashes/sha256';
+import { bytesToHex, hexToBytes, utf8ToBytes } from '@noble/hashes/utils';
+import type { Hex, ListUtxosQuery, ListUtxosResult, StorageAdapter, SyncCursor, UtxoRecord } from '../types';
+import type { ListOperationsQuery, OperationCreateInput, OperationDetailFor, OperationType, StoredOperation } from './internal/operationTypes';
+import { SdkError } from '../errors';
+import { applyUtxoQuery } from './internal/utxoQuery';
+
+const STORE_KEY_INFO = 'OCash.StoreKey';
+
+type SealedUtxoPayload = { amount: string; memo?: Hex };
+
+/**
+ * StorageAdapter decorator that encrypts sensitive payloads (XSalsa20-Poly1305) before delegating.
+ *
+ * - UTXO `amount` and `memo` are sealed into the inner record's `memo` field (inner `amount` is 0).
+ * - Operation `detail` is sealed into `{ sealed }`.
+ * - Query fields (chainId, assetId, commitment, nullifier, mkIndex, flags, timestamps) stay plaintext.
+ * - Merkle/Entry caches hold public chain data and are passed through unchanged.
+ */
+export class EncryptedStore implements StorageAdapter {
+  getMerkleLeaves?: StorageAdapter['getMerkleLeaves'];
+  appendMerkleLeaves?: StorageAdapter['appendMerkleLeaves'];
+  markSpentDetailed?: StorageAdapter['markSpentDetailed'];
+  deleteUtxosFrom?: StorageAdapter['deleteUtxosFrom'];
+  replaceUtxos?: StorageAdapter['replaceUtxos'];
+  pruneSpentUtxos?: StorageAdapter['pruneSpentUtxos'];
+  stats?: StorageAdapter['stats'];
+  appendCursorHistory?: StorageAdapter['appendCursorHistory'];
+  getCursorHistory?: StorageAdapter['getCursorHistory'];
+  subscribe?: StorageAdapter['subscribe'];
+  clearMerkleLeaves?: StorageAdapter['clearMerkleLeaves'];
+  getMerkleLeaf?: StorageAdapter['getMerkleLeaf'];
+  getChairmanMerkleNode?: StorageAdapter['getChairmanMerkleNode'];
+  putChairmanMerkleNodes?: StorageAdapter['putChairmanMerkleNodes'];
+  getChairmanMerkleVersion?: StorageAdapter['getChairmanMerkleVersion'];
+  getLatestChairmanMerkleVersion?: StorageAdapter['getLatestChairmanMerkleVersion'];
+  putChairmanMerkleVersion?: StorageAdapter['putChairmanMerkleVersion'];
+  clearChairmanMerkleTree?: StorageAdapter['clearChairmanMerkleTree'];
+  upsertEntryMemos?: StorageAdapter['upsertEntryMemos'];
+  listEntryMemos?: StorageAdapter['listEntryMemos'];
+  clearEntryMemos?: StorageAdapter['clearEntryMemos'];
+  upsertEntryNullifiers?: StorageAdapter['upsertEntryNullifiers'];
+  listEntryNullifiers?: StorageAdapter['listEntryNullifiers'];
+  clearEntryNullifiers?: StorageAdapter['clearEntryNullifiers'];
+  deleteOperation?: StorageAdapter['deleteOperation'];
+  clearOperations?: StorageAdapter['clearOperations'];
+  pruneOperations?: StorageAdapter['pruneOperations'];
+  reserveDepositCounter?: StorageAdapter['reserveDepositCounter'];
+
+  private readonly key: Uint8Array;
+
+  /**
+   * Wrap an inner store with a 32-byte secretbox key (see `EncryptedStore.deriveKey`).
+   */
+  constructor(
+    private readonly inner: StorageAdapter,
+    options: { key: Uint8Array },
+  ) {
+    if (options.key.length !== nacl.secretbox.keyLength) {
+      throw new SdkError('CONFIG', `EncryptedStore key must be ${nacl.secretbox.keyLength} bytes`, { length: options.key.length });
+    }
+    this.key = options.key;
+    this.getMerkleLeaves = inner.getMerkleLeaves?.bind(inner);
+    this.appendMerkleLeaves = inner.appendMerkleLeaves?.bind(inner);
+    this.markSpentDetailed = inner.markSpentDetailed?.bind(inner);
+    this.deleteUtxosFrom = inner.deleteUtxosFrom?.bind(inner);
+    if (inner.replaceUtxos) this.replaceUtxos = (chainId, utxos) => inner.replaceUtxos!(chainId, utxos.map((u) => this.sealUtxo(u)));
+    this.pruneSpentUtxos = inner.pruneSpentUtxos?.bind(inner);
+    this.stats = inner.stats?.bind(inner);
+    this.appendCursorHistory = inner.appendCursorHistory?.bind(inner);
+    this.getCursorHistory = inner.getCursorHistory?.bind(inner);
+    this.subscribe = inner.subscribe?.bind(inner);
+    this.clearMerkleLeaves = inner.clearMerkleLeaves?.bind(inner);
+    this.getMerkleLeaf = inner.getMerkleLeaf?.bind(inner);
+    this.getChairmanMerkleNode = inner.getChairmanMerkleNode?.bind(inner);
+    this.putChairmanMerkleNodes = inner.putChairmanMerkleNodes?.bind(inner);
+    this.getChairmanMerkleVersion = inner.getChairmanMerkleVersion?.bind(inner);
+    this.getLatestChairmanMerkleVersion = inner.getLatestChairmanMerkleVersion?.bind(inner);
+    this.putChairmanMerkleVersion = inner.putChairmanMerkleVersion?.bind(inner);
+    this.clearChairmanMerkleTree = inner.clearChairmanMerkleTree?.bind(inner);
+    this.upsertEntryMemos = inner.upsertEntryMemos?.bind(inner);
+    this.listEntryMemos = inner.listEntryMemos?.bind(inner);
+    this.clearEntryMemos = inner.clearEntryMemos?.bind(inner);
+    this.upsertEntryNullifiers = inner.upsertEntryNullifiers?.bind(inner);
+    this.listEntryNullifiers = inner.listEntryNullifiers?.bind(inner);
+    this.clearEntryNullifiers = inner.clearEntryNullifiers?.bind(inner);
+    this.deleteOperation = inner.deleteOperation?.bind(inner);
+    this.clearOperations = inner.clearOperations?.bind(inner);
+    this.pruneOperations = inner.pruneOperations?.bind(inner);
+    this.reserveDepositCounter = inner.reserveDepositCounter?.bind(inner);
+  }
+
+  /**
+   * Derive a storage encryption key from the wallet seed (HKDF-SHA256, separate from key derivation info).
+   */
+  static deriveKey(seed: string): Uint8Array {
+    return hkdf(sha256, utf8ToBytes(seed), undefined, utf8ToBytes(STORE_KEY_INFO), nacl.secretbox.keyLength);
+  }
+
+  private seal(value: unknown): Hex {
+    const nonce = nacl.randomBytes(nacl.secretbox.nonceLength);
+    const box = nacl.secretbox(utf8ToBytes(JSON.stringify(value)), nonce, this.key);
+    return `0x${bytesToHex(nonce)}${bytesToHex(box)}`;
+  }
+
+  private open<T>(sealed: string): T {
+    const bytes = hexToBytes(sealed.startsWith('0x') ? sealed.slice(2) : sealed);
+    const nonce = bytes.slice(0, nacl.secretbox.nonceLength);
+    const plain = nacl.secretbox.open(bytes.slice(nacl.secretbox.nonceLength), nonce, this.key);
+    if (!plain) throw new SdkError('STORAGE', 'EncryptedStore failed to decrypt record');
+    return JSON.parse(new TextDecoder().decode(plain)) as T;
+  }
+
+  private sealUtxo(utxo: UtxoRecord): UtxoRecord {
+    const payload: SealedUtxoPayload = { amount: utxo.amount.toString(), memo: utxo.memo };
+    return { ...utxo, amount: 0n, memo: this.seal(payload) };
+  }
+
+  private openUtxo(utxo: UtxoRecord): UtxoRecord {
+    if (!utxo.memo) throw new SdkError('STORAGE', 'EncryptedStore utxo is missing its sealed payload', { commitment: utxo.commitment });
+    const payload = this.open<SealedUtxoPayload>(utxo.memo);
+    return { ...utxo, amount: BigInt(payload.amount), memo: payload.memo };
+  }
+
+  private openOperation<T extends StoredOperation>(operation: T): T {
+    const sealed = (operation.detail as { sealed?: unknown } | undefined)?.sealed;
+    if (typeof sealed !== 'string') return operation;
+    return { ...operation, detail: this.open<T['detail']>(sealed) };
+  }
+
+  init(options?: { walletId?: string }) {
+    return this.inner.init?.(options);
+  }
+
+  close() {
+    return this.inner.close?.();
+  }
+
+  getSyncCursor(chainId: number): Promise<SyncCursor | undefined> {
+    return this.inner.getSyncCursor(chainId);
+  }
+
+  setSyncCursor(chainId: number, cursor: SyncCursor): Promise<void> {
+    return this.inner.setSyncCursor(chainId, cursor);
+  }
+
+  upsertUtxos(utxos: UtxoRecord[]): Promise<void> {
+    return this.inner.upsertUtxos(utxos.map((u) => this.sealUtxo(u)));
+  }
+
+  async listUtxos(query?: ListUtxosQuery): Promise<ListUtxosResult> {
+    if (query?.orderBy === 'amount') {
+      // Inner amounts are sealed (0), so fetch every match and sort/page on the opened rows.
+      const { offset: _offset, limit: _limit, orderBy: _orderBy, order: _order, ...filters } = query;
+      const all = await this.inner.listUtxos(filters);
+      return applyUtxoQuery(all.rows.map((u) => this.openUtxo(u)), query);
+    }
+    const result = await this.inner.listUtxos(query);
+    return { ...result, rows: result.rows.map((u) => this.openUtxo(u)) };
+  }
+
+  markSpent(input: { chainId: number; nullifiers: Hex[] }): Promise<number> {
+    return this.inner.markSpent(input);
+  }
+
+  createOperation<TType extends OperationType>(input: OperationCreateInput<TType>): StoredOperation<OperationDetailFor<TType>> & { type: TType } {
+    const sealedInput = input.detail === undefined ? input : { ...input, detail: { sealed: this.seal(input.detail) } as unknown as OperationDetailFor<TType> };
+    const created = this.inner.createOperation(sealedInput);
+    return { ...created, detail: input.detail };
+  }
+
+  updateOperation(id: string, patch: Partial<StoredOperation>) {
+    this.inner.updateOperation(id, patch.detail === undefined ? patch : { ...patch, detail: { sealed: this.seal(patch.detail) } });
+  }
+
+  listOperations(input?: number | ListOperationsQuery): StoredOperation[] {
+    return this.inner.listOperations(input).map((op) => this.openOperation(op));
+  }
+}
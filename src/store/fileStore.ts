@@ -1,6 +1,8 @@
 import type {
+  AssetBalance,
   ChairmanMerkleNodeRecord,
   ChairmanMerkleVersionRecord,
+  CursorHistoryEntry,
   EntryMemoRecord,
   EntryNullifierRecord,
   Hex,
@@ -8,6 +10,7 @@ import type {
   ListEntryNullifiersQuery,
   ListUtxosQuery,
   StorageAdapter,
+  StoreEvent,
   SyncCursor,
   UtxoRecord,
 } from '../types';
@@ -20,11 +23,15 @@ import type { PersistedSharedState, PersistedStoreState } from './internal/persi
 import { applyOperationsQuery } from './internal/operationsQuery';
 import { applyEntryMemoQuery } from './internal/entryMemoQuery';
 import { applyEntryNullifierQuery } from './internal/entryNullifierQuery';
-import { applyUtxoQuery } from './internal/utxoQuery';
+import { applyUtxoQuery, assertUtxosOnChain } from './internal/utxoQuery';
+import { appendBoundedCursorHistory, normalizeMaxCursorHistory, parseCursorHistory, tailCursorHistory } from './internal/cursorHistory';
+import { StoreListeners } from './internal/storeListeners';
+import { aggregateBalances } from './storeBalances';
 
 export type FileStoreOptions = {
   baseDir: string;
   maxOperations?: number;
+  maxCursorHistory?: number;
 };
 
 /**
@@ -34,23 +41,30 @@ export type FileStoreOptions = {
 export class FileStore implements StorageAdapter {
   private walletId: string | undefined;
   private readonly cursors = new Map<number, SyncCursor>();
+  private readonly cursorHistory = new Map<number, CursorHistoryEntry[]>();
   private readonly utxos = new Map<string, UtxoRecord>();
+  private readonly prunedNullifiers = new Set<string>();
   private operations: Array<StoredOperation> = [];
   private chairmanMerkleLatestVersions: Record<string, ChairmanMerkleVersionRecord> = {};
   private chairmanMerkleVersions: Record<string, Record<number, ChairmanMerkleVersionRecord>> = {};
   private chairmanMerkleNodes: Record<string, Record<string, ChairmanMerkleNodeRecord>> = {};
   private entryMemos: Record<string, EntryMemoRecord[]> = {};
   private entryNullifiers: Record<string, EntryNullifierRecord[]> = {};
+  private depositCounters: Record<string, number> = {};
   private saveChain: Promise<void> = Promise.resolve();
+  private readonly listeners = new StoreListeners();
   private readonly maxOperations: number;
+  private readonly maxCursorHistory: number;
   private readonly merkleNextCid = new Map<number, number>();
 
   /**
    * Create a FileStore with a base directory and optional limits.
+   * `maxCursorHistory` bounds the per-chain cursor history (default 256 entries).
    */
   constructor(private readonly options: FileStoreOptions) {
     const max = options.maxOperations;
     this.maxOperations = max == null ? Number.POSITIVE_INFINITY : Math.max(0, Math.floor(max));
+    this.maxCursorHistory = normalizeMaxCursorHistory(options.maxCursorHistory);
   }
 
   private samePlainRecord(a: Record<string, unknown> | undefined, b: Record<string, unknown>): boolean {
@@ -79,6 +93,13 @@ export class FileStore implements StorageAdapter {
     await this.saveChain;
   }
 
+  /**
+   * Register a change listener; returns its unsubscribe function. Events fire after the change is persisted.
+   */
+  subscribe(listener: (event: StoreEvent) => void): () => void {
+    return this.listeners.add(listener);
+  }
+
   /**
    * Compute the primary JSON state file path for the current wallet id.
    */
@@ -156,7 +177,9 @@ export class FileStore implements StorageAdapter {
     await mkdir(this.options.baseDir, { recursive: true });
     // Reset wallet-local state first; if the file is missing/bad for this wallet, we should not leak data from a previous walletId.
     this.cursors.clear();
+    this.cursorHistory.clear();
     this.utxos.clear();
+    this.prunedNullifiers.clear();
     this.operations = [];
     this.merkleNextCid.clear();
     this.chairmanMerkleLatestVersions = {};
@@ -164,15 +187,24 @@ export class FileStore implements StorageAdapter {
     this.chairmanMerkleNodes = {};
     this.entryMemos = {};
     this.entryNullifiers = {};
+    this.depositCounters = {};
     try {
       const raw = await readFile(this.filePath(), 'utf8');
       const parsed = JSON.parse(raw) as Partial<PersistedStoreState>;
       const hydrated = hydrateWalletState(parsed.wallet);
       for (const [k, v] of hydrated.cursors.entries()) this.cursors.set(k, v);
       for (const [k, v] of hydrated.utxos.entries()) this.utxos.set(k, v);
+      for (const key of hydrated.prunedNullifiers) this.prunedNullifiers.add(key);
 
       const operations = Array.isArray(parsed.operations) ? parsed.operations : [];
       this.operations = operations;
+
+      for (const [chainKey, entries] of Object.entries(parsed.cursorHistory ?? {})) {
+        const chainId = Number(chainKey);
+        if (!Number.isInteger(chainId)) continue;
+        const history = parseCursorHistory(entries, chainId);
+        if (history.length) this.cursorHistory.set(chainId, history.slice(Math.max(0, history.length - this.maxCursorHistory)));
+      }
     } catch {
       // ignore missing/bad file
     }
@@ -211,6 +243,11 @@ export class FileStore implements StorageAdapter {
       if (entryNullifiersRaw && typeof entryNullifiersRaw === 'object') {
         this.entryNullifiers = entryNullifiersRaw;
       }
+
+      const depositCountersRaw = parsed.depositCounters;
+      if (depositCountersRaw && typeof depositCountersRaw === 'object') {
+        this.depositCounters = depositCountersRaw;
+      }
     } catch {
       // ignore missing/bad shared file
     }
@@ -227,10 +264,13 @@ export class FileStore implements StorageAdapter {
       .catch(() => undefined)
       .then(async () => {
         await mkdir(this.options.baseDir, { recursive: true });
-        const wallet = serializeWalletState({ walletId: this.walletId, cursors: this.cursors, utxos: this.utxos });
+        const wallet = serializeWalletState({ walletId: this.walletId, cursors: this.cursors, utxos: this.utxos, prunedNullifiers: this.prunedNullifiers });
+        const cursorHistory: Record<string, CursorHistoryEntry[]> = {};
+        for (const [chainId, history] of this.cursorHistory.entries()) cursorHistory[String(chainId)] = history;
         const walletState: PersistedStoreState = {
           wallet,
           operations: this.operations,
+          cursorHistory,
         };
         const walletTarget = this.filePath();
         const walletTmp = `${walletTarget}.${process.pid}.${Date.now()}.tmp`;
@@ -253,6 +293,7 @@ export class FileStore implements StorageAdapter {
           chairmanMerkleNodes: this.chairmanMerkleNodes,
           entryMemos: this.entryMemos,
           entryNullifiers: this.entryNullifiers,
+          depositCounters: this.depositCounters,
         };
 
         const sharedTarget = this.sharedFilePath();
@@ -456,18 +497,51 @@ export class FileStore implements StorageAdapter {
   async setSyncCursor(chainId: number, cursor: SyncCursor): Promise<void> {
     this.cursors.set(chainId, { ...cursor });
     await this.saveWallet();
+    this.listeners.notify({ type: 'cursorUpdated', chainId, cursor: { ...cursor } });
+  }
+
+  /**
+   * Append a cursor move to the chain's bounded history and persist.
+   */
+  async appendCursorHistory(chainId: number, previous: SyncCursor, next: SyncCursor, timestamp: number): Promise<void> {
+    if (this.maxCursorHistory === 0) return;
+    this.cursorHistory.set(chainId, appendBoundedCursorHistory(this.cursorHistory.get(chainId) ?? [], { chainId, previous, next, timestamp }, this.maxCursorHistory));
+    await this.saveWallet();
   }
 
   /**
-   * Upsert UTXOs and persist.
+   * Most recent cursor moves for a chain, oldest first.
+   */
+  async getCursorHistory(chainId: number, limit?: number): Promise<CursorHistoryEntry[]> {
+    return tailCursorHistory(this.cursorHistory.get(chainId) ?? [], limit);
+  }
+
+  /**
+   * Upsert UTXOs and persist; previously pruned coins stay spent.
    */
   async upsertUtxos(utxos: UtxoRecord[]): Promise<void> {
     for (const utxo of utxos) {
       const key = `${utxo.chainId}:${utxo.commitment}`;
       const prev = this.utxos.get(key);
-      this.utxos.set(key, { ...utxo, isSpent: prev?.isSpent ?? utxo.isSpent });
+      const pruned = this.prunedNullifiers.has(`${utxo.chainId}:${utxo.nullifier.toLowerCase()}`);
+      this.utxos.set(key, { ...utxo, isSpent: prev?.isSpent ?? (pruned || utxo.isSpent) });
+    }
+    await this.saveWallet();
+    if (utxos.length) this.listeners.notify({ type: 'utxosUpserted', chainIds: [...new Set(utxos.map((u) => u.chainId))], count: utxos.length });
+  }
+
+  /**
+   * Swap a chain's UTXO set in memory in one step, then persist the wallet file (written to a temp file and renamed).
+   * Records are stored as given, including `isSpent`.
+   */
+  async replaceUtxos(chainId: number, utxos: UtxoRecord[]): Promise<void> {
+    assertUtxosOnChain(chainId, utxos);
+    for (const [key, utxo] of this.utxos.entries()) {
+      if (utxo.chainId === chainId) this.utxos.delete(key);
     }
+    for (const utxo of utxos) this.utxos.set(`${utxo.chainId}:${utxo.commitment}`, { ...utxo });
     await this.saveWallet();
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [chainId], count: utxos.length });
   }
 
   /**
@@ -479,22 +553,71 @@ export class FileStore implements StorageAdapter {
     return { total: paged.total, rows: paged.rows.map((utxo) => ({ ...utxo })) };
   }
 
+  /**
+   * Sum unspent UTXOs per asset from the in-memory set.
+   */
+  async getBalances(options?: { chainId?: number }): Promise<AssetBalance[]> {
+    return aggregateBalances(this.utxos.values(), options?.chainId);
+  }
+
   /**
    * Mark UTXOs as spent by nullifier and persist.
    */
   async markSpent(input: { chainId: number; nullifiers: Hex[] }): Promise<number> {
-    const wanted = new Set(input.nullifiers.map((nf) => nf.toLowerCase()));
-    let updated = 0;
+    return (await this.markSpentDetailed(input)).length;
+  }
+
+  /**
+   * Mark UTXOs as spent, persist, and return the supplied nullifiers that matched a previously unspent UTXO.
+   */
+  async markSpentDetailed(input: { chainId: number; nullifiers: Hex[] }): Promise<Hex[]> {
+    const wanted = new Map(input.nullifiers.map((nf) => [nf.toLowerCase(), nf] as const));
+    const matched: Hex[] = [];
     for (const [key, utxo] of this.utxos.entries()) {
       if (utxo.chainId !== input.chainId) continue;
-      if (!wanted.has(utxo.nullifier.toLowerCase())) continue;
+      const supplied = wanted.get(utxo.nullifier.toLowerCase());
+      if (supplied == null) continue;
       if (!utxo.isSpent) {
         this.utxos.set(key, { ...utxo, isSpent: true });
-        updated++;
+        matched.push(supplied);
       }
     }
-    if (updated) await this.saveWallet();
-    return updated;
+    if (matched.length) {
+      await this.saveWallet();
+      this.listeners.notify({ type: 'markedSpent', chainId: input.chainId, count: matched.length });
+    }
+    return matched;
+  }
+
+  /**
+   * Delete UTXOs with mkIndex >= fromMkIndex on a chain and persist.
+   */
+  async deleteUtxosFrom(input: { chainId: number; fromMkIndex: number }): Promise<number> {
+    let deleted = 0;
+    for (const [key, utxo] of this.utxos.entries()) {
+      if (utxo.chainId !== input.chainId || utxo.mkIndex < input.fromMkIndex) continue;
+      this.utxos.delete(key);
+      deleted++;
+    }
+    if (deleted) await this.saveWallet();
+    return deleted;
+  }
+
+  /**
+   * Delete spent, unfrozen UTXOs on a chain, optionally only those created before `olderThan`, and persist.
+   * Pruned nullifiers are saved with the wallet so `upsertUtxos` stores a re-synced copy as spent.
+   */
+  async pruneSpentUtxos(input: { chainId: number; olderThan?: number }): Promise<number> {
+    let deleted = 0;
+    for (const [key, utxo] of this.utxos.entries()) {
+      if (utxo.chainId !== input.chainId || !utxo.isSpent || utxo.isFrozen) continue;
+      if (input.olderThan != null && (utxo.createdAt == null || utxo.createdAt >= input.olderThan)) continue;
+      this.utxos.delete(key);
+      this.prunedNullifiers.add(`${utxo.chainId}:${utxo.nullifier.toLowerCase()}`);
+      deleted++;
+    }
+    if (deleted) await this.saveWallet();
+    return deleted;
   }
 
   /**
@@ -609,6 +732,18 @@ export class FileStore implements StorageAdapter {
     return before - this.operations.length;
   }
 
+  /**
+   * Hand out the next deterministic-deposit counter for an owner, advance it in the shared file,
+   * and resolve once the advanced value is on disk.
+   */
+  async reserveDepositCounter(owner: Hex): Promise<number> {
+    const key = owner.toLowerCase();
+    const counter = this.depositCounters[key] ?? 0;
+    this.depositCounters[key] = counter + 1;
+    await this.saveShared();
+    return counter;
+  }
+
   /**
    * List operations with filtering/pagination.
    */
@@ -1,6 +1,8 @@
 import type {
+  AssetBalance,
   ChairmanMerkleNodeRecord,
   ChairmanMerkleVersionRecord,
+  CursorHistoryEntry,
   EntryMemoRecord,
   EntryNullifierRecord,
   Hex,
@@ -8,6 +10,7 @@ import type {
   ListEntryNullifiersQuery,
   ListUtxosQuery,
   StorageAdapter,
+  StoreEvent,
   SyncCursor,
   UtxoRecord,
 } from '../types';
@@ -16,13 +19,17 @@ import { newOperationId } from './internal/operationTypes';
 import { applyOperationsQuery } from './internal/operationsQuery';
 import { applyEntryMemoQuery } from './internal/entryMemoQuery';
 import { applyEntryNullifierQuery } from './internal/entryNullifierQuery';
-import { applyUtxoQuery } from './internal/utxoQuery';
+import { applyUtxoQuery, assertUtxosOnChain } from './internal/utxoQuery';
+import { appendBoundedCursorHistory, normalizeMaxCursorHistory, parseCursorHistory, tailCursorHistory } from './internal/cursorHistory';
+import { StoreListeners } from './internal/storeListeners';
+import { aggregateBalances } from './storeBalances';
 
 export type IndexedDbStoreOptions = {
   dbName?: string;
   storeName?: string;
   indexedDb?: IDBFactory;
   maxOperations?: number;
+  maxCursorHistory?: number;
 };
 
 type CursorRow = { walletId: string; chainId: number } & SyncCursor;
@@ -33,6 +40,12 @@ type OperationRow = { walletId: string } & StoredOperation;
 
 type MerkleLeafRow = { chainId: number; cid: number; commitment: Hex };
 
+type DepositCounterRow = { owner: string; next: number };
+
+type PrunedNullifierRow = { walletId: string; chainId: number; nullifier: string };
+
+type CursorHistoryRow = { walletId: string; chainId: number; entries: CursorHistoryEntry[] };
+
 type StoreDef = {
   name: string;
   keyPath: string | string[];
@@ -43,20 +56,24 @@ type StoreDef = {
  * IndexedDB-backed StorageAdapter for browser environments.
  */
 export class IndexedDbStore implements StorageAdapter {
-  private static readonly DB_VERSION = 3;
+  private static readonly DB_VERSION = 6;
   private walletId: string | undefined;
   private readonly cursors = new Map<number, SyncCursor>();
   private operations: Array<StoredOperation> = [];
   private chairmanMerkleLatestVersions: Record<string, ChairmanMerkleVersionRecord> = {};
   private db: IDBDatabase | null = null;
+  private readonly listeners = new StoreListeners();
   private readonly maxOperations: number;
+  private readonly maxCursorHistory: number;
 
   /**
    * Create an IndexedDbStore with optional database settings.
+   * `maxCursorHistory` bounds the per-chain cursor history (default 256 entries).
    */
   constructor(private readonly options: IndexedDbStoreOptions = {}) {
     const max = options.maxOperations;
     this.maxOperations = max == null ? Number.POSITIVE_INFINITY : Math.max(0, Math.floor(max));
+    this.maxCursorHistory = normalizeMaxCursorHistory(options.maxCursorHistory);
   }
 
   /**
@@ -75,6 +92,13 @@ export class IndexedDbStore implements StorageAdapter {
     this.db = null;
   }
 
+  /**
+   * Register a change listener; returns its unsubscribe function. Events fire after the change is persisted.
+   */
+  subscribe(listener: (event: StoreEvent) => void): () => void {
+    return this.listeners.add(listener);
+  }
+
   /**
    * Resolve base object store name (default: ocash_store).
    */
@@ -104,6 +128,9 @@ export class IndexedDbStore implements StorageAdapter {
       { name: `${base}:merkleLeaves`, keyPath: ['chainId', 'cid'], indexes: [{ name: 'chainId', keyPath: 'chainId' }] },
       { name: `${base}:chairmanMerkleNodes`, keyPath: ['chainId', 'id'], indexes: [{ name: 'chainId', keyPath: 'chainId' }] },
       { name: `${base}:chairmanMerkleVersions`, keyPath: ['chainId', 'version'], indexes: [{ name: 'chainId', keyPath: 'chainId' }] },
+      { name: `${base}:depositCounters`, keyPath: 'owner' },
+      { name: `${base}:prunedNullifiers`, keyPath: ['walletId', 'chainId', 'nullifier'] },
+      { name: `${base}:cursorHistory`, keyPath: ['walletId', 'chainId'] },
     ];
   }
 
@@ -162,6 +189,9 @@ export class IndexedDbStore implements StorageAdapter {
       merkleLeaves: `${base}:merkleLeaves`,
       chairmanMerkleNodes: `${base}:chairmanMerkleNodes`,
       chairmanMerkleVersions: `${base}:chairmanMerkleVersions`,
+      depositCounters: `${base}:depositCounters`,
+      prunedNullifiers: `${base}:prunedNullifiers`,
+      cursorHistory: `${base}:cursorHistory`,
     };
   }
 
@@ -541,10 +571,43 @@ export class IndexedDbStore implements StorageAdapter {
   async setSyncCursor(chainId: number, cursor: SyncCursor): Promise<void> {
     this.cursors.set(chainId, { ...cursor });
     await this.putMany(this.storeNames().cursors, [{ walletId: this.walletKey(), chainId, ...cursor }]);
+    this.listeners.notify({ type: 'cursorUpdated', chainId, cursor: { ...cursor } });
+  }
+
+  /**
+   * Append a cursor move to the chain's bounded history (one row per chain, read and rewritten in one transaction).
+   */
+  async appendCursorHistory(chainId: number, previous: SyncCursor, next: SyncCursor, timestamp: number): Promise<void> {
+    if (this.maxCursorHistory === 0) return;
+    const db = await this.openDb();
+    const storeName = this.storeNames().cursorHistory;
+    const walletId = this.walletKey();
+    await new Promise<void>((resolve, reject) => {
+      const tx = db.transaction(storeName, 'readwrite');
+      tx.oncomplete = () => resolve();
+      tx.onerror = () => reject(tx.error ?? new Error('indexedDB cursor history append failed'));
+      const store = tx.objectStore(storeName);
+      const req = store.get([walletId, chainId]);
+      req.onerror = () => reject(req.error ?? new Error('indexedDB get failed'));
+      req.onsuccess = () => {
+        const row = req.result as CursorHistoryRow | undefined;
+        const entries = appendBoundedCursorHistory(parseCursorHistory(row?.entries, chainId), { chainId, previous, next, timestamp }, this.maxCursorHistory);
+        const updated: CursorHistoryRow = { walletId, chainId, entries };
+        store.put(updated);
+      };
+    });
   }
 
   /**
-   * Upsert UTXOs and persist.
+   * Most recent cursor moves for a chain, oldest first.
+   */
+  async getCursorHistory(chainId: number, limit?: number): Promise<CursorHistoryEntry[]> {
+    const row = await this.getByKey<CursorHistoryRow>(this.storeNames().cursorHistory, [this.walletKey(), chainId]);
+    return tailCursorHistory(parseCursorHistory(row?.entries, chainId), limit);
+  }
+
+  /**
+   * Upsert UTXOs and persist; previously pruned coins stay spent.
    */
   async upsertUtxos(utxos: UtxoRecord[]): Promise<void> {
     if (!utxos.length) return;
@@ -552,14 +615,48 @@ export class IndexedDbStore implements StorageAdapter {
     const walletId = this.walletKey();
     const keys = utxos.map((utxo) => [walletId, utxo.chainId, utxo.commitment] as IDBValidKey);
     const existingRows = await this.getByKeys<UtxoRow>(stores.utxos, keys);
+    const prunedRows = await this.getByKeys<PrunedNullifierRow>(
+      stores.prunedNullifiers,
+      utxos.map((utxo) => [walletId, utxo.chainId, utxo.nullifier.toLowerCase()] as IDBValidKey),
+    );
     const rows: UtxoRow[] = [];
     for (let i = 0; i < utxos.length; i++) {
       const utxo = utxos[i]!;
       const prev = existingRows[i];
-      const merged = { ...utxo, isSpent: prev?.isSpent ?? utxo.isSpent };
+      const merged = { ...utxo, isSpent: prev?.isSpent ?? (prunedRows[i] != null || utxo.isSpent) };
       rows.push({ walletId, ...merged });
     }
     await this.putMany(stores.utxos, rows);
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [...new Set(utxos.map((u) => u.chainId))], count: utxos.length });
+  }
+
+  /**
+   * Swap a chain's UTXO set in one readwrite transaction, so readers see either the old set or the new one.
+   * Records are stored as given, including `isSpent`.
+   */
+  async replaceUtxos(chainId: number, utxos: UtxoRecord[]): Promise<void> {
+    assertUtxosOnChain(chainId, utxos);
+    const db = await this.openDb();
+    const storeName = this.storeNames().utxos;
+    const walletId = this.walletKey();
+    await new Promise<void>((resolve, reject) => {
+      const tx = db.transaction(storeName, 'readwrite');
+      tx.oncomplete = () => resolve();
+      tx.onerror = () => reject(tx.error ?? new Error('indexedDB replace failed'));
+      const store = tx.objectStore(storeName);
+      const req = store.index('walletChain').openCursor(IDBKeyRange.only([walletId, chainId]));
+      req.onerror = () => reject(req.error ?? new Error('indexedDB cursor failed'));
+      req.onsuccess = () => {
+        const cursor = req.result;
+        if (cursor) {
+          cursor.delete();
+          cursor.continue();
+          return;
+        }
+        for (const utxo of utxos) store.put({ walletId, ...utxo });
+      };
+    });
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [chainId], count: utxos.length });
   }
 
   /**
@@ -580,29 +677,88 @@ export class IndexedDbStore implements StorageAdapter {
     return { total: paged.total, rows: paged.rows.map((utxo) => ({ ...utxo })) };
   }
 
+  /**
+   * Sum unspent UTXOs per asset over the wallet's (or one chain's) rows.
+   */
+  async getBalances(options?: { chainId?: number }): Promise<AssetBalance[]> {
+    const stores = this.storeNames();
+    const walletId = this.walletKey();
+    const rows =
+      options?.chainId == null
+        ? await this.getAllByIndex<UtxoRow>(stores.utxos, 'walletId', walletId)
+        : await this.getAllByIndex<UtxoRow>(stores.utxos, 'walletChain', [walletId, options.chainId]);
+    return aggregateBalances(rows, options?.chainId);
+  }
+
   /**
    * Mark UTXOs as spent by nullifier and persist.
    */
   async markSpent(input: { chainId: number; nullifiers: Hex[] }): Promise<number> {
-    if (!input.nullifiers.length) return 0;
+    return (await this.markSpentDetailed(input)).length;
+  }
+
+  /**
+   * Mark UTXOs as spent, persist, and return the supplied nullifiers that matched a previously unspent UTXO.
+   */
+  async markSpentDetailed(input: { chainId: number; nullifiers: Hex[] }): Promise<Hex[]> {
+    if (!input.nullifiers.length) return [];
     const stores = this.storeNames();
     const walletId = this.walletKey();
     const allRows = await this.getAllByIndex<UtxoRow>(stores.utxos, 'walletChain', [walletId, input.chainId]);
-    const wanted = new Set(input.nullifiers.map((nf) => nf.toLowerCase()));
-    let updated = 0;
+    const wanted = new Map(input.nullifiers.map((nf) => [nf.toLowerCase(), nf] as const));
+    const matched: Hex[] = [];
     const rows: UtxoRow[] = [];
     for (const row of allRows) {
       const { walletId: _walletId, ...utxo } = row;
       if (utxo.chainId !== input.chainId) continue;
-      if (!wanted.has(utxo.nullifier.toLowerCase())) continue;
+      const supplied = wanted.get(utxo.nullifier.toLowerCase());
+      if (supplied == null) continue;
       if (!utxo.isSpent) {
         const merged = { ...utxo, isSpent: true };
         rows.push({ walletId, ...merged });
-        updated++;
+        matched.push(supplied);
       }
     }
-    if (rows.length) await this.putMany(stores.utxos, rows);
-    return updated;
+    if (rows.length) {
+      await this.putMany(stores.utxos, rows);
+      this.listeners.notify({ type: 'markedSpent', chainId: input.chainId, count: rows.length });
+    }
+    return matched;
+  }
+
+  /**
+   * Delete UTXOs with mkIndex >= fromMkIndex on a chain.
+   */
+  async deleteUtxosFrom(input: { chainId: number; fromMkIndex: number }): Promise<number> {
+    const stores = this.storeNames();
+    const walletId = this.walletKey();
+    const rows = await this.getAllByIndex<UtxoRow>(stores.utxos, 'walletChain', [walletId, input.chainId]);
+    const keys = rows.filter((row) => row.mkIndex >= input.fromMkIndex).map((row) => [walletId, row.chainId, row.commitment] as IDBValidKey);
+    await this.deleteByKeys(stores.utxos, keys);
+    return keys.length;
+  }
+
+  /**
+   * Delete spent, unfrozen UTXOs on a chain, optionally only those created before `olderThan`.
+   * Their nullifiers are written to the `prunedNullifiers` store first, so a re-synced copy is stored as spent.
+   */
+  async pruneSpentUtxos(input: { chainId: number; olderThan?: number }): Promise<number> {
+    const stores = this.storeNames();
+    const walletId = this.walletKey();
+    const rows = await this.getAllByIndex<UtxoRow>(stores.utxos, 'walletChain', [walletId, input.chainId]);
+    const pruned = rows.filter((row) => {
+      if (!row.isSpent || row.isFrozen) return false;
+      return input.olderThan == null || (row.createdAt != null && row.createdAt < input.olderThan);
+    });
+    await this.putMany<PrunedNullifierRow>(
+      stores.prunedNullifiers,
+      pruned.map((row) => ({ walletId, chainId: row.chainId, nullifier: row.nullifier.toLowerCase() })),
+    );
+    await this.deleteByKeys(
+      stores.utxos,
+      pruned.map((row) => [walletId, row.chainId, row.commitment] as IDBValidKey),
+    );
+    return pruned.length;
   }
 
   /**
@@ -668,6 +824,29 @@ export class IndexedDbStore implements StorageAdapter {
     return before - this.operations.length;
   }
 
+  /**
+   * Hand out the next deterministic-deposit counter for an owner.
+   * The read and the advanced write share one readwrite transaction, so concurrent reservations never see the same value.
+   */
+  async reserveDepositCounter(owner: Hex): Promise<number> {
+    const db = await this.openDb();
+    const storeName = this.storeNames().depositCounters;
+    const key = owner.toLowerCase();
+    return new Promise<number>((resolve, reject) => {
+      const tx = db.transaction(storeName, 'readwrite');
+      let counter = 0;
+      tx.oncomplete = () => resolve(counter);
+      tx.onerror = () => reject(tx.error ?? new Error('indexedDB deposit counter failed'));
+      const store = tx.objectStore(storeName);
+      const req = store.get(key);
+      req.onerror = () => reject(req.error ?? new Error('indexedDB get failed'));
+      req.onsuccess = () => {
+        counter = (req.result as DepositCounterRow | undefined)?.next ?? 0;
+        store.put({ owner: key, next: counter + 1 });
+      };
+    });
+  }
+
   /**
    * List operations with filtering/pagination.
    */
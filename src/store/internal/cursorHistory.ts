@@ -0,0 +1,55 @@
+import type { CursorHistoryEntry, SyncCursor } from '../../types';
+
+/**
+ * Default per-chain bound for `appendCursorHistory`; oldest entries are dropped first.
+ */
+export const DEFAULT_MAX_CURSOR_HISTORY = 256;
+
+/**
+ * Normalize the `maxCursorHistory` store option (0 disables recording).
+ */
+export function normalizeMaxCursorHistory(value: number | undefined) {
+  return Math.max(0, Math.floor(value ?? DEFAULT_MAX_CURSOR_HISTORY));
+}
+
+/**
+ * Copy a history entry so callers cannot mutate stored cursors.
+ */
+export function copyCursorHistoryEntry(entry: CursorHistoryEntry): CursorHistoryEntry {
+  return { chainId: entry.chainId, previous: { ...entry.previous }, next: { ...entry.next }, timestamp: entry.timestamp };
+}
+
+/**
+ * Append one move to a chain's history and drop the oldest entries beyond `max`.
+ */
+export function appendBoundedCursorHistory(
+  history: CursorHistoryEntry[],
+  input: { chainId: number; previous: SyncCursor; next: SyncCursor; timestamp: number },
+  max: number,
+): CursorHistoryEntry[] {
+  const out = [...history, copyCursorHistoryEntry(input)];
+  return out.length > max ? out.slice(out.length - max) : out;
+}
+
+/**
+ * The latest `limit` entries (all when omitted), oldest first, as copies.
+ */
+export function tailCursorHistory(history: CursorHistoryEntry[], limit?: number): CursorHistoryEntry[] {
+  const start = limit == null ? 0 : Math.max(0, history.length - Math.max(0, Math.floor(limit)));
+  return history.slice(start).map(copyCursorHistoryEntry);
+}
+
+/**
+ * Keep only well-formed entries from persisted JSON.
+ */
+export function parseCursorHistory(raw: unknown, chainId: number): CursorHistoryEntry[] {
+  if (!Array.isArray(raw)) return [];
+  const out: CursorHistoryEntry[] = [];
+  for (const row of raw) {
+    if (!row || typeof row !== 'object') continue;
+    const r = row as Partial<CursorHistoryEntry>;
+    if (!r.previous || !r.next || typeof r.timestamp !== 'number') continue;
+    out.push(copyCursorHistoryEntry({ chainId, previous: r.previous, next: r.next, timestamp: r.timestamp }));
+  }
+  return out;
+}
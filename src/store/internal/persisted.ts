@@ -1,10 +1,12 @@
-import type { ChairmanMerkleNodeRecord, ChairmanMerkleVersionRecord, EntryMemoRecord, EntryNullifierRecord, Hex } from '../../types';
+import type { ChairmanMerkleNodeRecord, ChairmanMerkleVersionRecord, CursorHistoryEntry, EntryMemoRecord, EntryNullifierRecord, Hex } from '../../types';
 import type { PersistedWalletState } from './persistedWalletState';
 import type { StoredOperation } from './operationTypes';
 
 export type PersistedStoreState = {
   wallet: PersistedWalletState;
   operations: StoredOperation[];
+  /** Bounded cursor move log, keyed by chainId. */
+  cursorHistory?: Record<string, CursorHistoryEntry[]>;
 };
 
 export type PersistedSharedState = {
@@ -37,4 +39,10 @@ export type PersistedSharedState = {
    * Used for fast local proof generation.
    */
   chairmanMerkleNodes?: Record<string, Record<string, ChairmanMerkleNodeRecord>>;
+
+  /**
+   * Next unused deterministic-deposit counter, keyed by lowercased owner address.
+   * Shared across wallet ids so a counter is never handed out twice.
+   */
+  depositCounters?: Record<string, number>;
 };
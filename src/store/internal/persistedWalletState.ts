@@ -6,6 +6,8 @@ export type PersistedWalletState = {
   walletId?: string;
   cursors: Record<string, SyncCursor>;
   utxos: Record<string, PersistedUtxoRecord>;
+  /** `chainId:nullifier` (lowercase) of spent UTXOs removed by `pruneSpentUtxos`. */
+  prunedNullifiers?: string[];
 };
 
 /**
@@ -13,10 +15,33 @@ export type PersistedWalletState = {
  */
 export const defaultCursor = (): SyncCursor => ({ memo: 0, nullifier: 0, merkle: 0 });
 
+/**
+ * Check that a persisted utxo row has the current shape (every required field with its current type).
+ */
+const isPersistedUtxoRecord = (row: unknown): row is PersistedUtxoRecord => {
+  if (!row || typeof row !== 'object') return false;
+  const r = row as Record<string, unknown>;
+  return (
+    typeof r.chainId === 'number' &&
+    typeof r.assetId === 'string' &&
+    typeof r.amount === 'string' &&
+    typeof r.commitment === 'string' &&
+    typeof r.nullifier === 'string' &&
+    typeof r.mkIndex === 'number' &&
+    typeof r.isFrozen === 'boolean' &&
+    typeof r.isSpent === 'boolean'
+  );
+};
+
 /**
  * Serialize wallet state (convert bigint amounts to strings).
  */
-export function serializeWalletState(input: { walletId?: string; cursors: Map<number, SyncCursor>; utxos: Map<string, UtxoRecord> }): PersistedWalletState {
+export function serializeWalletState(input: {
+  walletId?: string;
+  cursors: Map<number, SyncCursor>;
+  utxos: Map<string, UtxoRecord>;
+  prunedNullifiers?: Set<string>;
+}): PersistedWalletState {
   const cursors: PersistedWalletState['cursors'] = {};
   for (const [chainId, cursor] of input.cursors.entries()) {
     cursors[String(chainId)] = cursor;
@@ -27,15 +52,22 @@ export function serializeWalletState(input: { walletId?: string; cursors: Map<nu
     utxos[key] = { ...utxo, amount: utxo.amount.toString() };
   }
 
-  return { walletId: input.walletId, cursors, utxos };
+  return { walletId: input.walletId, cursors, utxos, prunedNullifiers: Array.from(input.prunedNullifiers ?? []) };
 }
 
 /**
  * Hydrate wallet state from persisted JSON (convert amounts to bigint).
+ * State written by an older schema is not migrated: if any utxo row does not match the current shape, the whole
+ * wallet state is dropped (cursors included) so the wallet resyncs from scratch.
  */
 export function hydrateWalletState(state: PersistedWalletState | undefined) {
   const cursors = new Map<number, SyncCursor>();
   const utxos = new Map<string, UtxoRecord>();
+  const prunedNullifiers = new Set<string>();
+  const rows = Object.entries(state?.utxos ?? {});
+  if (!rows.every(([, v]) => isPersistedUtxoRecord(v))) {
+    return { walletId: state?.walletId, cursors, utxos, prunedNullifiers };
+  }
 
   for (const [k, v] of Object.entries(state?.cursors ?? {})) {
     const raw = (v ?? {}) as Partial<SyncCursor>;
@@ -49,7 +81,7 @@ export function hydrateWalletState(state: PersistedWalletState | undefined) {
     });
   }
 
-  for (const [k, v] of Object.entries(state?.utxos ?? {})) {
+  for (const [k, v] of rows) {
     try {
       utxos.set(k, { ...v, amount: BigInt(v.amount) });
     } catch {
@@ -57,5 +89,9 @@ export function hydrateWalletState(state: PersistedWalletState | undefined) {
     }
   }
 
-  return { walletId: state?.walletId, cursors, utxos };
+  for (const key of Array.isArray(state?.prunedNullifiers) ? state.prunedNullifiers : []) {
+    if (typeof key === 'string') prunedNullifiers.add(key);
+  }
+
+  return { walletId: state?.walletId, cursors, utxos, prunedNullifiers };
 }
@@ -0,0 +1,25 @@
+import type { StoreEvent } from '../../types';
+
+/**
+ * Listener set behind `StorageAdapter.subscribe` for the built-in stores.
+ */
+export class StoreListeners {
+  private readonly listeners = new Set<(event: StoreEvent) => void>();
+
+  /**
+   * Register a change listener; returns its unsubscribe function.
+   */
+  add(listener: (event: StoreEvent) => void): () => void {
+    this.listeners.add(listener);
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+
+  /**
+   * Deliver a change event to every listener.
+   */
+  notify(event: StoreEvent) {
+    for (const listener of this.listeners) listener(event);
+  }
+}
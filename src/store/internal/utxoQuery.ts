@@ -50,6 +50,11 @@ export function applyUtxoQuery(rows: UtxoRecord[], query?: ListUtxosQuery): List
   });
 
   const sorted = [...filtered].sort((a, b) => {
+    if (orderBy === 'amount') {
+      if (a.amount !== b.amount) return (a.amount < b.amount ? -1 : 1) * factor;
+      const mkDiff = a.mkIndex - b.mkIndex;
+      return mkDiff === 0 ? 0 : mkDiff * factor;
+    }
     if (orderBy === 'createdAt') {
       const createdDiff = compareCreatedAt(a, b, factor);
       if (createdDiff !== 0) return createdDiff;
@@ -68,3 +73,11 @@ export function applyUtxoQuery(rows: UtxoRecord[], query?: ListUtxosQuery): List
   const rowsPage = limit == null ? sorted.slice(offset) : sorted.slice(offset, offset + limit);
   return { total, rows: rowsPage };
 }
+
+/**
+ * Throw when `replaceUtxos` is handed a record that belongs to another chain.
+ */
+export function assertUtxosOnChain(chainId: number, utxos: UtxoRecord[]) {
+  const mismatched = utxos.find((utxo) => utxo.chainId !== chainId);
+  if (mismatched) throw new Error(`replaceUtxos: utxo ${mismatched.commitment} belongs to chain ${mismatched.chainId}, not ${chainId}`);
+}
@@ -1,6 +1,8 @@
 import type {
+  AssetBalance,
   ChairmanMerkleNodeRecord,
   ChairmanMerkleVersionRecord,
+  CursorHistoryEntry,
   EntryMemoRecord,
   EntryNullifierRecord,
   Hex,
@@ -8,6 +10,7 @@ import type {
   ListEntryNullifiersQuery,
   ListUtxosQuery,
   StorageAdapter,
+  StoreEvent,
   SyncCursor,
   UtxoRecord,
 } from '../types';
@@ -16,12 +19,17 @@ import { newOperationId } from './internal/operationTypes';
 import { applyOperationsQuery } from './internal/operationsQuery';
 import { applyEntryMemoQuery } from './internal/entryMemoQuery';
 import { applyEntryNullifierQuery } from './internal/entryNullifierQuery';
-import { applyUtxoQuery } from './internal/utxoQuery';
+import { applyUtxoQuery, assertUtxosOnChain } from './internal/utxoQuery';
+import { appendBoundedCursorHistory, normalizeMaxCursorHistory, parseCursorHistory, tailCursorHistory } from './internal/cursorHistory';
+import { StoreListeners } from './internal/storeListeners';
+import { aggregateBalances } from './storeBalances';
+import { SdkError } from '../errors';
 
 export type KeyValueStoreOptions = {
   client: KeyValueClient;
   keyPrefix?: string;
   maxOperations?: number;
+  maxCursorHistory?: number;
 };
 
 type PersistedUtxoRecord = Omit<UtxoRecord, 'amount'> & { amount: string };
@@ -36,8 +44,12 @@ export class KeyValueStore implements StorageAdapter {
   private operationIds: string[] = [];
   private operations: Array<StoredOperation> = [];
   private readonly cursorCache = new Map<number, SyncCursor | undefined>();
+  // Per-chain cursor move log, read on first use for that chain.
+  private readonly cursorHistoryCache = new Map<number, CursorHistoryEntry[]>();
   private readonly utxoCache = new Map<string, UtxoRecord | undefined>();
   private readonly operationCache = new Map<string, StoredOperation | undefined>();
+  // `chainId:nullifier` of pruned spent UTXOs; loaded on first use so `init` reads stay at the three wallet meta keys.
+  private prunedNullifiers: Set<string> | undefined;
 
   private merkleLeafCids: Record<string, Set<number>> = {};
   private chairmanMerkleLatestVersions: Record<string, ChairmanMerkleVersionRecord> = {};
@@ -53,12 +65,15 @@ export class KeyValueStore implements StorageAdapter {
   private readonly loadedEntryNullifiers = new Set<number>();
 
   private saveChain: Promise<void> = Promise.resolve();
+  private readonly listeners = new StoreListeners();
   private readonly maxOperations: number;
+  private readonly maxCursorHistory: number;
   private walletMetaLoaded = false;
 
   constructor(private readonly options: KeyValueStoreOptions) {
     const max = options.maxOperations;
     this.maxOperations = max == null ? Number.POSITIVE_INFINITY : Math.max(0, Math.floor(max));
+    this.maxCursorHistory = normalizeMaxCursorHistory(options.maxCursorHistory);
   }
 
   async init(options?: { walletId?: string }) {
@@ -70,6 +85,13 @@ export class KeyValueStore implements StorageAdapter {
     await this.saveChain;
   }
 
+  /**
+   * Register a change listener; returns its unsubscribe function. Events fire after the change is persisted.
+   */
+  subscribe(listener: (event: StoreEvent) => void): () => void {
+    return this.listeners.add(listener);
+  }
+
   private keyPrefix() {
     return this.options.keyPrefix ?? 'ocash:sdk:store';
   }
@@ -79,7 +101,7 @@ export class KeyValueStore implements StorageAdapter {
     return `${this.keyPrefix()}:${id}:wallet`;
   }
 
-  private walletMetaKey(part: 'cursorChains' | 'utxoRefs' | 'operationIds') {
+  private walletMetaKey(part: 'cursorChains' | 'utxoRefs' | 'operationIds' | 'prunedNullifiers') {
     return `${this.walletBaseKey()}:meta:${part}`;
   }
 
@@ -87,6 +109,10 @@ export class KeyValueStore implements StorageAdapter {
     return `${this.walletBaseKey()}:cursor:${chainId}`;
   }
 
+  private walletCursorHistoryKey(chainId: number) {
+    return `${this.walletBaseKey()}:cursorHistory:${chainId}`;
+  }
+
   private walletUtxoKey(ref: string) {
     return `${this.walletBaseKey()}:utxo:${ref}`;
   }
@@ -103,6 +129,10 @@ export class KeyValueStore implements StorageAdapter {
     return `${this.keyPrefix()}:shared:${part}:${chainId}:${id}`;
   }
 
+  private depositCounterKey(owner: Hex) {
+    return `${this.keyPrefix()}:shared:depositCounter:${owner.toLowerCase()}`;
+  }
+
   private parseJson<T>(raw: string | null, fallback: T): T {
     if (!raw) return fallback;
     try {
@@ -150,8 +180,10 @@ export class KeyValueStore implements StorageAdapter {
     this.operationIds = [];
     this.operations = [];
     this.cursorCache.clear();
+    this.cursorHistoryCache.clear();
     this.utxoCache.clear();
     this.operationCache.clear();
+    this.prunedNullifiers = undefined;
     this.walletMetaLoaded = false;
 
     this.merkleLeafCids = {};
@@ -222,6 +254,26 @@ export class KeyValueStore implements StorageAdapter {
     this.walletMetaLoaded = true;
   }
 
+  private async ensurePrunedNullifiersLoaded(): Promise<Set<string>> {
+    if (this.prunedNullifiers) return this.prunedNullifiers;
+    const raw = await this.options.client.get(this.walletMetaKey('prunedNullifiers'));
+    // A concurrent caller may have loaded (and extended) the set while this read was in flight.
+    if (!this.prunedNullifiers) this.prunedNullifiers = new Set(this.parseStringIndex(raw));
+    return this.prunedNullifiers;
+  }
+
+  private async readCursorHistory(chainId: number): Promise<CursorHistoryEntry[]> {
+    const cached = this.cursorHistoryCache.get(chainId);
+    if (cached) return cached;
+    const raw = await this.options.client.get(this.walletCursorHistoryKey(chainId));
+    // A concurrent append may have populated the cache while this read was in flight.
+    const current = this.cursorHistoryCache.get(chainId);
+    if (current) return current;
+    const history = parseCursorHistory(this.parseJson<unknown>(raw, []), chainId);
+    this.cursorHistoryCache.set(chainId, history);
+    return history;
+  }
+
   private normalizeCursor(cursor: SyncCursor | null): SyncCursor | undefined {
     if (!cursor) return undefined;
     const memo = Number(cursor.memo);
@@ -587,17 +639,31 @@ export class KeyValueStore implements StorageAdapter {
         await this.writeJson(this.walletMetaKey('cursorChains'), chains);
       }
     });
+    this.listeners.notify({ type: 'cursorUpdated', chainId, cursor: { ...cursor } });
+  }
+
+  async appendCursorHistory(chainId: number, previous: SyncCursor, next: SyncCursor, timestamp: number): Promise<void> {
+    if (this.maxCursorHistory === 0) return;
+    const history = appendBoundedCursorHistory(await this.readCursorHistory(chainId), { chainId, previous, next, timestamp }, this.maxCursorHistory);
+    this.cursorHistoryCache.set(chainId, history);
+    await this.enqueueWrite(() => this.writeJson(this.walletCursorHistoryKey(chainId), history));
+  }
+
+  async getCursorHistory(chainId: number, limit?: number): Promise<CursorHistoryEntry[]> {
+    return tailCursorHistory(await this.readCursorHistory(chainId), limit);
   }
 
   async upsertUtxos(utxos: UtxoRecord[]): Promise<void> {
     if (!utxos.length) return;
     await this.ensureWalletMetaLoaded();
+    const pruned = await this.ensurePrunedNullifiersLoaded();
     const newRefs = new Set<string>();
     const rows: Array<{ ref: string; utxo: UtxoRecord }> = [];
     for (const utxo of utxos) {
       const ref = `${utxo.chainId}:${utxo.commitment}`;
       const prev = await this.readUtxo(ref);
-      const merged = { ...utxo, isSpent: prev?.isSpent ?? utxo.isSpent };
+      // A coin pruned while spent stays spent when it is synced again.
+      const merged = { ...utxo, isSpent: prev?.isSpent ?? (pruned.has(`${utxo.chainId}:${utxo.nullifier.toLowerCase()}`) || utxo.isSpent) };
       this.utxoCache.set(ref, merged);
       this.utxoRefs.add(ref);
       if (!prev) newRefs.add(ref);
@@ -609,6 +675,33 @@ export class KeyValueStore implements StorageAdapter {
         await this.writeJson(this.walletMetaKey('utxoRefs'), Array.from(this.utxoRefs));
       }
     });
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [...new Set(utxos.map((u) => u.chainId))], count: utxos.length });
+  }
+
+  /**
+   * Swap a chain's UTXO set. The in-memory index switches in one step; on the backend the new records are written
+   * before the `utxoRefs` index that points at them, and stale records are deleted only after it.
+   */
+  async replaceUtxos(chainId: number, utxos: UtxoRecord[]): Promise<void> {
+    assertUtxosOnChain(chainId, utxos);
+    await this.ensureWalletMetaLoaded();
+    const rows = utxos.map((utxo) => ({ ref: `${utxo.chainId}:${utxo.commitment}`, utxo: { ...utxo } }));
+    const kept = new Set(rows.map(({ ref }) => ref));
+    const removed = Array.from(this.utxoRefs).filter((ref) => ref.startsWith(`${chainId}:`) && !kept.has(ref));
+    for (const ref of removed) {
+      this.utxoRefs.delete(ref);
+      this.utxoCache.delete(ref);
+    }
+    for (const { ref, utxo } of rows) {
+      this.utxoRefs.add(ref);
+      this.utxoCache.set(ref, utxo);
+    }
+    await this.enqueueWrite(async () => {
+      await Promise.all(rows.map(({ ref, utxo }) => this.writeJson(this.walletUtxoKey(ref), this.toPersistedUtxo(utxo))));
+      await this.writeJson(this.walletMetaKey('utxoRefs'), Array.from(this.utxoRefs));
+      await Promise.all(removed.map((ref) => this.deleteOrReset(this.walletUtxoKey(ref), null)));
+    });
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [chainId], count: utxos.length });
   }
 
   async listUtxos(query?: ListUtxosQuery): Promise<{ total: number; rows: UtxoRecord[] }> {
@@ -620,30 +713,94 @@ export class KeyValueStore implements StorageAdapter {
     return { total: paged.total, rows: paged.rows.map((utxo) => ({ ...utxo })) };
   }
 
+  async getBalances(options?: { chainId?: number }): Promise<AssetBalance[]> {
+    await this.ensureWalletMetaLoaded();
+    const refs = options?.chainId == null ? Array.from(this.utxoRefs) : Array.from(this.utxoRefs).filter((ref) => ref.startsWith(`${options.chainId}:`));
+    const rows = await Promise.all(refs.map((ref) => this.readUtxo(ref)));
+    return aggregateBalances(
+      rows.filter((row): row is UtxoRecord => row != null),
+      options?.chainId,
+    );
+  }
+
   async markSpent(input: { chainId: number; nullifiers: Hex[] }): Promise<number> {
+    return (await this.markSpentDetailed(input)).length;
+  }
+
+  async markSpentDetailed(input: { chainId: number; nullifiers: Hex[] }): Promise<Hex[]> {
     await this.ensureWalletMetaLoaded();
-    const wanted = new Set(input.nullifiers.map((nf) => nf.toLowerCase()));
-    let updated = 0;
+    const wanted = new Map(input.nullifiers.map((nf) => [nf.toLowerCase(), nf] as const));
+    const matched: Hex[] = [];
     const rows: Array<{ ref: string; utxo: UtxoRecord }> = [];
     const refs = Array.from(this.utxoRefs).filter((ref) => ref.startsWith(`${input.chainId}:`));
     for (const ref of refs) {
       const utxo = await this.readUtxo(ref);
       if (!utxo) continue;
       if (utxo.chainId !== input.chainId) continue;
-      if (!wanted.has(utxo.nullifier.toLowerCase())) continue;
+      const supplied = wanted.get(utxo.nullifier.toLowerCase());
+      if (supplied == null) continue;
       if (!utxo.isSpent) {
         const merged = { ...utxo, isSpent: true };
         this.utxoCache.set(ref, merged);
         rows.push({ ref, utxo: merged });
-        updated++;
+        matched.push(supplied);
       }
     }
     if (rows.length) {
       await this.enqueueWrite(async () => {
         await Promise.all(rows.map(({ ref, utxo }) => this.writeJson(this.walletUtxoKey(ref), this.toPersistedUtxo(utxo))));
       });
+      this.listeners.notify({ type: 'markedSpent', chainId: input.chainId, count: rows.length });
     }
-    return updated;
+    return matched;
+  }
+
+  async deleteUtxosFrom(input: { chainId: number; fromMkIndex: number }): Promise<number> {
+    await this.ensureWalletMetaLoaded();
+    const removed: string[] = [];
+    const refs = Array.from(this.utxoRefs).filter((ref) => ref.startsWith(`${input.chainId}:`));
+    for (const ref of refs) {
+      const utxo = await this.readUtxo(ref);
+      if (!utxo || utxo.mkIndex < input.fromMkIndex) continue;
+      this.utxoCache.delete(ref);
+      this.utxoRefs.delete(ref);
+      removed.push(ref);
+    }
+    if (removed.length) {
+      await this.enqueueWrite(async () => {
+        await Promise.all(removed.map((ref) => this.deleteOrReset(this.walletUtxoKey(ref), null)));
+        await this.writeJson(this.walletMetaKey('utxoRefs'), Array.from(this.utxoRefs));
+      });
+    }
+    return removed.length;
+  }
+
+  /**
+   * Delete spent, unfrozen UTXOs on a chain, optionally only those created before `olderThan`.
+   * The pruned nullifier index is written before the records are dropped, so a re-synced copy is stored as spent.
+   */
+  async pruneSpentUtxos(input: { chainId: number; olderThan?: number }): Promise<number> {
+    await this.ensureWalletMetaLoaded();
+    const pruned = await this.ensurePrunedNullifiersLoaded();
+    const removed: string[] = [];
+    const refs = Array.from(this.utxoRefs).filter((ref) => ref.startsWith(`${input.chainId}:`));
+    for (const ref of refs) {
+      const utxo = await this.readUtxo(ref);
+      if (!utxo || !utxo.isSpent || utxo.isFrozen) continue;
+      if (input.olderThan != null && (utxo.createdAt == null || utxo.createdAt >= input.olderThan)) continue;
+      pruned.add(`${utxo.chainId}:${utxo.nullifier.toLowerCase()}`);
+      this.utxoCache.delete(ref);
+      this.utxoRefs.delete(ref);
+      removed.push(ref);
+    }
+    if (removed.length) {
+      await this.enqueueWrite(async () => {
+        await this.writeJson(this.walletMetaKey('prunedNullifiers'), Array.from(pruned));
+        await this.writeJson(this.walletMetaKey('utxoRefs'), Array.from(this.utxoRefs));
+        await Promise.all(removed.map((ref) => this.deleteOrReset(this.walletUtxoKey(ref), null)));
+      });
+    }
+    return removed.length;
   }
 
   createOperation<TType extends OperationType>(
@@ -712,6 +869,24 @@ export class KeyValueStore implements StorageAdapter {
     return removed.length;
   }
 
+  /**
+   * Hand out the next deterministic-deposit counter for an owner. The read and the advanced write run on the
+   * write queue, so reservations from this instance never interleave; the key is shared across wallet ids.
+   */
+  async reserveDepositCounter(owner: Hex): Promise<number> {
+    const key = this.depositCounterKey(owner);
+    let counter = 0;
+    await this.enqueueWrite(async () => {
+      const stored = this.parseJson<number | null>(await this.options.client.get(key), null) ?? 0;
+      if (!Number.isSafeInteger(stored) || stored < 0) {
+        throw new SdkError('STORAGE', 'Persisted deposit counter is invalid', { owner, stored });
+      }
+      counter = stored;
+      await this.writeJson(key, counter + 1);
+    });
+    return counter;
+  }
+
   listOperations(input?: number | ListOperationsQuery) {
     return applyOperationsQuery(this.operations, input);
   }
@@ -1,6 +1,8 @@
 import type {
+  AssetBalance,
   ChairmanMerkleNodeRecord,
   ChairmanMerkleVersionRecord,
+  CursorHistoryEntry,
   EntryMemoRecord,
   EntryNullifierRecord,
   ListEntryMemosQuery,
@@ -8,6 +10,7 @@ import type {
   ListUtxosQuery,
   MerkleLeafRecord,
   StorageAdapter,
+  StoreEvent,
   SyncCursor,
   UtxoRecord,
   Hex,
@@ -17,7 +20,41 @@ import { newOperationId } from './internal/operationTypes';
 import { applyOperationsQuery } from './internal/operationsQuery';
 import { applyEntryMemoQuery } from './internal/entryMemoQuery';
 import { applyEntryNullifierQuery } from './internal/entryNullifierQuery';
-import { applyUtxoQuery } from './internal/utxoQuery';
+import { applyUtxoQuery, assertUtxosOnChain } from './internal/utxoQuery';
+import { appendBoundedCursorHistory, normalizeMaxCursorHistory, tailCursorHistory } from './internal/cursorHistory';
+import { StoreListeners } from './internal/storeListeners';
+import { aggregateBalances } from './storeBalances';
+
+const AUDITED_METHODS = [
+  'setSyncCursor',
+  'appendCursorHistory',
+  'upsertUtxos',
+  'replaceUtxos',
+  'markSpent',
+  'markSpentDetailed',
+  'deleteUtxosFrom',
+  'pruneSpentUtxos',
+  'appendMerkleLeaves',
+  'clearMerkleLeaves',
+  'putChairmanMerkleNodes',
+  'putChairmanMerkleVersion',
+  'clearChairmanMerkleTree',
+  'upsertEntryMemos',
+  'clearEntryMemos',
+  'upsertEntryNullifiers',
+  'clearEntryNullifiers',
+  'createOperation',
+  'updateOperation',
+  'deleteOperation',
+  'clearOperations',
+  'pruneOperations',
+] as const;
+
+/**
+ * Mutating store call captured by `MemoryStore` when `auditLog` is enabled.
+ * Arguments are deep-copied at call time.
+ */
+export type StoreCall = { method: (typeof AUDITED_METHODS)[number]; args: unknown[] };
 
 /**
  * In-memory StorageAdapter implementation.
@@ -26,7 +63,12 @@ import { applyUtxoQuery } from './internal/utxoQuery';
 export class MemoryStore implements StorageAdapter {
   private walletId: string | undefined;
   private readonly cursors = new Map<number, SyncCursor>();
+  private readonly cursorHistoryByChain = new Map<number, CursorHistoryEntry[]>();
   private readonly utxos = new Map<string, UtxoRecord>();
+  // `${chainId}:${nullifier lowercased}` -> keys into `utxos`, so spent-marking skips the full scan.
+  private readonly utxoKeysByNullifier = new Map<string, Set<string>>();
+  // Nullifiers of pruned UTXOs (same key format), so a coin re-synced from its memo is stored as spent again.
+  private readonly prunedNullifiers = new Set<string>();
   private operations: Array<StoredOperation> = [];
   private readonly merkleLeavesByChain = new Map<number, Array<{ cid: number; commitment: Hex }>>();
   private readonly chairmanMerkleVersionsByChain = new Map<number, Map<number, ChairmanMerkleVersionRecord>>();
@@ -34,13 +76,43 @@ export class MemoryStore implements StorageAdapter {
   private readonly entryMemosByChain = new Map<number, Map<number, EntryMemoRecord>>();
   private readonly entryNullifiersByChain = new Map<number, Map<number, EntryNullifierRecord>>();
   private readonly maxOperations: number;
+  private readonly maxCursorHistory: number;
+  private readonly calls: StoreCall[] = [];
+  private readonly listeners = new StoreListeners();
 
   /**
    * Create a MemoryStore with an optional maxOperations limit.
+   * `maxCursorHistory` bounds the per-chain cursor history (default 256 entries, oldest dropped first).
+   * `auditLog` records every mutating call (for debugging tests), see `auditLog()`.
    */
-  constructor(options?: { maxOperations?: number }) {
+  constructor(options?: { maxOperations?: number; maxCursorHistory?: number; auditLog?: boolean }) {
     const max = options?.maxOperations;
     this.maxOperations = max == null ? Number.POSITIVE_INFINITY : Math.max(0, Math.floor(max));
+    this.maxCursorHistory = normalizeMaxCursorHistory(options?.maxCursorHistory);
+    if (options?.auditLog) {
+      const self = this as unknown as Record<string, (...args: unknown[]) => unknown>;
+      for (const method of AUDITED_METHODS) {
+        const original = self[method]!.bind(this);
+        self[method] = (...args: unknown[]) => {
+          this.calls.push({ method, args: structuredClone(args) });
+          return original(...args);
+        };
+      }
+    }
+  }
+
+  /**
+   * Mutating calls recorded so far, in call order (empty unless `auditLog` is enabled).
+   */
+  auditLog(): StoreCall[] {
+    return [...this.calls];
+  }
+
+  /**
+   * Register a change listener; returns its unsubscribe function.
+   */
+  subscribe(listener: (event: StoreEvent) => void): () => void {
+    return this.listeners.add(listener);
   }
 
   /**
@@ -50,7 +122,10 @@ export class MemoryStore implements StorageAdapter {
     const nextWalletId = options?.walletId ?? this.walletId;
     if (nextWalletId !== this.walletId) {
       this.cursors.clear();
+      this.cursorHistoryByChain.clear();
       this.utxos.clear();
+      this.utxoKeysByNullifier.clear();
+      this.prunedNullifiers.clear();
       this.operations = [];
       this.merkleLeavesByChain.clear();
       this.chairmanMerkleVersionsByChain.clear();
@@ -91,21 +166,87 @@ export class MemoryStore implements StorageAdapter {
    */
   setSyncCursor(chainId: number, cursor: SyncCursor): Promise<void> {
     this.cursors.set(chainId, { ...cursor });
+    this.listeners.notify({ type: 'cursorUpdated', chainId, cursor: { ...cursor } });
     return Promise.resolve();
   }
 
   /**
-   * Upsert UTXOs; preserves spent flag on existing records.
+   * Append a cursor move to the chain's bounded history.
+   */
+  appendCursorHistory(chainId: number, previous: SyncCursor, next: SyncCursor, timestamp: number): Promise<void> {
+    if (this.maxCursorHistory === 0) return Promise.resolve();
+    const history = this.cursorHistoryByChain.get(chainId) ?? [];
+    this.cursorHistoryByChain.set(chainId, appendBoundedCursorHistory(history, { chainId, previous, next, timestamp }, this.maxCursorHistory));
+    return Promise.resolve();
+  }
+
+  /**
+   * Most recent cursor moves for a chain, oldest first.
+   */
+  getCursorHistory(chainId: number, limit?: number): Promise<CursorHistoryEntry[]> {
+    return Promise.resolve(tailCursorHistory(this.cursorHistoryByChain.get(chainId) ?? [], limit));
+  }
+
+  /**
+   * Upsert UTXOs; preserves spent flag on existing records and keeps previously pruned coins spent.
    */
   upsertUtxos(utxos: UtxoRecord[]): Promise<void> {
     for (const utxo of utxos) {
       const key = `${utxo.chainId}:${utxo.commitment}`;
       const prev = this.utxos.get(key);
-      this.utxos.set(key, { ...utxo, isSpent: prev?.isSpent ?? utxo.isSpent });
+      if (prev) this.unindexNullifier(prev, key);
+      const pruned = this.prunedNullifiers.has(`${utxo.chainId}:${utxo.nullifier.toLowerCase()}`);
+      this.utxos.set(key, { ...utxo, isSpent: prev?.isSpent ?? (pruned || utxo.isSpent) });
+      this.indexNullifier(utxo, key);
+    }
+    if (utxos.length) this.listeners.notify({ type: 'utxosUpserted', chainIds: [...new Set(utxos.map((u) => u.chainId))], count: utxos.length });
+    return Promise.resolve();
+  }
+
+  /**
+   * Swap a chain's UTXO set for `utxos` in one synchronous step, so no reader observes a partial or empty set.
+   * Records are stored as given (spent flags are not carried over from the old set).
+   */
+  replaceUtxos(chainId: number, utxos: UtxoRecord[]): Promise<void> {
+    try {
+      assertUtxosOnChain(chainId, utxos);
+    } catch (error) {
+      return Promise.reject(error);
+    }
+    for (const [key, utxo] of this.utxos.entries()) {
+      if (utxo.chainId !== chainId) continue;
+      this.utxos.delete(key);
+      this.unindexNullifier(utxo, key);
+    }
+    for (const utxo of utxos) {
+      const key = `${utxo.chainId}:${utxo.commitment}`;
+      this.utxos.set(key, { ...utxo });
+      this.indexNullifier(utxo, key);
     }
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [chainId], count: utxos.length });
     return Promise.resolve();
   }
 
+  /**
+   * Add a stored UTXO to the nullifier index.
+   */
+  private indexNullifier(utxo: UtxoRecord, key: string) {
+    const nullifierKey = `${utxo.chainId}:${utxo.nullifier.toLowerCase()}`;
+    const keys = this.utxoKeysByNullifier.get(nullifierKey);
+    if (keys) keys.add(key);
+    else this.utxoKeysByNullifier.set(nullifierKey, new Set([key]));
+  }
+
+  /**
+   * Drop a stored UTXO's entry from the nullifier index.
+   */
+  private unindexNullifier(utxo: UtxoRecord, key: string) {
+    const nullifierKey = `${utxo.chainId}:${utxo.nullifier.toLowerCase()}`;
+    const keys = this.utxoKeysByNullifier.get(nullifierKey);
+    keys?.delete(key);
+    if (keys?.size === 0) this.utxoKeysByNullifier.delete(nullifierKey);
+  }
+
   /**
    * List UTXOs with query filtering and pagination.
    */
@@ -115,21 +256,75 @@ export class MemoryStore implements StorageAdapter {
     return Promise.resolve({ total: paged.total, rows: paged.rows.map((utxo) => ({ ...utxo })) });
   }
 
+  /**
+   * Sum unspent UTXOs per asset in place, without copying or sorting the rows.
+   */
+  getBalances(options?: { chainId?: number }): Promise<AssetBalance[]> {
+    return Promise.resolve(aggregateBalances(this.utxos.values(), options?.chainId));
+  }
+
   /**
    * Mark matching UTXOs as spent by nullifier.
    */
   markSpent(input: { chainId: number; nullifiers: Hex[] }): Promise<number> {
-    const wanted = new Set(input.nullifiers.map((nf) => nf.toLowerCase()));
-    let updated = 0;
-    for (const [key, utxo] of this.utxos.entries()) {
-      if (utxo.chainId !== input.chainId) continue;
-      if (!wanted.has(utxo.nullifier.toLowerCase())) continue;
-      if (!utxo.isSpent) {
-        this.utxos.set(key, { ...utxo, isSpent: true });
-        updated++;
+    return Promise.resolve(this.markSpentMatching(input).length);
+  }
+
+  /**
+   * Mark UTXOs as spent and return the supplied nullifiers that matched a previously unspent UTXO.
+   */
+  markSpentDetailed(input: { chainId: number; nullifiers: Hex[] }): Promise<Hex[]> {
+    return Promise.resolve(this.markSpentMatching(input));
+  }
+
+  /**
+   * Mark unspent UTXOs whose nullifier is in `input.nullifiers` and return the matched inputs.
+   */
+  private markSpentMatching(input: { chainId: number; nullifiers: Hex[] }): Hex[] {
+    const wanted = new Map(input.nullifiers.map((nf) => [nf.toLowerCase(), nf] as const));
+    const matched: Hex[] = [];
+    for (const [nullifier, supplied] of wanted) {
+      for (const key of this.utxoKeysByNullifier.get(`${input.chainId}:${nullifier}`) ?? []) {
+        const utxo = this.utxos.get(key)!;
+        if (!utxo.isSpent) {
+          this.utxos.set(key, { ...utxo, isSpent: true });
+          matched.push(supplied);
+        }
       }
     }
-    return Promise.resolve(updated);
+    if (matched.length) this.listeners.notify({ type: 'markedSpent', chainId: input.chainId, count: matched.length });
+    return matched;
+  }
+
+  /**
+   * Delete UTXOs with mkIndex >= fromMkIndex on a chain.
+   */
+  deleteUtxosFrom(input: { chainId: number; fromMkIndex: number }): Promise<number> {
+    let deleted = 0;
+    for (const [key, utxo] of this.utxos.entries()) {
+      if (utxo.chainId !== input.chainId || utxo.mkIndex < input.fromMkIndex) continue;
+      this.utxos.delete(key);
+      this.unindexNullifier(utxo, key);
+      deleted++;
+    }
+    return Promise.resolve(deleted);
+  }
+
+  /**
+   * Delete spent, unfrozen UTXOs on a chain, optionally only those created before `olderThan`.
+   * Pruned nullifiers are remembered so `upsertUtxos` stores a re-synced copy as spent.
+   */
+  pruneSpentUtxos(input: { chainId: number; olderThan?: number }): Promise<number> {
+    let deleted = 0;
+    for (const [key, utxo] of this.utxos.entries()) {
+      if (utxo.chainId !== input.chainId || !utxo.isSpent || utxo.isFrozen) continue;
+      if (input.olderThan != null && (utxo.createdAt == null || utxo.createdAt >= input.olderThan)) continue;
+      this.utxos.delete(key);
+      this.unindexNullifier(utxo, key);
+      this.prunedNullifiers.add(`${utxo.chainId}:${utxo.nullifier.toLowerCase()}`);
+      deleted++;
+    }
+    return Promise.resolve(deleted);
   }
 
   /**
@@ -1,4 +1,6 @@
 import type {
+  AssetBalance,
+  CursorHistoryEntry,
   EntryMemoRecord,
   EntryNullifierRecord,
   Hex,
@@ -9,11 +11,17 @@ import type {
   ChairmanMerkleNodeRecord,
   ChairmanMerkleVersionRecord,
   StorageAdapter,
+  StoreEvent,
+  StoreStats,
   SyncCursor,
   UtxoRecord,
 } from '../types';
 import type { ListOperationsQuery, OperationDetailFor, OperationType, StoredOperation } from './internal/operationTypes';
 import { newOperationId } from './internal/operationTypes';
+import { assertUtxosOnChain } from './internal/utxoQuery';
+import { normalizeMaxCursorHistory, parseCursorHistory } from './internal/cursorHistory';
+import { StoreListeners } from './internal/storeListeners';
+import { aggregateBalances } from './storeBalances';
 
 type SqliteBindValue = string | number | bigint | Uint8Array | null;
 
@@ -48,6 +56,7 @@ export type SqliteStoreOptions = {
   filename: string;
   walletId?: string;
   maxOperations?: number;
+  maxCursorHistory?: number;
   readonly?: boolean;
   createIfMissing?: boolean;
   busyTimeoutMs?: number;
@@ -124,11 +133,14 @@ function sortTextValues(value: string | string[] | undefined): string[] | undefi
 export class SqliteStore implements StorageAdapter {
   private walletId: string | undefined;
   private readonly maxOperations: number;
+  private readonly maxCursorHistory: number;
   private db: SqliteDatabase | null;
+  private readonly listeners = new StoreListeners();
 
   constructor(private readonly options: SqliteStoreOptions) {
     const max = options.maxOperations;
     this.maxOperations = max == null ? Number.POSITIVE_INFINITY : Math.max(0, Math.floor(max));
+    this.maxCursorHistory = normalizeMaxCursorHistory(options.maxCursorHistory);
     this.walletId = options.walletId;
     this.db = options.database ?? null;
   }
@@ -146,6 +158,13 @@ export class SqliteStore implements StorageAdapter {
     this.db = null;
   }
 
+  /**
+   * Register a change listener; returns its unsubscribe function. Events fire after the change is committed.
+   */
+  subscribe(listener: (event: StoreEvent) => void): () => void {
+    return this.listeners.add(listener);
+  }
+
   private walletKey(): string {
     return this.walletId ?? 'default';
   }
@@ -225,6 +244,16 @@ export class SqliteStore implements StorageAdapter {
         PRIMARY KEY (wallet_id, chain_id)
       );
 
+      CREATE TABLE IF NOT EXISTS cursor_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        wallet_id TEXT NOT NULL,
+        chain_id INTEGER NOT NULL,
+        previous_cursor TEXT NOT NULL,
+        next_cursor TEXT NOT NULL,
+        timestamp INTEGER NOT NULL
+      );
+      CREATE INDEX IF NOT EXISTS idx_cursor_history_wallet_chain ON cursor_history(wallet_id, chain_id, id);
+
       CREATE TABLE IF NOT EXISTS utxos (
         wallet_id TEXT NOT NULL,
         chain_id INTEGER NOT NULL,
@@ -244,6 +273,13 @@ export class SqliteStore implements StorageAdapter {
       CREATE INDEX IF NOT EXISTS idx_utxos_wallet_nullifier ON utxos(wallet_id, chain_id, nullifier);
       CREATE INDEX IF NOT EXISTS idx_utxos_wallet_state ON utxos(wallet_id, is_spent, is_frozen);
 
+      CREATE TABLE IF NOT EXISTS pruned_nullifiers (
+        wallet_id TEXT NOT NULL,
+        chain_id INTEGER NOT NULL,
+        nullifier TEXT NOT NULL,
+        PRIMARY KEY (wallet_id, chain_id, nullifier)
+      );
+
       CREATE TABLE IF NOT EXISTS operations (
         wallet_id TEXT NOT NULL,
         id TEXT NOT NULL,
@@ -309,6 +345,12 @@ export class SqliteStore implements StorageAdapter {
         PRIMARY KEY (chain_id, nid)
       );
       CREATE INDEX IF NOT EXISTS idx_entry_nullifiers_chain_created ON entry_nullifiers(chain_id, created_at);
+
+      CREATE TABLE IF NOT EXISTS deposit_counters (
+        owner TEXT NOT NULL,
+        next_counter INTEGER NOT NULL,
+        PRIMARY KEY (owner)
+      );
     `);
   }
 
@@ -355,6 +397,49 @@ export class SqliteStore implements StorageAdapter {
          merkle = excluded.merkle`,
       [this.walletKey(), chainId, cursor.memo, cursor.nullifier, cursor.merkle],
     );
+    this.listeners.notify({ type: 'cursorUpdated', chainId, cursor: { ...cursor } });
+  }
+
+  /**
+   * Append a cursor move and drop the chain's entries beyond `maxCursorHistory`, in one transaction.
+   */
+  async appendCursorHistory(chainId: number, previous: SyncCursor, next: SyncCursor, timestamp: number): Promise<void> {
+    if (this.maxCursorHistory === 0) return;
+    const db = this.ensureDb();
+    const walletId = this.walletKey();
+    db.exec('BEGIN IMMEDIATE');
+    try {
+      this.run(`INSERT INTO cursor_history (wallet_id, chain_id, previous_cursor, next_cursor, timestamp) VALUES (?, ?, ?, ?, ?)`, [
+        walletId,
+        chainId,
+        JSON.stringify(previous),
+        JSON.stringify(next),
+        timestamp,
+      ]);
+      this.run(
+        `DELETE FROM cursor_history WHERE wallet_id = ? AND chain_id = ? AND id NOT IN (
+           SELECT id FROM cursor_history WHERE wallet_id = ? AND chain_id = ? ORDER BY id DESC LIMIT ?
+         )`,
+        [walletId, chainId, walletId, chainId, this.maxCursorHistory],
+      );
+      db.exec('COMMIT');
+    } catch (error) {
+      db.exec('ROLLBACK');
+      throw error;
+    }
+  }
+
+  /**
+   * Most recent cursor moves for a chain, oldest first.
+   */
+  async getCursorHistory(chainId: number, limit?: number): Promise<CursorHistoryEntry[]> {
+    const rowLimit = limit == null ? -1 : Math.max(0, Math.floor(limit));
+    const rows = this.rows<{ previous_cursor: string; next_cursor: string; timestamp: number }>(
+      `SELECT previous_cursor, next_cursor, timestamp FROM cursor_history WHERE wallet_id = ? AND chain_id = ? ORDER BY id DESC LIMIT ?`,
+      [this.walletKey(), chainId, rowLimit],
+    );
+    const entries = rows.reverse().map((row) => ({ previous: JSON.parse(row.previous_cursor), next: JSON.parse(row.next_cursor), timestamp: Number(row.timestamp) }));
+    return parseCursorHistory(entries, chainId);
   }
 
   async upsertUtxos(utxos: UtxoRecord[]): Promise<void> {
@@ -378,10 +463,53 @@ export class SqliteStore implements StorageAdapter {
         END`,
     );
 
+    const prunedStmt = db.prepare(`SELECT 1 AS pruned FROM pruned_nullifiers WHERE wallet_id = ? AND chain_id = ? AND nullifier = ?`);
+
     db.exec('BEGIN IMMEDIATE');
     try {
       for (const utxo of utxos) {
+        // A coin pruned while spent stays spent when it is synced again.
+        const pruned = prunedStmt.get(this.walletKey(), utxo.chainId, utxo.nullifier.toLowerCase()) != null;
         stmt.run(
+          this.walletKey(),
+          utxo.chainId,
+          utxo.assetId,
+          utxo.amount.toString(),
+          utxo.commitment,
+          utxo.nullifier,
+          utxo.mkIndex,
+          boolToInt(utxo.isFrozen),
+          boolToInt(utxo.isSpent || pruned),
+          utxo.memo ?? null,
+          utxo.createdAt ?? null,
+        );
+      }
+      db.exec('COMMIT');
+    } catch (error) {
+      db.exec('ROLLBACK');
+      throw error;
+    }
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [...new Set(utxos.map((u) => u.chainId))], count: utxos.length });
+  }
+
+  /**
+   * Swap a chain's UTXO set inside one transaction, so readers see either the old set or the new one.
+   * Records are stored as given, including `isSpent`.
+   */
+  async replaceUtxos(chainId: number, utxos: UtxoRecord[]): Promise<void> {
+    assertUtxosOnChain(chainId, utxos);
+    const db = this.ensureDb();
+    const insert = db.prepare(
+      `INSERT INTO utxos (
+        wallet_id, chain_id, asset_id, amount, commitment, nullifier, mk_index, is_frozen, is_spent, memo, created_at
+      ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)`,
+    );
+
+    db.exec('BEGIN IMMEDIATE');
+    try {
+      this.run(`DELETE FROM utxos WHERE wallet_id = ? AND chain_id = ?`, [this.walletKey(), chainId]);
+      for (const utxo of utxos) {
+        insert.run(
           this.walletKey(),
           utxo.chainId,
           utxo.assetId,
@@ -400,6 +528,7 @@ export class SqliteStore implements StorageAdapter {
       db.exec('ROLLBACK');
       throw error;
     }
+    this.listeners.notify({ type: 'utxosUpserted', chainIds: [chainId], count: utxos.length });
   }
 
   async listUtxos(query?: ListUtxosQuery): Promise<{ total: number; rows: UtxoRecord[] }> {
@@ -442,10 +571,13 @@ export class SqliteStore implements StorageAdapter {
     const total = totalRow?.total ?? 0;
 
     const direction = order === 'desc' ? 'DESC' : 'ASC';
+    // Amounts are stored as decimal text without leading zeros, so length then text orders them numerically.
     const orderSql =
-      orderBy === 'createdAt'
-        ? `ORDER BY (created_at IS NULL) ASC, created_at ${direction}, mk_index ${direction}`
-        : `ORDER BY mk_index ${direction}, (created_at IS NULL) ASC, created_at ${direction}`;
+      orderBy === 'amount'
+        ? `ORDER BY LENGTH(amount) ${direction}, amount ${direction}, mk_index ${direction}`
+        : orderBy === 'createdAt'
+          ? `ORDER BY (created_at IS NULL) ASC, created_at ${direction}, mk_index ${direction}`
+          : `ORDER BY mk_index ${direction}, (created_at IS NULL) ASC, created_at ${direction}`;
 
     const offset = Math.max(0, Math.floor(query?.offset ?? 0));
     const limit = query?.limit == null ? undefined : Math.max(0, Math.floor(query.limit));
@@ -481,19 +613,121 @@ export class SqliteStore implements StorageAdapter {
     };
   }
 
+  /**
+   * Sum unspent UTXOs per asset. Amounts are stored as decimal text beyond SQLite's integer range,
+   * so the rows are summed as bigint in JS rather than with `SUM()`.
+   */
+  async getBalances(options?: { chainId?: number }): Promise<AssetBalance[]> {
+    const where = ['wallet_id = ?', 'is_spent = 0'];
+    const params: SqliteBindValue[] = [this.walletKey()];
+    if (options?.chainId != null) {
+      where.push('chain_id = ?');
+      params.push(options.chainId);
+    }
+    const rows = this.rows<{ chain_id: number; asset_id: string; amount: string; is_frozen: number }>(
+      `SELECT chain_id, asset_id, amount, is_frozen FROM utxos WHERE ${where.join(' AND ')}`,
+      params,
+    );
+    return aggregateBalances(
+      rows.map((row) => ({
+        chainId: Number(row.chain_id),
+        assetId: row.asset_id,
+        amount: BigInt(row.amount),
+        commitment: '0x',
+        nullifier: '0x',
+        mkIndex: 0,
+        isFrozen: Boolean(row.is_frozen),
+        isSpent: false,
+      })),
+      options?.chainId,
+    );
+  }
+
   async markSpent(input: { chainId: number; nullifiers: Hex[] }): Promise<number> {
-    if (!input.nullifiers.length) return 0;
-    const lower = Array.from(new Set(input.nullifiers.map((nf) => nf.toLowerCase())));
-    const placeholders = this.inClause(lower);
-    const sql = `
-      UPDATE utxos
-      SET is_spent = 1
-      WHERE wallet_id = ?
-        AND chain_id = ?
-        AND is_spent = 0
-        AND lower(nullifier) IN (${placeholders})
-    `;
-    return this.run(sql, [this.walletKey(), input.chainId, ...lower]);
+    return (await this.markSpentDetailed(input)).length;
+  }
+
+  /**
+   * Mark UTXOs as spent and return the supplied nullifiers that matched a previously unspent UTXO.
+   * The matching rows are read and updated inside one transaction.
+   */
+  async markSpentDetailed(input: { chainId: number; nullifiers: Hex[] }): Promise<Hex[]> {
+    if (!input.nullifiers.length) return [];
+    const db = this.ensureDb();
+    const wanted = new Map(input.nullifiers.map((nf) => [nf.toLowerCase(), nf] as const));
+    const lower = Array.from(wanted.keys());
+    const condition = `wallet_id = ? AND chain_id = ? AND is_spent = 0 AND lower(nullifier) IN (${this.inClause(lower)})`;
+    const params: SqliteBindValue[] = [this.walletKey(), input.chainId, ...lower];
+
+    db.exec('BEGIN IMMEDIATE');
+    let matched: Hex[];
+    try {
+      const rows = this.rows<{ nullifier: string }>(`SELECT lower(nullifier) AS nullifier FROM utxos WHERE ${condition}`, params);
+      this.run(`UPDATE utxos SET is_spent = 1 WHERE ${condition}`, params);
+      db.exec('COMMIT');
+      matched = rows.map((row) => wanted.get(row.nullifier)!);
+    } catch (error) {
+      db.exec('ROLLBACK');
+      throw error;
+    }
+    if (matched.length) this.listeners.notify({ type: 'markedSpent', chainId: input.chainId, count: matched.length });
+    return matched;
+  }
+
+  async deleteUtxosFrom(input: { chainId: number; fromMkIndex: number }): Promise<number> {
+    return this.run(`DELETE FROM utxos WHERE wallet_id = ? AND chain_id = ? AND mk_index >= ?`, [this.walletKey(), input.chainId, input.fromMkIndex]);
+  }
+
+  /**
+   * Delete spent, unfrozen UTXOs on a chain (optionally only those created before `olderThan`), recording their
+   * nullifiers in `pruned_nullifiers` in the same transaction so a re-synced copy is stored as spent.
+   */
+  async pruneSpentUtxos(input: { chainId: number; olderThan?: number }): Promise<number> {
+    const db = this.ensureDb();
+    const where = ['wallet_id = ?', 'chain_id = ?', 'is_spent = 1', 'is_frozen = 0'];
+    const params: SqliteBindValue[] = [this.walletKey(), input.chainId];
+    if (input.olderThan != null) {
+      where.push('created_at IS NOT NULL', 'created_at < ?');
+      params.push(input.olderThan);
+    }
+    const condition = where.join(' AND ');
+
+    db.exec('BEGIN IMMEDIATE');
+    try {
+      this.run(`INSERT OR IGNORE INTO pruned_nullifiers (wallet_id, chain_id, nullifier) SELECT wallet_id, chain_id, lower(nullifier) FROM utxos WHERE ${condition}`, params);
+      const deleted = this.run(`DELETE FROM utxos WHERE ${condition}`, params);
+      db.exec('COMMIT');
+      return deleted;
+    } catch (error) {
+      db.exec('ROLLBACK');
+      throw error;
+    }
+  }
+
+  async stats(): Promise<StoreStats> {
+    const walletId = this.walletKey();
+    const utxos = this.row<{ total: number; unspent: number | null }>(
+      `SELECT COUNT(*) AS total, SUM(CASE WHEN is_spent = 0 THEN 1 ELSE 0 END) AS unspent FROM utxos WHERE wallet_id = ?`,
+      [walletId],
+    );
+    const cursors: Record<number, SyncCursor> = {};
+    for (const row of this.rows<{ chain_id: number; memo: number; nullifier: number; merkle: number }>(
+      `SELECT chain_id, memo, nullifier, merkle FROM sync_cursors WHERE wallet_id = ?`,
+      [walletId],
+    )) {
+      cursors[row.chain_id] = { memo: row.memo, nullifier: row.nullifier, merkle: row.merkle };
+    }
+    const byStatus: Record<string, number> = {};
+    let total = 0;
+    for (const row of this.rows<{ status: string; count: number }>(`SELECT status, COUNT(*) AS count FROM operations WHERE wallet_id = ? GROUP BY status`, [walletId])) {
+      byStatus[row.status] = Number(row.count);
+      total += Number(row.count);
+    }
+    return {
+      utxos: { total: Number(utxos?.total ?? 0), unspent: Number(utxos?.unspent ?? 0) },
+      cursors,
+      operations: { total, byStatus },
+    };
   }
 
   async getMerkleLeaves(chainId: number): Promise<Array<{ cid: number; commitment: Hex }> | undefined> {
@@ -964,6 +1198,18 @@ export class SqliteStore implements StorageAdapter {
     return ids.length;
   }
 
+  async reserveDepositCounter(owner: Hex): Promise<number> {
+    // Single upsert statement, so the advance is atomic even across connections to the same file.
+    const row = this.row<{ next_counter: number | bigint }>(
+      `INSERT INTO deposit_counters (owner, next_counter) VALUES (?, 1)
+       ON CONFLICT(owner) DO UPDATE SET next_counter = next_counter + 1
+       RETURNING next_counter`,
+      [owner.toLowerCase()],
+    );
+    if (!row) throw new Error('SqliteStore failed to reserve a deposit counter');
+    return Number(row.next_counter) - 1;
+  }
+
   listOperations(input?: number | ListOperationsQuery): StoredOperation[] {
     const query: ListOperationsQuery = typeof input === 'number' || input == null ? { limit: input } : input;
 
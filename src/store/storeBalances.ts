@@ -0,0 +1,32 @@
+import type { AssetBalance, StorageAdapter, UtxoRecord } from '../types';
+
+/**
+ * Sum unspent UTXOs per `(chainId, assetId)`, ordered by chain id then asset id.
+ * Spent rows are skipped; frozen rows count toward `frozen` and `total` only.
+ */
+export function aggregateBalances(utxos: Iterable<UtxoRecord>, chainId?: number): AssetBalance[] {
+  const byKey = new Map<string, AssetBalance>();
+  for (const utxo of utxos) {
+    if (utxo.isSpent || (chainId != null && utxo.chainId !== chainId)) continue;
+    const key = `${utxo.chainId}:${utxo.assetId}`;
+    let balance = byKey.get(key);
+    if (!balance) {
+      balance = { chainId: utxo.chainId, assetId: utxo.assetId, spendable: 0n, frozen: 0n, total: 0n };
+      byKey.set(key, balance);
+    }
+    if (utxo.isFrozen) balance.frozen += utxo.amount;
+    else balance.spendable += utxo.amount;
+    balance.total += utxo.amount;
+  }
+  return [...byKey.values()].sort((a, b) => a.chainId - b.chainId || (a.assetId < b.assetId ? -1 : a.assetId > b.assetId ? 1 : 0));
+}
+
+/**
+ * Unspent balances per asset, preferring the adapter's own `getBalances` implementation.
+ * The fallback lists every unspent UTXO (frozen included) and sums them.
+ */
+export async function getStoreBalances(store: StorageAdapter, options?: { chainId?: number }): Promise<AssetBalance[]> {
+  if (store.getBalances) return store.getBalances(options);
+  const { rows } = await store.listUtxos({ chainId: options?.chainId, includeFrozen: true });
+  return aggregateBalances(rows, options?.chainId);
+}
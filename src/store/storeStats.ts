@@ -0,0 +1,29 @@
+import type { StorageAdapter, StoreStats, SyncCursor } from '../types';
+
+/**
+ * Collect store aggregates, preferring the adapter's own `stats` implementation.
+ *
+ * The fallback scans `listUtxos`/`listOperations`; cursors are reported for `chainIds`
+ * plus every chain that holds UTXOs, since the adapter interface cannot enumerate cursors.
+ */
+export async function getStoreStats(store: StorageAdapter, options?: { chainIds?: number[] }): Promise<StoreStats> {
+  if (store.stats) return store.stats(options);
+
+  const { rows } = await store.listUtxos({ includeSpent: true, includeFrozen: true });
+  const chainIds = new Set([...(options?.chainIds ?? []), ...rows.map((u) => u.chainId)]);
+  const cursors: Record<number, SyncCursor> = {};
+  for (const chainId of chainIds) {
+    const cursor = await store.getSyncCursor(chainId);
+    if (cursor) cursors[chainId] = cursor;
+  }
+
+  const operations = store.listOperations({ limit: Number.MAX_SAFE_INTEGER });
+  const byStatus: Record<string, number> = {};
+  for (const op of operations) byStatus[op.status] = (byStatus[op.status] ?? 0) + 1;
+
+  return {
+    utxos: { total: rows.length, unspent: rows.filter((u) => !u.isSpent).length },
+    cursors,
+    operations: { total: operations.length, byStatus },
+  };
+}
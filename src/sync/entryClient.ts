@@ -1,4 +1,4 @@
-import type { Hex, SdkEvent } from '../types';
+import type { ChainId, ContractAddress, Hex, SdkEvent } from '../types';
 import { SdkError } from '../errors';
 import { isHexStrict } from '../utils/hex';
 import { errorToDebug, nonOkResponseDetail } from '../utils/httpDebug';
@@ -26,7 +26,25 @@ interface EntryListResponse<T> {
   data?: { data?: T[]; total?: number } | { data?: T[]; total?: number; ready?: boolean };
 }
 
+interface EntryUpdatesResponse {
+  code?: number;
+  message?: string;
+  data?: {
+    memos?: { data?: EntryMemo[]; total?: number };
+    nullifiers?: { data?: EntryNullifier[]; total?: number; ready?: boolean };
+  };
+}
+
+/**
+ * Combined memo + nullifier page returned by `EntryClient.listUpdates`.
+ */
+export interface EntryUpdatesResult {
+  memos: { items: EntryMemo[]; total: number };
+  nullifiers: { items: EntryNullifier[]; total: number; ready: boolean };
+}
+
 import { joinUrl } from '../utils/url';
+import { isTimeoutError } from '../utils/signal';
 
 /**
  * Append query parameters to a base URL.
@@ -138,19 +156,124 @@ const unwrapListWithReady = <T>(payload: EntryListResponse<T>, detail: Record<st
 
 type DebugEmitter = (event: Extract<SdkEvent, { type: 'debug' }>) => void;
 
+const ETAG_CACHE_MAX_ENTRIES = 64;
+const DEFAULT_SUBSCRIBE_POLL_MS = 5_000;
+const DEFAULT_SUBSCRIBE_PAGE_SIZE = 512;
+
+/**
+ * Resolve after `ms`, or reject as soon as `signal` aborts.
+ */
+const waitFor = (ms: number, signal?: AbortSignal) =>
+  new Promise<void>((resolve, reject) => {
+    const t = setTimeout(resolve, ms);
+    const onAbort = () => {
+      clearTimeout(t);
+      reject(signal?.reason ?? new SdkError('SYNC', 'Aborted'));
+    };
+    if (signal) {
+      if (signal.aborted) return onAbort();
+      signal.addEventListener('abort', onAbort, { once: true });
+    }
+  });
+
 /**
  * HTTP client for EntryService memo/nullifier endpoints.
  */
 export class EntryClient {
+  private readonly retry: { attempts: number; baseDelayMs: number; maxDelayMs: number };
+  // Last ETag and parsed body per request URL (endpoint + cursor), oldest first.
+  private readonly etags = new Map<string, { etag: string; payload: unknown }>();
+
+  /**
+   * `retry` re-sends requests that fail with a network error, HTTP 429 or 5xx, waiting a jittered exponential backoff
+   * between attempts; other 4xx responses fail immediately. Defaults to a single attempt.
+   */
   constructor(
     private readonly baseUrl: string,
     private readonly debugEmit?: DebugEmitter,
-  ) {}
+    options?: { retry?: { attempts?: number; baseDelayMs?: number; maxDelayMs?: number } },
+  ) {
+    this.retry = {
+      attempts: Math.max(1, Math.floor(options?.retry?.attempts ?? 1)),
+      baseDelayMs: Math.max(0, options?.retry?.baseDelayMs ?? 250),
+      maxDelayMs: Math.max(0, options?.retry?.maxDelayMs ?? 5_000),
+    };
+  }
+
+  /**
+   * GET `url` and parse its JSON body. When the service tagged the previous response for this URL with an ETag,
+   * the request carries `If-None-Match` and a 304 reuses the cached body instead of downloading it again.
+   */
+  private async getJson(url: string, signal: AbortSignal | undefined, failure: string): Promise<unknown> {
+    const cached = this.etags.get(url);
+    const response = await this.get(url, signal, failure, cached?.etag);
+    if (cached && response.status === 304) {
+      this.debugEmit?.({ type: 'debug', payload: { scope: 'http:entry', message: 'not_modified', detail: { url } } });
+      return cached.payload;
+    }
+    const payload = await response.json();
+    const etag = response.headers?.get('etag');
+    this.etags.delete(url);
+    if (etag) {
+      this.etags.set(url, { etag, payload });
+      if (this.etags.size > ETAG_CACHE_MAX_ENTRIES) this.etags.delete(this.etags.keys().next().value!);
+    }
+    return payload;
+  }
+
+  /**
+   * GET `url` under the retry policy. Throws SdkError(SYNC) with `failure` and the attempt count on a final non-2xx
+   * response; a network error is rethrown as-is after a single attempt and wrapped once retries are exhausted.
+   * An expired timeout signal throws SdkError(SYNC) with `reason: 'timeout'` instead.
+   * With `etag`, a 304 Not Modified response is returned to the caller instead of failing.
+   */
+  private async get(url: string, signal: AbortSignal | undefined, failure: string, etag?: string): Promise<Response> {
+    for (let attempt = 1; ; attempt++) {
+      this.debugEmit?.({
+        type: 'debug',
+        payload: { scope: 'http:entry', message: 'request', detail: { method: 'GET', url, attempt } },
+      });
+      let response: Response;
+      try {
+        response = await fetch(url, etag ? { signal, headers: { 'If-None-Match': etag } } : { signal });
+      } catch (error) {
+        this.debugEmit?.({ type: 'debug', payload: { scope: 'http:entry', message: 'network_error', detail: { url, attempt, error: errorToDebug(error) } } });
+        // The caller's timeout signal is shared by every attempt, so an expired one ends the retry loop too.
+        if (isTimeoutError(error) || (signal?.aborted && isTimeoutError(signal.reason))) {
+          throw new SdkError('SYNC', 'Entry request timed out', { reason: 'timeout', url, attempts: attempt }, error);
+        }
+        if (signal?.aborted || attempt >= this.retry.attempts) {
+          if (attempt === 1) throw error;
+          throw new SdkError('SYNC', `${failure} after ${attempt} attempts`, { url, attempts: attempt }, error);
+        }
+        await waitFor(this.backoffDelay(attempt), signal);
+        continue;
+      }
+      this.debugEmit?.({
+        type: 'debug',
+        payload: { scope: 'http:entry', message: 'response', detail: { url, attempt, status: response.status, ok: response.ok } },
+      });
+      if (response.ok || (etag && response.status === 304)) return response;
+      const retryable = response.status === 429 || response.status >= 500;
+      if (!retryable || attempt >= this.retry.attempts) {
+        throw new SdkError('SYNC', failure, { ...(await nonOkResponseDetail(response, url)), attempts: attempt });
+      }
+      await waitFor(this.backoffDelay(attempt), signal);
+    }
+  }
+
+  /**
+   * Equal-jitter exponential backoff: half of the capped delay is fixed, the other half random.
+   */
+  private backoffDelay(attempt: number) {
+    const capped = Math.min(this.retry.maxDelayMs, this.retry.baseDelayMs * 2 ** Math.min(attempt - 1, 16));
+    return Math.floor(capped / 2 + Math.random() * (capped / 2));
+  }
 
   /**
    * Fetch memo pages for a viewing address.
    */
-  async listMemos(input: { chainId: number; address: string; offset: number; limit: number; signal?: AbortSignal }) {
+  async listMemos(input: { chainId: ChainId; address: ContractAddress; offset: number; limit: number; signal?: AbortSignal }) {
     const url = withQuery(joinUrl(this.baseUrl, '/api/v1/viewing/memos/list'), {
       offset: input.offset,
       limit: input.limit,
@@ -158,33 +281,76 @@ export class EntryClient {
       address: input.address,
       order: 'asc',
     });
-    this.debugEmit?.({
-      type: 'debug',
-      payload: { scope: 'http:entry', message: 'request', detail: { method: 'GET', url } },
-    });
-    let response: Response;
-    try {
-      response = await fetch(url, { signal: input.signal });
-    } catch (error) {
-      this.debugEmit?.({ type: 'debug', payload: { scope: 'http:entry', message: 'network_error', detail: { url, error: errorToDebug(error) } } });
-      throw error;
-    }
-    this.debugEmit?.({
-      type: 'debug',
-      payload: { scope: 'http:entry', message: 'response', detail: { url, status: response.status, ok: response.ok } },
-    });
-    if (!response.ok) {
-      throw new SdkError('SYNC', 'EntryService memos request failed', await nonOkResponseDetail(response, url));
-    }
-    const payload = (await response.json()) as EntryListResponse<EntryMemo>;
+    const payload = (await this.getJson(url, input.signal, 'EntryService memos request failed')) as EntryListResponse<EntryMemo>;
     const { items, total } = unwrapList(payload, { url });
     return { items: items.map(normalizeMemoEntry), total };
   }
 
+  /**
+   * Fetch a single memo by cid (a one-item page at offset=cid).
+   * Returns null when the cid has not been indexed yet.
+   */
+  async getMemo(input: { chainId: ChainId; address: ContractAddress; cid: number; signal?: AbortSignal }): Promise<EntryMemo | null> {
+    if (!Number.isInteger(input.cid) || input.cid < 0) {
+      throw new SdkError('SYNC', 'Invalid memo cid', { cid: input.cid });
+    }
+    const { items } = await this.listMemos({ chainId: input.chainId, address: input.address, offset: input.cid, limit: 1, signal: input.signal });
+    return items.find((item) => item.cid === input.cid) ?? null;
+  }
+
+  /**
+   * Stream memos from `fromCid` onward by polling `listMemos`.
+   *
+   * Each poll pages from the cid after the last delivered memo and yields the rows in order, skipping replayed ones.
+   * Full pages are followed by an immediate poll; otherwise the stream waits `pollMs` (default 5s) before polling
+   * again. A page that starts past the next expected cid throws SdkError(SYNC). Aborting `signal` ends the stream.
+   */
+  async *subscribeMemos(input: {
+    chainId: ChainId;
+    address: ContractAddress;
+    fromCid: number;
+    signal?: AbortSignal;
+    pollMs?: number;
+    pageSize?: number;
+  }): AsyncGenerator<EntryMemo, void, undefined> {
+    if (!Number.isInteger(input.fromCid) || input.fromCid < 0) {
+      throw new SdkError('SYNC', 'Invalid memo cid', { cid: input.fromCid });
+    }
+    const pollMs = input.pollMs ?? DEFAULT_SUBSCRIBE_POLL_MS;
+    const pageSize = input.pageSize ?? DEFAULT_SUBSCRIBE_PAGE_SIZE;
+    let nextCid = input.fromCid;
+
+    while (!input.signal?.aborted) {
+      let items: EntryMemo[];
+      try {
+        ({ items } = await this.listMemos({ chainId: input.chainId, address: input.address, offset: nextCid, limit: pageSize, signal: input.signal }));
+      } catch (error) {
+        if (input.signal?.aborted) return;
+        throw error;
+      }
+      for (const memo of items) {
+        if (memo.cid != null) {
+          if (memo.cid < nextCid) continue;
+          if (memo.cid > nextCid) {
+            throw new SdkError('SYNC', 'EntryService memo page skipped cids', { chainId: input.chainId, expected: nextCid, cid: memo.cid });
+          }
+        }
+        nextCid += 1;
+        yield memo;
+      }
+      if (items.length >= pageSize) continue;
+      try {
+        await waitFor(pollMs, input.signal);
+      } catch {
+        return;
+      }
+    }
+  }
+
   /**
    * Fetch nullifier pages for a viewing address.
    */
-  async listNullifiers(input: { chainId: number; address: string; offset: number; limit: number; signal?: AbortSignal }) {
+  async listNullifiers(input: { chainId: ChainId; address: ContractAddress; offset: number; limit: number; signal?: AbortSignal }) {
     const url = withQuery(joinUrl(this.baseUrl, '/api/v1/viewing/nullifier/list'), {
       offset: input.offset,
       limit: input.limit,
@@ -192,25 +358,7 @@ export class EntryClient {
       address: input.address,
       order: 'asc',
     });
-    this.debugEmit?.({
-      type: 'debug',
-      payload: { scope: 'http:entry', message: 'request', detail: { method: 'GET', url } },
-    });
-    let response: Response;
-    try {
-      response = await fetch(url, { signal: input.signal });
-    } catch (error) {
-      this.debugEmit?.({ type: 'debug', payload: { scope: 'http:entry', message: 'network_error', detail: { url, error: errorToDebug(error) } } });
-      throw error;
-    }
-    this.debugEmit?.({
-      type: 'debug',
-      payload: { scope: 'http:entry', message: 'response', detail: { url, status: response.status, ok: response.ok } },
-    });
-    if (!response.ok) {
-      throw new SdkError('SYNC', 'EntryService nullifier request failed', await nonOkResponseDetail(response, url));
-    }
-    const payload = (await response.json()) as EntryListResponse<EntryNullifier>;
+    const payload = (await this.getJson(url, input.signal, 'EntryService nullifier request failed')) as EntryListResponse<EntryNullifier>;
     const { items, total } = unwrapList(payload, { url });
     return { items: items.map(normalizeNullifierEntry), total };
   }
@@ -219,7 +367,7 @@ export class EntryClient {
    * Fetch nullifiers with the block-indexed pagination API.
    * This endpoint may return a "ready" flag indicating if more pages are expected.
    */
-  async listNullifiersByBlock(input: { chainId: number; address: string; offset: number; limit: number; signal?: AbortSignal }) {
+  async listNullifiersByBlock(input: { chainId: ChainId; address: ContractAddress; offset: number; limit: number; signal?: AbortSignal }) {
     const url = withQuery(joinUrl(this.baseUrl, '/api/v1/viewing/nullifier/list_by_block'), {
       offset: input.offset,
       limit: input.limit,
@@ -227,26 +375,46 @@ export class EntryClient {
       address: input.address,
       order: 'asc',
     });
-    this.debugEmit?.({
-      type: 'debug',
-      payload: { scope: 'http:entry', message: 'request', detail: { method: 'GET', url } },
+    const payload = (await this.getJson(url, input.signal, 'EntryService nullifier list_by_block request failed')) as EntryListResponse<EntryNullifier>;
+    const { items, total, ready } = unwrapListWithReady(payload, { url });
+    return { items: items.map(normalizeNullifierEntry), total, ready };
+  }
+
+  /**
+   * Fetch a memo page and a block-indexed nullifier page in one request.
+   * Each resource is paged independently from its own offset; both share `limit`.
+   * The endpoint is not part of the published Entry API, so callers pass the deployment's `path`; there is no default.
+   */
+  async listUpdates(input: {
+    chainId: ChainId;
+    address: ContractAddress;
+    memoOffset: number;
+    nullifierOffset: number;
+    limit: number;
+    path: string;
+    signal?: AbortSignal;
+  }): Promise<EntryUpdatesResult> {
+    const url = withQuery(joinUrl(this.baseUrl, input.path), {
+      memo_offset: input.memoOffset,
+      nullifier_offset: input.nullifierOffset,
+      limit: input.limit,
+      chain_id: input.chainId,
+      address: input.address,
+      order: 'asc',
     });
-    let response: Response;
-    try {
-      response = await fetch(url, { signal: input.signal });
-    } catch (error) {
-      this.debugEmit?.({ type: 'debug', payload: { scope: 'http:entry', message: 'network_error', detail: { url, error: errorToDebug(error) } } });
-      throw error;
+    const payload = (await this.getJson(url, input.signal, 'EntryService updates request failed')) as EntryUpdatesResponse;
+    if (typeof payload?.code === 'number' && payload.code !== 0) {
+      throw new SdkError('SYNC', payload.message || 'EntryService request failed', payload);
     }
-    this.debugEmit?.({
-      type: 'debug',
-      payload: { scope: 'http:entry', message: 'response', detail: { url, status: response.status, ok: response.ok } },
-    });
-    if (!response.ok) {
-      throw new SdkError('SYNC', 'EntryService nullifier list_by_block request failed', await nonOkResponseDetail(response, url));
+    const data = payload?.data;
+    if (!data || typeof data !== 'object' || !data.memos || !data.nullifiers) {
+      throw new SdkError('SYNC', 'Invalid entry response: data.memos and data.nullifiers are required', { url, data });
     }
-    const payload = (await response.json()) as EntryListResponse<EntryNullifier>;
-    const { items, total, ready } = unwrapListWithReady(payload, { url });
-    return { items: items.map(normalizeNullifierEntry), total, ready };
+    const memos = unwrapList<EntryMemo>({ data: data.memos }, { url, resource: 'memos' });
+    const nullifiers = unwrapListWithReady<EntryNullifier>({ data: data.nullifiers }, { url, resource: 'nullifiers' });
+    return {
+      memos: { items: memos.items.map(normalizeMemoEntry), total: memos.total },
+      nullifiers: { items: nullifiers.items.map(normalizeNullifierEntry), total: nullifiers.total, ready: nullifiers.ready },
+    };
   }
 }
@@ -0,0 +1,103 @@
+const DEFAULT_EVENT_QUEUE_CAPACITY = 1024;
+
+export type EventQueueOptions = {
+  /** Events buffered before the overflow policy applies (default 1024). */
+  capacity?: number;
+  /**
+   * What happens when the queue is full. `drop` (default) discards the oldest queued event, so sync never waits.
+   * `block` keeps every event and pauses sync at the next page boundary until the handler catches up.
+   */
+  overflow?: 'drop' | 'block';
+};
+
+/**
+ * Bounded FIFO that delivers events to a handler off the caller's stack, one at a time,
+ * awaiting handlers that return a promise. Handler errors are swallowed so delivery continues.
+ * Events matched by `retain` are never dropped, so under `drop` they may push the queue past `capacity`.
+ */
+export class EventQueue<E> {
+  private readonly queue: E[] = [];
+  private readonly capacity: number;
+  private readonly overflow: 'drop' | 'block';
+  private draining = false;
+  private spaceWaiters: Array<() => void> = [];
+  private idleWaiters: Array<() => void> = [];
+  private droppedCount = 0;
+
+  constructor(
+    private readonly handler: (event: E) => void | Promise<void>,
+    options?: EventQueueOptions,
+    private readonly retain: (event: E) => boolean = () => false,
+  ) {
+    const capacity = options?.capacity;
+    this.capacity = typeof capacity === 'number' && Number.isFinite(capacity) ? Math.max(1, Math.floor(capacity)) : DEFAULT_EVENT_QUEUE_CAPACITY;
+    this.overflow = options?.overflow === 'block' ? 'block' : 'drop';
+  }
+
+  /** Events waiting for delivery. */
+  get size(): number {
+    return this.queue.length;
+  }
+
+  /** Events discarded by the `drop` policy. */
+  get dropped(): number {
+    return this.droppedCount;
+  }
+
+  /**
+   * Queue an event without waiting for delivery. Under `drop`, a full queue discards its oldest event not matched by `retain`.
+   */
+  push(event: E) {
+    this.queue.push(event);
+    if (this.overflow === 'drop' && this.queue.length > this.capacity) {
+      const victim = this.queue.findIndex((queued) => !this.retain(queued));
+      if (victim !== -1) {
+        this.queue.splice(victim, 1);
+        this.droppedCount++;
+      }
+    }
+    if (!this.draining) void this.drain();
+  }
+
+  /**
+   * Resolves once the queue is below capacity. Always immediate under the `drop` policy.
+   */
+  waitForSpace(): Promise<void> {
+    if (this.overflow === 'drop' || this.queue.length < this.capacity) return Promise.resolve();
+    return new Promise((resolve) => this.spaceWaiters.push(resolve));
+  }
+
+  /**
+   * Resolves once every queued event has been handed to the handler and settled.
+   */
+  idle(): Promise<void> {
+    if (!this.draining && this.queue.length === 0) return Promise.resolve();
+    return new Promise((resolve) => this.idleWaiters.push(resolve));
+  }
+
+  private async drain() {
+    this.draining = true;
+    try {
+      // Start on a later tick so `push` never runs the handler on the caller's stack.
+      await Promise.resolve();
+      while (this.queue.length) {
+        const event = this.queue.shift()!;
+        if (this.queue.length < this.capacity && this.spaceWaiters.length) {
+          const waiters = this.spaceWaiters;
+          this.spaceWaiters = [];
+          for (const resolve of waiters) resolve();
+        }
+        try {
+          await this.handler(event);
+        } catch {
+          // A failing handler must not stall later events.
+        }
+      }
+    } finally {
+      this.draining = false;
+      const waiters = this.idleWaiters;
+      this.idleWaiters = [];
+      for (const resolve of waiters) resolve();
+    }
+  }
+}
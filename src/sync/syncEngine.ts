@@ -1,8 +1,10 @@
-import type { AssetsApi, SdkEvent, StorageAdapter, SyncApi, SyncChainStatus, SyncCursor } from '../types';
+import type { AssetsApi, ChainConfigInput, ContractAddress, SdkEvent, StorageAdapter, SyncApi, SyncChainBacklog, SyncChainStatus, SyncCheckpoint, SyncCursor, SyncMetricsSnapshot, SyncStreamEvent } from '../types';
 import { SdkError } from '../errors';
-import { EntryClient } from './entryClient';
+import { EntryClient, type EntryUpdatesResult } from './entryClient';
 import { WalletService } from '../wallet/walletService';
 import type { MerkleEngine } from '../merkle/merkleEngine';
+import { EventQueue, type EventQueueOptions } from './eventQueue';
+import { requireChainId, requireContractAddress } from '../utils/validators';
 
 const DEFAULT_PAGE_SIZE = 512;
 const DEFAULT_POLL_MS = 15_000;
@@ -10,13 +12,24 @@ const DEFAULT_REQUEST_TIMEOUT_MS = 20_000;
 const MERKLE_TEMP_ARRAY_SIZE_DEFAULT = 32;
 
 export type SyncEngineOptions = {
+  /** Sets both `memoPageSize` and `nullifierPageSize`; the per-resource fields take precedence. */
   pageSize?: number;
+  memoPageSize?: number;
+  nullifierPageSize?: number;
+  /** Chains synced in parallel by one pass (default: all of them). */
+  maxConcurrency?: number;
   pollMs?: number;
   requestTimeoutMs?: number;
   retry?: { attempts?: number; baseDelayMs?: number; maxDelayMs?: number };
+  /** Fetch memos and nullifiers in one Entry request served at `path` (default false: separate list requests). */
+  combinedUpdates?: false | { path: string };
+  /** Feed downloaded memo commitments into the local merkle tree (default true; must stay on for a local-mode engine). */
+  buildMerkle?: boolean;
+  /** Deliver events to the handler through a bounded queue instead of calling it inline (default: inline). */
+  eventQueue?: EventQueueOptions;
 };
 
-type NormalizedSyncEngineOptions = Omit<Required<SyncEngineOptions>, 'retry'> & {
+type NormalizedSyncEngineOptions = Omit<Required<SyncEngineOptions>, 'retry' | 'eventQueue'> & {
   retry: { attempts: number; baseDelayMs: number; maxDelayMs: number };
 };
 
@@ -40,15 +53,22 @@ const normalizeSyncEngineOptions = (options?: SyncEngineOptions): NormalizedSync
     pollMs: DEFAULT_POLL_MS,
     requestTimeoutMs: DEFAULT_REQUEST_TIMEOUT_MS,
     retry: {},
+    combinedUpdates: false,
     ...(options ?? {}),
   };
+  const pageSize = toBoundedInt(merged.pageSize, DEFAULT_PAGE_SIZE, { min: 1 });
   const retryAttempts = merged.retry?.attempts;
   const retryBaseDelayMs = merged.retry?.baseDelayMs;
   const retryMaxDelayMs = merged.retry?.maxDelayMs;
   return {
-    pageSize: toBoundedInt(merged.pageSize, DEFAULT_PAGE_SIZE, { min: 1 }),
+    pageSize,
+    memoPageSize: toBoundedInt(merged.memoPageSize, pageSize, { min: 1 }),
+    nullifierPageSize: toBoundedInt(merged.nullifierPageSize, pageSize, { min: 1 }),
+    maxConcurrency: toBoundedInt(merged.maxConcurrency, Number.POSITIVE_INFINITY, { min: 1 }),
     pollMs: toBoundedInt(merged.pollMs, DEFAULT_POLL_MS, { min: 250 }),
     requestTimeoutMs: toBoundedInt(merged.requestTimeoutMs, DEFAULT_REQUEST_TIMEOUT_MS, { min: 1000 }),
+    combinedUpdates: merged.combinedUpdates ? { path: merged.combinedUpdates.path } : false,
+    buildMerkle: merged.buildMerkle !== false,
     retry: {
       attempts: retryAttempts == null ? 1 : toBoundedInt(retryAttempts, 1, { min: 1 }),
       baseDelayMs: retryBaseDelayMs == null ? 250 : toBoundedInt(retryBaseDelayMs, 250, { min: 0 }),
@@ -62,6 +82,35 @@ const normalizeSyncEngineOptions = (options?: SyncEngineOptions): NormalizedSync
  */
 const defaultCursor = (): SyncCursor => ({ memo: 0, nullifier: 0, merkle: 0 });
 
+/**
+ * A chain's pool contract address for Entry queries; undefined when it is missing or not a 20-byte address.
+ */
+const entryContractAddress = (chain: Pick<ChainConfigInput, 'ocashContractAddress' | 'contract'>): ContractAddress | undefined => {
+  const value = chain.ocashContractAddress ?? chain.contract;
+  if (value == null) return undefined;
+  try {
+    return requireContractAddress(value, 'ocashContractAddress');
+  } catch {
+    return undefined;
+  }
+};
+
+type SyncResource = 'memo' | 'nullifier' | 'merkle';
+const ALL_RESOURCES: SyncResource[] = ['memo', 'nullifier', 'merkle'];
+
+/**
+ * Settle with `promise`, or reject with the abort reason as soon as `signal` aborts (leaving `promise` running).
+ */
+const raceSignal = <T>(promise: Promise<T>, signal: AbortSignal | undefined): Promise<T> => {
+  if (!signal) return promise;
+  if (signal.aborted) return Promise.reject(signal.reason ?? new SdkError('SYNC', 'Aborted'));
+  return new Promise<T>((resolve, reject) => {
+    const onAbort = () => reject(signal.reason ?? new SdkError('SYNC', 'Aborted'));
+    signal.addEventListener('abort', onAbort, { once: true });
+    promise.then(resolve, reject).finally(() => signal.removeEventListener('abort', onAbort));
+  });
+};
+
 // Merkle cursor represents the current merkle root index (not leaf/cid).
 // The on-chain accumulator advances the root index in fixed batches (default 32 leaves).
 /**
@@ -73,6 +122,11 @@ const currentMerkleRootIndex = (totalElements: number, tempArraySize = MERKLE_TE
   return Math.floor((totalElements - 1) / tempArraySize);
 };
 
+/**
+ * Clamp a page's reported total so it never trails the rows actually returned.
+ */
+const reconcilePageTotal = (page: { items: unknown[]; total: number }, offset: number) => Math.max(Number.isFinite(page.total) ? page.total : 0, offset + page.items.length);
+
 /**
  * Filter and retain only contiguous memo entries starting at expected cid.
  */
@@ -113,7 +167,7 @@ const sampleCids = (memos: Array<{ cid: number | null }>, limit = 10): number[]
   return out.slice(0, limit);
 };
 
-import { signalTimeout, signalAny } from '../utils/signal';
+import { isTimeoutError, signalTimeout, signalAny } from '../utils/signal';
 
 /**
  * Find first duplicate in a list (case-insensitive).
@@ -128,6 +182,16 @@ const findDuplicate = (values: string[]): string | null => {
   return null;
 };
 
+/**
+ * Error for a request ended by an abort signal. SdkError reasons pass through, an expired timeout becomes
+ * `reason: 'timeout'`, and any other reason (e.g. a bare DOMException) is kept as the cause.
+ */
+const syncAbortError = (reason: unknown, meta: { chainId: number; resource: string }): SdkError => {
+  if (reason instanceof SdkError) return reason;
+  if (isTimeoutError(reason)) return new SdkError('SYNC', 'Entry request timed out', { reason: 'timeout', chainId: meta.chainId, resource: meta.resource }, reason);
+  return new SdkError('SYNC', 'Aborted', { reason: 'aborted', chainId: meta.chainId, resource: meta.resource }, reason);
+};
+
 /**
  * Convert error objects to a user-facing message string.
  */
@@ -175,18 +239,85 @@ export class SyncEngine implements SyncApi {
   private readonly status: Record<number, SyncChainStatus> = {};
   private timer: ReturnType<typeof setInterval> | null = null;
   private abortController: AbortController | null = null;
-  private readonly runningChains = new Set<number>();
+  // In-flight per-chain sync and the resources it covers; concurrent callers for the same chain join it (single-flight).
+  // A cursor reset holds the slot with no resources, so every caller waits for it and then runs its own pass.
+  private readonly runningChains = new Map<number, { task: Promise<void>; resources: ReadonlySet<SyncResource> }>();
   private readonly options: NormalizedSyncEngineOptions;
+  private readonly metrics: SyncMetricsSnapshot = { memosDownloaded: 0, ownedMemosFound: 0, nullifiersProcessed: 0, syncErrors: 0, lastSyncAt: {} };
+  private readonly streamListeners = new Set<(evt: SdkEvent) => void>();
+  // Kept across passes so each client's ETag cache lets idle polls skip unchanged pages.
+  private readonly entryClients = new Map<string, EntryClient>();
+  private readonly eventQueue?: EventQueue<SdkEvent>;
 
   constructor(
     private readonly assets: AssetsApi,
     private readonly storage: StorageAdapter,
     private readonly wallet: WalletService,
-    private readonly emit: (evt: SdkEvent) => void,
-    private readonly merkle?: Pick<MerkleEngine, 'ingestEntryMemos'>,
+    private readonly onEvent: (evt: SdkEvent) => void | Promise<void>,
+    private readonly merkle?: Pick<MerkleEngine, 'ingestEntryMemos'> & Partial<Pick<MerkleEngine, 'backfillFromEntryMemos' | 'seedFromCheckpoint' | 'mode'>>,
     options?: SyncEngineOptions,
   ) {
     this.options = normalizeSyncEngineOptions(options);
+    // Local mode serves every proof from the synced tree, so it cannot run without building it.
+    if (!this.options.buildMerkle && merkle?.mode === 'local') {
+      throw new SdkError('CONFIG', 'sync.buildMerkle cannot be false when merkle mode is local');
+    }
+    // Failures and pass completions are never dropped: consumers rely on them to learn how a pass ended.
+    if (options?.eventQueue) this.eventQueue = new EventQueue((evt) => this.deliver(evt), options.eventQueue, (evt) => evt.type === 'error' || evt.type === 'sync:done');
+  }
+
+  /**
+   * Forward an event to the SDK handler (inline, or through the event queue) and any open `syncStream` iterators.
+   */
+  private emit(evt: SdkEvent) {
+    if (this.eventQueue) this.eventQueue.push(evt);
+    else void this.deliver(evt);
+    for (const listener of this.streamListeners) listener(evt);
+  }
+
+  /**
+   * Hand one event to the handler. A rejected handler promise is re-emitted as an `error` event
+   * (unless it was already handling one), so it never surfaces as an unhandled rejection.
+   */
+  private deliver(evt: SdkEvent): Promise<void> {
+    return Promise.resolve(this.onEvent(evt)).catch((error) => {
+      if (evt.type === 'error') return;
+      this.emit({ type: 'error', payload: { code: 'CONFIG', message: 'onEvent handler failed', detail: { event: evt.type }, cause: error } });
+    });
+  }
+
+  /**
+   * Resolves once every queued event has reached the handler. Immediate when `eventQueue` is not configured.
+   */
+  flushEvents(): Promise<void> {
+    return this.eventQueue?.idle() ?? Promise.resolve();
+  }
+
+  /**
+   * Entry client for a service URL, created on first use.
+   */
+  private entryClient(entryUrl: string) {
+    let client = this.entryClients.get(entryUrl);
+    if (!client) {
+      client = new EntryClient(entryUrl, (e) => this.emit(e), { retry: this.options.retry });
+      this.entryClients.set(entryUrl, client);
+    }
+    return client;
+  }
+
+  /**
+   * Save a chain cursor and, when it moved, append the move to the adapter's cursor history.
+   * History is an audit aid, so a failing append never fails the sync.
+   */
+  private async persistCursor(chainId: number, previous: SyncCursor, next: SyncCursor) {
+    await this.storage.setSyncCursor(chainId, next);
+    if (!this.storage.appendCursorHistory) return;
+    if (previous.memo === next.memo && previous.nullifier === next.nullifier && previous.merkle === next.merkle) return;
+    try {
+      await this.storage.appendCursorHistory(chainId, { ...previous }, { ...next }, Date.now());
+    } catch (error) {
+      this.emit({ type: 'debug', payload: { scope: 'sync', message: 'cursor:history_failed', detail: { chainId, error: error instanceof Error ? error.message : String(error) } } });
+    }
   }
 
   /**
@@ -196,6 +327,138 @@ export class SyncEngine implements SyncApi {
     return { ...this.status };
   }
 
+  /**
+   * Return a copy of the cumulative sync counters (e.g. for a Prometheus scrape handler).
+   */
+  metricsSnapshot(): SyncMetricsSnapshot {
+    return { ...this.metrics, lastSyncAt: { ...this.metrics.lastSyncAt } };
+  }
+
+  /**
+   * Report how far each chain's cursors trail the Entry totals.
+   * Only fetches one-row pages for their `total`; nothing is applied or persisted.
+   */
+  async estimateBacklog(options?: { chainIds?: number[]; signal?: AbortSignal; requestTimeoutMs?: number }): Promise<SyncChainBacklog[]> {
+    const chainIds = options?.chainIds ?? this.assets.getChains().map((c) => c.chainId);
+    const requestTimeoutMs = toBoundedInt(options?.requestTimeoutMs, this.options.requestTimeoutMs, { min: 1000 });
+    return Promise.all(
+      chainIds.map(async (chainId) => {
+        const chain = this.assets.getChain(chainId);
+        const contractAddress = entryContractAddress(chain);
+        if (!chain.entryUrl) throw new SdkError('CONFIG', `Chain ${chainId} missing entryUrl`, { chainId });
+        if (!contractAddress) throw new SdkError('CONFIG', `Chain ${chainId} missing a valid ocashContractAddress`, { chainId });
+        const entryChainId = requireChainId(chainId, 'chainId');
+        const client = this.entryClient(chain.entryUrl);
+        const cursor = (await this.storage.getSyncCursor(chainId)) ?? defaultCursor();
+        const meta = { chainId, signal: options?.signal, timeoutMs: requestTimeoutMs };
+        const [memos, nullifiers] = await Promise.all([
+          this.withRetries((signal) => client.listMemos({ chainId: entryChainId, address: contractAddress, offset: cursor.memo, limit: 1, signal }), { ...meta, resource: 'memo' }),
+          this.withRetries((signal) => client.listNullifiersByBlock({ chainId: entryChainId, address: contractAddress, offset: cursor.nullifier, limit: 1, signal }), {
+            ...meta,
+            resource: 'nullifier',
+          }),
+        ]);
+        const memoTotal = reconcilePageTotal(memos, cursor.memo);
+        const nullifierTotal = reconcilePageTotal(nullifiers, cursor.nullifier);
+        return {
+          chainId,
+          memo: { cursor: cursor.memo, total: memoTotal, pending: memoTotal - cursor.memo },
+          nullifier: { cursor: cursor.nullifier, total: nullifierTotal, pending: nullifierTotal - cursor.nullifier },
+        };
+      }),
+    );
+  }
+
+  /**
+   * Run `task` with `chainId` held in `runningChains`, so no sync pass or other cursor rewrite starts until it settles.
+   * The slot is taken synchronously, before `task` reaches its first await.
+   */
+  private withChainReserved(chainId: number, busyMessage: string, task: () => Promise<void>): Promise<void> {
+    if (this.runningChains.has(chainId)) {
+      return Promise.reject(new SdkError('SYNC', busyMessage, { chainId }));
+    }
+    const run = task().finally(() => {
+      this.runningChains.delete(chainId);
+    });
+    this.runningChains.set(chainId, { task: run.catch(() => undefined), resources: new Set() });
+    return run;
+  }
+
+  /**
+   * Rewind a chain's stored cursor so the next pass re-downloads from `memo` / `nullifier`.
+   * Targets must not be ahead of the current cursor. With `clearDownstream`, UTXOs with
+   * `mkIndex >= memo` are deleted so they are rebuilt from the re-synced memos, and the nullifier
+   * cursor is rewound to 0 so rebuilt UTXOs that were already spent are marked spent again.
+   */
+  forceResyncFrom(chainId: number, target: { memo: number; nullifier: number; clearDownstream?: boolean }): Promise<void> {
+    return this.withChainReserved(chainId, 'Cannot reset cursor while chain is syncing', () => this.resetCursor(chainId, target));
+  }
+
+  private async resetCursor(chainId: number, target: { memo: number; nullifier: number; clearDownstream?: boolean }) {
+    const cursor = (await this.storage.getSyncCursor(chainId)) ?? defaultCursor();
+    for (const resource of ['memo', 'nullifier'] as const) {
+      const value = target[resource];
+      if (!Number.isInteger(value) || value < 0 || value > cursor[resource]) {
+        throw new SdkError('CONFIG', `Invalid ${resource} resync target`, { chainId, target: value, current: cursor[resource] });
+      }
+    }
+    if (target.clearDownstream) {
+      if (!this.storage.deleteUtxosFrom) {
+        throw new SdkError('STORAGE', 'Storage adapter does not support deleting utxos', { chainId });
+      }
+      await this.storage.deleteUtxosFrom({ chainId, fromMkIndex: target.memo });
+    }
+    const next = { memo: target.memo, nullifier: target.clearDownstream ? 0 : target.nullifier, merkle: this.merkleCursorFor(target.memo, cursor.merkle) };
+    await this.persistCursor(chainId, cursor, next);
+    const status = this.initChainStatus(chainId);
+    status.memo = { status: 'idle', downloaded: next.memo };
+    status.nullifier = { status: 'idle', downloaded: next.nullifier };
+    status.merkle = { status: 'idle', cursor: next.merkle };
+    this.emit({ type: 'debug', payload: { scope: 'sync', message: 'cursor:reset', detail: { chainId, from: cursor, to: next } } });
+  }
+
+  /**
+   * Merkle root-index cursor for a memo cursor. Without `buildMerkle` no tree is built, so the cursor never moves forward.
+   */
+  private merkleCursorFor(memo: number, previous: number) {
+    const derived = currentMerkleRootIndex(memo);
+    return this.options.buildMerkle ? derived : Math.min(previous, derived);
+  }
+
+  /**
+   * Warm-start a chain that has never synced from a trusted checkpoint: the local merkle tree is
+   * validated against the checkpoint root and seeded, then the cursor moves to the checkpoint.
+   * Memos before the checkpoint are never downloaded, so use it only for wallets with no notes before it.
+   */
+  applyCheckpoint(chainId: number, checkpoint: SyncCheckpoint): Promise<void> {
+    return this.withChainReserved(chainId, 'Cannot apply checkpoint while chain is syncing', () => this.seedCheckpoint(chainId, checkpoint));
+  }
+
+  private async seedCheckpoint(chainId: number, checkpoint: SyncCheckpoint) {
+    if (!this.merkle?.seedFromCheckpoint) {
+      throw new SdkError('CONFIG', 'Applying a checkpoint requires a merkle engine', { chainId });
+    }
+    const { memo, nullifier } = checkpoint;
+    if (!Number.isInteger(memo) || memo < 0 || checkpoint.leaves.length !== memo) {
+      throw new SdkError('CONFIG', 'Checkpoint leaves must cover every cid before the memo cursor', { chainId, memo, leaves: checkpoint.leaves.length });
+    }
+    if (!Number.isInteger(nullifier) || nullifier < 0) {
+      throw new SdkError('CONFIG', 'Invalid checkpoint nullifier cursor', { chainId, nullifier });
+    }
+    const cursor = (await this.storage.getSyncCursor(chainId)) ?? defaultCursor();
+    if (cursor.memo > 0 || cursor.nullifier > 0) {
+      throw new SdkError('SYNC', 'Checkpoint can only be applied before the first sync', { chainId, cursor });
+    }
+    await this.merkle.seedFromCheckpoint(chainId, checkpoint);
+    const next = { memo, nullifier, merkle: currentMerkleRootIndex(memo) };
+    await this.persistCursor(chainId, cursor, next);
+    const status = this.initChainStatus(chainId);
+    status.memo = { status: 'idle', downloaded: next.memo };
+    status.nullifier = { status: 'idle', downloaded: next.nullifier };
+    status.merkle = { status: 'idle', cursor: next.merkle };
+    this.emit({ type: 'debug', payload: { scope: 'sync', message: 'cursor:checkpoint', detail: { chainId, to: next, merkleRoot: checkpoint.merkleRoot } } });
+  }
+
   /**
    * Start background polling. Runs an initial sync immediately.
    */
@@ -224,40 +487,136 @@ export class SyncEngine implements SyncApi {
   /**
    * Run a single sync pass for requested chains/resources.
    */
-  async syncOnce(options?: { chainIds?: number[]; resources?: Array<'memo' | 'nullifier' | 'merkle'>; signal?: AbortSignal; requestTimeoutMs?: number; pageSize?: number; continueOnError?: boolean }) {
+  async syncOnce(options?: {
+    chainIds?: number[];
+    resources?: Array<'memo' | 'nullifier' | 'merkle'>;
+    signal?: AbortSignal;
+    requestTimeoutMs?: number;
+    pageSize?: number;
+    memoPageSize?: number;
+    nullifierPageSize?: number;
+    maxConcurrency?: number;
+    continueOnError?: boolean;
+  }) {
     const chainIds = options?.chainIds ?? this.assets.getChains().map((c) => c.chainId);
     const requestTimeoutMs = toBoundedInt(options?.requestTimeoutMs, this.options.requestTimeoutMs, { min: 1000 });
-    const pageSize = toBoundedInt(options?.pageSize, this.options.pageSize, { min: 1 });
-    const tasks = chainIds.map((chainId) => {
-      if (options?.signal?.aborted) {
-        return Promise.reject(options.signal.reason ?? new SdkError('SYNC', 'Aborted'));
-      }
-      if (this.runningChains.has(chainId)) {
-        this.emit({
-          type: 'error',
-          payload: {
-            code: 'SYNC',
-            message: 'Sync skipped: chain already syncing',
-            detail: { chainId, skipped: true },
-          },
-        });
-        return Promise.resolve();
+    const memoPageSize = toBoundedInt(options?.memoPageSize ?? options?.pageSize, this.options.memoPageSize, { min: 1 });
+    const nullifierPageSize = toBoundedInt(options?.nullifierPageSize ?? options?.pageSize, this.options.nullifierPageSize, { min: 1 });
+    const maxConcurrency = toBoundedInt(options?.maxConcurrency, this.options.maxConcurrency, { min: 1 });
+    const resources = options?.resources ?? ALL_RESOURCES;
+    // A pass already covering every requested resource is joined; otherwise the caller waits for it and runs its own.
+    // Either way the caller's own signal ends its wait without cancelling the pass it waited on. A joined pass keeps
+    // the page sizes and timeouts it was started with.
+    const runChain = async (chainId: number): Promise<void> => {
+      if (options?.signal?.aborted) throw options.signal.reason ?? new SdkError('SYNC', 'Aborted');
+      const running = this.runningChains.get(chainId);
+      if (running) {
+        const covered = resources.every((resource) => running.resources.has(resource));
+        this.emit({ type: 'debug', payload: { scope: 'sync', message: covered ? 'syncChain:joined' : 'syncChain:queued', detail: { chainId, resources } } });
+        if (covered) return raceSignal(running.task, options?.signal);
+        await raceSignal(running.task.catch(() => undefined), options?.signal);
+        return runChain(chainId);
       }
-      this.runningChains.add(chainId);
-      return this.syncChain(chainId, options?.resources, {
+      const task = this.syncChain(chainId, resources, {
         signal: options?.signal,
         requestTimeoutMs,
-        pageSize,
+        memoPageSize,
+        nullifierPageSize,
       }).finally(() => {
         this.runningChains.delete(chainId);
       });
+      this.runningChains.set(chainId, { task, resources: new Set(resources) });
+      return task;
+    };
+
+    // At most `maxConcurrency` chains in flight; a failing chain never stops the others from being synced.
+    const failures: Array<{ chainId: number; error: unknown }> = [];
+    let next = 0;
+    const worker = async () => {
+      while (next < chainIds.length) {
+        const chainId = chainIds[next++]!;
+        try {
+          await runChain(chainId);
+        } catch (error) {
+          failures.push({ chainId, error });
+        }
+      }
+    };
+    await Promise.all(Array.from({ length: Math.min(maxConcurrency, chainIds.length) }, worker));
+
+    if (options?.continueOnError || !failures.length) return;
+    if (failures.length === 1) throw failures[0]!.error;
+    throw new SdkError(
+      'SYNC',
+      `Sync failed for ${failures.length} chains`,
+      { failures: failures.map(({ chainId, error }) => ({ chainId, message: error instanceof Error ? error.message : String(error) })) },
+      failures[0]!.error,
+    );
+  }
+
+  /**
+   * Run one sync pass as an async iterator of the `sync:*` and `error` events for the requested chains.
+   *
+   * Events are buffered until pulled. Leaving the loop early (`break`, `return` or a throw in the loop body) aborts the
+   * pass and waits for the in-flight page to settle, so every page applied so far has its cursor persisted by the time
+   * the loop exits. A failed pass (without `continueOnError`) rethrows after its buffered events are drained.
+   */
+  async *syncStream(options?: {
+    chainIds?: number[];
+    resources?: Array<'memo' | 'nullifier' | 'merkle'>;
+    signal?: AbortSignal;
+    requestTimeoutMs?: number;
+    pageSize?: number;
+    memoPageSize?: number;
+    nullifierPageSize?: number;
+    maxConcurrency?: number;
+    continueOnError?: boolean;
+  }): AsyncGenerator<SyncStreamEvent, void, undefined> {
+    const chainIds = options?.chainIds ?? this.assets.getChains().map((c) => c.chainId);
+    const queue: SyncStreamEvent[] = [];
+    let wake: (() => void) | null = null;
+    const listener = (evt: SdkEvent) => {
+      if (evt.type !== 'sync:start' && evt.type !== 'sync:progress' && evt.type !== 'sync:done' && evt.type !== 'error') return;
+      const chainId = evt.type === 'error' ? (evt.payload.detail as { chainId?: number } | undefined)?.chainId : evt.payload.chainId;
+      if (chainId != null && !chainIds.includes(chainId)) return;
+      queue.push(evt);
+      wake?.();
+    };
+    this.streamListeners.add(listener);
+
+    const controller = new AbortController();
+    const outcome: { settled: boolean; failed: boolean; error?: unknown } = { settled: false, failed: false };
+    const task = this.syncOnce({ ...options, chainIds, signal: signalAny([options?.signal, controller.signal]) }).then(
+      () => undefined,
+      (error) => {
+        outcome.failed = true;
+        outcome.error = error;
+      },
+    );
+    void task.finally(() => {
+      outcome.settled = true;
+      wake?.();
     });
 
-    if (options?.continueOnError) {
-      await Promise.all(tasks.map((t) => t.catch(() => undefined)));
-      return;
+    try {
+      while (true) {
+        const next = queue.shift();
+        if (next) {
+          yield next;
+          continue;
+        }
+        if (outcome.settled) break;
+        await new Promise<void>((resolve) => {
+          wake = resolve;
+        });
+        wake = null;
+      }
+      if (outcome.failed) throw outcome.error;
+    } finally {
+      if (!outcome.settled) controller.abort(new SdkError('SYNC', 'Aborted'));
+      await task;
+      this.streamListeners.delete(listener);
     }
-    await Promise.all(tasks);
   }
 
   /**
@@ -277,9 +636,15 @@ export class SyncEngine implements SyncApi {
   /**
    * Sync a single chain's memo/nullifier resources and update cursors.
    */
-  private async syncChain(chainId: number, resources?: Array<'memo' | 'nullifier' | 'merkle'>, options?: { signal?: AbortSignal; requestTimeoutMs: number; pageSize: number }) {
+  private async syncChain(chainId: number, resources?: Array<'memo' | 'nullifier' | 'merkle'>, options?: { signal?: AbortSignal; requestTimeoutMs: number; memoPageSize: number; nullifierPageSize: number }) {
     const chain = this.assets.getChain(chainId);
     const cursor = (await this.storage.getSyncCursor(chainId)) ?? defaultCursor();
+    // `cursor` is advanced in place; `saved` is the last persisted value, so each save records one history entry.
+    let saved = { ...cursor };
+    const saveCursor = async () => {
+      await this.persistCursor(chainId, saved, cursor);
+      saved = { ...cursor };
+    };
     this.emit({
       type: 'debug',
       payload: {
@@ -289,7 +654,8 @@ export class SyncEngine implements SyncApi {
           chainId,
           resources: resources ?? ['memo', 'nullifier', 'merkle'],
           cursor,
-          pageSize: options?.pageSize,
+          memoPageSize: options?.memoPageSize,
+          nullifierPageSize: options?.nullifierPageSize,
           requestTimeoutMs: options?.requestTimeoutMs,
         },
       },
@@ -299,6 +665,7 @@ export class SyncEngine implements SyncApi {
     const status = this.initChainStatus(chainId);
     this.emit({ type: 'sync:start', payload: { chainId, source: 'entry' } });
     let hadError = false;
+    let completed = false;
     try {
       // `cursor.merkle` tracks the merkle root index cursor, derived from memo sync (total elements).
       // Proof fetching is on-demand during transfer/withdraw and is not part of sync.
@@ -320,9 +687,10 @@ export class SyncEngine implements SyncApi {
       }
 
       const needsEntry = enabled.has('memo') || enabled.has('nullifier');
-      const client = needsEntry ? (chain.entryUrl ? new EntryClient(chain.entryUrl, (e) => this.emit(e)) : null) : null;
+      const client = needsEntry ? (chain.entryUrl ? this.entryClient(chain.entryUrl) : null) : null;
       let viewingAddress: string | null = null;
-      const contractAddress = (chain.ocashContractAddress ?? chain.contract) as string | undefined;
+      const contractAddress = entryContractAddress(chain);
+      const entryChainId = requireChainId(chainId, 'chainId');
       if (needsEntry) {
         try {
           viewingAddress = this.wallet.getViewingAddress();
@@ -354,7 +722,7 @@ export class SyncEngine implements SyncApi {
         return;
       }
       if (needsEntry && !contractAddress) {
-        const message = `Chain ${chainId} missing ocashContractAddress`;
+        const message = `Chain ${chainId} missing a valid ocashContractAddress`;
         if (enabled.has('memo')) status.memo = { status: 'error', downloaded: cursor.memo, errorMessage: message };
         if (enabled.has('nullifier')) status.nullifier = { status: 'error', downloaded: cursor.nullifier, errorMessage: message };
         this.emit({
@@ -371,6 +739,13 @@ export class SyncEngine implements SyncApi {
         });
       }
 
+      // With `combinedUpdates`, every memo page request also carries the next nullifier page. Those pages are
+      // buffered by offset and drained by the nullifier loop before it falls back to `list_by_block`.
+      const combinedUpdates = this.options.combinedUpdates;
+      const combinedPath = combinedUpdates && enabled.has('memo') && enabled.has('nullifier') ? combinedUpdates.path : undefined;
+      const prefetchedNullifiers = new Map<number, EntryUpdatesResult['nullifiers']>();
+      let nullifierPrefetchOffset = cursor.nullifier;
+
       if (enabled.has('memo')) {
         try {
           status.memo = { status: 'syncing', downloaded: cursor.memo };
@@ -378,21 +753,57 @@ export class SyncEngine implements SyncApi {
 
           // Derive merkle root-index cursor from the memo cursor (total elements).
           // This keeps cursor/status consistent even when there are no new memos in the current run.
-          const derivedMerkleCursor = currentMerkleRootIndex(cursor.memo);
+          const derivedMerkleCursor = this.merkleCursorFor(cursor.memo, cursor.merkle);
           if (cursor.merkle !== derivedMerkleCursor) {
             cursor.merkle = derivedMerkleCursor;
-            await this.storage.setSyncCursor(chainId, cursor);
+            await saveCursor();
           }
           if (enabled.has('merkle')) status.merkle.cursor = cursor.merkle;
 
+          // Invariant: tree leaves <= memo cursor. A pass interrupted after memo download but before the
+          // tree build leaves the tree behind; rebuild the missing range from cached memos before moving on.
+          const merkle = this.options.buildMerkle ? this.merkle : undefined;
+          const backfilled = await merkle?.backfillFromEntryMemos?.(chainId, cursor.memo);
+          if (backfilled) {
+            this.emit({ type: 'debug', payload: { scope: 'sync:merkle', message: 'backfill', detail: { chainId, memoCursor: cursor.memo, leaves: backfilled } } });
+          }
+
           let offset = cursor.memo;
           while (true) {
             if (options?.signal?.aborted) throw options.signal.reason ?? new SdkError('SYNC', 'Aborted');
-            const signal = signalAny([options?.signal, signalTimeout(options?.requestTimeoutMs ?? DEFAULT_REQUEST_TIMEOUT_MS)]);
-            const pageSize = options?.pageSize ?? DEFAULT_PAGE_SIZE;
-            this.emit({ type: 'debug', payload: { scope: 'sync:memo', message: 'page:request', detail: { chainId, offset, limit: pageSize } } });
-            const page = await this.withRetries(() => client!.listMemos({ chainId, address: contractAddress!, offset, limit: pageSize, signal }), { chainId, resource: 'memo', signal });
-            status.memo.total = page.total;
+            // Under the `block` overflow policy, wait here for a slow event handler to make room.
+            await this.eventQueue?.waitForSpace();
+            const pageSize = options?.memoPageSize ?? DEFAULT_PAGE_SIZE;
+            this.emit({
+              type: 'debug',
+              payload: { scope: 'sync:memo', message: 'page:request', detail: { chainId, offset, limit: pageSize, endpoint: combinedPath ? 'updates' : 'list' } },
+            });
+            const page = await this.withRetries(
+              async (signal) => {
+                if (!combinedPath) return client!.listMemos({ chainId: entryChainId, address: contractAddress!, offset, limit: pageSize, signal });
+                const updates = await client!.listUpdates({
+                  chainId: entryChainId,
+                  address: contractAddress!,
+                  memoOffset: offset,
+                  nullifierOffset: nullifierPrefetchOffset,
+                  limit: pageSize,
+                  path: combinedPath,
+                  signal,
+                });
+                prefetchedNullifiers.set(nullifierPrefetchOffset, updates.nullifiers);
+                nullifierPrefetchOffset += updates.nullifiers.items.length;
+                return updates.memos;
+              },
+              { chainId, resource: 'memo', signal: options?.signal, timeoutMs: options?.requestTimeoutMs ?? DEFAULT_REQUEST_TIMEOUT_MS },
+            );
+            const total = reconcilePageTotal(page, offset);
+            if (total !== page.total) {
+              this.emit({
+                type: 'debug',
+                payload: { scope: 'sync:memo', message: 'page:total_inconsistent', detail: { chainId, offset, returned: page.items.length, reportedTotal: page.total, total } },
+              });
+            }
+            status.memo.total = total;
             const contiguous = sanitizeContiguousMemos(page.items, offset);
             if (page.items.length > 0 && contiguous.length === 0) {
               throw new SdkError('SYNC', 'EntryService memos are not contiguous', {
@@ -401,10 +812,10 @@ export class SyncEngine implements SyncApi {
                 firstCid: minCid(page.items),
                 cids: sampleCids(page.items),
                 returned: page.items.length,
-                total: page.total,
+                total,
               });
             }
-            this.emit({ type: 'sync:progress', payload: { chainId, resource: 'memo', downloaded: offset, total: page.total } });
+            this.emit({ type: 'sync:progress', payload: { chainId, resource: 'memo', downloaded: offset, total } });
             if (!contiguous.length) break;
             if (this.storage.upsertEntryMemos) {
               try {
@@ -428,8 +839,10 @@ export class SyncEngine implements SyncApi {
                 // best-effort cache
               }
             }
-            await this.merkle?.ingestEntryMemos?.(chainId, contiguous);
+            await merkle?.ingestEntryMemos?.(chainId, contiguous);
             const added = await this.wallet.applyMemos(chainId, contiguous);
+            this.metrics.memosDownloaded += contiguous.length;
+            this.metrics.ownedMemosFound += added;
             this.emit({
               type: 'debug',
               payload: { scope: 'sync:memo', message: 'page:applied', detail: { chainId, offset, returned: page.items.length, contiguous: contiguous.length, added } },
@@ -438,8 +851,8 @@ export class SyncEngine implements SyncApi {
             offset = lastCid + 1;
             cursor.memo = offset;
             // Update merkle root-index cursor from total elements.
-            cursor.merkle = currentMerkleRootIndex(offset);
-            await this.storage.setSyncCursor(chainId, cursor);
+            cursor.merkle = this.merkleCursorFor(offset, cursor.merkle);
+            await saveCursor();
             status.memo.downloaded = offset;
             if (enabled.has('merkle')) status.merkle.cursor = cursor.merkle;
             if (contiguous.length < page.items.length) {
@@ -450,10 +863,11 @@ export class SyncEngine implements SyncApi {
                 cids: sampleCids(page.items),
                 contiguousApplied: contiguous.length,
                 returned: page.items.length,
-                total: page.total,
+                total,
               });
             }
-            if (contiguous.length < pageSize) break;
+            // A short page only means we are caught up when `total` agrees; the server may cap `limit` below pageSize.
+            if (contiguous.length < pageSize && offset >= total) break;
           }
           status.memo.status = 'synced';
           if (enabled.has('merkle')) status.merkle.status = 'synced';
@@ -485,26 +899,38 @@ export class SyncEngine implements SyncApi {
           let offset = cursor.nullifier;
           while (true) {
             if (options?.signal?.aborted) throw options.signal.reason ?? new SdkError('SYNC', 'Aborted');
-            const signal = signalAny([options?.signal, signalTimeout(options?.requestTimeoutMs ?? DEFAULT_REQUEST_TIMEOUT_MS)]);
-            const pageSize = options?.pageSize ?? DEFAULT_PAGE_SIZE;
+            await this.eventQueue?.waitForSpace();
+            const pageSize = options?.nullifierPageSize ?? DEFAULT_PAGE_SIZE;
+            const prefetched = prefetchedNullifiers.get(offset);
+            prefetchedNullifiers.delete(offset);
             this.emit({
               type: 'debug',
-              payload: { scope: 'sync:nullifier', message: 'page:request', detail: { chainId, offset, limit: pageSize, endpoint: 'list_by_block' } },
+              payload: { scope: 'sync:nullifier', message: 'page:request', detail: { chainId, offset, limit: pageSize, endpoint: prefetched ? 'updates' : 'list_by_block' } },
             });
-            const page = await this.withRetries(() => client!.listNullifiersByBlock({ chainId, address: contractAddress!, offset, limit: pageSize, signal }), {
-              chainId,
-              resource: 'nullifier',
-              signal,
-            });
-            status.nullifier.total = page.total;
+            const page =
+              prefetched ??
+              (await this.withRetries((signal) => client!.listNullifiersByBlock({ chainId: entryChainId, address: contractAddress!, offset, limit: pageSize, signal }), {
+                chainId,
+                resource: 'nullifier',
+                signal: options?.signal,
+                timeoutMs: options?.requestTimeoutMs ?? DEFAULT_REQUEST_TIMEOUT_MS,
+              }));
+            const total = reconcilePageTotal(page, offset);
+            if (total !== page.total) {
+              this.emit({
+                type: 'debug',
+                payload: { scope: 'sync:nullifier', message: 'page:total_inconsistent', detail: { chainId, offset, returned: page.items.length, reportedTotal: page.total, total } },
+              });
+            }
+            status.nullifier.total = total;
             this.emit({
               type: 'sync:progress',
-              payload: { chainId, resource: 'nullifier', downloaded: offset, total: page.total },
+              payload: { chainId, resource: 'nullifier', downloaded: offset, total },
             });
             if (!page.items.length) {
-              if (page.total > offset) {
+              if (total > offset) {
                 if (page.ready === false) break;
-                throw new SdkError('SYNC', 'EntryService nullifiers returned empty page before reaching total', { chainId, offset, total: page.total, limit: pageSize });
+                throw new SdkError('SYNC', 'EntryService nullifiers returned empty page before reaching total', { chainId, offset, total, limit: pageSize });
               }
               break;
             }
@@ -527,15 +953,18 @@ export class SyncEngine implements SyncApi {
               }
             }
             await this.wallet.markSpent({ chainId, nullifiers: page.items.map((n) => n.nullifier) });
+            this.metrics.nullifiersProcessed += page.items.length;
             this.emit({
               type: 'debug',
-              payload: { scope: 'sync:nullifier', message: 'page:applied', detail: { chainId, offset, returned: page.items.length, total: page.total, ready: page.ready } },
+              payload: { scope: 'sync:nullifier', message: 'page:applied', detail: { chainId, offset, returned: page.items.length, total, ready: page.ready } },
             });
             offset += page.items.length;
             cursor.nullifier = offset;
-            await this.storage.setSyncCursor(chainId, cursor);
+            await saveCursor();
             status.nullifier.downloaded = offset;
-            if (page.items.length < pageSize) break;
+            // Prefetched pages came from `updates`, which takes a single limit: the memo page size.
+            const requestedLimit = prefetched ? (options?.memoPageSize ?? DEFAULT_PAGE_SIZE) : pageSize;
+            if (page.items.length < requestedLimit && !prefetchedNullifiers.has(offset)) break;
           }
           status.nullifier.status = 'synced';
         } catch (error) {
@@ -551,26 +980,37 @@ export class SyncEngine implements SyncApi {
           });
         }
       }
+      completed = true;
     } finally {
+      if (hadError || !completed) this.metrics.syncErrors++;
+      else this.metrics.lastSyncAt[chainId] = Date.now();
       this.emit({ type: 'sync:done', payload: { chainId, cursor } });
     }
   }
 
   /**
    * Retry wrapper with exponential backoff and abort support.
+   * Each attempt gets its own `timeoutMs` signal (combined with the caller's `signal`), so an attempt that times out is
+   * retried with a fresh deadline. Timeouts and aborts surface as SdkError(SYNC), never as a bare abort reason.
    */
-  private async withRetries<T>(fn: () => Promise<T>, meta: { chainId: number; resource: 'memo' | 'nullifier' | 'merkle'; signal?: AbortSignal }): Promise<T> {
+  private async withRetries<T>(
+    fn: (signal: AbortSignal | undefined) => Promise<T>,
+    meta: { chainId: number; resource: 'memo' | 'nullifier' | 'merkle'; signal?: AbortSignal; timeoutMs?: number },
+  ): Promise<T> {
     const attempts = this.options.retry.attempts;
     const baseDelayMs = this.options.retry.baseDelayMs;
     const maxDelayMs = this.options.retry.maxDelayMs;
     let lastError: unknown;
     for (let attempt = 1; attempt <= attempts; attempt++) {
-      if (meta.signal?.aborted) throw meta.signal?.reason ?? new SdkError('SYNC', 'Aborted');
+      if (meta.signal?.aborted) throw syncAbortError(meta.signal.reason, meta);
+      const attemptSignal = meta.timeoutMs ? signalAny([meta.signal, signalTimeout(meta.timeoutMs)]) : meta.signal;
       try {
-        return await fn();
+        return await fn(attemptSignal);
       } catch (error) {
-        lastError = error;
-        const retryable = this.shouldRetry(error);
+        if (meta.signal?.aborted) throw syncAbortError(meta.signal.reason, meta);
+        const timedOut = isTimeoutError(error) || (attemptSignal?.aborted && isTimeoutError(attemptSignal.reason));
+        lastError = timedOut && !(error instanceof SdkError) ? syncAbortError(attemptSignal?.reason ?? error, meta) : error;
+        const retryable = this.shouldRetry(lastError);
         if (!retryable || attempt >= attempts) break;
         const delay = Math.min(maxDelayMs, Math.floor(baseDelayMs * Math.min(32, 2 ** (attempt - 1))));
         this.emit({
@@ -579,14 +1019,14 @@ export class SyncEngine implements SyncApi {
             code: 'SYNC',
             message: 'Sync request failed, retrying',
             detail: { chainId: meta.chainId, resource: meta.resource, attempt, delayMs: delay },
-            cause: error,
+            cause: lastError,
           },
         });
         await new Promise<void>((resolve, reject) => {
           const t = setTimeout(resolve, delay);
           const onAbort = () => {
             clearTimeout(t);
-            reject(meta.signal?.reason ?? new SdkError('SYNC', 'Aborted'));
+            reject(syncAbortError(meta.signal?.reason, meta));
           };
           if (meta.signal) {
             if (meta.signal.aborted) return onAbort();
@@ -600,9 +1040,13 @@ export class SyncEngine implements SyncApi {
 
   /**
    * Determine whether a failure is retryable (HTTP 429/5xx or transport).
+   * Entry requests already retried by `EntryClient` (same policy) are not retried again; timeouts still are.
    */
   private shouldRetry(error: unknown): boolean {
     if (error instanceof SdkError) {
+      if ((error.detail as any)?.reason === 'timeout') return true;
+      const attempts = (error.detail as any)?.attempts;
+      if (typeof attempts === 'number' && attempts > 1) return false;
       const status = (error.detail as any)?.status;
       if (typeof status === 'number') {
         if (status === 429) return true;
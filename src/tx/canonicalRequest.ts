@@ -0,0 +1,30 @@
+import { sha256 } from '@noble/hashes/sha256';
+import { bytesToHex, concatBytes, utf8ToBytes } from '@noble/hashes/utils';
+import type { Hex, RelayerRequest } from '../types';
+import { stableStringify } from '../utils/json';
+
+/**
+ * 4-byte big-endian length prefix followed by the UTF-8 bytes of `value`.
+ */
+const lengthPrefixed = (value: string): Uint8Array => {
+  const bytes = utf8ToBytes(value);
+  const out = new Uint8Array(4 + bytes.length);
+  new DataView(out.buffer).setUint32(0, bytes.length);
+  out.set(bytes, 4);
+  return out;
+};
+
+/**
+ * Deterministic byte encoding of a relayer request for signing and idempotency keys.
+ *
+ * Fields are written in a fixed order (`kind`, `method`, `path`, `body`), each length-prefixed.
+ * The body is stable JSON (sorted keys, bigint as decimal, undefined fields dropped), so two
+ * structurally-equal requests encode identically regardless of construction order.
+ */
+export const canonicalRelayerRequestBytes = (request: RelayerRequest): Uint8Array =>
+  concatBytes(lengthPrefixed(request.kind), lengthPrefixed(request.method), lengthPrefixed(request.path), lengthPrefixed(stableStringify(request.body)));
+
+/**
+ * SHA-256 of the canonical encoding, suitable as an idempotency key.
+ */
+export const relayerRequestDigest = (request: RelayerRequest): Hex => `0x${bytesToHex(sha256(canonicalRelayerRequestBytes(request)))}`;
@@ -0,0 +1,39 @@
+import type { RelayerAction, RelayerRequest } from '../types';
+import { SdkError } from '../errors';
+
+/**
+ * Relayer endpoint for each action. Withdrawals are served by the relayer's `burn` route.
+ */
+export const RELAYER_ACTION_PATHS: Readonly<Record<RelayerAction, string>> = {
+  transfer: '/api/v1/transfer',
+  withdraw: '/api/v1/burn',
+};
+
+const isRelayerAction = (value: unknown): value is RelayerAction => typeof value === 'string' && Object.prototype.hasOwnProperty.call(RELAYER_ACTION_PATHS, value);
+
+/**
+ * Builds a `RelayerRequest` from a typed action instead of a free-form path.
+ * `build()` throws until both the action and the body are set; the wire format is unchanged.
+ */
+export class RelayerRequestBuilder {
+  private actionValue?: RelayerAction;
+  private bodyValue?: Record<string, unknown>;
+
+  action(action: RelayerAction): this {
+    if (!isRelayerAction(action)) throw new SdkError('CONFIG', 'Unknown relayer action', { action });
+    this.actionValue = action;
+    return this;
+  }
+
+  body(body: Record<string, unknown>): this {
+    if (body == null || typeof body !== 'object' || Array.isArray(body)) throw new SdkError('CONFIG', 'Relayer request body must be an object');
+    this.bodyValue = body;
+    return this;
+  }
+
+  build(): RelayerRequest {
+    if (!this.actionValue) throw new SdkError('CONFIG', 'Relayer request action is required');
+    if (!this.bodyValue) throw new SdkError('CONFIG', 'Relayer request body is required', { action: this.actionValue });
+    return { kind: 'relayer', method: 'POST', path: RELAYER_ACTION_PATHS[this.actionValue], body: this.bodyValue };
+  }
+}
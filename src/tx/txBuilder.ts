@@ -1,5 +1,6 @@
 import type { ProofResult, RelayerRequest, TxBuilderApi } from '../types';
 import { SdkError } from '../errors';
+import { RelayerRequestBuilder } from './relayerRequest';
 import { requireHex, requireNumber, requireAddress, requireBigint } from '../utils/validators';
 
 /**
@@ -21,11 +22,9 @@ export class TxBuilder implements TxBuilderApi {
     }
     extraData.forEach((entry, idx) => requireHex(entry, `extra_data[${idx}]`));
 
-    const request: RelayerRequest = {
-      kind: 'relayer',
-      method: 'POST',
-      path: '/api/v1/transfer',
-      body: {
+    return new RelayerRequestBuilder()
+      .action('transfer')
+      .body({
         proof: proof.proof,
         input: proof.public_input,
         extra_data: extraData,
@@ -33,10 +32,8 @@ export class TxBuilder implements TxBuilderApi {
         array_hash_index: arrayHashIndex,
         relayer,
         flatten_input: proof.flatten_input,
-      },
-    };
-
-    return request;
+      })
+      .build();
   }
 
   /**
@@ -60,11 +57,9 @@ export class TxBuilder implements TxBuilderApi {
     }
     const extraDataHex = requireHex(extraData, 'extra_data');
 
-    const request: RelayerRequest = {
-      kind: 'relayer',
-      method: 'POST',
-      path: '/api/v1/burn',
-      body: {
+    return new RelayerRequestBuilder()
+      .action('withdraw')
+      .body({
         proof: proof.proof,
         input: proof.public_input,
         extra_data: extraDataHex,
@@ -76,9 +71,7 @@ export class TxBuilder implements TxBuilderApi {
         gas_drop_value: gasDropValue.toString(),
         burn_amount: burnAmount.toString(),
         flatten_input: proof.flatten_input,
-      },
-    };
-
-    return request;
+      })
+      .build();
   }
 }
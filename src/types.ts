@@ -4,13 +4,19 @@ export type { ListOperationsQuery, OperationCreateInput, OperationDetailFor, Ope
 
 /** Hex-encoded bytes with 0x prefix. */
 export type Hex = `0x${string}`;
+/** Chain id validated by `Utils.toChainId`; a plain number does not type-check where a ChainId is expected. */
+export type ChainId = number & { readonly __brand: 'ChainId' };
+/** Checksummed contract address validated by `Utils.toContractAddress`. */
+export type ContractAddress = Address & { readonly __brand: 'ContractAddress' };
+/** Amount string representation: lowercase 0x-hex, decimal base units, or human decimal scaled by token decimals. */
+export type AmountFormat = 'hex' | 'base-units' | 'decimal';
 /** Decimal string representing a bigint value. */
 export type BigintLikeString = string;
 /** viem transaction receipt type alias. */
 export type TransactionReceipt = Awaited<ReturnType<PublicClient['waitForTransactionReceipt']>>;
 
 /** SDK error code namespaces. */
-export type SdkErrorCode = 'CONFIG' | 'ASSETS' | 'STORAGE' | 'SYNC' | 'CRYPTO' | 'MERKLE' | 'WITNESS' | 'PROOF' | 'RELAYER';
+export type SdkErrorCode = 'CONFIG' | 'ASSETS' | 'STORAGE' | 'SYNC' | 'CRYPTO' | 'MERKLE' | 'WITNESS' | 'PROOF' | 'RELAYER' | 'MEMO_TOO_LARGE';
 
 /** Token configuration for a shielded pool. */
 export interface TokenMetadata {
@@ -75,6 +81,8 @@ export interface MemoWorkerConfig {
   workerUrl?: string;
   concurrency?: number;
   type?: 'classic' | 'module';
+  /** Largest memo payload (bytes) to trial-decrypt, on the worker and the main thread; defaults to 4096. */
+  maxMemoBytes?: number;
 }
 
 /** Asset override entry: URL/path or sharded list. */
@@ -105,6 +113,16 @@ export interface OCashSdkConfig {
      * Merkle depth used by the on-chain tree (defaults to 32).
      */
     treeDepth?: number;
+    /**
+     * Poseidon2 domain for local tree nodes and zero hashes (defaults to `Poseidon2Domain.Merkle`).
+     * Only change this for deployments whose contract hashes its tree under another domain.
+     */
+    hashDomain?: bigint;
+    /**
+     * Maps each commitment to the leaf the contract inserts (defaults to identity: `leaf = commitment`).
+     * Set it for deployments that insert `H(commitment)`; local proof paths then start from the transformed leaf.
+     */
+    leafTransform?: (commitment: bigint) => bigint;
     /**
      * Optional callback to read `merkleRoots(rootIndex)` from the on-chain contract.
      * When provided, each batch merge is verified against the contract root.
@@ -112,9 +130,21 @@ export interface OCashSdkConfig {
      * A zero root (0x000...0) is also treated as "not yet committed".
      */
     readContractRoot?: (chainId: number, rootIndex: number) => Promise<Hex | null>;
+    /**
+     * Optional proof source used by `ops` instead of the SDK merkle engine, e.g. a light wallet
+     * backend that serves proofs without the client building or syncing any tree.
+     */
+    proofSource?: MerkleProofSource;
   };
   sync?: {
+    /** Entries per page for both memos and nullifiers (default 512). */
     pageSize?: number;
+    /** Memo page size; memo pages carry ciphertext and are much larger than nullifier pages. Defaults to `pageSize`. */
+    memoPageSize?: number;
+    /** Nullifier page size. Defaults to `pageSize`. */
+    nullifierPageSize?: number;
+    /** Maximum number of chains synced in parallel by one pass (defaults to all configured chains). */
+    maxConcurrency?: number;
     pollMs?: number;
     requestTimeoutMs?: number;
     /**
@@ -122,8 +152,52 @@ export interface OCashSdkConfig {
      * Defaults to no retries.
      */
     retry?: { attempts?: number; baseDelayMs?: number; maxDelayMs?: number };
+    /**
+     * Fetch memos and nullifiers together from an Entry `updates` endpoint (one request per memo page
+     * instead of two). The endpoint is not part of the published Entry API, so it is opt-in and needs the deployment's
+     * `path`. Defaults to false (the published memo and nullifier list endpoints).
+     */
+    combinedUpdates?: false | { path: string };
+    /**
+     * Build the local merkle tree (leaves and tree nodes in storage) from memo commitments as they sync,
+     * so proofs can be served offline. Set to false when proofs always come from the remote service; the merkle cursor
+     * then stops advancing. Not allowed with `merkle.mode: 'local'` (throws SdkError(CONFIG)). Defaults to true.
+     */
+    buildMerkle?: boolean;
+    /**
+     * Deliver sync events through a bounded queue drained on a later tick, so a slow `onEvent` handler does not hold up
+     * syncing. `overflow: 'drop'` (default) discards the oldest queued event when `capacity` (default 1024) is reached;
+     * `'block'` keeps every event and pauses sync between pages until the queue has room. Omit to call handlers inline.
+     */
+    eventQueue?: { capacity?: number; overflow?: 'drop' | 'block' };
+  };
+  wallet?: {
+    /**
+     * How many memo hashes that failed to decode for the open wallet are remembered, so re-syncs
+     * skip trial decryption of foreign memos (defaults to 10000; 0 disables the cache).
+     */
+    foreignMemoCacheSize?: number;
   };
-  onEvent?: (event: SdkEvent) => void;
+  planner?: {
+    /**
+     * Optional per-asset relayer/protocol fees used when the relayer or contract does not provide one.
+     */
+    fees?: PlannerFeeConfig;
+    /**
+     * Default transfer coin-selection strategy (`greedy` when omitted).
+     * `Planner.plan` accepts a per-call `selection` override.
+     */
+    selection?: PlannerSelectionStrategy;
+    /**
+     * Transfer output order (`fixed` when omitted).
+     */
+    outputOrder?: PlannerOutputOrder;
+  };
+  /**
+   * Receives every SDK event. May return a promise: with `sync.eventQueue` the queue awaits it before delivering the
+   * next sync event (and `overflow: 'block'` pauses sync on it); otherwise the result is ignored.
+   */
+  onEvent?: (event: SdkEvent) => void | Promise<void>;
 }
 
 /** Serialized error payload used in events. */
@@ -163,6 +237,9 @@ export type SdkEvent =
   | { type: 'zkp:done'; payload: { circuit: 'transfer' | 'withdraw'; costMs: number } }
   | { type: 'error'; payload: SdkErrorPayload };
 
+/** Events yielded by `sync.syncStream`. */
+export type SyncStreamEvent = Extract<SdkEvent, { type: 'sync:start' | 'sync:progress' | 'sync:done' | 'error' }>;
+
 /** Record opening used in commitments and memos. */
 export interface CommitmentData {
   asset_id: bigint;
@@ -172,6 +249,17 @@ export interface CommitmentData {
   is_frozen: boolean;
 }
 
+/**
+ * Freezer policy used to derive the nullifier key.
+ * - `none`: no freezer (identity point), the secret key is used directly.
+ * - `single`: one freezer public key.
+ * - `set`: multiple freezers; `freezerPk` is the member the record's pool is bound to (the key the circuit checks).
+ */
+export type FreezePolicy =
+  | { kind: 'none' }
+  | { kind: 'single'; freezerPk: [bigint, bigint] }
+  | { kind: 'set'; freezers: Array<[bigint, bigint]>; freezerPk: [bigint, bigint] };
+
 /** Decoded memo record with metadata. */
 export interface MemoRecord {
   commitment: Hex;
@@ -366,10 +454,33 @@ export interface SyncCursor {
   /**
    * Merkle cursor is the merkle root index (batch cursor), derived from memo sync (total elements).
    * The root index typically advances only after a full batch (e.g. 32 leaves), so it will not equal `memo`.
+   * Invariant: `merkle <= rootIndex(memo)`; the local tree is never built past downloaded memos. If a pass stops
+   * after memo download but before the tree build, leaves in `[tree size, memo)` are rebuilt from cached entry
+   * memos at the start of the next memo sync.
    */
   merkle: number;
 }
 
+/** One recorded cursor move (see `StorageAdapter.appendCursorHistory`). */
+export interface CursorHistoryEntry {
+  chainId: number;
+  previous: SyncCursor;
+  next: SyncCursor;
+  /** Milliseconds since epoch when the move was saved. */
+  timestamp: number;
+}
+
+/**
+ * Trusted per-chain sync checkpoint (e.g. shipped with the app) used to skip syncing from genesis.
+ * `leaves` holds the commitments for cids `0..memo - 1` and must fold to `merkleRoot`.
+ */
+export interface SyncCheckpoint {
+  memo: number;
+  nullifier: number;
+  merkleRoot: Hex;
+  leaves: Hex[];
+}
+
 /** Per-chain sync status (memo/nullifier/merkle). */
 export interface SyncChainStatus {
   memo: { status: 'idle' | 'syncing' | 'synced' | 'error'; downloaded: number; total?: number; errorMessage?: string };
@@ -377,6 +488,47 @@ export interface SyncChainStatus {
   merkle: { status: 'idle' | 'syncing' | 'synced' | 'error'; cursor: number; errorMessage?: string };
 }
 
+/** Aggregate storage counts returned by `StorageAdapter.stats` / `getStoreStats`. */
+export interface StoreStats {
+  utxos: { total: number; unspent: number };
+  /** Sync cursor per chain id. */
+  cursors: Record<number, SyncCursor>;
+  operations: { total: number; byStatus: Record<string, number> };
+}
+
+/** Unspent value of one asset on one chain, returned by `StorageAdapter.getBalances` / `getStoreBalances`. */
+export interface AssetBalance {
+  chainId: number;
+  assetId: string;
+  /** Unspent, unfrozen amount. */
+  spendable: bigint;
+  /** Unspent amount held in frozen UTXOs. */
+  frozen: bigint;
+  /** `spendable + frozen`. */
+  total: bigint;
+}
+
+/** Pending Entry items per resource relative to the stored sync cursor. */
+export interface SyncChainBacklog {
+  chainId: number;
+  memo: { cursor: number; total: number; pending: number };
+  nullifier: { cursor: number; total: number; pending: number };
+}
+
+/** Cumulative sync counters since the engine was created (JSON-serializable for metrics endpoints). */
+export interface SyncMetricsSnapshot {
+  /** Contiguous memos downloaded and applied. */
+  memosDownloaded: number;
+  /** Memos that decrypted to UTXOs owned by the wallet. */
+  ownedMemosFound: number;
+  /** Nullifiers downloaded and applied. */
+  nullifiersProcessed: number;
+  /** Chain sync passes that finished with at least one resource in error. */
+  syncErrors: number;
+  /** Per-chain completion times (epoch ms) of the last error-free sync pass. */
+  lastSyncAt: Record<number, number>;
+}
+
 /** UTXO list query options. */
 export type ListUtxosQuery = {
   /** Filter by chain id. */
@@ -395,8 +547,8 @@ export type ListUtxosQuery = {
   offset?: number;
   /** Result pagination limit (default: no limit). */
   limit?: number;
-  /** Order by field (default: mkIndex). */
-  orderBy?: 'mkIndex' | 'createdAt';
+  /** Order by field (default: mkIndex). Ties on `createdAt` or `amount` are broken by `mkIndex` in the same direction. */
+  orderBy?: 'mkIndex' | 'createdAt' | 'amount';
   /** Order direction (default: asc). */
   order?: 'asc' | 'desc';
 };
@@ -530,6 +682,14 @@ export type MerkleLeafRecord = {
   commitment: Hex;
 };
 
+/**
+ * Store change notification delivered to `StorageAdapter.subscribe` listeners (e.g. to refresh balances).
+ * Events carry counts and chain ids only, never record contents.
+ */
+export type StoreEvent =
+  | { type: 'utxosUpserted'; chainIds: number[]; count: number }
+  | { type: 'markedSpent'; chainId: number; count: number }
+  | { type: 'cursorUpdated'; chainId: number; cursor: SyncCursor };
 
 /** Storage adapter interface for persistence. */
 export interface StorageAdapter {
@@ -545,12 +705,24 @@ export interface StorageAdapter {
   getSyncCursor(chainId: number): Promise<SyncCursor | undefined>;
   /** Persist the current sync cursor for a chain. */
   setSyncCursor(chainId: number, cursor: SyncCursor): Promise<void>;
+  /**
+   * Optional audit log of cursor moves, appended by the sync engine after each `setSyncCursor` that changed the cursor
+   * (advances, and resets from `resyncFrom`/`applyCheckpoint`). Implementations may bound it and drop the oldest entries.
+   */
+  appendCursorHistory?(chainId: number, previous: SyncCursor, next: SyncCursor, timestamp: number): Promise<void> | void;
+  /** The most recent `limit` (default all retained) cursor moves for a chain, oldest first. */
+  getCursorHistory?(chainId: number, limit?: number): Promise<CursorHistoryEntry[]>;
 
   /**
    * Insert or update UTXO records by `(chainId, commitment)`.
    * Implementations should preserve `isSpent` when upserting the same UTXO.
    */
   upsertUtxos(utxos: UtxoRecord[]): Promise<void>;
+  /**
+   * Atomically replace every UTXO on `chainId` with `utxos` (e.g. after a full resync), so readers see either the old
+   * set or the new one, never an empty or partial set. Records are stored as given, including `isSpent`.
+   */
+  replaceUtxos?(chainId: number, utxos: UtxoRecord[]): Promise<void>;
   /**
    * List UTXOs with optional filters and pagination.
    * Pagination is applied after filtering.
@@ -561,6 +733,38 @@ export interface StorageAdapter {
    * @returns number of updated records.
    */
   markSpent(input: { chainId: number; nullifiers: Hex[] }): Promise<number>;
+  /**
+   * Like `markSpent`, but returns the supplied nullifiers that matched an unspent UTXO and were newly marked.
+   * Nullifiers absent from the result are foreign or were already spent.
+   */
+  markSpentDetailed?(input: { chainId: number; nullifiers: Hex[] }): Promise<Hex[]>;
+  /**
+   * Delete UTXOs on a chain whose `mkIndex >= fromMkIndex` (used when re-syncing from an earlier cid).
+   * @returns number of deleted records.
+   */
+  deleteUtxosFrom?(input: { chainId: number; fromMkIndex: number }): Promise<number>;
+  /**
+   * Delete spent, unfrozen UTXOs on a chain; with `olderThan`, only those whose `createdAt` is set and below it
+   * (same unit as `createdAt`). Unspent and frozen records are never removed.
+   * Implementations must remember pruned nullifiers so a UTXO re-synced from its memo is stored as spent, not revived.
+   * @returns number of deleted records.
+   */
+  pruneSpentUtxos?(input: { chainId: number; olderThan?: number }): Promise<number>;
+  /**
+   * Aggregate counts for observability. Optional: `getStoreStats` falls back to the list methods.
+   * Adapters that can enumerate cursors should include every chain, not only `chainIds`.
+   */
+  stats?(options?: { chainIds?: number[] }): Promise<StoreStats>;
+  /**
+   * Unspent balances grouped by `(chainId, assetId)`, optionally for one chain. Optional: `getStoreBalances` falls back
+   * to summing `listUtxos`.
+   */
+  getBalances?(options?: { chainId?: number }): Promise<AssetBalance[]>;
+  /**
+   * Observe store changes (new UTXOs, spent markers, cursor updates). Returns an unsubscribe function.
+   * Adapters without it emit nothing; listeners must not throw.
+   */
+  subscribe?(listener: (event: StoreEvent) => void): () => void;
 
   /**
    * Create a local operation record (e.g. deposit/transfer/withdraw).
@@ -580,6 +784,14 @@ export interface StorageAdapter {
   clearOperations?(): Promise<void> | void;
   pruneOperations?(options?: { max?: number }): Promise<number> | number;
 
+  /**
+   * Optional counter behind `ops.prepareDepositDeterministic`: return the next unused counter for `owner` (a viewing
+   * address) and advance it in the same step, so no value is ever handed out twice. Counters start at 0, survive
+   * `init` with a new `walletId` and process restarts, and must never move backwards. Only durable adapters should
+   * implement it: a counter that resets would reuse blindings.
+   */
+  reserveDepositCounter?(owner: Hex): Promise<number>;
+
   /**
    * Optional merkle leaf persistence for `MerkleEngine` local/hybrid modes.
    * Leaves are expected to be contiguous and cid-ordered (starting at 0).
@@ -730,6 +942,36 @@ export interface SyncApi {
     continueOnError?: boolean;
   }): Promise<void>;
   getStatus(): Record<number, SyncChainStatus>;
+  /** Copy of the cumulative sync counters since the SDK was created. */
+  metricsSnapshot(): SyncMetricsSnapshot;
+  /** Dry run: how far each chain's stored cursors trail the Entry totals. Fetches one-row pages only; nothing is applied. */
+  estimateBacklog(options?: { chainIds?: number[]; signal?: AbortSignal; requestTimeoutMs?: number }): Promise<SyncChainBacklog[]>;
+  /**
+   * Rewind a chain's cursor so the next pass re-downloads from `memo` / `nullifier` (never ahead of the current cursor).
+   * With `clearDownstream`, UTXOs with `mkIndex >= memo` are deleted and the nullifier cursor restarts at 0.
+   * Rejects with `SYNC` while the chain is syncing.
+   */
+  forceResyncFrom(chainId: number, target: { memo: number; nullifier: number; clearDownstream?: boolean }): Promise<void>;
+  /**
+   * Warm-start a chain that has never synced from a trusted checkpoint: `leaves` must fold to `merkleRoot`, then the
+   * local tree is seeded and the cursor moves to the checkpoint. Rejects with `SYNC` while the chain is syncing or once
+   * it has synced.
+   */
+  applyCheckpoint(chainId: number, checkpoint: SyncCheckpoint): Promise<void>;
+  /**
+   * Run one sync pass as an async iterator of its `sync:*` and `error` events (takes the `syncOnce` options).
+   * Leaving the loop early aborts the pass once the in-flight page has been applied and its cursor persisted.
+   */
+  syncStream(options?: {
+    chainIds?: number[];
+    resources?: Array<'memo' | 'nullifier' | 'merkle'>;
+    signal?: AbortSignal;
+    requestTimeoutMs?: number;
+    pageSize?: number;
+    continueOnError?: boolean;
+  }): AsyncGenerator<SyncStreamEvent, void, undefined>;
+  /** Resolves once every event queued under `sync.eventQueue` has reached `onEvent` and settled; immediate without it. */
+  flushEvents(): Promise<void>;
 }
 
 /** Merkle proof response shape from remote service. */
@@ -739,6 +981,14 @@ export interface RemoteMerkleProofResponse {
   latest_cid: number;
 }
 
+/**
+ * Where `Ops` fetches membership proofs for the inputs it spends.
+ * `MerkleEngine` is the default source (local tree, remote service or both, per `merkle.mode`).
+ */
+export interface MerkleProofSource {
+  getProofByCids: (input: { chainId: number; cids: number[]; totalElements: bigint }) => Promise<RemoteMerkleProofResponse>;
+}
+
 /** Merkle API for proof generation and witness building. */
 export interface MerkleApi {
   currentMerkleRootIndex: (totalElements: number, tempArraySize?: number) => number;
@@ -862,6 +1112,40 @@ export type PlannerFeeSummary = {
   inputCount: number;
 };
 
+/** Fee schedule applied by the planner for a single asset. */
+export type PlannerFeeSchedule = {
+  transferRelayerFee?: bigint;
+  withdrawRelayerFee?: bigint;
+  withdrawFeeBps?: number;
+};
+
+/**
+ * Planner fee fallbacks keyed by asset id.
+ * The relayer's fee table and the contract's `withdrawFeeBps` always win; these entries only fill in fees neither
+ * of them provides, per-asset entries before `default`.
+ */
+export type PlannerFeeConfig = {
+  default?: PlannerFeeSchedule;
+  assets?: Record<string, PlannerFeeSchedule>;
+};
+
+/**
+ * Transfer coin-selection strategy.
+ * - `greedy`: largest-first until the amount is covered.
+ * - `privacy`: prefer a single input (even with larger change), then inputs sharing a source
+ *   (same `createdAt`, i.e. outputs already linked on-chain), before merging unrelated coins.
+ * - `optimal`: the subset (within the input cap) that covers the amount with the least change.
+ */
+export type PlannerSelectionStrategy = 'greedy' | 'privacy' | 'optimal';
+
+/**
+ * Transfer output order.
+ * - `fixed`: recipient, change, dummy.
+ * - `commitment`: ascending commitment value, so an output's position does not reveal its role.
+ *   `extraData` memos are reordered with their outputs.
+ */
+export type PlannerOutputOrder = 'fixed' | 'commitment';
+
 /** Planner max estimate result for transfer/withdraw. */
 export type PlannerMaxEstimateResult = {
   action: 'transfer' | 'withdraw';
@@ -942,9 +1226,16 @@ export type PlannerPlanResult = TransferPlan | TransferMergePlan | WithdrawPlan;
 /** Planner API for fee estimation and plan creation. */
 export interface PlannerApi {
   /** Estimate fees and check if balance is sufficient for an operation. */
-  estimate(input: { chainId: number; assetId: string; action: 'transfer' | 'withdraw'; amount: bigint; payIncludesFee?: boolean }): Promise<PlannerEstimateResult>;
+  estimate(input: { chainId: number; assetId: string; action: 'transfer' | 'withdraw'; amount: bigint; payIncludesFee?: boolean; selection?: PlannerSelectionStrategy }): Promise<PlannerEstimateResult>;
   /** Calculate the maximum transferable/withdrawable amount after fees. */
   estimateMax(input: { chainId: number; assetId: string; action: 'transfer' | 'withdraw'; payIncludesFee?: boolean }): Promise<PlannerMaxEstimateResult>;
+  /**
+   * Plan a transfer of everything the `maxInputs` largest UTXOs hold, minus the relayer fee (no change output).
+   * Throws `CONFIG` when the selection does not exceed the fee.
+   */
+  planSendMax(input: { chainId: number; assetId: string; to: Hex; relayerUrl?: string; maxInputs?: number }): Promise<TransferPlan>;
+  /** Group the open wallet's smallest UTXOs of an asset into self-transfer batches (one consolidation round). */
+  planConsolidation(input: { chainId: number; assetId: string; maxInputs?: number; dustThreshold?: bigint }): Promise<Array<{ selected: UtxoRecord[]; sum: bigint }>>;
   /** Build a full transaction plan (coin selection, outputs, proof binding). */
   plan(input: Record<string, unknown>): Promise<PlannerPlanResult>;
 }
@@ -958,6 +1249,9 @@ export interface ZkpApi {
   proveWithdraw: (witness: WithdrawWitnessInput | string, context?: WitnessContext) => Promise<ProofResult>;
 }
 
+/** Relayer operation a request submits; see `RelayerRequestBuilder` for the endpoint each maps to. */
+export type RelayerAction = 'transfer' | 'withdraw';
+
 /** Relayer request payload built from proofs. */
 export interface RelayerRequest {
   kind: 'relayer';
@@ -966,6 +1260,16 @@ export interface RelayerRequest {
   body: Record<string, unknown>;
 }
 
+/** Relayer-side state of a submission, looked up by one of its spent nullifiers. */
+export interface RelayerSubmissionStatus {
+  nullifier: Hex;
+  status: 'pending' | 'submitted' | 'confirmed' | 'failed';
+  relayerTxHash?: Hex;
+  /** On-chain tx hash once the relayer has broadcast the submission. */
+  txHash?: Hex;
+  blockNumber?: number;
+}
+
 /** Tx builder API for relayer request construction. */
 export interface TxBuilderApi {
   buildTransferCalldata: (input: { chainId: number; proof: ProofResult }) => Promise<RelayerRequest>;
@@ -1047,6 +1351,33 @@ export interface OpsApi {
     };
   }>;
 
+  /**
+   * Prepare one deposit per output, in submission order (the order their commitments land on-chain). ERC20 allowance is
+   * tracked across the batch. With `blindingDerivation`, blindings are derived as in `prepareDepositDeterministic` and
+   * each entry carries its `counter`.
+   */
+  prepareDepositBatch(input: {
+    chainId: number;
+    outputs: Array<{ assetId: string; amount: bigint }>;
+    ownerPublicKey: UserPublicKey;
+    account: Address;
+    publicClient: PublicClient;
+    blindingDerivation?: { ownerSecretKey: UserSecretKey };
+  }): Promise<Array<Awaited<ReturnType<OpsApi['prepareDeposit']>> & { counter?: number }>>;
+
+  /**
+   * Like `prepareDeposit`, but the blinding factor is derived from `ownerSecretKey` and a counter reserved from the
+   * storage adapter (`reserveDepositCounter`), so notes can be recovered from the key alone. Returns the counter used.
+   */
+  prepareDepositDeterministic(input: {
+    chainId: number;
+    assetId: string;
+    amount: bigint;
+    ownerSecretKey: UserSecretKey;
+    account: Address;
+    publicClient: PublicClient;
+  }): Promise<Awaited<ReturnType<OpsApi['prepareDeposit']>> & { counter: number }>;
+
   /** Execute deposit on-chain: optionally auto-approve ERC-20 then call deposit(). */
   submitDeposit(input: {
     prepared: Awaited<ReturnType<OpsApi['prepareDeposit']>>;
@@ -0,0 +1,56 @@
+import { formatUnits } from 'viem';
+import type { AmountFormat } from '../types';
+import { SdkError } from '../errors';
+
+/**
+ * Resolve token decimals, required only by the `decimal` format.
+ */
+const requireDecimals = (decimals: number | undefined): number => {
+  if (typeof decimals === 'number' && Number.isInteger(decimals) && decimals >= 0 && decimals <= 255) return decimals;
+  throw new SdkError('CONFIG', 'decimal amount format requires integer token decimals', { decimals });
+};
+
+/**
+ * Render a base-unit amount as lowercase 0x-hex, decimal base units, or human decimal (`1.5`).
+ */
+export const formatAmountAs = (value: bigint, format: AmountFormat, decimals?: number): string => {
+  if (value < 0n) throw new SdkError('CONFIG', 'amount must be non-negative', { value });
+  switch (format) {
+    case 'hex':
+      return `0x${value.toString(16)}`;
+    case 'base-units':
+      return value.toString();
+    case 'decimal':
+      return formatUnits(value, requireDecimals(decimals));
+    default:
+      throw new SdkError('CONFIG', 'Unknown amount format', { format });
+  }
+};
+
+/**
+ * Parse an amount string produced by `formatAmountAs` back into base units.
+ * Decimal input with more fractional digits than `decimals` is rejected rather than rounded.
+ */
+export const parseAmountAs = (value: string, format: AmountFormat, decimals?: number): bigint => {
+  switch (format) {
+    case 'hex':
+      if (/^0x[0-9a-fA-F]+$/.test(value)) return BigInt(value);
+      break;
+    case 'base-units':
+      if (/^\d+$/.test(value)) return BigInt(value);
+      break;
+    case 'decimal': {
+      const scale = requireDecimals(decimals);
+      const match = /^(\d+)(?:\.(\d+))?$/.exec(value);
+      if (!match) break;
+      const fraction = match[2] ?? '';
+      if (fraction.length > scale) {
+        throw new SdkError('CONFIG', 'amount has more fractional digits than token decimals', { value, decimals: scale });
+      }
+      return BigInt(match[1]!) * 10n ** BigInt(scale) + BigInt(fraction.padEnd(scale, '0') || '0');
+    }
+    default:
+      throw new SdkError('CONFIG', 'Unknown amount format', { format });
+  }
+  throw new SdkError('CONFIG', `Invalid ${format} amount`, { value });
+};
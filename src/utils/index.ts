@@ -1,5 +1,8 @@
 import { serializeBigInt as serializeBigIntHelper } from './json';
 import { randomBytes32 as coreRandomBytes32, randomBytes32Bigint } from './random';
+import { requireChainId, requireContractAddress } from './validators';
+import { formatAmountAs, parseAmountAs } from './amount';
+import type { ChainId, ContractAddress } from '../types';
 
 const BASIS_POINTS = 10_000n;
 
@@ -21,6 +24,18 @@ export const randomBytes32 = () => coreRandomBytes32();
  */
 export const serializeBigInt = <T>(value: T): string => serializeBigIntHelper(value);
 
+/**
+ * Validate and brand a chain id (positive safe integer).
+ */
+export const toChainId = (value: number): ChainId => requireChainId(value, 'chainId');
+
+/**
+ * Validate and brand a contract address (returned checksummed).
+ */
+export const toContractAddress = (value: string): ContractAddress => requireContractAddress(value, 'contractAddress');
+
+export { formatAmountAs, parseAmountAs };
+
 /**
  * Convenience namespace for commonly used utils.
  */
@@ -29,4 +44,8 @@ export const Utils = {
   randomBytes32,
   randomBytes32Bigint,
   serializeBigInt,
+  toChainId,
+  toContractAddress,
+  formatAmountAs,
+  parseAmountAs,
 };
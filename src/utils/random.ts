@@ -1,6 +1,12 @@
-import { bytesToHex } from '@noble/hashes/utils';
+import { sha256 } from '@noble/hashes/sha256';
+import { bytesToHex, concatBytes, utf8ToBytes } from '@noble/hashes/utils';
 import { BN254_FIELD_MODULUS } from '../crypto/field';
 
+/**
+ * Source of random bytes (`size` bytes per call).
+ */
+export type RandomSource = (size: number) => Uint8Array;
+
 /**
  * Read cryptographically secure random bytes from global crypto.
  */
@@ -21,11 +27,30 @@ export const randomBytes32 = (): Uint8Array => getRandomBytes(32);
 /**
  * 生成 32 字节随机数，并根据需要截断为 BN254 有限域元素
  */
-export const randomBytes32Bigint = (isBabyJubScalar = false): bigint => {
-  const buf = getRandomBytes(32);
+export const randomBytes32Bigint = (isBabyJubScalar = false, source: RandomSource = getRandomBytes): bigint => {
+  const buf = source(32);
   let result = BigInt(`0x${bytesToHex(buf)}`);
   if (isBabyJubScalar) {
     result %= BN254_FIELD_MODULUS;
   }
   return result;
 };
+
+/**
+ * Deterministic random source (SHA-256 over `seed || counter`, counter as big-endian u32) for reproducible tests.
+ * Never use it for real funds: outputs are predictable from the seed. Deliberately not part of the public exports.
+ */
+export const createSeededRandomSource = (seed: string): RandomSource => {
+  const seedBytes = utf8ToBytes(seed);
+  let counter = 0;
+  return (size: number) => {
+    const out = new Uint8Array(size);
+    for (let filled = 0; filled < size; filled += 32) {
+      const counterBytes = new Uint8Array(4);
+      new DataView(counterBytes.buffer).setUint32(0, counter++, false);
+      const block = sha256(concatBytes(seedBytes, counterBytes));
+      out.set(block.subarray(0, Math.min(32, size - filled)), filled);
+    }
+    return out;
+  };
+};
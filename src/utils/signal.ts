@@ -1,11 +1,17 @@
 /**
- * Create an AbortSignal that fires after a timeout.
+ * Whether an abort reason (or the error `fetch` rejected with) came from a `signalTimeout` expiring.
+ */
+export const isTimeoutError = (error: unknown): boolean => error instanceof Error && error.name === 'TimeoutError';
+
+/**
+ * Create an AbortSignal that fires after a timeout. Its abort reason is a `TimeoutError` either way.
  */
 export const signalTimeout = (ms: number): AbortSignal => {
   const anyAbortSignal = AbortSignal;
   if (typeof anyAbortSignal?.timeout === 'function') return anyAbortSignal.timeout(ms) as AbortSignal;
   const controller = new AbortController();
-  const t = setTimeout(() => controller.abort(new Error('timeout')), ms);
+  const timeoutError = Object.assign(new Error('timeout'), { name: 'TimeoutError' });
+  const t = setTimeout(() => controller.abort(timeoutError), ms);
   controller.signal.addEventListener('abort', () => clearTimeout(t), { once: true });
   return controller.signal;
 };
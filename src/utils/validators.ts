@@ -1,5 +1,5 @@
 import { getAddress, type Address } from 'viem';
-import type { Hex } from '../types';
+import type { ChainId, ContractAddress, Hex } from '../types';
 import { SdkError } from '../errors';
 import { isHexStrict } from './hex';
 
@@ -38,3 +38,19 @@ export const requireBigint = (value: unknown, name: string): bigint => {
   if (typeof value === 'number' && Number.isSafeInteger(value)) return BigInt(value);
   throw new SdkError('CONFIG', `${name} must be a bigint-compatible value`);
 };
+
+/**
+ * Require a positive safe-integer chain id.
+ */
+export const requireChainId = (value: unknown, name: string): ChainId => {
+  if (typeof value === 'number' && Number.isSafeInteger(value) && value > 0) return value as ChainId;
+  throw new SdkError('CONFIG', `${name} must be a positive integer chain id`, { value });
+};
+
+/**
+ * Require a 20-byte EVM contract address; returns the checksummed form.
+ */
+export const requireContractAddress = (value: unknown, name: string): ContractAddress => {
+  if (typeof value === 'string' && /^0x[0-9a-fA-F]{40}$/.test(value)) return getAddress(value) as ContractAddress;
+  throw new SdkError('CONFIG', `${name} must be a 20-byte hex address`, { value });
+};
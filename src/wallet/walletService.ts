@@ -1,9 +1,13 @@
-import type { AssetsApi, ChainConfigInput, Hex, ListUtxosQuery, ListUtxosResult, SdkEvent, StorageAdapter, UtxoRecord, WalletSessionInput } from '../types';
+import { sha256 } from '@noble/hashes/sha256';
+import { bytesToHex, utf8ToBytes } from '@noble/hashes/utils';
+import type { AssetsApi, ChainConfigInput, CommitmentData, Hex, ListUtxosQuery, ListUtxosResult, SdkEvent, StorageAdapter, UtxoRecord, WalletSessionInput } from '../types';
 import { SdkError } from '../errors';
 import { KeyManager } from '../crypto/keyManager';
 import { CryptoToolkit } from '../crypto/cryptoToolkit';
 import { MemoKit } from '../memo/memoKit';
 
+const DEFAULT_FOREIGN_MEMO_CACHE_SIZE = 10_000;
+
 type AssetLookup = {
   assetId: string;
   viewerPk: [bigint, bigint];
@@ -23,6 +27,11 @@ const normalizeSeed = (seed: string | Uint8Array): string => {
  */
 const toBigintPoint = (input: [string, string]): [bigint, bigint] => [BigInt(input[0]), BigInt(input[1])];
 
+/**
+ * Cache key for a memo payload (transparent and encrypted decoding are cached separately).
+ */
+const foreignMemoKey = (memo: Hex, isTransparent?: boolean) => `${isTransparent ? 't' : 'e'}:${bytesToHex(sha256(utf8ToBytes(memo.toLowerCase())))}`;
+
 /**
  * Wallet service manages key material, UTXO state, and memo decryption.
  * It is stateful and must be opened before use.
@@ -32,12 +41,18 @@ export class WalletService {
   private secretKey: bigint | null = null;
   private address: Hex | null = null;
   private assetByChainPoolId = new Map<string, AssetLookup>();
+  // LRU (insertion-ordered) of memo hashes that failed to decode for the current key.
+  private foreignMemos = new Set<string>();
+  private readonly foreignMemoCacheSize: number;
 
   constructor(
     private readonly assets: AssetsApi,
     private readonly storage: StorageAdapter,
     private readonly emit: (evt: SdkEvent) => void,
-  ) {}
+    options?: { foreignMemoCacheSize?: number },
+  ) {
+    this.foreignMemoCacheSize = Math.max(0, Math.floor(options?.foreignMemoCacheSize ?? DEFAULT_FOREIGN_MEMO_CACHE_SIZE));
+  }
 
   /**
    * Open a wallet session: derive keypair, set viewing address, and init storage.
@@ -49,6 +64,8 @@ export class WalletService {
     this.secretKey = keyPair.user_sk.address_sk;
     this.address = KeyManager.userPkToAddress(keyPair.user_pk);
     this.assetByChainPoolId = this.buildAssetLookup(this.assets.getChains());
+    // A different key may own memos the previous one could not decode.
+    this.foreignMemos.clear();
     await this.storage.init?.({ walletId: this.address });
     this.opened = true;
   }
@@ -62,6 +79,7 @@ export class WalletService {
     // Setting to null removes the reference; actual memory clearing depends on GC.
     this.secretKey = null;
     this.address = null;
+    this.foreignMemos.clear();
     await this.storage.close?.();
   }
 
@@ -119,6 +137,7 @@ export class WalletService {
 
   /**
    * Process memo entries:
+   * - skip memos already found not to be ours (bounded cache, reset on open/close)
    * - decrypt and validate commitment
    * - map to asset metadata
    * - compute nullifier
@@ -143,13 +162,29 @@ export class WalletService {
     let refreshedAssets = false;
     for (const entry of memos) {
       if (typeof entry.cid !== 'number' || !Number.isInteger(entry.cid) || entry.cid < 0) continue;
-      const ro = MemoKit.decodeMemoForOwner({
-        secretKey,
-        memo: entry.memo,
-        expectedAddress: this.address,
-        isTransparent: entry.is_transparent,
-      });
-      if (!ro) continue;
+      const memoKey = this.foreignMemoCacheSize > 0 ? foreignMemoKey(entry.memo, entry.is_transparent) : null;
+      if (memoKey && this.foreignMemos.has(memoKey)) {
+        this.foreignMemos.delete(memoKey);
+        this.foreignMemos.add(memoKey);
+        continue;
+      }
+      let ro: CommitmentData | null;
+      try {
+        ro = MemoKit.decodeMemoForOwner({
+          secretKey,
+          memo: entry.memo,
+          expectedAddress: this.address,
+          isTransparent: entry.is_transparent,
+        });
+      } catch (error) {
+        // An oversized memo cannot be ours (the SDK never writes one); skip it rather than failing the scan.
+        if (error instanceof SdkError && error.code === 'MEMO_TOO_LARGE') continue;
+        throw error;
+      }
+      if (!ro) {
+        if (memoKey) this.rememberForeignMemo(memoKey);
+        continue;
+      }
       if (entry.amount && entry.asset_id && entry.partial_hash) {
         try {
           ro.asset_id = BigInt(entry.asset_id);
@@ -193,6 +228,16 @@ export class WalletService {
     return added.length;
   }
 
+  /**
+   * Record a memo that does not decode for this wallet, evicting the least recently seen entry when full.
+   */
+  private rememberForeignMemo(key: string) {
+    this.foreignMemos.add(key);
+    if (this.foreignMemos.size > this.foreignMemoCacheSize) {
+      this.foreignMemos.delete(this.foreignMemos.values().next().value!);
+    }
+  }
+
   /**
    * Build a lookup table from chain+poolId to asset metadata and PKs.
    * Used to resolve assetId and freezerPk during memo processing.
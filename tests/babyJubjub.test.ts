@@ -0,0 +1,20 @@
+import { describe, expect, it } from 'vitest';
+import { BabyJubjub } from '../src/crypto/babyJubjub';
+
+describe('BabyJubjub point pairs', () => {
+  const base: [bigint, bigint] = [BabyJubjub.BASE_POINT.x, BabyJubjub.BASE_POINT.y];
+
+  it('round-trips the base point through hex and decimal pairs', () => {
+    const hex = BabyJubjub.pointToHexPair(base);
+    expect(hex[0]).toMatch(/^0x[0-9a-f]{64}$/);
+    expect(BabyJubjub.pointFromPair(hex)).toEqual(base);
+    expect(BabyJubjub.pointFromPair([base[0].toString(), base[1].toString()])).toEqual(base);
+    expect(BabyJubjub.pointFromPair(['0', '1'])).toEqual([0n, 1n]);
+  });
+
+  it('rejects off-curve, out-of-field and malformed points', () => {
+    expect(() => BabyJubjub.pointFromPair([base[0], base[1] + 1n])).toThrow('not on curve');
+    expect(() => BabyJubjub.pointFromPair([base[0] + BabyJubjub.SCALAR_FIELD, base[1]])).toThrow('outside the scalar field');
+    expect(() => BabyJubjub.pointFromPair(['0xzz', '1'])).toThrow('Invalid BabyJubjub point coordinates');
+  });
+});
@@ -0,0 +1,61 @@
+import { existsSync, readFileSync } from 'node:fs';
+import { dirname, resolve } from 'node:path';
+import { fileURLToPath } from 'node:url';
+import { describe, expect, it } from 'vitest';
+import * as CryptoEntry from '../src/index.crypto';
+import * as MainEntry from '../src/index';
+import * as MerkleProofModule from '../src/merkle/merkleProof';
+
+const record = {
+  asset_id: 7n,
+  asset_amount: 1000n,
+  user_pk: { user_address: [11n, 22n] as [bigint, bigint] },
+  blinding_factor: 33n,
+  is_frozen: false,
+};
+
+describe('crypto entrypoint', () => {
+  it('computes commitments and domain hashes without the full SDK', () => {
+    const { CryptoToolkit, Poseidon2, Poseidon2Domain, fieldToHex } = CryptoEntry;
+    const commitment = CryptoToolkit.commitment(record, 'hex');
+    expect(commitment).toBe(MainEntry.CryptoToolkit.commitment(record, 'hex'));
+    expect(commitment).toBe(fieldToHex(Poseidon2.hashSequenceWithDomain([11n, 22n, 33n, 7n, 1000n], Poseidon2Domain.Record)));
+    const nullifier = CryptoToolkit.nullifier(5n, commitment);
+    expect(nullifier).toBe(fieldToHex(Poseidon2.hashDomain(5n, BigInt(commitment), Poseidon2Domain.Nullifier)));
+  });
+
+  it('re-exports every merkle proof helper', () => {
+    for (const name of Object.keys(MerkleProofModule)) {
+      expect(CryptoEntry, name).toHaveProperty(name, (MainEntry as Record<string, unknown>)[name]);
+    }
+  });
+
+  it('only imports pure crypto modules, transitively', () => {
+    // Walk every runtime import reachable from the entry; `import type` / `export type` are erased and skipped.
+    const srcDir = fileURLToPath(new URL('../src/', import.meta.url));
+    const queue = [resolve(srcDir, 'index.crypto.ts')];
+    const modules = new Set<string>();
+    const packages = new Set<string>();
+    while (queue.length) {
+      const file = queue.pop()!;
+      if (modules.has(file)) continue;
+      modules.add(file);
+      const source = readFileSync(file, 'utf8');
+      for (const match of source.matchAll(/^(?:import|export)\s+(?!type\b)[^;]*?from\s+'([^']+)'/gms)) {
+        const spec = match[1]!;
+        if (!spec.startsWith('.')) {
+          packages.add(spec);
+          continue;
+        }
+        const base = resolve(dirname(file), spec);
+        queue.push(existsSync(`${base}.ts`) ? `${base}.ts` : resolve(base, 'index.ts'));
+      }
+    }
+    for (const file of modules) {
+      expect(file.slice(srcDir.length)).toMatch(/^(index\.crypto|types|errors|crypto\/|merkle\/merkleProof|merkle\/zeroHashes|utils\/random)/);
+    }
+    for (const pkg of packages) {
+      expect(pkg).toMatch(/^@noble\/hashes\//);
+    }
+  });
+});
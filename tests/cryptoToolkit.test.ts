@@ -0,0 +1,143 @@
+import { describe, expect, it } from 'vitest';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { Poseidon2, Poseidon2Domain } from '../src/crypto/poseidon2';
+import { BN254_FIELD_MODULUS, fieldToHex } from '../src/crypto/field';
+import { BabyJubjub, BABYJUBJUB_ORDER } from '../src/crypto/babyJubjub';
+import { RecordCodec } from '../src/crypto/recordCodec';
+import { KeyManager } from '../src/crypto/keyManager';
+
+describe('CryptoToolkit frozen bit', () => {
+  const amounts = [0n, 1n, 10n ** 18n, (1n << 128n) - 1n];
+
+  it('sets, detects and clears the frozen bit without touching the amount', () => {
+    for (const amount of amounts) {
+      const frozen = CryptoToolkit.setFrozenBit(amount);
+      expect(frozen).toBe(amount + (1n << 128n));
+      expect(CryptoToolkit.hasFrozenBit(frozen)).toBe(true);
+      expect(CryptoToolkit.hasFrozenBit(amount)).toBe(false);
+      expect(CryptoToolkit.clearFrozenBit(frozen)).toBe(amount);
+      expect(CryptoToolkit.clearFrozenBit(amount)).toBe(amount);
+      expect(CryptoToolkit.setFrozenBit(frozen)).toBe(frozen);
+    }
+  });
+
+  it('commits frozen records with the frozen bit set', () => {
+    const record = { asset_id: 7n, asset_amount: 100n, user_pk: { user_address: [1n, 2n] as [bigint, bigint] }, blinding_factor: 3n, is_frozen: true };
+    const expected = Poseidon2.hashSequenceWithDomain([1n, 2n, 3n, 7n, 100n | (1n << 128n)], Poseidon2Domain.Record);
+    expect(CryptoToolkit.commitment(record, 'bigint')).toBe(expected);
+  });
+});
+
+describe('CryptoToolkit.commitmentTrace', () => {
+  it('returns one accumulator per fold step ending in the commitment', () => {
+    const record = { asset_id: 7n, asset_amount: 100n, user_pk: { user_address: [1n, 2n] as [bigint, bigint] }, blinding_factor: 3n, is_frozen: false };
+    const { commitment, intermediates } = CryptoToolkit.commitmentTrace(record);
+    expect(intermediates).toHaveLength(4);
+    expect(intermediates[0]).toBe(Poseidon2.hashDomain(1n, 2n, Poseidon2Domain.Record));
+    expect(intermediates[1]).toBe(Poseidon2.hashDomain(intermediates[0]!, 3n, Poseidon2Domain.Record));
+    expect(intermediates[intermediates.length - 1]).toBe(commitment);
+    expect(commitment).toBe(CryptoToolkit.commitment(record, 'bigint'));
+  });
+});
+
+describe('CryptoToolkit.commitment construction', () => {
+  it('equals a hand-written pairwise fold of the record fields under the Record domain', () => {
+    const owner = BabyJubjub.scalarMult(123456789n);
+    const record = {
+      asset_id: 0x2a5f00c1n,
+      asset_amount: 10n ** 18n + 7n,
+      user_pk: { user_address: owner },
+      blinding_factor: BN254_FIELD_MODULUS - 12345n,
+      is_frozen: false,
+    };
+    const d = Poseidon2Domain.Record;
+    // Field order: owner x, owner y, blinding factor, asset id, amount.
+    let h = Poseidon2.hashDomain(owner[0], owner[1], d);
+    h = Poseidon2.hashDomain(h, record.blinding_factor, d);
+    h = Poseidon2.hashDomain(h, record.asset_id, d);
+    h = Poseidon2.hashDomain(h, record.asset_amount, d);
+
+    expect(CryptoToolkit.commitment(record, 'bigint')).toBe(h);
+    expect(CryptoToolkit.commitment(record, 'hex')).toBe(fieldToHex(h));
+    // The same fields folded in another order, or under another domain, must not collide.
+    const swapped = Poseidon2.hashDomain(Poseidon2.hashDomain(Poseidon2.hashDomain(Poseidon2.hashDomain(owner[0], owner[1], d), record.asset_id, d), record.blinding_factor, d), record.asset_amount, d);
+    expect(swapped).not.toBe(h);
+    expect(Poseidon2.hashSequenceWithDomain([owner[0], owner[1], record.blinding_factor, record.asset_id, record.asset_amount], Poseidon2Domain.None)).not.toBe(h);
+  });
+});
+
+describe('CryptoToolkit output amount limit', () => {
+  const user_pk = { user_address: [1n, 2n] as [bigint, bigint] };
+
+  it('accepts amounts up to 2^128 - 1', () => {
+    const record = CryptoToolkit.createRecordOpening({ asset_id: 7n, asset_amount: (1n << 128n) - 1n, user_pk, blinding_factor: 3n });
+    expect(record.asset_amount).toBe((1n << 128n) - 1n);
+  });
+
+  it('rejects amounts that would collide with the frozen bit', () => {
+    for (const asset_amount of [1n << 128n, (1n << 129n) + 5n]) {
+      expect(() => CryptoToolkit.createRecordOpening({ asset_id: 7n, asset_amount, user_pk })).toThrow(/128-bit record limit/);
+    }
+  });
+});
+
+describe('CryptoToolkit strict inputs', () => {
+  const record = { asset_id: 7n, asset_amount: 100n, user_pk: { user_address: [1n, 2n] as [bigint, bigint] }, blinding_factor: 3n, is_frozen: false };
+
+  it('reduces over-modulus fields by default but rejects them when strict', () => {
+    const overModulus = { ...record, asset_amount: 100n + BN254_FIELD_MODULUS };
+    expect(CryptoToolkit.commitment(overModulus, 'hex')).toBe(CryptoToolkit.commitment(record, 'hex'));
+    expect(() => CryptoToolkit.commitment(overModulus, 'hex', { strict: true })).toThrow(expect.objectContaining({ code: 'CRYPTO', detail: expect.objectContaining({ field: 'asset_amount' }) }));
+    expect(() => CryptoToolkit.commitment({ ...record, asset_amount: 1n << 128n }, 'bigint', { strict: true })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+    expect(() => RecordCodec.encode({ ...record, blinding_factor: BN254_FIELD_MODULUS }, { strict: true })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+    expect(CryptoToolkit.commitment(record, 'hex', { strict: true })).toBe(CryptoToolkit.commitment(record, 'hex'));
+  });
+
+  it('rejects an out-of-range nullifier secret key or commitment when strict', () => {
+    const commitment = CryptoToolkit.commitment(record, 'hex');
+    expect(CryptoToolkit.nullifier(5n, commitment, undefined, { strict: true })).toBe(CryptoToolkit.nullifier(5n, commitment));
+    expect(() => CryptoToolkit.nullifier(BABYJUBJUB_ORDER, commitment, undefined, { strict: true })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+    expect(() => CryptoToolkit.nullifier(5n, fieldToHex(BN254_FIELD_MODULUS + 1n), undefined, { strict: true })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+  });
+});
+
+describe('CryptoToolkit.commitmentFromParts', () => {
+  const owner = KeyManager.deriveKeyPair('crypto-toolkit-parts-seed');
+  const record = { asset_id: 7n, asset_amount: 1_000n, user_pk: owner.user_pk, blinding_factor: 12345n, is_frozen: false };
+
+  it('matches the low-level commitment for typed, address and string inputs', () => {
+    const expected = CryptoToolkit.commitment(record, 'hex');
+    const parts = { assetId: '7', amount: '0x3e8', blindingFactor: '12345' };
+    expect(CryptoToolkit.commitmentFromParts({ owner, ...parts })).toBe(expected);
+    expect(CryptoToolkit.commitmentFromParts({ owner: owner.user_pk, ...parts })).toBe(expected);
+    expect(CryptoToolkit.commitmentFromParts({ owner: KeyManager.userPkToAddress(owner.user_pk), ...parts })).toBe(expected);
+    const [x, y] = owner.user_pk.user_address;
+    expect(CryptoToolkit.commitmentFromParts({ owner: { user_address: [fieldToHex(x), y.toString()] }, assetId: 7n, amount: 1_000, blindingFactor: 12345n })).toBe(expected);
+    expect(CryptoToolkit.commitmentFromParts({ owner, ...parts, isFrozen: true })).toBe(CryptoToolkit.commitment({ ...record, is_frozen: true }, 'hex'));
+  });
+
+  it('rejects malformed or non-canonical parts', () => {
+    expect(() => CryptoToolkit.commitmentFromParts({ owner, assetId: 'seven', amount: 1n, blindingFactor: 1n })).toThrow(expect.objectContaining({ code: 'CRYPTO', detail: { field: 'asset_id', value: 'seven' } }));
+    expect(() => CryptoToolkit.commitmentFromParts({ owner, assetId: 7n, amount: 1n << 128n, blindingFactor: 1n })).toThrow('Invalid record opening field');
+    expect(() => CryptoToolkit.commitmentFromParts({ owner: '0x1234', assetId: 7n, amount: 1n, blindingFactor: 1n })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+  });
+});
+
+describe('CryptoToolkit.resolveFreezerPk', () => {
+  const freezers: Array<[bigint, bigint]> = [BabyJubjub.scalarMult(11n), BabyJubjub.scalarMult(13n)];
+
+  it('uses the explicit set member as the freezer key', () => {
+    expect(CryptoToolkit.resolveFreezerPk({ kind: 'set', freezers, freezerPk: freezers[1]! })).toEqual(freezers[1]);
+    expect(CryptoToolkit.nullifierWithPolicy(5n, '0x01', { kind: 'set', freezers, freezerPk: freezers[1]! })).toBe(CryptoToolkit.nullifier(5n, '0x01', freezers[1]));
+  });
+
+  it('rejects a set freezer key that is not a member of the set', () => {
+    expect(() => CryptoToolkit.resolveFreezerPk({ kind: 'set', freezers, freezerPk: BabyJubjub.scalarMult(17n) })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+    expect(() => CryptoToolkit.resolveFreezerPk({ kind: 'set', freezers: [], freezerPk: freezers[0]! })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+  });
+
+  it('resolves the identity and single-freezer policies', () => {
+    expect(CryptoToolkit.resolveFreezerPk({ kind: 'none' })).toEqual([0n, 1n]);
+    expect(CryptoToolkit.resolveFreezerPk({ kind: 'single', freezerPk: freezers[0]! })).toEqual(freezers[0]);
+  });
+});
@@ -0,0 +1,61 @@
+import { describe, expect, it } from 'vitest';
+import { EdDSA } from '../src/crypto/eddsa';
+import { BabyJubjub, BABYJUBJUB_ORDER, BABYJUBJUB_SCALAR_FIELD } from '../src/crypto/babyJubjub';
+
+// Computed by scripts/refVectors.py, which shares no hashing or curve code with the SDK.
+const vectors = [
+  {
+    sk: 0x2an,
+    message: [1n, 2n, 3n],
+    r: [0x91f359c32e65017c346d2f41f9d030e68056ad75ce950e28826179c4d1412ben, 0x1119a0dc0b22c3493ee91120f85f0a3543b2074121ce46475d5f6054b28d9ecn],
+    s: 0x2810973f18db45d6c66546dd08fa9f2312b7f922c5fd477a2755bf2f2a9e8e8n,
+    packed: '0xecd9284b05f6d57564e41c1274203b54a3f0850f1291ee93342cb2c00d9a1101e8e8a9f2f25b75a277d45f2c927f2b31f2a98fd06d54666c5db48df173098102',
+  },
+  {
+    sk: 0x2an,
+    message: [],
+    r: [0x2a8435f07e96c212ba114820c141048a459c52c8cdc0691093c87a604ece16c5n, 0x13a74efdb3012648d9cf18e713358a274969a70da3f72fedf076cf5f264d2580n],
+    s: 0x33ac09336621f280535351864b0920746c6cd8479b20a0ca6c19a260fa8c9dcn,
+    packed: '0x80254d265fcf76f0ed2ff7a30da76949278a3513e718cfd9482601b3fd4ea793dcc9a80f269ac1a60c0ab27984cdc6460792b06418353505281f623693c03a03',
+  },
+  {
+    sk: 987654321987654321n,
+    message: [BABYJUBJUB_SCALAR_FIELD - 1n],
+    r: [0x2785af0437abda4612ba9214b6a53148e83b38ed918512d996f0b3a82093ec65n, 0x1ae20c3d9c2ae0d7cde4a153f8c0f34c098442af8e7a375d6b973f96fa6b01b2n],
+    s: 0x36ecabf88bf422ba8d0813b67daf673ea8f0bfc53b5cf9efe733b47e6c34723n,
+    packed: '0xb2016bfa963f976b5d377a8eaf4284094cf3c0f853a1e4cdd7e02a9c3d0ce29a2347c3e6473b73fe9ecfb553fc0b8fea73f6da673b81d0a82b42bf88bfca6e03',
+  },
+];
+
+describe('EdDSA', () => {
+  it('matches the reference vectors and verifies them', () => {
+    for (const v of vectors) {
+      const signature = EdDSA.sign(v.sk, v.message);
+      expect(signature).toEqual({ r: v.r, s: v.s });
+      expect(EdDSA.packSignature(signature)).toBe(v.packed);
+      expect(EdDSA.unpackSignature(v.packed)).toEqual(signature);
+      expect(EdDSA.verify(BabyJubjub.scalarMult(v.sk), v.message, signature)).toBe(true);
+    }
+  });
+
+  it('rejects tampered messages, signatures and keys', () => {
+    const [v] = vectors;
+    const publicKey = BabyJubjub.scalarMult(v!.sk);
+    const signature = EdDSA.sign(v!.sk, v!.message);
+    expect(EdDSA.verify(publicKey, [1n, 2n, 4n], signature)).toBe(false);
+    expect(EdDSA.verify(publicKey, [], signature)).toBe(false);
+    expect(EdDSA.verify(publicKey, v!.message, { ...signature, s: (signature.s + 1n) % BABYJUBJUB_ORDER })).toBe(false);
+    expect(EdDSA.verify(publicKey, v!.message, { ...signature, s: signature.s + BABYJUBJUB_ORDER })).toBe(false);
+    expect(EdDSA.verify(BabyJubjub.scalarMult(v!.sk + 1n), v!.message, signature)).toBe(false);
+    expect(EdDSA.verify([0n, 1n], v!.message, signature)).toBe(false);
+    expect(EdDSA.verify(publicKey, v!.message, { r: [1n, 1n], s: signature.s })).toBe(false);
+  });
+
+  it('rejects malformed packed signatures and invalid inputs', () => {
+    expect(() => EdDSA.unpackSignature(vectors[0]!.packed.slice(0, -2))).toThrow(/length/);
+    expect(() => EdDSA.unpackSignature(`${vectors[0]!.packed.slice(0, 66)}${'ff'.repeat(32)}`)).toThrow(/out of range/);
+    expect(() => EdDSA.sign(0n, [1n])).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+    expect(() => EdDSA.sign(BABYJUBJUB_ORDER, [1n])).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+    expect(() => EdDSA.sign(1n, [BABYJUBJUB_SCALAR_FIELD])).toThrow(/scalar field/);
+  });
+});
@@ -0,0 +1,73 @@
+import { describe, expect, it } from 'vitest';
+import { EncryptedStore } from '../src/store/encryptedStore';
+import { MemoryStore } from '../src/store/memoryStore';
+import type { UtxoRecord } from '../src/types';
+
+const utxo: UtxoRecord = {
+  chainId: 1,
+  assetId: '7',
+  amount: 123n,
+  commitment: '0x01',
+  nullifier: '0x02',
+  mkIndex: 3,
+  isFrozen: false,
+  isSpent: false,
+  memo: '0xdeadbeef',
+};
+
+describe('EncryptedStore', () => {
+  it('stores ciphertext in the inner store and returns plaintext records', async () => {
+    const inner = new MemoryStore();
+    const store = new EncryptedStore(inner, { key: EncryptedStore.deriveKey('encrypted-store-seed') });
+    await store.upsertUtxos([utxo]);
+
+    const raw = (await inner.listUtxos()).rows[0]!;
+    expect(raw.amount).toBe(0n);
+    expect(raw.memo).not.toContain('deadbeef');
+    expect(raw.commitment).toBe('0x01');
+
+    const { rows } = await store.listUtxos({ chainId: 1 });
+    expect(rows).toEqual([utxo]);
+
+    await store.markSpent({ chainId: 1, nullifiers: ['0x02'] });
+    expect((await store.listUtxos({ includeSpent: true })).rows[0]).toMatchObject({ amount: 123n, isSpent: true });
+  });
+
+  it('orders and pages by the plaintext amount', async () => {
+    const store = new EncryptedStore(new MemoryStore(), { key: EncryptedStore.deriveKey('encrypted-store-seed') });
+    await store.upsertUtxos([40n, 7n, 300n, 7n].map((amount, i) => ({ ...utxo, amount, commitment: `0x1${i}`, nullifier: `0x2${i}`, mkIndex: i })));
+
+    const result = await store.listUtxos({ chainId: 1, orderBy: 'amount', order: 'desc', offset: 1, limit: 2 });
+    expect(result.total).toBe(4);
+    expect(result.rows.map((u) => [u.amount, u.mkIndex])).toEqual([
+      [40n, 0],
+      [7n, 3],
+    ]);
+  });
+
+  it('seals operation details', () => {
+    const inner = new MemoryStore();
+    const store = new EncryptedStore(inner, { key: EncryptedStore.deriveKey('encrypted-store-seed') });
+    const created = store.createOperation({ type: 'deposit', chainId: 1, tokenId: '7', detail: { token: 'T', amount: '5' } });
+    expect(created.detail).toEqual({ token: 'T', amount: '5' });
+
+    expect(JSON.stringify(inner.listOperations()[0]!.detail)).not.toContain('"amount"');
+    expect(store.listOperations()[0]!.detail).toEqual({ token: 'T', amount: '5' });
+
+    store.updateOperation(created.id, { status: 'confirmed' });
+    expect(store.listOperations()[0]).toMatchObject({ status: 'confirmed', detail: { token: 'T', amount: '5' } });
+  });
+
+  it('rejects reads with the wrong key', async () => {
+    const inner = new MemoryStore();
+    await new EncryptedStore(inner, { key: EncryptedStore.deriveKey('encrypted-store-seed') }).upsertUtxos([utxo]);
+    const other = new EncryptedStore(inner, { key: EncryptedStore.deriveKey('another-store-seed') });
+    await expect(other.listUtxos()).rejects.toMatchObject({ name: 'SdkError', code: 'STORAGE' });
+  });
+
+  it('only exposes optional methods the inner store implements', () => {
+    const store = new EncryptedStore({} as any, { key: new Uint8Array(32) });
+    expect(store.upsertEntryMemos).toBeUndefined();
+    expect(new EncryptedStore(new MemoryStore(), { key: new Uint8Array(32) }).upsertEntryMemos).toBeTypeOf('function');
+  });
+});
@@ -1,5 +1,7 @@
 import { afterEach, describe, expect, it, vi } from 'vitest';
 import { EntryClient } from '../src/sync/entryClient';
+import { SdkError } from '../src/errors';
+import { signalTimeout } from '../src/utils/signal';
 
 afterEach(() => {
   vi.unstubAllGlobals();
@@ -25,6 +27,22 @@ describe('EntryClient', () => {
     expect(res.items[0]).toMatchObject({ commitment: '0x01', memo: '0x02', cid: 1, created_at: 123 });
   });
 
+  it('getMemo requests a single-item page and returns the matching cid', async () => {
+    const fetchMock = vi.fn(async (url: string) => {
+      const offset = Number(new URL(url).searchParams.get('offset'));
+      const data = offset === 7 ? [{ commitment: '0x07', memo: '0x08', cid: 7, created_at: 1 }] : [];
+      return new Response(JSON.stringify({ code: 0, data: { data, total: 8 } }), { status: 200, headers: { 'content-type': 'application/json' } });
+    });
+    vi.stubGlobal('fetch', fetchMock);
+    const client = new EntryClient('https://entry.example');
+
+    await expect(client.getMemo({ chainId: 1, address: '0xabc', cid: 7 })).resolves.toMatchObject({ commitment: '0x07', cid: 7 });
+    await expect(client.getMemo({ chainId: 1, address: '0xabc', cid: 9 })).resolves.toBeNull();
+    const firstUrl = new URL(fetchMock.mock.calls[0]![0]);
+    expect(firstUrl.searchParams.get('offset')).toBe('7');
+    expect(firstUrl.searchParams.get('limit')).toBe('1');
+  });
+
   it('listMemos throws SdkError(SYNC) on invalid cid', async () => {
     vi.stubGlobal(
       'fetch',
@@ -119,4 +137,147 @@ describe('EntryClient', () => {
       message: 'bad',
     });
   });
+
+  it('listUpdates returns memo and nullifier pages from one request', async () => {
+    const fetchMock = vi.fn(async (_url: string) =>
+      new Response(
+        JSON.stringify({
+          code: 0,
+          data: {
+            memos: { data: [{ commitment: '0x01', memo: '0x02', cid: 4 }], total: '5' },
+            nullifiers: { data: [{ nullifier: '0x0a', created_at: 9 }], total: 3, ready: false },
+          },
+        }),
+        { status: 200, headers: { 'content-type': 'application/json' } },
+      ),
+    );
+    vi.stubGlobal('fetch', fetchMock);
+    const client = new EntryClient('https://entry.example');
+    const res = await client.listUpdates({ chainId: 1, address: '0xabc', memoOffset: 4, nullifierOffset: 2, limit: 10, path: '/v2/updates' });
+    expect(res.memos).toMatchObject({ total: 5, items: [{ commitment: '0x01', cid: 4 }] });
+    expect(res.nullifiers).toEqual({ total: 3, ready: false, items: [{ nullifier: '0x0a', created_at: 9 }] });
+    const url = new URL(fetchMock.mock.calls[0]![0]);
+    expect(url.pathname).toBe('/v2/updates');
+    expect(url.searchParams.get('memo_offset')).toBe('4');
+    expect(url.searchParams.get('nullifier_offset')).toBe('2');
+
+    fetchMock.mockImplementation(async () => new Response(JSON.stringify({ code: 0, data: { memos: { data: [], total: 0 } } }), { status: 200 }));
+    await expect(client.listUpdates({ chainId: 1, address: '0xabc', memoOffset: 0, nullifierOffset: 0, limit: 10, path: '/v2/updates' })).rejects.toMatchObject({ name: 'SdkError', code: 'SYNC' });
+  });
+
+  it('subscribeMemos polls listMemos from the cid after the last delivered memo', async () => {
+    const fetchMock = vi.fn(async (url: string) => {
+      const offset = Number(new URL(url).searchParams.get('offset'));
+      const limit = Number(new URL(url).searchParams.get('limit'));
+      // Replays the row before `offset`, as a lagging replica would.
+      const data = [3, 4, 5].filter((cid) => cid >= offset - 1).slice(0, limit).map((cid) => ({ commitment: `0x0${cid}`, memo: '0x0a', cid }));
+      return new Response(JSON.stringify({ code: 0, data: { data, total: 6 } }), { status: 200 });
+    });
+    vi.stubGlobal('fetch', fetchMock);
+    const controller = new AbortController();
+    const client = new EntryClient('https://entry.example');
+    const stream = client.subscribeMemos({ chainId: 1, address: '0xabc', fromCid: 3, signal: controller.signal, pollMs: 0, pageSize: 2 });
+
+    const cids: Array<number | null | undefined> = [];
+    for (let i = 0; i < 3; i++) cids.push((await stream.next()).value?.cid);
+    expect(cids).toEqual([3, 4, 5]);
+    const offsets = () => fetchMock.mock.calls.map(([url]) => new URL(String(url)).searchParams.get('offset'));
+    expect(offsets()).toEqual(['3', '5']);
+
+    const next = stream.next();
+    await vi.waitFor(() => expect(offsets()).toContain('6'));
+    controller.abort();
+    await expect(next).resolves.toEqual({ done: true, value: undefined });
+  });
+
+  it('subscribeMemos throws SdkError(SYNC) when a page skips cids', async () => {
+    vi.stubGlobal('fetch', vi.fn(async () => new Response(JSON.stringify({ code: 0, data: { data: [{ commitment: '0x05', memo: '0x0a', cid: 5 }], total: 6 } }), { status: 200 })));
+    const client = new EntryClient('https://entry.example');
+    const stream = client.subscribeMemos({ chainId: 1, address: '0xabc', fromCid: 3, pollMs: 0 });
+    await expect(stream.next()).rejects.toMatchObject({ name: 'SdkError', code: 'SYNC', message: 'EntryService memo page skipped cids' });
+  });
+});
+
+describe('EntryClient retry', () => {
+  const ok = () => new Response(JSON.stringify({ code: 0, data: { data: [{ nullifier: '0x0a' }], total: 1, ready: true } }), { status: 200, headers: { 'content-type': 'application/json' } });
+  const status = (code: number) => new Response('unavailable', { status: code });
+
+  it('retries 5xx responses with backoff and succeeds on the third attempt', async () => {
+    const responses = [status(503), status(503), ok()];
+    const fetchMock = vi.fn(async () => responses.shift()!);
+    vi.stubGlobal('fetch', fetchMock);
+    const client = new EntryClient('https://entry.example', undefined, { retry: { attempts: 3, baseDelayMs: 1, maxDelayMs: 2 } });
+
+    const res = await client.listNullifiersByBlock({ chainId: 1, address: '0xabc', offset: 0, limit: 10 });
+    expect(res.items).toEqual([expect.objectContaining({ nullifier: '0x0a' })]);
+    expect(fetchMock).toHaveBeenCalledTimes(3);
+  });
+
+  it('fails 4xx immediately and reports the attempt count once retries run out', async () => {
+    const badRequest = vi.fn(async () => status(400));
+    vi.stubGlobal('fetch', badRequest);
+    const client = new EntryClient('https://entry.example', undefined, { retry: { attempts: 3, baseDelayMs: 0 } });
+    await expect(client.listMemos({ chainId: 1, address: '0xabc', offset: 0, limit: 10 })).rejects.toMatchObject({ code: 'SYNC', detail: { status: 400, attempts: 1 } });
+    expect(badRequest).toHaveBeenCalledTimes(1);
+
+    const unavailable = vi.fn(async () => status(503));
+    vi.stubGlobal('fetch', unavailable);
+    await expect(client.listMemos({ chainId: 1, address: '0xabc', offset: 0, limit: 10 })).rejects.toMatchObject({ code: 'SYNC', detail: { status: 503, attempts: 3 } });
+    expect(unavailable).toHaveBeenCalledTimes(3);
+
+    const offline = vi.fn(async () => {
+      throw new TypeError('fetch failed');
+    });
+    vi.stubGlobal('fetch', offline);
+    await expect(client.listMemos({ chainId: 1, address: '0xabc', offset: 0, limit: 10 })).rejects.toMatchObject({ code: 'SYNC', detail: { attempts: 3 } });
+    expect(offline).toHaveBeenCalledTimes(3);
+  });
+
+  it('reports an expired request timeout as a timeout instead of retrying', async () => {
+    const stalled = vi.fn(
+      (_url: string, init?: RequestInit) =>
+        new Promise<Response>((_resolve, reject) => {
+          init?.signal?.addEventListener('abort', () => reject(init.signal!.reason), { once: true });
+        }),
+    );
+    vi.stubGlobal('fetch', stalled);
+    const client = new EntryClient('https://entry.example', undefined, { retry: { attempts: 3, baseDelayMs: 0 } });
+    const error = await client.listMemos({ chainId: 1, address: '0xabc', offset: 0, limit: 10, signal: signalTimeout(20) }).catch((e) => e);
+    expect(error).toBeInstanceOf(SdkError);
+    expect(error).toMatchObject({ code: 'SYNC', message: 'Entry request timed out', detail: { reason: 'timeout', attempts: 1 } });
+    expect(error.detail.url).toContain('/api/v1/viewing/memos/list');
+    expect(stalled).toHaveBeenCalledTimes(1);
+  });
+});
+
+describe('EntryClient ETag cache', () => {
+  it('sends If-None-Match on a repeated request and treats 304 as no new data', async () => {
+    const sent: Array<string | undefined> = [];
+    const fetchMock = vi.fn(async (_url: string, init?: RequestInit) => {
+      const ifNoneMatch = (init?.headers as Record<string, string> | undefined)?.['If-None-Match'];
+      sent.push(ifNoneMatch);
+      if (ifNoneMatch === '"tail-5"') return new Response(null, { status: 304 });
+      return new Response(JSON.stringify({ code: 0, data: { data: [], total: 5 } }), { status: 200, headers: { 'content-type': 'application/json', etag: '"tail-5"' } });
+    });
+    vi.stubGlobal('fetch', fetchMock);
+    const events: string[] = [];
+    const client = new EntryClient('https://entry.example', (e) => events.push(e.payload.message));
+
+    const first = await client.listMemos({ chainId: 1, address: '0xabc', offset: 5, limit: 10 });
+    const second = await client.listMemos({ chainId: 1, address: '0xabc', offset: 5, limit: 10 });
+    expect(first).toEqual({ items: [], total: 5 });
+    expect(second).toEqual({ items: [], total: 5 });
+    expect(sent).toEqual([undefined, '"tail-5"']);
+    expect(events).toContain('not_modified');
+
+    // Tags are per URL: another cursor is fetched unconditionally.
+    await client.listMemos({ chainId: 1, address: '0xabc', offset: 6, limit: 10 });
+    expect(sent[2]).toBeUndefined();
+  });
+
+  it('fails on a 304 it did not ask for', async () => {
+    vi.stubGlobal('fetch', vi.fn(async () => new Response(null, { status: 304 })));
+    const client = new EntryClient('https://entry.example');
+    await expect(client.listNullifiers({ chainId: 1, address: '0xabc', offset: 0, limit: 10 })).rejects.toMatchObject({ code: 'SYNC', detail: { status: 304 } });
+  });
 });
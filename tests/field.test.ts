@@ -0,0 +1,44 @@
+import { describe, expect, it } from 'vitest';
+import { BN254_FIELD_MODULUS, fieldToHex } from '../src/crypto/field';
+import { Poseidon2 } from '../src/crypto/poseidon2';
+import { BabyJubjub } from '../src/crypto/babyJubjub';
+
+describe('fieldToHex', () => {
+  it('left-pads to 32 big-endian bytes for odd and even digit counts', () => {
+    expect(fieldToHex(0n)).toBe(`0x${'0'.repeat(64)}`);
+    expect(fieldToHex(0xabcn)).toBe(`0x${'0'.repeat(61)}abc`);
+    expect(fieldToHex(0xabcdn)).toBe(`0x${'0'.repeat(60)}abcd`);
+    expect(fieldToHex(1n << 248n)).toBe(`0x01${'0'.repeat(62)}`);
+    expect(BigInt(fieldToHex(BN254_FIELD_MODULUS - 1n))).toBe(BN254_FIELD_MODULUS - 1n);
+  });
+
+  it('rejects values that do not fit in 32 bytes', () => {
+    expect(fieldToHex((1n << 256n) - 1n)).toBe(`0x${'f'.repeat(64)}`);
+    expect(() => fieldToHex(1n << 256n)).toThrow(RangeError);
+    expect(() => fieldToHex(-1n)).toThrow(RangeError);
+  });
+});
+
+describe('byte order', () => {
+  it('compresses BabyJubjub points as little-endian y', () => {
+    const point = BabyJubjub.scalarMult(3n);
+    const compressed = BabyJubjub.compressPoint(point);
+    const y = point[1];
+    expect(compressed[0]).toBe(Number(y & 0xffn));
+    expect(compressed[31]! & 0x7f).toBe(Number((y >> 248n) & 0x7fn));
+  });
+});
+
+describe('Poseidon2 round constants', () => {
+  it('are canonical field elements in the expected layout', () => {
+    const { FULL_ROUND_KEYS_START, PARTIAL_ROUND_KEYS, FULL_ROUND_KEYS_END } = Poseidon2 as unknown as Record<string, readonly (bigint | readonly bigint[])[]>;
+    expect(FULL_ROUND_KEYS_START).toHaveLength(4);
+    expect(PARTIAL_ROUND_KEYS).toHaveLength(56);
+    expect(FULL_ROUND_KEYS_END).toHaveLength(4);
+    const all = [...FULL_ROUND_KEYS_START!, ...PARTIAL_ROUND_KEYS!, ...FULL_ROUND_KEYS_END!].flat();
+    expect(all).toHaveLength(4 * 3 + 56 + 4 * 3);
+    for (const constant of all) {
+      expect(constant >= 0n && constant < BN254_FIELD_MODULUS).toBe(true);
+    }
+  });
+});
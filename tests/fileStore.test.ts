@@ -3,6 +3,18 @@ import { mkdtemp, readFile, rm } from 'node:fs/promises';
 import os from 'node:os';
 import path from 'node:path';
 import { FileStore } from '../src/store/fileStore';
+import type { StoreEvent } from '../src/types';
+
+const utxo = (chainId: number, mkIndex: number, isSpent = false) => ({
+  chainId,
+  assetId: 'T',
+  amount: BigInt(mkIndex),
+  commitment: `0x${mkIndex.toString(16).padStart(2, '0')}` as const,
+  nullifier: `0xf${mkIndex}` as const,
+  mkIndex,
+  isFrozen: false,
+  isSpent,
+});
 
 describe('FileStore', () => {
   it('persists wallet state and operations across instances', async () => {
@@ -110,4 +122,123 @@ describe('FileStore', () => {
       await rm(await Promise.resolve(dir), { recursive: true, force: true });
     }
   });
+
+  it('keeps deposit counters across instances and wallet ids', async () => {
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-filestore-'));
+    try {
+      const owner = '0x00000000000000000000000000000000000000aa' as const;
+      const store1 = new FileStore({ baseDir: dir });
+      await store1.init({ walletId: 'wallet_1' });
+      await expect(Promise.all([store1.reserveDepositCounter(owner), store1.reserveDepositCounter(owner)])).resolves.toEqual([0, 1]);
+      await store1.close();
+
+      const store2 = new FileStore({ baseDir: dir });
+      await store2.init({ walletId: 'wallet_2' });
+      await expect(store2.reserveDepositCounter('0x00000000000000000000000000000000000000AA')).resolves.toBe(2);
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('replaces one chain\'s utxos and persists the swap', async () => {
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-filestore-'));
+    try {
+      const store1 = new FileStore({ baseDir: dir });
+      await store1.init({ walletId: 'wallet_1' });
+      await store1.upsertUtxos([utxo(1, 1), utxo(1, 2), utxo(2, 3)]);
+      await store1.replaceUtxos(1, [utxo(1, 2, true), utxo(1, 4)]);
+      await expect(store1.replaceUtxos(1, [utxo(2, 5)])).rejects.toThrow('belongs to chain 2');
+      await store1.close();
+
+      const store2 = new FileStore({ baseDir: dir });
+      await store2.init({ walletId: 'wallet_1' });
+      const { rows } = await store2.listUtxos({ includeSpent: true });
+      expect(rows.map((u) => [u.chainId, u.mkIndex, u.isSpent])).toEqual([
+        [1, 2, true],
+        [2, 3, false],
+        [1, 4, false],
+      ]);
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('prunes old spent utxos and keeps them spent after a restart and re-sync', async () => {
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-filestore-'));
+    try {
+      const store1 = new FileStore({ baseDir: dir });
+      await store1.init({ walletId: 'wallet_1' });
+      await store1.upsertUtxos([{ ...utxo(1, 1, true), createdAt: 100 }, utxo(1, 2), { ...utxo(1, 3, true), isFrozen: true, createdAt: 100 }, { ...utxo(1, 4, true), createdAt: 500 }]);
+      await expect(store1.pruneSpentUtxos({ chainId: 1, olderThan: 200 })).resolves.toBe(1);
+      await store1.close();
+
+      const store2 = new FileStore({ baseDir: dir });
+      await store2.init({ walletId: 'wallet_1' });
+      expect((await store2.listUtxos({ includeSpent: true, includeFrozen: true })).rows.map((u) => u.mkIndex)).toEqual([2, 3, 4]);
+      await store2.upsertUtxos([utxo(1, 1)]);
+      expect((await store2.listUtxos()).rows.map((u) => u.mkIndex)).toEqual([2]);
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+  });
+  it('keeps a bounded cursor history across instances', async () => {
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-filestore-'));
+    try {
+      const store1 = new FileStore({ baseDir: dir, maxCursorHistory: 2 });
+      await store1.init({ walletId: 'wallet_1' });
+      for (let memo = 1; memo <= 3; memo++) {
+        await store1.appendCursorHistory(1, { memo: memo - 1, nullifier: 0, merkle: 0 }, { memo, nullifier: 0, merkle: 0 }, memo);
+      }
+      await store1.close();
+
+      const store2 = new FileStore({ baseDir: dir, maxCursorHistory: 2 });
+      await store2.init({ walletId: 'wallet_1' });
+      expect((await store2.getCursorHistory(1)).map((entry) => entry.next.memo)).toEqual([2, 3]);
+      expect(await store2.getCursorHistory(1, 1)).toEqual([{ chainId: 1, previous: { memo: 2, nullifier: 0, merkle: 0 }, next: { memo: 3, nullifier: 0, merkle: 0 }, timestamp: 3 }]);
+      expect(await store2.getCursorHistory(2)).toEqual([]);
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('notifies subscribers after persisting', async () => {
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-filestore-'));
+    try {
+      const store = new FileStore({ baseDir: dir });
+      await store.init({ walletId: 'wallet_1' });
+      const events: StoreEvent[] = [];
+      const unsubscribe = store.subscribe((event) => events.push(event));
+      await store.upsertUtxos([utxo(1, 1)]);
+      await store.markSpent({ chainId: 1, nullifiers: ['0xf1'] });
+      await store.markSpent({ chainId: 1, nullifiers: ['0xf1'] });
+      await store.setSyncCursor(1, { memo: 1, nullifier: 1, merkle: 0 });
+      expect(events).toEqual([
+        { type: 'utxosUpserted', chainIds: [1], count: 1 },
+        { type: 'markedSpent', chainId: 1, count: 1 },
+        { type: 'cursorUpdated', chainId: 1, cursor: { memo: 1, nullifier: 1, merkle: 0 } },
+      ]);
+
+      unsubscribe();
+      await store.upsertUtxos([utxo(1, 2)]);
+      expect(events).toHaveLength(3);
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('markSpentDetailed returns only the owned nullifiers that were newly spent', async () => {
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-filestore-'));
+    try {
+      const store = new FileStore({ baseDir: dir });
+      await store.init({ walletId: 'wallet_1' });
+      await store.upsertUtxos([utxo(1, 1), utxo(1, 2), utxo(1, 3), utxo(2, 4)]);
+      await store.markSpent({ chainId: 1, nullifiers: ['0xf3'] });
+      const matched = await store.markSpentDetailed({ chainId: 1, nullifiers: ['0xF1', '0xff', '0xf2', '0xf3', '0xf4'] });
+      expect(matched.sort()).toEqual(['0xF1', '0xf2']);
+      expect((await store.listUtxos({ chainId: 1 })).rows).toHaveLength(0);
+      expect(await store.markSpentDetailed({ chainId: 1, nullifiers: ['0xf1'] })).toEqual([]);
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+  });
 });
@@ -1,4 +1,5 @@
 import { vi } from 'vitest';
+import { RELAYER_ACTION_PATHS } from '../src/tx/relayerRequest';
 
 export const createProofBridgeMock = () => ({
   init: vi.fn(),
@@ -15,3 +16,87 @@ export const createProofBridgeMock = () => ({
 });
 
 export type ProofBridgeMock = ReturnType<typeof createProofBridgeMock>;
+
+/** Chain 1 behind a stubbed Entry at `https://entry.test`, shared by the SyncEngine tests. */
+export const syncTestChain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+
+export const syncTestAssets = { getChains: () => [syncTestChain], getChain: () => syncTestChain } as any;
+
+/**
+ * Minimal wallet for SyncEngine: owns nothing unless `applyMemos` is overridden.
+ */
+export const createSyncTestWallet = (overrides: { applyMemos?: (chainId: number, memos: any[]) => Promise<number> } = {}) =>
+  ({
+    getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+    applyMemos: async () => 0,
+    markSpent: async () => undefined,
+    ...overrides,
+  }) as any;
+
+type MockEntryMemo = { commitment: `0x${string}`; memo: `0x${string}`; cid: number };
+
+/**
+ * In-process Entry + Relayer services behind a `fetch` stub, backed by a scripted dataset.
+ *
+ * Entry serves `memos/list` and `nullifier/list_by_block` from `memos` / `nullifiers`.
+ * The relayer serves `relayer_config`, accepts `POST` on each `RELAYER_ACTION_PATHS` route (`/api/v1/transfer`, `/api/v1/burn`), and "mines" a
+ * submission on its second `txhash` poll: `extra_data` memos are appended with the commitments in
+ * `input.commitments`, and `input.nullifiers` are published.
+ */
+export const createMockServices = (input: { entryUrl: string; relayerUrl: string; relayerConfig: unknown }) => {
+  const memos: MockEntryMemo[] = [];
+  const nullifiers: Array<{ nullifier: `0x${string}` }> = [];
+  const submissions = new Map<string, { body: any; polls: number; txHash?: `0x${string}` }>();
+  const requests: string[] = [];
+  let mined = 0;
+
+  const ok = (data: unknown) => new Response(JSON.stringify({ code: 0, data }), { status: 200, headers: { 'content-type': 'application/json' } });
+  const page = <T>(rows: T[], params: URLSearchParams) => {
+    const offset = Number(params.get('offset') ?? 0);
+    const limit = Number(params.get('limit') ?? rows.length);
+    return ok({ data: rows.slice(offset, offset + limit), total: rows.length });
+  };
+  const hash32 = (n: number) => `0x${n.toString(16).padStart(64, '0')}` as const;
+
+  const addMemo = (memo: Omit<MockEntryMemo, 'cid'>) => {
+    memos.push({ ...memo, cid: memos.length });
+  };
+
+  const mine = (body: any) => {
+    const commitments = body?.input?.commitments as `0x${string}`[];
+    (body.extra_data as `0x${string}`[]).forEach((memo, i) => addMemo({ memo, commitment: commitments[i]! }));
+    for (const nullifier of (body?.input?.nullifiers ?? []) as `0x${string}`[]) nullifiers.push({ nullifier });
+  };
+
+  const fetch = async (resource: string | URL, init?: RequestInit) => {
+    const url = new URL(String(resource));
+    requests.push(`${init?.method ?? 'GET'} ${url.origin}${url.pathname}`);
+    if (url.origin === new URL(input.entryUrl).origin) {
+      if (url.pathname === '/api/v1/viewing/memos/list') return page(memos, url.searchParams);
+      if (url.pathname === '/api/v1/viewing/nullifier/list_by_block') return page(nullifiers, url.searchParams);
+    }
+    if (url.origin === new URL(input.relayerUrl).origin) {
+      if (url.pathname === '/api/v1/relayer_config') {
+        return new Response(JSON.stringify(input.relayerConfig), { status: 200, headers: { 'content-type': 'application/json' } });
+      }
+      if (init?.method === 'POST' && Object.values(RELAYER_ACTION_PATHS).includes(url.pathname)) {
+        const relayerTxHash = hash32(0xa000 + submissions.size);
+        submissions.set(relayerTxHash, { body: JSON.parse(String(init.body)), polls: 0 });
+        return ok(relayerTxHash);
+      }
+      if (url.pathname === '/api/v1/txhash') {
+        const submission = submissions.get(url.searchParams.get('txhash') ?? '');
+        if (!submission) return ok(null);
+        submission.polls++;
+        if (!submission.txHash && submission.polls >= 2) {
+          mine(submission.body);
+          submission.txHash = hash32(0xb000 + mined++);
+        }
+        return ok(submission.txHash ?? null);
+      }
+    }
+    return new Response('not found', { status: 404 });
+  };
+
+  return { fetch, memos, nullifiers, submissions, requests, addMemo };
+};
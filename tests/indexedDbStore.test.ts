@@ -1,5 +1,6 @@
 import { describe, expect, it } from 'vitest';
 import { IndexedDbStore } from '../src/store/indexedDbStore';
+import type { StoreEvent } from '../src/types';
 
 type FakeDbState = Map<
   string,
@@ -167,6 +168,17 @@ function createFakeIndexedDb() {
   return factory as IDBFactory;
 }
 
+const utxo = (chainId: number, mkIndex: number, isSpent = false) => ({
+  chainId,
+  assetId: 'T',
+  amount: BigInt(mkIndex),
+  commitment: `0x${mkIndex.toString(16).padStart(2, '0')}` as const,
+  nullifier: `0xf${mkIndex}` as const,
+  mkIndex,
+  isFrozen: false,
+  isSpent,
+});
+
 describe('IndexedDbStore', () => {
   it('persists wallet state and operations across instances', async () => {
     const indexedDb = createFakeIndexedDb();
@@ -219,4 +231,112 @@ describe('IndexedDbStore', () => {
       { cid: 1, commitment: '0x02' },
     ]);
   });
+
+  it('keeps deposit counters across instances', async () => {
+    const indexedDb = createFakeIndexedDb();
+    const owner = '0x00000000000000000000000000000000000000aa' as const;
+
+    const store1 = new IndexedDbStore({ dbName: 'db_counters', indexedDb });
+    await store1.init({ walletId: 'wallet_1' });
+    await expect(store1.reserveDepositCounter(owner)).resolves.toBe(0);
+    await expect(store1.reserveDepositCounter(owner)).resolves.toBe(1);
+    await store1.close();
+
+    const store2 = new IndexedDbStore({ dbName: 'db_counters', indexedDb });
+    await store2.init({ walletId: 'wallet_2' });
+    await expect(store2.reserveDepositCounter(owner)).resolves.toBe(2);
+  });
+
+  it('replaces one chain\'s utxos in place', async () => {
+    const indexedDb = createFakeIndexedDb();
+    const store1 = new IndexedDbStore({ dbName: 'db_replace', indexedDb });
+    await store1.init({ walletId: 'wallet_1' });
+    await store1.upsertUtxos([utxo(1, 1), utxo(1, 2), utxo(2, 3)]);
+    await store1.replaceUtxos(1, [utxo(1, 2, true), utxo(1, 4)]);
+    await expect(store1.replaceUtxos(1, [utxo(2, 5)])).rejects.toThrow('belongs to chain 2');
+    await store1.close();
+
+    const store2 = new IndexedDbStore({ dbName: 'db_replace', indexedDb });
+    await store2.init({ walletId: 'wallet_1' });
+    const { rows } = await store2.listUtxos({ includeSpent: true });
+    expect(rows.map((u) => [u.chainId, u.mkIndex, u.isSpent])).toEqual([
+      [1, 2, true],
+      [2, 3, false],
+      [1, 4, false],
+    ]);
+  });
+
+  it('prunes old spent utxos and keeps them spent after a restart and re-sync', async () => {
+    const indexedDb = createFakeIndexedDb();
+    const store1 = new IndexedDbStore({ dbName: 'db_prune', indexedDb });
+    await store1.init({ walletId: 'wallet_1' });
+    await store1.upsertUtxos([{ ...utxo(1, 1, true), createdAt: 100 }, utxo(1, 2), { ...utxo(1, 3, true), isFrozen: true, createdAt: 100 }, { ...utxo(1, 4, true), createdAt: 500 }]);
+    await expect(store1.pruneSpentUtxos({ chainId: 1, olderThan: 200 })).resolves.toBe(1);
+    await store1.close();
+
+    const store2 = new IndexedDbStore({ dbName: 'db_prune', indexedDb });
+    await store2.init({ walletId: 'wallet_1' });
+    expect((await store2.listUtxos({ includeSpent: true, includeFrozen: true })).rows.map((u) => u.mkIndex)).toEqual([2, 3, 4]);
+    await store2.upsertUtxos([utxo(1, 1)]);
+    expect((await store2.listUtxos()).rows.map((u) => u.mkIndex)).toEqual([2]);
+  });
+
+  it('sums balances from the persisted utxos', async () => {
+    const indexedDb = createFakeIndexedDb();
+    const store = new IndexedDbStore({ dbName: 'db_balances', indexedDb });
+    await store.init({ walletId: 'wallet_1' });
+    await store.upsertUtxos([utxo(1, 1), { ...utxo(1, 2), isFrozen: true }, utxo(1, 4, true), utxo(2, 8)]);
+
+    await expect(store.getBalances()).resolves.toEqual([
+      { chainId: 1, assetId: 'T', spendable: 1n, frozen: 2n, total: 3n },
+      { chainId: 2, assetId: 'T', spendable: 8n, frozen: 0n, total: 8n },
+    ]);
+    await expect(store.getBalances({ chainId: 2 })).resolves.toEqual([{ chainId: 2, assetId: 'T', spendable: 8n, frozen: 0n, total: 8n }]);
+  });
+  it('keeps a bounded cursor history across instances', async () => {
+    const indexedDb = createFakeIndexedDb();
+    const store1 = new IndexedDbStore({ dbName: 'db_history', indexedDb, maxCursorHistory: 2 });
+    await store1.init({ walletId: 'wallet_1' });
+    for (let memo = 1; memo <= 3; memo++) {
+      await store1.appendCursorHistory(1, { memo: memo - 1, nullifier: 0, merkle: 0 }, { memo, nullifier: 0, merkle: 0 }, memo);
+    }
+    await store1.close();
+
+    const store2 = new IndexedDbStore({ dbName: 'db_history', indexedDb, maxCursorHistory: 2 });
+    await store2.init({ walletId: 'wallet_1' });
+    expect((await store2.getCursorHistory(1)).map((entry) => entry.next.memo)).toEqual([2, 3]);
+    expect(await store2.getCursorHistory(1, 1)).toEqual([{ chainId: 1, previous: { memo: 2, nullifier: 0, merkle: 0 }, next: { memo: 3, nullifier: 0, merkle: 0 }, timestamp: 3 }]);
+    expect(await store2.getCursorHistory(2)).toEqual([]);
+  });
+
+  it('notifies subscribers after persisting', async () => {
+    const store = new IndexedDbStore({ dbName: 'db_events', indexedDb: createFakeIndexedDb() });
+    await store.init({ walletId: 'wallet_1' });
+    const events: StoreEvent[] = [];
+    const unsubscribe = store.subscribe((event) => events.push(event));
+    await store.upsertUtxos([utxo(1, 1)]);
+    await store.markSpent({ chainId: 1, nullifiers: ['0xf1'] });
+    await store.markSpent({ chainId: 1, nullifiers: ['0xf1'] });
+    await store.setSyncCursor(1, { memo: 1, nullifier: 1, merkle: 0 });
+    expect(events).toEqual([
+      { type: 'utxosUpserted', chainIds: [1], count: 1 },
+      { type: 'markedSpent', chainId: 1, count: 1 },
+      { type: 'cursorUpdated', chainId: 1, cursor: { memo: 1, nullifier: 1, merkle: 0 } },
+    ]);
+
+    unsubscribe();
+    await store.upsertUtxos([utxo(1, 2)]);
+    expect(events).toHaveLength(3);
+  });
+
+  it('markSpentDetailed returns only the owned nullifiers that were newly spent', async () => {
+    const store = new IndexedDbStore({ dbName: 'db_detailed', indexedDb: createFakeIndexedDb() });
+    await store.init({ walletId: 'wallet_1' });
+    await store.upsertUtxos([utxo(1, 1), utxo(1, 2), utxo(1, 3), utxo(2, 4)]);
+    await store.markSpent({ chainId: 1, nullifiers: ['0xf3'] });
+    const matched = await store.markSpentDetailed({ chainId: 1, nullifiers: ['0xF1', '0xff', '0xf2', '0xf3', '0xf4'] });
+    expect(matched.sort()).toEqual(['0xF1', '0xf2']);
+    expect((await store.listUtxos({ chainId: 1 })).rows).toHaveLength(0);
+    expect(await store.markSpentDetailed({ chainId: 1, nullifiers: ['0xf1'] })).toEqual([]);
+  });
 });
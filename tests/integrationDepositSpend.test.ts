@@ -0,0 +1,84 @@
+import { describe, expect, it } from 'vitest';
+import { Ops } from '../src/ops/ops';
+import { TxBuilder } from '../src/tx/txBuilder';
+import { MerkleEngine } from '../src/merkle/merkleEngine';
+import { computeMerkleRootFromLeaves, verifyMerkleProof } from '../src/merkle/merkleProof';
+import { MemoryStore } from '../src/store/memoryStore';
+import { KeyManager } from '../src/crypto/keyManager';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { BabyJubjub } from '../src/crypto/babyJubjub';
+import { fieldToHex } from '../src/crypto/field';
+
+const chainId = 1;
+const wrappedErc20 = '0x00000000000000000000000000000000000000e2';
+const viewer = BabyJubjub.scalarMult(5n);
+const freezer = BabyJubjub.scalarMult(7n);
+const poolId = CryptoToolkit.poolId(wrappedErc20, viewer, freezer);
+const token = {
+  id: poolId.toString(),
+  symbol: 'MOCK',
+  decimals: 18,
+  wrappedErc20,
+  viewerPk: [viewer[0].toString(), viewer[1].toString()] as [string, string],
+  freezerPk: [freezer[0].toString(), freezer[1].toString()] as [string, string],
+  depositFeeBps: 0,
+};
+
+describe('integration: deposit to spendable input', () => {
+  it('turns a prepared deposit into an input secret whose proof, commitment and nullifier agree', async () => {
+    const seed = 'integration-deposit-spend-seed';
+    const owner = KeyManager.deriveKeyPair(seed);
+    const assets = {
+      getChain: () => ({ chainId, ocashContractAddress: '0x00000000000000000000000000000000000000c1', tokens: [token] }),
+      getPoolInfo: () => token,
+    } as any;
+    const publicClient = {
+      readContract: async ({ functionName }: any) => {
+        if (functionName === 'depositRelayerFee') return 0n;
+        if (functionName === 'allowance') return 1000n;
+        throw new Error(`unexpected ${functionName}`);
+      },
+    } as any;
+    const ops = new Ops(assets, {} as any, {} as any, {} as any, new TxBuilder(), { markSpent: async () => {} }, undefined, undefined);
+    const deposit = await ops.prepareDeposit({
+      chainId,
+      assetId: token.id,
+      amount: 250n,
+      ownerPublicKey: KeyManager.getPublicKeyBySeed(seed),
+      account: '0x0000000000000000000000000000000000000003',
+      publicClient,
+    });
+
+    // The contract derives the commitment from the deposit call arguments.
+    const [assetId, amount, userPk, blindingFactor] = deposit.depositRequest.args;
+    const commitment = CryptoToolkit.commitment({ asset_id: assetId, asset_amount: amount, user_pk: { user_address: userPk }, blinding_factor: blindingFactor, is_frozen: false }, 'hex');
+    expect(commitment).toBe(CryptoToolkit.commitment(deposit.recordOpening, 'hex'));
+
+    // Insert it among unrelated leaves so the proof crosses a merged 32-leaf batch.
+    const cid = 5;
+    const leaves = Array.from({ length: 33 }, (_, i) => (i === cid ? commitment : fieldToHex(BigInt(i + 1))));
+    const merkle = new MerkleEngine(() => ({}), {} as any, { mode: 'local' }, new MemoryStore());
+    await merkle.ingestEntryMemos(chainId, leaves.map((leaf, i) => ({ cid: i, commitment: leaf })));
+
+    const remote = await merkle.getProofByCid({ chainId, cid, totalElements: BigInt(leaves.length) });
+    expect(BigInt(remote.merkle_root)).toBe(computeMerkleRootFromLeaves(leaves.slice(0, 32)));
+
+    const [secret] = await merkle.buildInputSecretsFromUtxos({
+      remote,
+      utxos: [{ commitment, memo: deposit.memo, mkIndex: cid }],
+      ownerKeyPair: owner,
+      arrayHash: 0n,
+      totalElements: BigInt(leaves.length),
+    });
+    const { ro, acc_member_witness: witness } = secret as any;
+    expect(CryptoToolkit.commitment(ro, 'hex')).toBe(commitment);
+    expect(ro.asset_amount).toBe(250n);
+    expect(witness.index).toBe(cid);
+    expect(BigInt(witness.path[0])).toBe(BigInt(commitment));
+    expect(verifyMerkleProof({ path: witness.path, leafIndex: witness.index }, witness.root)).toBe(true);
+
+    const nullifier = CryptoToolkit.nullifier(owner.user_sk.address_sk, CryptoToolkit.commitment(ro, 'hex'), freezer);
+    expect(nullifier).toBe(CryptoToolkit.nullifier(owner.user_sk.address_sk, commitment, freezer));
+    expect(nullifier).not.toBe(CryptoToolkit.nullifier(owner.user_sk.address_sk, fieldToHex(1n), freezer));
+  });
+});
@@ -0,0 +1,136 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { toHex } from 'viem';
+import { createMockServices } from './helpers';
+import { LedgerInfo } from '../src/ledger/ledgerInfo';
+import { MemoryStore } from '../src/store/memoryStore';
+import { WalletService } from '../src/wallet/walletService';
+import { MerkleEngine } from '../src/merkle/merkleEngine';
+import { getZeroHash } from '../src/merkle/zeroHashes';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { Planner } from '../src/planner/planner';
+import { Ops } from '../src/ops/ops';
+import { TxBuilder } from '../src/tx/txBuilder';
+import { KeyManager } from '../src/crypto/keyManager';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { BabyJubjub } from '../src/crypto/babyJubjub';
+import { MemoKit } from '../src/memo/memoKit';
+import type { CommitmentData, ProofBridge } from '../src/types';
+
+afterEach(() => {
+  vi.unstubAllGlobals();
+});
+
+const chainId = 1;
+const entryUrl = 'https://entry.mock';
+const relayerUrl = 'https://relayer.mock';
+const ocashContractAddress = '0x00000000000000000000000000000000000000c1';
+const relayerAddress = '0x00000000000000000000000000000000000000aa';
+const wrappedErc20 = '0x00000000000000000000000000000000000000e2';
+const viewer = BabyJubjub.scalarMult(5n);
+const freezer = BabyJubjub.scalarMult(7n);
+const poolId = CryptoToolkit.poolId(wrappedErc20, viewer, freezer);
+const token = {
+  id: poolId.toString(),
+  symbol: 'MOCK',
+  decimals: 18,
+  wrappedErc20,
+  viewerPk: [viewer[0].toString(), viewer[1].toString()] as [string, string],
+  freezerPk: [freezer[0].toString(), freezer[1].toString()] as [string, string],
+};
+const relayerFee = 5n;
+const relayerConfig = {
+  config: { contract_address: ocashContractAddress, chain_id: chainId, name: 'mock', relayer_address: relayerAddress },
+  fee_configure: {
+    valid_time: 0,
+    transfer: { [toHex(poolId, { size: 32 })]: { token_address: wrappedErc20, fee: Number(relayerFee) } },
+    withdraw: {},
+  },
+};
+
+const owner = KeyManager.deriveKeyPair('integration-owner-seed');
+const recipient = KeyManager.deriveKeyPair('integration-recipient-seed');
+
+const note = (user: typeof owner, amount: bigint) => {
+  const ro = CryptoToolkit.createRecordOpening({ asset_id: poolId, asset_amount: amount, user_pk: { user_address: user.user_pk.user_address } });
+  return { memo: MemoKit.createMemo(ro), commitment: CryptoToolkit.commitment(ro, 'hex') };
+};
+
+const bridge = {
+  createDummyRecordOpening: async () => CryptoToolkit.createRecordOpening({ asset_id: poolId, asset_amount: 0n, user_pk: { user_address: recipient.user_pk.user_address } }),
+  createDummyInputSecret: async () => ({ dummy: true }),
+} as unknown as ProofBridge;
+
+// Stand-in prover: exposes output commitments and input nullifiers as public inputs, the way the contract derives them.
+const zkp = {
+  proveTransfer: vi.fn(async (witness: any, context: any) => ({
+    proof: Array.from({ length: 8 }, () => '0') as any,
+    flatten_input: [] as string[],
+    public_input: {
+      commitments: (witness.output_record_openings as CommitmentData[]).map((ro) => CryptoToolkit.commitment(ro, 'hex')),
+      nullifiers: (witness.input_secrets as any[])
+        .filter((secret) => secret.ro)
+        .map((secret) => CryptoToolkit.nullifier(secret.owner_keypair.user_sk.address_sk, CryptoToolkit.commitment(secret.ro, 'hex'), freezer)),
+    },
+    ...context,
+  })),
+} as any;
+
+describe('integration: sync, transfer and relayer against mock services', () => {
+  it('spends synced notes through the relayer and picks up the change on the next sync', async () => {
+    const services = createMockServices({ entryUrl, relayerUrl, relayerConfig });
+    vi.stubGlobal('fetch', services.fetch);
+    services.addMemo(note(owner, 60n));
+    services.addMemo(note(recipient, 20n));
+    services.addMemo(note(owner, 50n));
+
+    const assets = new LedgerInfo([{ chainId, entryUrl, relayerUrl, ocashContractAddress, tokens: [token] }]);
+    const store = new MemoryStore();
+    const wallet = new WalletService(assets, store, () => undefined);
+    await wallet.open({ seed: 'integration-owner-seed' });
+    const merkle = new MerkleEngine(() => ({}), bridge, { mode: 'local' }, store);
+    const sync = new SyncEngine(assets, store, wallet, () => undefined, merkle);
+    const planner = new Planner(assets, wallet, bridge);
+    const ops = new Ops(assets, planner, merkle, zkp, new TxBuilder(), wallet, store, undefined);
+    const publicClient = {
+      readContract: vi.fn(async ({ functionName }: any) => {
+        if (functionName === 'getArray') return [1n, 2n, 3n];
+        if (functionName === 'digest') return [0n, 123n];
+        if (functionName === 'totalElements') return BigInt(services.memos.length);
+        if (functionName === 'merkleRoots') return BigInt(getZeroHash(32));
+        throw new Error(`unexpected ${functionName}`);
+      }),
+      waitForTransactionReceipt: vi.fn(async () => ({ status: 'success' })),
+    } as any;
+
+    await sync.syncOnce({ chainIds: [chainId], continueOnError: false });
+    expect(await wallet.getBalance({ chainId, assetId: token.id })).toBe(110n);
+
+    const prepared = await ops.prepareTransfer({
+      chainId,
+      assetId: token.id,
+      amount: 70n,
+      to: KeyManager.userPkToAddress(recipient.user_pk),
+      ownerKeyPair: owner,
+      publicClient,
+    });
+    if (prepared.kind !== 'transfer') throw new Error('expected a direct transfer');
+    expect(prepared.plan.selectedInputs.map((u) => u.mkIndex).sort()).toEqual([0, 2]);
+    expect(prepared.request.path).toBe('/api/v1/transfer');
+
+    const submitted = await ops.submitRelayerRequest<`0x${string}`>({ prepared, publicClient, relayerIntervalMs: 1 });
+    expect(services.submissions.has(submitted.result)).toBe(true);
+    const txHash = await submitted.waitRelayerTxHash;
+    expect(services.submissions.get(submitted.result)?.txHash).toBe(txHash);
+    await submitted.transactionReceipt;
+    expect(store.listOperations()[0]).toMatchObject({ status: 'confirmed', txHash });
+
+    await sync.syncOnce({ chainIds: [chainId], continueOnError: false });
+    expect(await store.getSyncCursor(chainId)).toMatchObject({ memo: 6, nullifier: 2 });
+
+    const { rows } = await wallet.getUtxos({ chainId, includeSpent: true });
+    expect(rows.filter((u) => u.isSpent).map((u) => u.mkIndex).sort()).toEqual([0, 2]);
+    expect(rows.filter((u) => !u.isSpent).map((u) => ({ mkIndex: u.mkIndex, amount: u.amount }))).toEqual([{ mkIndex: 4, amount: 110n - 70n - relayerFee }]);
+    expect(await wallet.getBalance({ chainId, assetId: token.id })).toBe(35n);
+    expect(services.requests).toContain(`POST ${relayerUrl}/api/v1/transfer`);
+  });
+});
@@ -0,0 +1,100 @@
+import { afterEach, describe, expect, it } from 'vitest';
+import { sha256 } from '@noble/hashes/sha256';
+import { bytesToHex } from '@noble/hashes/utils';
+import { KeyManager } from '../src/crypto/keyManager';
+import { BABYJUBJUB_ORDER } from '../src/crypto/babyJubjub';
+
+describe('KeyManager keypair cache', () => {
+  afterEach(() => {
+    KeyManager.configureKeyPairCache({ maxEntries: 0 });
+  });
+
+  it('serves repeated derivations from the cache', () => {
+    KeyManager.configureKeyPairCache({ maxEntries: 4 });
+    const before = KeyManager.getKeyPairCacheStats().derivations;
+    const first = KeyManager.deriveKeyPair('key-manager-cache-seed', '1');
+    const second = KeyManager.deriveKeyPair('key-manager-cache-seed', '1');
+    expect(KeyManager.getKeyPairCacheStats().derivations).toBe(before + 1);
+    expect(second).toEqual(first);
+
+    KeyManager.deriveKeyPair('key-manager-cache-seed', '2');
+    expect(KeyManager.getKeyPairCacheStats().derivations).toBe(before + 2);
+  });
+
+  it('evicts least recently used entries and re-derives after clear', () => {
+    KeyManager.configureKeyPairCache({ maxEntries: 1 });
+    KeyManager.deriveKeyPair('key-manager-cache-seed', 'a');
+    KeyManager.deriveKeyPair('key-manager-cache-seed', 'b');
+    expect(KeyManager.getKeyPairCacheStats().size).toBe(1);
+
+    const before = KeyManager.getKeyPairCacheStats().derivations;
+    KeyManager.deriveKeyPair('key-manager-cache-seed', 'a');
+    expect(KeyManager.getKeyPairCacheStats().derivations).toBe(before + 1);
+
+    KeyManager.clearKeyPairCache();
+    expect(KeyManager.getKeyPairCacheStats().size).toBe(0);
+  });
+
+  it('does not cache when disabled', () => {
+    const before = KeyManager.getKeyPairCacheStats().derivations;
+    KeyManager.getPublicKeyBySeed('key-manager-cache-seed');
+    KeyManager.getPublicKeyBySeed('key-manager-cache-seed');
+    expect(KeyManager.getKeyPairCacheStats()).toEqual({ size: 0, derivations: before + 2 });
+  });
+});
+
+describe('KeyManager nonce rotation', () => {
+  it('increments the numeric suffix of a nonce', () => {
+    expect(KeyManager.nextAccountNonce()).toBe('1');
+    expect(KeyManager.nextAccountNonce('')).toBe('1');
+    expect(KeyManager.nextAccountNonce('9')).toBe('10');
+    expect(KeyManager.nextAccountNonce('acct-7')).toBe('acct-8');
+    expect(KeyManager.nextAccountNonce('savings')).toBe('savings-1');
+  });
+
+  it('keeps the zero-padded width of the numeric suffix', () => {
+    expect(KeyManager.nextAccountNonce('007')).toBe('008');
+    expect(KeyManager.nextAccountNonce('acct-009')).toBe('acct-010');
+    expect(KeyManager.nextAccountNonce('acct-099')).toBe('acct-100');
+    expect(KeyManager.nextAccountNonce('acct-999')).toBe('acct-1000');
+    expect(KeyManager.deriveNext('key-manager-rotation-seed', 'acct-00').keyPair).toEqual(KeyManager.deriveKeyPair('key-manager-rotation-seed', 'acct-01'));
+  });
+
+  it('walks three nonces to distinct, reproducible keypairs', () => {
+    const seed = 'key-manager-rotation-seed';
+    const walk = () => {
+      const steps: Array<{ nonce: string; keyPair: ReturnType<typeof KeyManager.deriveKeyPair> }> = [];
+      let nonce: string | undefined;
+      for (let i = 0; i < 3; i++) {
+        const next = KeyManager.deriveNext(seed, nonce);
+        steps.push(next);
+        nonce = next.nonce;
+      }
+      return steps;
+    };
+    const first = walk();
+    expect(first.map((s) => s.nonce)).toEqual(['1', '2', '3']);
+    expect(first[1]!.keyPair).toEqual(KeyManager.deriveKeyPair(seed, '2'));
+    const addresses = first.map((s) => KeyManager.userPkToAddress(s.keyPair.user_pk));
+    expect(new Set([...addresses, KeyManager.userPkToAddress(KeyManager.deriveKeyPair(seed).user_pk)]).size).toBe(4);
+    expect(walk()).toEqual(first);
+  });
+});
+
+describe('KeyManager.deriveRawOkm', () => {
+  const seed = 'key-manager-okm-vector-seed';
+
+  it('matches the HKDF-SHA256 reference vectors', () => {
+    expect(bytesToHex(KeyManager.deriveRawOkm(seed))).toBe('26f7ff313fc698cbac1117a0729c15fe5d6ce212a8541608106f0f45b0e1bcd1');
+    expect(bytesToHex(KeyManager.deriveRawOkm(seed, '1'))).toBe('1a9aaae2576f912e0d7e988d74a27c4026689bc4ded02f532c83e8bda074a74f');
+    expect(() => KeyManager.deriveRawOkm('short')).toThrow('Seed must be at least 16 characters');
+  });
+
+  it('hashes the OKM bytes, not their hex text, into the secret key', () => {
+    for (const nonce of [undefined, '1']) {
+      const okm = KeyManager.deriveRawOkm(seed, nonce);
+      const expected = BigInt(`0x${bytesToHex(sha256(okm))}`) % BABYJUBJUB_ORDER;
+      expect(KeyManager.deriveKeyPair(seed, nonce).user_sk.address_sk).toBe(expected);
+    }
+  });
+});
@@ -0,0 +1,224 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import nacl from 'tweetnacl';
+import { MAX_MEMO_BYTES_DEFAULT, MemoKit } from '../src/memo/memoKit';
+import { handleDecrypt } from '../src/memo/worker-shim';
+import { KeyManager } from '../src/crypto/keyManager';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { BabyJubjub, BABYJUBJUB_ORDER } from '../src/crypto/babyJubjub';
+import { bytesToHex, hexToBytes } from '@noble/hashes/utils';
+
+describe('MemoKit.decryptAndVerify', () => {
+  const keyPair = KeyManager.deriveKeyPair('memo-kit-verify-seed');
+  const ro = CryptoToolkit.createRecordOpening({
+    asset_id: 1n,
+    asset_amount: 10n,
+    user_pk: { user_address: keyPair.user_pk.user_address },
+    blinding_factor: 42n,
+  });
+  const memo = MemoKit.createMemo(ro);
+
+  it('returns the record opening when the commitment matches', () => {
+    const commitment = CryptoToolkit.commitment(ro, 'hex');
+    expect(MemoKit.decryptAndVerify(keyPair.user_sk.address_sk, memo, commitment)).toEqual(ro);
+  });
+
+  it('returns null when the commitment does not match', () => {
+    const other = CryptoToolkit.commitment({ ...ro, asset_amount: 11n }, 'bigint');
+    expect(MemoKit.decryptAndVerify(keyPair.user_sk.address_sk, memo, other)).toBeNull();
+  });
+
+  it('returns null when the memo cannot be decrypted', () => {
+    const commitment = CryptoToolkit.commitment(ro, 'hex');
+    const stranger = KeyManager.deriveKeyPair('memo-kit-verify-other');
+    expect(MemoKit.decryptAndVerify(stranger.user_sk.address_sk, memo, commitment)).toBeNull();
+  });
+});
+
+describe('MemoKit memo size limit', () => {
+  afterEach(() => {
+    vi.restoreAllMocks();
+  });
+
+  it('rejects oversized memos with MEMO_TOO_LARGE before attempting decryption', () => {
+    const openSpy = vi.spyOn(nacl.secretbox, 'open');
+    const oversized = `0x${'ab'.repeat(MAX_MEMO_BYTES_DEFAULT + 1)}` as const;
+    const tooLarge = expect.objectContaining({ name: 'SdkError', code: 'MEMO_TOO_LARGE', detail: { length: MAX_MEMO_BYTES_DEFAULT + 1, maxMemoBytes: MAX_MEMO_BYTES_DEFAULT } });
+
+    expect(() => MemoKit.decryptMemo(1n, oversized)).toThrow(tooLarge);
+    expect(() => MemoKit.decryptMemoLegacy(1n, oversized)).toThrow(tooLarge);
+    expect(() => MemoKit.decodeMemoForOwner({ secretKey: 1n, memo: oversized })).toThrow(tooLarge);
+    expect(() => MemoKit.decodeMemoForOwner({ secretKey: 1n, memo: oversized, isTransparent: true })).toThrow(tooLarge);
+    expect(openSpy).not.toHaveBeenCalled();
+  });
+
+  it('honours a per-call limit, including inside the worker handler', () => {
+    const keyPair = KeyManager.deriveKeyPair('memo-kit-limit-seed');
+    const ro = CryptoToolkit.createRecordOpening({ asset_id: 1n, asset_amount: 1n, user_pk: { user_address: keyPair.user_pk.user_address } });
+    const memo = MemoKit.createMemo(ro);
+    const secretKey = keyPair.user_sk.address_sk;
+
+    expect(() => MemoKit.decryptMemo(secretKey, memo, { maxMemoBytes: 32 })).toThrow(expect.objectContaining({ code: 'MEMO_TOO_LARGE' }));
+    expect(MemoKit.decryptMemo(secretKey, memo)).toEqual(ro);
+    expect(handleDecrypt({ secretKey: secretKey.toString(), memos: [{ index: 0, memo }], maxMemoBytes: 32 })).toEqual([{ index: 0, error: 'Memo exceeds maximum length' }]);
+    expect(handleDecrypt({ secretKey: secretKey.toString(), memos: [{ index: 0, memo }] })).toEqual([{ index: 0, record: ro }]);
+  });
+});
+
+describe('MemoKit.decryptStream', () => {
+  const owner = KeyManager.deriveKeyPair('memo-kit-stream-owner');
+  const stranger = KeyManager.deriveKeyPair('memo-kit-stream-stranger');
+  const opening = (pk: { user_address: [bigint, bigint] }, amount: bigint) =>
+    CryptoToolkit.createRecordOpening({ asset_id: 1n, asset_amount: amount, user_pk: { user_address: pk.user_address }, blinding_factor: amount + 100n });
+
+  it('yields owned memos in source order and null for the rest', async () => {
+    const mine1 = opening(owner.user_pk, 5n);
+    const mine2 = opening(owner.user_pk, 7n);
+    const memos = [MemoKit.createMemo(mine1), MemoKit.createMemo(opening(stranger.user_pk, 6n)), MemoKit.createMemo(mine2)];
+    let pulled = 0;
+    async function* feed() {
+      for (const memo of memos) {
+        await new Promise((resolve) => setTimeout(resolve, 1));
+        pulled++;
+        yield memo;
+      }
+    }
+
+    const results = [];
+    for await (const ro of MemoKit.decryptStream(owner.user_sk.address_sk, feed(), { concurrency: 2 })) {
+      results.push(ro);
+    }
+    expect(results).toEqual([mine1, null, mine2]);
+    expect(pulled).toBe(3);
+  });
+
+  it('stops reading the source when the consumer breaks early', async () => {
+    const memo = MemoKit.createMemo(opening(owner.user_pk, 9n));
+    let pulled = 0;
+    function* endless() {
+      while (true) {
+        pulled++;
+        yield memo;
+      }
+    }
+
+    for await (const ro of MemoKit.decryptStream(owner.user_sk.address_sk, endless(), { concurrency: 3 })) {
+      expect(ro?.asset_amount).toBe(9n);
+      break;
+    }
+    expect(pulled).toBeLessThanOrEqual(4);
+  });
+});
+
+describe('MemoKit memo ciphers', () => {
+  const keyPair = KeyManager.deriveKeyPair('memo-kit-cipher-seed');
+  const ro = CryptoToolkit.createRecordOpening({
+    asset_id: 2n,
+    asset_amount: 123n,
+    user_pk: { user_address: keyPair.user_pk.user_address },
+    blinding_factor: 77n,
+  });
+  const sk = keyPair.user_sk.address_sk;
+
+  it('round-trips the default XSalsa20-Poly1305 memo in the legacy unprefixed layout', () => {
+    const memo = MemoKit.createMemo(ro);
+    expect((memo.length - 2) / 2).toBe(32 + 160 + 16);
+    expect(MemoKit.decryptMemo(sk, memo)).toEqual(ro);
+  });
+
+  it('round-trips an AES-256-GCM memo tagged with its version byte', () => {
+    const memo = MemoKit.createMemo(ro, undefined, { cipher: 'aes-256-gcm' });
+    expect(memo.slice(0, 4)).toBe('0x01');
+    expect((memo.length - 2) / 2).toBe(1 + 32 + 160 + 16);
+    expect(MemoKit.decryptMemo(sk, memo)).toEqual(ro);
+  });
+
+  it('dispatches on the version so both ciphers decrypt side by side', () => {
+    const legacy = MemoKit.createMemo(ro);
+    const aes = MemoKit.createMemo(ro, undefined, { cipher: 'aes-256-gcm' });
+    expect([legacy, aes].map((memo) => MemoKit.decryptMemo(sk, memo))).toEqual([ro, ro]);
+
+    const unknownVersion = `0x7f${aes.slice(4)}` as const;
+    expect(MemoKit.decryptMemo(sk, unknownVersion)).toBeNull();
+    const aesBodyAsLegacy = `0x${aes.slice(4)}` as const;
+    expect(MemoKit.decryptMemo(sk, aesBodyAsLegacy)).toBeNull();
+  });
+
+  it('rejects unknown version bytes in strict mode and keeps a legacy decode path', () => {
+    const aes = MemoKit.createMemo(ro, undefined, { cipher: 'aes-256-gcm' });
+    expect(MemoKit.decryptMemo(sk, aes, { strict: true })).toEqual(ro);
+
+    const unsupported = `0xff${aes.slice(4)}` as const;
+    expect(MemoKit.decryptMemo(sk, unsupported)).toBeNull();
+    expect(() => MemoKit.decryptMemo(sk, unsupported, { strict: true })).toThrow(expect.objectContaining({ code: 'CRYPTO', message: 'Unsupported memo version', detail: expect.objectContaining({ version: 0xff }) }));
+    expect(() => MemoKit.decryptMemoBytes(sk, hexToBytes(unsupported.slice(2)), { strict: true })).toThrow('Unsupported memo version');
+
+    const legacy = MemoKit.createMemo(ro);
+    expect(MemoKit.decryptMemo(sk, legacy, { strict: true })).toEqual(ro);
+    expect(MemoKit.decryptMemoLegacy(sk, legacy)).toEqual(ro);
+    expect(MemoKit.decryptMemoLegacy(sk, aes)).toBeNull();
+  });
+
+  it('rejects an unknown cipher name with SdkError(CRYPTO)', () => {
+    expect(() => MemoKit.createMemo(ro, undefined, { cipher: 'rot13' as any })).toThrow(
+      expect.objectContaining({ name: 'SdkError', code: 'CRYPTO', message: 'Unknown memo cipher: rot13', detail: { name: 'rot13' } }),
+    );
+  });
+
+  it('treats an undecompressable ephemeral key as not owned instead of throwing', () => {
+    const memo = MemoKit.createMemo(ro);
+    const garbageKey = `0x${'ff'.repeat(32)}${memo.slice(2 + 64)}` as const;
+    expect(BabyJubjub.tryDecompressPoint(hexToBytes('ff'.repeat(32)))).toBeNull();
+    expect(MemoKit.decryptMemo(sk, garbageKey)).toBeNull();
+    expect([memo, garbageKey, memo].map((m) => MemoKit.decryptMemo(sk, m))).toEqual([ro, null, ro]);
+  });
+});
+
+describe('MemoKit byte payloads', () => {
+  const keyPair = KeyManager.deriveKeyPair('memo-kit-bytes-seed');
+  const ro = CryptoToolkit.createRecordOpening({
+    asset_id: 3n,
+    asset_amount: 55n,
+    user_pk: { user_address: keyPair.user_pk.user_address },
+    blinding_factor: 9n,
+  });
+  const sk = keyPair.user_sk.address_sk;
+
+  it('agrees with the hex API on the same memo', () => {
+    for (const cipher of ['xsalsa20-poly1305', 'aes-256-gcm'] as const) {
+      const memo = MemoKit.createMemo(ro, undefined, { cipher });
+      const bytes = hexToBytes(memo.slice(2));
+      expect(MemoKit.decryptMemoBytes(sk, bytes)).toEqual(MemoKit.decryptMemo(sk, memo));
+      expect(MemoKit.decryptMemo(sk, `0x${bytesToHex(MemoKit.createMemoBytes(ro, undefined, { cipher }))}`)).toEqual(ro);
+    }
+    expect(MemoKit.decryptMemoBytes(KeyManager.deriveKeyPair('memo-kit-other-seed').user_sk.address_sk, MemoKit.createMemoBytes(ro))).toBeNull();
+  });
+
+  it('applies the size limit to byte payloads', () => {
+    expect(() => MemoKit.decryptMemoBytes(sk, new Uint8Array(MAX_MEMO_BYTES_DEFAULT + 1))).toThrow(expect.objectContaining({ code: 'MEMO_TOO_LARGE' }));
+  });
+});
+
+describe('MemoKit.createMemoDeterministic', () => {
+  const sender = KeyManager.deriveKeyPair('memo-kit-sender-seed');
+  const owner = KeyManager.deriveKeyPair('memo-kit-deterministic-owner-seed');
+  const ro = CryptoToolkit.createRecordOpening({ asset_id: 3n, asset_amount: 42n, user_pk: { user_address: owner.user_pk.user_address } });
+
+  it('produces identical memos for the same ephemeral key that still decrypt', () => {
+    const eph = MemoKit.deriveMemoEphemeral(sender.user_sk.address_sk, ro);
+    expect(MemoKit.deriveMemoEphemeral(sender.user_sk.address_sk, ro)).toBe(eph);
+    const first = MemoKit.createMemoDeterministic(ro, eph);
+    expect(MemoKit.createMemoDeterministic(ro, eph)).toBe(first);
+    expect(MemoKit.decryptMemo(owner.user_sk.address_sk, first)).toEqual(ro);
+    expect(MemoKit.createMemoDeterministic(ro, eph, { cipher: 'aes-256-gcm' })).toBe(MemoKit.createMemoDeterministic(ro, eph, { cipher: 'aes-256-gcm' }));
+
+    // Other senders and other records get other ephemeral keys; the random path stays random.
+    expect(MemoKit.deriveMemoEphemeral(owner.user_sk.address_sk, ro)).not.toBe(eph);
+    expect(MemoKit.deriveMemoEphemeral(sender.user_sk.address_sk, { ...ro, blinding_factor: ro.blinding_factor + 1n })).not.toBe(eph);
+    expect(MemoKit.createMemo(ro)).not.toBe(MemoKit.createMemo(ro));
+  });
+
+  it('rejects ephemeral keys outside the curve order', () => {
+    expect(() => MemoKit.createMemoDeterministic(ro, 0n)).toThrow('Memo ephemeral secret key out of range');
+    expect(() => MemoKit.createMemoDeterministic(ro, BABYJUBJUB_ORDER)).toThrow('Memo ephemeral secret key out of range');
+  });
+});
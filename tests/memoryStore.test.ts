@@ -1,5 +1,7 @@
 import { describe, expect, it } from 'vitest';
 import { MemoryStore } from '../src/store/memoryStore';
+import { SyncEngine } from '../src/sync/syncEngine';
+import type { StoreEvent } from '../src/types';
 
 describe('MemoryStore', () => {
   it('does not leak state when switching walletId', async () => {
@@ -48,4 +50,217 @@ describe('MemoryStore', () => {
     await store.clearMerkleLeaves?.(1);
     await expect(store.getMerkleLeaves?.(1)).resolves.toBeUndefined();
   });
+
+  it('records mutating calls in order when auditLog is enabled', async () => {
+    (globalThis as any).fetch = async (url: string) => ({
+      ok: true,
+      json: async () => ({
+        code: 0,
+        data: url.includes('/memos/')
+          ? { data: [{ commitment: '0x01', memo: '0x02', cid: 0, created_at: 1 }], total: 1 }
+          : { data: [{ nullifier: '0x03', created_at: 1 }], total: 1 },
+      }),
+    });
+    const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+    const assets = { getChains: () => [chain], getChain: () => chain } as any;
+    const wallet = {
+      getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+      applyMemos: async () => 0,
+      markSpent: async () => undefined,
+    } as any;
+
+    const store = new MemoryStore({ auditLog: true });
+    const engine = new SyncEngine(assets, store, wallet, () => undefined, undefined);
+    await engine.syncOnce({ chainIds: [1], resources: ['memo', 'nullifier'], continueOnError: false });
+
+    const log = store.auditLog();
+    expect(log.map((c) => c.method)).toEqual(['upsertEntryMemos', 'setSyncCursor', 'appendCursorHistory', 'upsertEntryNullifiers', 'setSyncCursor', 'appendCursorHistory']);
+    expect(log[1]!.args).toEqual([1, { memo: 1, nullifier: 0, merkle: 0 }]);
+    expect(log[2]!.args).toEqual([1, { memo: 0, nullifier: 0, merkle: 0 }, { memo: 1, nullifier: 0, merkle: 0 }, expect.any(Number)]);
+    expect(log[4]!.args).toEqual([1, { memo: 1, nullifier: 1, merkle: 0 }]);
+    expect(new MemoryStore().auditLog()).toEqual([]);
+  });
+
+  it('markSpentDetailed returns only the owned nullifiers that were newly spent', async () => {
+    const store = new MemoryStore();
+    const utxo = (n: number, chainId = 1) => ({
+      chainId,
+      assetId: '1',
+      amount: 1n,
+      commitment: `0xc${n}` as const,
+      nullifier: `0xa${n}` as const,
+      mkIndex: n,
+      isFrozen: false,
+      isSpent: false,
+    });
+    await store.upsertUtxos([utxo(1), utxo(2), utxo(3), utxo(4, 2)]);
+    await store.markSpent({ chainId: 1, nullifiers: ['0xa3'] });
+
+    const matched = await store.markSpentDetailed({ chainId: 1, nullifiers: ['0xA1', '0xff', '0xa2', '0xa3', '0xa4'] });
+    expect(matched.sort()).toEqual(['0xA1', '0xa2']);
+    expect((await store.listUtxos({ chainId: 1, includeSpent: false })).rows).toHaveLength(0);
+    expect(await store.markSpentDetailed({ chainId: 1, nullifiers: ['0xa1'] })).toEqual([]);
+  });
+
+  it('marks the same UTXOs spent as a linear nullifier scan on a large store', async () => {
+    const utxo = (n: number) => ({
+      chainId: 1 + (n % 2),
+      assetId: '1',
+      amount: BigInt(n),
+      commitment: `0xc${n.toString(16)}` as `0x${string}`,
+      nullifier: `0x${(n % 700).toString(16).padStart(4, '0')}${n % 3 === 0 ? 'AB' : 'ab'}` as `0x${string}`,
+      mkIndex: n,
+      isFrozen: false,
+      isSpent: n % 11 === 0,
+    });
+    const records = Array.from({ length: 1000 }, (_, n) => utxo(n));
+    const store = new MemoryStore();
+    await store.upsertUtxos(records);
+    // Re-upsert with a new nullifier and drop the tail, so the index must follow both.
+    await store.upsertUtxos([{ ...records[5]!, nullifier: '0xfeed' }]);
+    await store.deleteUtxosFrom({ chainId: 2, fromMkIndex: 900 });
+
+    const reference = new Map((await store.listUtxos({ includeSpent: true })).rows.map((u) => [u.commitment, { ...u }]));
+    const scan = (chainId: number, nullifiers: string[]) => {
+      const wanted = new Set(nullifiers.map((nf) => nf.toLowerCase()));
+      let count = 0;
+      for (const u of reference.values()) {
+        if (u.chainId !== chainId || u.isSpent || !wanted.has(u.nullifier.toLowerCase())) continue;
+        u.isSpent = true;
+        count++;
+      }
+      return count;
+    };
+
+    for (const [chainId, nullifiers] of [
+      [1, Array.from({ length: 300 }, (_, i) => `0x${(i * 2).toString(16).padStart(4, '0')}ab`)],
+      [2, Array.from({ length: 300 }, (_, i) => `0x${(i * 3 + 1).toString(16).padStart(4, '0')}AB`)],
+      [1, ['0x0005ab', '0xfeed', '0xFEED']],
+      [2, ['0x0005ab', '0x0385ab', '0xdead']],
+    ] as Array<[number, `0x${string}`[]]>) {
+      expect(await store.markSpent({ chainId, nullifiers })).toBe(scan(chainId, nullifiers));
+    }
+    const rows = (await store.listUtxos({ includeSpent: true })).rows;
+    expect(rows).toHaveLength(reference.size);
+    for (const row of rows) expect(row.isSpent).toBe(reference.get(row.commitment)!.isSpent);
+  });
+
+  it('orders by amount with mkIndex breaking ties and pages past the end to empty', async () => {
+    const amounts = [5n, 9n, 5n, 1n, 9n, 5n];
+    const store = new MemoryStore();
+    await store.upsertUtxos(
+      amounts.map((amount, mkIndex) => ({ chainId: 1, assetId: '1', amount, commitment: `0xc${mkIndex}`, nullifier: `0xa${mkIndex}`, mkIndex, isFrozen: false, isSpent: false })),
+    );
+    const page = async (query: { order?: 'asc' | 'desc'; offset?: number; limit?: number }) =>
+      (await store.listUtxos({ chainId: 1, orderBy: 'amount', ...query })).rows.map((u) => [u.amount, u.mkIndex]);
+
+    expect(await page({ order: 'desc' })).toEqual([
+      [9n, 4],
+      [9n, 1],
+      [5n, 5],
+      [5n, 2],
+      [5n, 0],
+      [1n, 3],
+    ]);
+    expect(await page({})).toEqual([
+      [1n, 3],
+      [5n, 0],
+      [5n, 2],
+      [5n, 5],
+      [9n, 1],
+      [9n, 4],
+    ]);
+    const pages = [...(await page({ offset: 0, limit: 2 })), ...(await page({ offset: 2, limit: 2 })), ...(await page({ offset: 4, limit: 2 }))];
+    expect(pages).toEqual(await page({}));
+
+    const beyond = await store.listUtxos({ chainId: 1, orderBy: 'amount', offset: 10, limit: 2 });
+    expect(beyond).toEqual({ total: 6, rows: [] });
+  });
+
+  it('prunes old spent UTXOs and leaves unspent and frozen ones untouched', async () => {
+    const utxo = (mkIndex: number, flags: { isSpent?: boolean; isFrozen?: boolean; createdAt?: number; chainId?: number }) => ({
+      chainId: 1,
+      assetId: '1',
+      amount: BigInt(mkIndex + 1),
+      commitment: `0xc${mkIndex}` as `0x${string}`,
+      nullifier: `0xa${mkIndex}` as `0x${string}`,
+      mkIndex,
+      isFrozen: false,
+      isSpent: false,
+      ...flags,
+    });
+    const store = new MemoryStore();
+    await store.upsertUtxos([
+      utxo(0, { isSpent: true, createdAt: 100 }),
+      utxo(1, { createdAt: 100 }),
+      utxo(2, { isSpent: true, isFrozen: true, createdAt: 100 }),
+      utxo(3, { isSpent: true, createdAt: 500 }),
+      utxo(4, { isSpent: true }),
+      utxo(5, { isSpent: true, createdAt: 100, chainId: 2 }),
+    ]);
+    const remaining = async () => (await store.listUtxos({ includeSpent: true, includeFrozen: true })).rows.map((u) => `${u.chainId}:${u.mkIndex}`);
+
+    await expect(store.pruneSpentUtxos({ chainId: 1, olderThan: 200 })).resolves.toBe(1);
+    expect(await remaining()).toEqual(['1:1', '1:2', '1:3', '1:4', '2:5']);
+    await expect(store.pruneSpentUtxos({ chainId: 1 })).resolves.toBe(2);
+    expect(await remaining()).toEqual(['1:1', '1:2', '2:5']);
+    await expect(store.pruneSpentUtxos({ chainId: 1 })).resolves.toBe(0);
+
+    // A pruned coin re-synced from its memo is stored as spent, not revived.
+    await store.upsertUtxos([utxo(0, { createdAt: 100 })]);
+    expect((await store.listUtxos({ chainId: 1 })).rows.map((u) => u.mkIndex)).toEqual([1]);
+    expect((await store.listUtxos({ chainId: 1, includeSpent: true })).rows.find((u) => u.mkIndex === 0)?.isSpent).toBe(true);
+  });
+
+  it('replaces one chain\'s UTXOs without exposing an empty or mixed set to readers', async () => {
+    const utxo = (chainId: number, mkIndex: number, amount: bigint, isSpent = false) => ({
+      chainId,
+      assetId: '1',
+      amount,
+      commitment: `0x${chainId}c${mkIndex}` as `0x${string}`,
+      nullifier: `0x${chainId}a${mkIndex}` as `0x${string}`,
+      mkIndex,
+      isFrozen: false,
+      isSpent,
+    });
+    const store = new MemoryStore();
+    const before = [utxo(1, 0, 10n), utxo(1, 1, 20n)];
+    const after = [utxo(1, 1, 20n, true), utxo(1, 2, 30n), utxo(1, 3, 40n)];
+    await store.upsertUtxos([...before, utxo(2, 0, 5n)]);
+
+    const read = async () => (await store.listUtxos({ chainId: 1, includeSpent: true })).rows.map((u) => u.commitment).join(',');
+    const oldSet = before.map((u) => u.commitment).join(',');
+    const newSet = after.map((u) => u.commitment).join(',');
+    const reads = await Promise.all([read(), read(), store.replaceUtxos(1, after).then(read), read(), read()]);
+    for (const seen of reads) expect([oldSet, newSet]).toContain(seen);
+    expect(reads[2]).toBe(newSet);
+
+    // Spent flags come from the new set, the other chain is untouched, and the nullifier index follows the swap.
+    expect((await store.listUtxos({ chainId: 1 })).rows.map((u) => u.mkIndex)).toEqual([2, 3]);
+    expect((await store.listUtxos({ chainId: 2 })).rows).toHaveLength(1);
+    await expect(store.markSpent({ chainId: 1, nullifiers: ['0x1a0', '0x1a2'] })).resolves.toBe(1);
+    await expect(store.replaceUtxos(1, [utxo(2, 9, 1n)])).rejects.toThrow('belongs to chain 2');
+    expect((await store.listUtxos({ chainId: 1, includeSpent: true })).rows).toHaveLength(3);
+  });
+
+  it('notifies subscribers of upserts, spent markers and cursor updates', async () => {
+    const store = new MemoryStore();
+    const events: StoreEvent[] = [];
+    const unsubscribe = store.subscribe((event) => events.push(event));
+    const utxo = { chainId: 1, assetId: '1', amount: 1n, commitment: '0xc1', nullifier: '0xa1', mkIndex: 0, isFrozen: false, isSpent: false } as const;
+
+    await store.upsertUtxos([utxo]);
+    await store.markSpent({ chainId: 1, nullifiers: ['0xa1'] });
+    await store.markSpent({ chainId: 1, nullifiers: ['0xa1'] });
+    await store.setSyncCursor(1, { memo: 1, nullifier: 1, merkle: 0 });
+    expect(events).toEqual([
+      { type: 'utxosUpserted', chainIds: [1], count: 1 },
+      { type: 'markedSpent', chainId: 1, count: 1 },
+      { type: 'cursorUpdated', chainId: 1, cursor: { memo: 1, nullifier: 1, merkle: 0 } },
+    ]);
+
+    unsubscribe();
+    await store.upsertUtxos([utxo]);
+    expect(events).toHaveLength(3);
+  });
 });
@@ -3,6 +3,10 @@ import { MerkleEngine } from '../src/merkle/merkleEngine';
 import { KeyManager } from '../src/crypto/keyManager';
 import { MemoKit } from '../src/memo/memoKit';
 import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { MemoryStore } from '../src/store/memoryStore';
+import { computeMerkleRootFromLeaves } from '../src/merkle/merkleProof';
+import { getZeroHash } from '../src/merkle/zeroHashes';
+import { Poseidon2, Poseidon2Domain } from '../src/crypto/poseidon2';
 import type { CommitmentData, ProofBridge } from '../src/types';
 
 const bridge: ProofBridge = {
@@ -100,4 +104,173 @@ describe('MerkleEngine', () => {
     expect(out[1]).toMatchObject({ dummy: true });
     expect(out[2]).toMatchObject({ dummy: true });
   });
+
+  it('ingestLeaf produces the same root as a batch ingest', async () => {
+    const memos = Array.from({ length: 1000 }, (_, i) => ({ cid: i, commitment: BigInt(i + 1) }));
+
+    const batchStore = new MemoryStore();
+    const batch = new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, batchStore);
+    await batch.ingestEntryMemos(1, memos);
+
+    const singleStore = new MemoryStore();
+    const single = new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, singleStore);
+    for (const memo of memos) {
+      await single.ingestLeaf(1, memo.cid, memo.commitment);
+    }
+
+    const batchRoot = await batchStore.getLatestChairmanMerkleVersion(1);
+    const singleRoot = await singleStore.getLatestChairmanMerkleVersion(1);
+    expect(singleRoot?.version).toBe(992);
+    expect(singleRoot?.rootHash).toBe(batchRoot?.rootHash);
+    expect(await singleStore.getMerkleLeaves(1)).toHaveLength(1000);
+  });
+
+  it('ingestLeaf rejects a non-contiguous cid in local mode', async () => {
+    const engine = new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, new MemoryStore());
+    await engine.ingestLeaf(1, 0, 1n);
+    await engine.ingestLeaf(1, 0, 1n);
+    await expect(engine.ingestLeaf(1, 2, 3n)).rejects.toMatchObject({ name: 'SdkError', code: 'MERKLE' });
+  });
+
+  it('keeps the root incremental, hashing the same number of nodes per batch whatever the tree size', async () => {
+    const memos = Array.from({ length: 2048 }, (_, i) => ({ cid: i, commitment: BigInt(i + 1) }));
+    const store = new MemoryStore();
+    const engine = new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, store);
+    const hashSpy = vi.spyOn(Poseidon2, 'hashToHex');
+    const hashesPerBatch: number[] = [];
+    for (let start = 0; start < memos.length; start += 32) {
+      hashSpy.mockClear();
+      await engine.ingestEntryMemos(1, memos.slice(start, start + 32));
+      hashesPerBatch.push(hashSpy.mock.calls.length);
+    }
+    hashSpy.mockRestore();
+
+    // A merge hashes its 32-leaf subtree (31 nodes) plus one node per level above it, never the existing leaves.
+    expect(new Set(hashesPerBatch)).toEqual(new Set([31 + 27]));
+    const latest = await store.getLatestChairmanMerkleVersion(1);
+    expect(latest?.version).toBe(2048);
+    expect(BigInt(latest!.rootHash)).toBe(computeMerkleRootFromLeaves(memos.map((m) => m.commitment)));
+  });
+
+  it('setLeaf fills gaps out of order and matches an in-order root once complete', async () => {
+    const memos = Array.from({ length: 64 }, (_, i) => ({ cid: i, commitment: BigInt(i + 1) }));
+    const referenceStore = new MemoryStore();
+    await new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, referenceStore).ingestEntryMemos(1, memos);
+
+    const store = new MemoryStore();
+    const engine = new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, store);
+    await engine.setLeaf(1, 5, memos[5]!.commitment);
+    expect(await store.getMerkleLeaves(1)).toBeUndefined();
+    for (const cid of [0, 1, 2, 3, 4]) await engine.setLeaf(1, cid, memos[cid]!.commitment);
+    expect(await store.getMerkleLeaves(1)).toHaveLength(6);
+    for (let cid = 63; cid >= 6; cid--) await engine.setLeaf(1, cid, memos[cid]!.commitment);
+
+    expect((await store.getLatestChairmanMerkleVersion(1))?.version).toBe(64);
+    expect((await store.getLatestChairmanMerkleVersion(1))?.rootHash).toBe((await referenceStore.getLatestChairmanMerkleVersion(1))?.rootHash);
+    expect(await store.getMerkleLeaves(1)).toHaveLength(64);
+  });
+
+  it('setLeaf rejects conflicting leaves and accepts identical re-sets', async () => {
+    const memos = Array.from({ length: 34 }, (_, i) => ({ cid: i, commitment: BigInt(i + 1) }));
+    const store = new MemoryStore();
+    const engine = new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, store);
+    for (let cid = 0; cid < 33; cid++) await engine.setLeaf(1, cid, memos[cid]!.commitment);
+    await engine.setLeaf(1, 40, 41n);
+    expect((await store.getLatestChairmanMerkleVersion(1))?.version).toBe(32);
+
+    // merged, pending and held indices
+    for (const cid of [3, 32, 40]) {
+      await expect(engine.setLeaf(1, cid, 999n)).rejects.toMatchObject({ code: 'MERKLE' });
+    }
+    await engine.setLeaf(1, 3, memos[3]!.commitment);
+    await engine.setLeaf(1, 32, memos[32]!.commitment);
+    await engine.setLeaf(1, 40, 41n);
+    expect((await store.getMerkleLeaves(1))?.map((l) => l.commitment)).toEqual(memos.slice(0, 33).map((m) => MerkleEngine.normalizeHex32(m.commitment, 'leaf')));
+  });
+
+  it('setLeaf bounds parked leaves and drops them on rollback', async () => {
+    const newEngine = (store: MemoryStore) => new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, store);
+    const capped = newEngine(new MemoryStore());
+    for (let cid = 1; cid <= 4096; cid++) await capped.setLeaf(1, cid, BigInt(cid));
+    await expect(capped.setLeaf(1, 4097, 1n)).rejects.toMatchObject({ code: 'MERKLE' });
+    await expect(capped.setLeaf(1, 4096, 4096n)).resolves.toBeUndefined();
+
+    const rolledStore = new MemoryStore();
+    const rolled = newEngine(rolledStore);
+    await rolled.setLeaf(1, 1, 2n);
+    await rolled.rollback(1);
+    await rolled.setLeaf(1, 0, 1n);
+    expect(await rolledStore.getMerkleLeaves(1)).toHaveLength(1);
+  });
+
+  it('hashes nodes and zero subtrees under the configured domain', async () => {
+    const memos = Array.from({ length: 64 }, (_, i) => ({ cid: i, commitment: BigInt(i + 1) }));
+    const rootFor = async (hashDomain?: bigint) => {
+      const store = new MemoryStore();
+      await new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local', hashDomain }, store).ingestEntryMemos(1, memos);
+      return BigInt((await store.getLatestChairmanMerkleVersion(1))!.rootHash);
+    };
+
+    const merkleRoot = await rootFor();
+    const undomainedRoot = await rootFor(Poseidon2Domain.None);
+    expect(merkleRoot).toBe(await rootFor(Poseidon2Domain.Merkle));
+    expect(undomainedRoot).not.toBe(merkleRoot);
+    expect(merkleRoot).toBe(computeMerkleRootFromLeaves(memos.map((m) => m.commitment)));
+    expect(undomainedRoot).toBe(computeMerkleRootFromLeaves(memos.map((m) => m.commitment), 32, Poseidon2Domain.None));
+
+    expect(getZeroHash(1)).toBe(Poseidon2.hashToHex(0n, 0n, Poseidon2Domain.Merkle));
+    expect(getZeroHash(32, Poseidon2Domain.Merkle)).toBe(getZeroHash(32));
+    expect(getZeroHash(1, Poseidon2Domain.None)).toBe(Poseidon2.hashToHex(0n, 0n, Poseidon2Domain.None));
+    expect(getZeroHash(0, Poseidon2Domain.None)).toBe(getZeroHash(0));
+  });
+
+  it('resumes after a crash between memo download and tree build by appending only missing leaves', async () => {
+    const memos = Array.from({ length: 80 }, (_, i) => ({ cid: i, commitment: BigInt(i + 1) }));
+    const newEngine = (store: MemoryStore) => new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local' }, store);
+
+    const store = new MemoryStore({ auditLog: true });
+    await store.upsertEntryMemos(memos.slice(0, 70).map((m) => ({ chainId: 1, cid: m.cid, commitment: `0x${m.commitment.toString(16).padStart(64, '0')}` as const, memo: '0x00' })));
+    await newEngine(store).ingestEntryMemos(1, memos.slice(0, 40));
+
+    // Restart: memo cursor is 70 but the tree only holds 40 leaves.
+    const resumed = newEngine(store);
+    await expect(resumed.backfillFromEntryMemos(1, 70)).resolves.toBe(30);
+    await expect(resumed.backfillFromEntryMemos(1, 70)).resolves.toBe(0);
+    await resumed.ingestEntryMemos(1, memos.slice(60, 80));
+
+    const appended = store
+      .auditLog()
+      .filter((call) => call.method === 'appendMerkleLeaves')
+      .map((call) => (call.args[1] as Array<{ cid: number }>).map((l) => l.cid));
+    expect(appended).toEqual([memos.slice(0, 40).map((m) => m.cid), memos.slice(40, 70).map((m) => m.cid), memos.slice(70, 80).map((m) => m.cid)]);
+
+    const referenceStore = new MemoryStore();
+    await newEngine(referenceStore).ingestEntryMemos(1, memos);
+    expect((await store.getLatestChairmanMerkleVersion(1))?.rootHash).toBe((await referenceStore.getLatestChairmanMerkleVersion(1))?.rootHash);
+    expect(await store.getMerkleLeaves(1)).toHaveLength(80);
+  });
+
+  it('applies the leaf transform before storing leaves', async () => {
+    const memos = Array.from({ length: 64 }, (_, i) => ({ cid: i, commitment: BigInt(i + 1) }));
+    const hashLeaf = (commitment: bigint) => Poseidon2.hashSequenceWithDomain([commitment], Poseidon2Domain.Merkle);
+    const build = async (leafTransform?: (commitment: bigint) => bigint) => {
+      const store = new MemoryStore();
+      const engine = new MerkleEngine(() => ({ merkleProofUrl: 'https://x.invalid' }), bridge, { mode: 'local', leafTransform }, store);
+      await engine.ingestEntryMemos(1, memos);
+      return { store, engine, root: BigInt((await store.getLatestChairmanMerkleVersion(1))!.rootHash) };
+    };
+
+    const plain = await build();
+    const identity = await build((commitment) => commitment);
+    expect(identity.root).toBe(plain.root);
+    expect(identity.root).toBe(computeMerkleRootFromLeaves(memos.map((m) => m.commitment)));
+
+    const hashed = await build(hashLeaf);
+    expect(hashed.root).not.toBe(plain.root);
+    expect(hashed.root).toBe(computeMerkleRootFromLeaves(memos.map((m) => hashLeaf(m.commitment))));
+    expect(BigInt((await hashed.store.getMerkleLeaf(1, 3))!.commitment)).toBe(hashLeaf(4n));
+    const proof = await hashed.engine.getProofByCid({ chainId: 1, cid: 3, totalElements: 65n });
+    expect(BigInt(proof.proof[0]!.path[0]!)).toBe(hashLeaf(4n));
+    expect(BigInt(proof.merkle_root)).toBe(hashed.root);
+  });
 });
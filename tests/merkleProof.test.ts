@@ -0,0 +1,174 @@
+import { describe, expect, it } from 'vitest';
+import {
+  buildMerkleConsistencyProof,
+  buildMerkleMultiProof,
+  computeMerkleRoot,
+  computeMerkleRootFromLeaves,
+  decodeMerkleProof,
+  encodeMerkleProof,
+  merkleProofFromJson,
+  merkleProofToJson,
+  verifyMerkleConsistency,
+  verifyMerkleMultiProof,
+  verifyMerkleProof,
+  verifyMerkleProofAtDepth,
+  verifyMerkleProofs,
+  verifyMerkleProofTruncated,
+} from '../src/merkle/merkleProof';
+import { getZeroHash, TREE_DEPTH_DEFAULT } from '../src/merkle/zeroHashes';
+import { Poseidon2, Poseidon2Domain } from '../src/crypto/poseidon2';
+
+const hashPair = (l: bigint, r: bigint) => Poseidon2.hashDomain(l, r, Poseidon2Domain.Merkle);
+
+// Depth-2 tree with leaves [11, 22, 33, 0]; proof for leaf index 2 padded to full depth.
+const leaves = [11n, 22n, 33n, 0n];
+const reducedRoot = hashPair(hashPair(leaves[0]!, leaves[1]!), hashPair(leaves[2]!, leaves[3]!));
+const fullPath = [leaves[2]!, leaves[3]!, hashPair(leaves[0]!, leaves[1]!), ...Array.from({ length: TREE_DEPTH_DEFAULT - 2 }, (_, i) => BigInt(getZeroHash(i + 2)))];
+const proof = { path: fullPath, leafIndex: 2 };
+
+describe('merkleProof', () => {
+  it('verifies a full proof at a reduced effective depth', () => {
+    expect(computeMerkleRoot(proof, 2)).toBe(reducedRoot);
+    expect(verifyMerkleProofTruncated(proof, reducedRoot, 2)).toBe(true);
+    expect(verifyMerkleProofTruncated(proof, `0x${reducedRoot.toString(16)}`, 2)).toBe(true);
+  });
+
+  it('verifies the same proof at full depth', () => {
+    const fullRoot = computeMerkleRoot(proof);
+    expect(verifyMerkleProof(proof, fullRoot)).toBe(true);
+    expect(verifyMerkleProofTruncated(proof, fullRoot, TREE_DEPTH_DEFAULT)).toBe(true);
+    expect(verifyMerkleProof(proof, reducedRoot)).toBe(false);
+  });
+
+  it('rejects truncation when the leaf index or upper siblings exceed the effective depth', () => {
+    expect(verifyMerkleProofTruncated({ path: fullPath, leafIndex: 6 }, reducedRoot, 2)).toBe(false);
+    const nonZeroUpper = [...fullPath];
+    nonZeroUpper[4] = 1n;
+    expect(verifyMerkleProofTruncated({ path: nonZeroUpper, leafIndex: 2 }, reducedRoot, 2)).toBe(false);
+    expect(verifyMerkleProofTruncated(proof, reducedRoot, TREE_DEPTH_DEFAULT + 1)).toBe(false);
+  });
+
+  it('verifies a depth-32 proof through the explicit-depth API and rejects mismatched shapes', () => {
+    const fullRoot = computeMerkleRoot(proof);
+    expect(verifyMerkleProofAtDepth(proof, fullRoot, TREE_DEPTH_DEFAULT)).toBe(true);
+    expect(verifyMerkleProofAtDepth(proof, reducedRoot, TREE_DEPTH_DEFAULT)).toBe(false);
+    expect(verifyMerkleProofAtDepth({ path: fullPath.slice(0, 3), leafIndex: 2 }, reducedRoot, 2)).toBe(true);
+
+    expect(() => verifyMerkleProofAtDepth(proof, reducedRoot, 8)).toThrow('merkle proof path has 33 nodes but depth 8 needs 9');
+    expect(() => verifyMerkleProofAtDepth({ path: fullPath.slice(0, 3), leafIndex: 4 }, reducedRoot, 2)).toThrow('does not fit in a depth-2 tree');
+    expect(() => verifyMerkleProofAtDepth(proof, fullRoot, -1)).toThrow('invalid merkle depth');
+  });
+
+  it('reports a result per proof in a batch', () => {
+    const fullRoot = computeMerkleRoot(proof);
+    const siblingProof = { path: [leaves[3]!, ...fullPath.slice(1).map((node, i) => (i === 0 ? leaves[2]! : node))], leafIndex: 3 };
+    const corrupted = { path: fullPath.map((node, i) => (i === 2 ? node + 1n : node)), leafIndex: 2 };
+    expect(verifyMerkleProofs([proof, corrupted, siblingProof], fullRoot)).toEqual([true, false, true]);
+    expect(verifyMerkleProofs([], fullRoot)).toEqual([]);
+  });
+
+  it('computes the full-depth root from leaves consistently with a proof path', () => {
+    expect(computeMerkleRootFromLeaves(leaves.slice(0, 3), 2)).toBe(reducedRoot);
+    expect(computeMerkleRootFromLeaves(leaves.slice(0, 3))).toBe(computeMerkleRoot(proof));
+  });
+
+  it('proves a 5-leaf tree is a prefix of an 8-leaf tree', () => {
+    const newLeaves = Array.from({ length: 8 }, (_, i) => BigInt(100 + i));
+    const oldRoot = computeMerkleRootFromLeaves(newLeaves.slice(0, 5));
+    const newRoot = computeMerkleRootFromLeaves(newLeaves);
+    const consistency = buildMerkleConsistencyProof(newLeaves, 5);
+
+    expect(verifyMerkleConsistency(consistency, oldRoot, newRoot)).toBe(true);
+    expect(verifyMerkleConsistency(consistency, `0x${oldRoot.toString(16)}`, newRoot)).toBe(true);
+    expect(verifyMerkleConsistency(buildMerkleConsistencyProof(newLeaves, 0), computeMerkleRootFromLeaves([]), newRoot)).toBe(true);
+    expect(verifyMerkleConsistency(buildMerkleConsistencyProof(newLeaves, 8), newRoot, newRoot)).toBe(true);
+
+    // A rewritten history (leaf 2 changed) cannot be proven consistent with the old root.
+    const rewritten = [...newLeaves];
+    rewritten[2] = 999n;
+    const rewrittenProof = buildMerkleConsistencyProof(rewritten, 5);
+    expect(verifyMerkleConsistency(rewrittenProof, oldRoot, computeMerkleRootFromLeaves(rewritten))).toBe(false);
+    expect(verifyMerkleConsistency(consistency, oldRoot, computeMerkleRootFromLeaves(rewritten))).toBe(false);
+    expect(verifyMerkleConsistency({ ...consistency, oldSize: 4 }, oldRoot, newRoot)).toBe(false);
+  });
+});
+
+describe('merkle multiproof', () => {
+  const values = Array.from({ length: 10 }, (_, i) => BigInt(i + 100));
+  const root = computeMerkleRootFromLeaves(values);
+  const singleProof = (index: number) => ({ path: [values[index]!, ...buildMerkleMultiProof(values, [index]).siblings], leafIndex: index });
+
+  it('reconstructs the same root as the individual proofs with shared siblings deduplicated', () => {
+    const cids = [7, 0, 1];
+    expect(verifyMerkleProofs(cids.map(singleProof), root)).toEqual([true, true, true]);
+
+    const multi = buildMerkleMultiProof(values, cids);
+    expect(multi.indices).toEqual([0, 1, 7]);
+    expect(multi.leaves).toEqual([values[0], values[1], values[7]]);
+    // Leaf 6, the (0..1) / (6..7) subtree neighbours at level 1, then one sibling per level above.
+    expect(multi.siblings).toHaveLength(1 + 2 + (TREE_DEPTH_DEFAULT - 3));
+    expect(multi.siblings.length).toBeLessThan(cids.length * TREE_DEPTH_DEFAULT);
+    expect(verifyMerkleMultiProof(multi, root)).toBe(true);
+  });
+
+  it('rejects tampered or malformed multiproofs', () => {
+    const multi = buildMerkleMultiProof(values, [0, 1, 7]);
+    expect(verifyMerkleMultiProof({ ...multi, leaves: [values[0]!, values[1]!, 1n] }, root)).toBe(false);
+    expect(verifyMerkleMultiProof({ ...multi, siblings: multi.siblings.slice(1) }, root)).toBe(false);
+    expect(verifyMerkleMultiProof({ ...multi, siblings: [...multi.siblings, 0n] }, root)).toBe(false);
+    expect(verifyMerkleMultiProof({ ...multi, indices: [1, 0, 7] }, root)).toBe(false);
+    expect(() => buildMerkleMultiProof(values, [10])).toThrow();
+  });
+});
+
+describe('merkle proof helpers under a custom hash domain', () => {
+  const domain = Poseidon2Domain.None;
+  const values = [11n, 12n, 13n, 14n, 15n];
+  const root = computeMerkleRootFromLeaves(values, 8, domain);
+  const proofFor = (index: number) => ({ path: [values[index]!, ...buildMerkleMultiProof(values, [index], 8, domain).siblings], leafIndex: index });
+
+  it('verifies single, batched and truncated proofs only under the tree domain', () => {
+    expect(verifyMerkleProofs([proofFor(0), proofFor(4)], root, domain)).toEqual([true, true]);
+    expect(verifyMerkleProofs([proofFor(0)], root)).toEqual([false]);
+    expect(verifyMerkleProofTruncated(proofFor(4), computeMerkleRootFromLeaves(values, 3, domain), 3, domain)).toBe(true);
+    expect(verifyMerkleProofTruncated(proofFor(4), computeMerkleRootFromLeaves(values, 3, domain), 3)).toBe(false);
+  });
+
+  it('builds and verifies multiproofs and consistency proofs in the tree domain', () => {
+    const multi = buildMerkleMultiProof(values, [1, 3], 8, domain);
+    expect(verifyMerkleMultiProof(multi, root, domain)).toBe(true);
+    expect(verifyMerkleMultiProof(multi, root)).toBe(false);
+
+    const consistency = buildMerkleConsistencyProof(values, 3, 8, domain);
+    const oldRoot = computeMerkleRootFromLeaves(values.slice(0, 3), 8, domain);
+    expect(verifyMerkleConsistency(consistency, oldRoot, root, 8, domain)).toBe(true);
+    expect(verifyMerkleConsistency(consistency, oldRoot, root, 8)).toBe(false);
+  });
+});
+
+describe('merkle proof serialization', () => {
+  const fullRoot = computeMerkleRoot(proof);
+
+  it('round-trips through JSON and keeps verifying', () => {
+    const json = merkleProofToJson(proof);
+    expect(json.leafIndex).toBe(2);
+    expect(json.path[0]).toBe(`0x${'0'.repeat(62)}21`);
+    const parsed = merkleProofFromJson(JSON.parse(JSON.stringify(json)));
+    expect(parsed.path).toEqual(fullPath);
+    expect(verifyMerkleProof(parsed, fullRoot)).toBe(true);
+  });
+
+  it('round-trips through the binary form and rejects a wrong length', () => {
+    const bytes = encodeMerkleProof(proof);
+    expect(bytes).toHaveLength(4 + 32 * (TREE_DEPTH_DEFAULT + 1));
+    expect([...bytes.slice(0, 4)]).toEqual([0, 0, 0, 2]);
+    const decoded = decodeMerkleProof(bytes);
+    expect(decoded).toEqual({ leafIndex: 2, path: fullPath });
+    expect(verifyMerkleProof(decoded, fullRoot)).toBe(true);
+    expect(verifyMerkleProof(decodeMerkleProof(encodeMerkleProof({ path: fullPath.slice(0, 3), leafIndex: 2 }), 2), reducedRoot)).toBe(true);
+
+    expect(() => decodeMerkleProof(bytes.slice(0, -1))).toThrow(/expected/);
+    expect(() => decodeMerkleProof(bytes, 2)).toThrow(/expected/);
+    expect(() => encodeMerkleProof({ path: fullPath, leafIndex: 2 ** 32 })).toThrow();
+  });
+});
@@ -2,6 +2,15 @@ import { describe, expect, it } from 'vitest';
 import { Ops } from '../src/ops/ops';
 import { TxBuilder } from '../src/tx/txBuilder';
 import { KeyManager } from '../src/crypto/keyManager';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { MemoKit } from '../src/memo/memoKit';
+import { MemoryStore } from '../src/store/memoryStore';
+import { KeyValueStore } from '../src/store/keyValueStore';
+
+const durableStore = () => {
+  const db = new Map<string, string>();
+  return new KeyValueStore({ client: { get: async (key) => db.get(key) ?? null, set: async (key, value) => void db.set(key, value) } });
+};
 
 describe('Ops.prepareDeposit', () => {
   it('returns approveNeeded for ERC20 when allowance is low', async () => {
@@ -45,3 +54,133 @@ describe('Ops.prepareDeposit', () => {
     expect(res.memo).toBe(res.depositRequest.args[4]);
   });
 });
+
+describe('Ops.prepareDepositBatch', () => {
+  it('prepares one deposit per output in order and tracks allowance across the batch', async () => {
+    const chainId = 1;
+    const assets = {
+      getChain: () => ({ chainId, ocashContractAddress: '0x0000000000000000000000000000000000000001', tokens: [] }),
+      getPoolInfo: () => ({
+        id: '1',
+        symbol: 'T',
+        decimals: 18,
+        wrappedErc20: '0x0000000000000000000000000000000000000002',
+        viewerPk: ['1', '2'],
+        freezerPk: ['3', '4'],
+        depositFeeBps: 0,
+      }),
+    } as any;
+    const reads: string[] = [];
+    const publicClient = {
+      readContract: async ({ functionName }: any) => {
+        reads.push(functionName);
+        if (functionName === 'depositRelayerFee') return 7n;
+        if (functionName === 'allowance') return 120n;
+        throw new Error('unexpected');
+      },
+    } as any;
+
+    const ops = new Ops(assets, {} as any, {} as any, {} as any, new TxBuilder(), { markSpent: async () => {} }, undefined, undefined);
+    const owner = KeyManager.getSecretKeyBySeed('opsDeposit-test-seed', '0');
+    const batch = await ops.prepareDepositBatch({
+      chainId,
+      outputs: [
+        { assetId: '1', amount: 100n },
+        { assetId: '1', amount: 50n },
+        { assetId: '1', amount: 25n },
+      ],
+      ownerPublicKey: { user_pk: owner.user_pk },
+      account: '0x0000000000000000000000000000000000000003',
+      publicClient,
+    });
+
+    expect(batch.map((p) => p.amount)).toEqual([100n, 50n, 25n]);
+    expect(batch.map((p) => p.depositRequest.args[1])).toEqual([100n, 50n, 25n]);
+    for (const prepared of batch) {
+      expect(prepared.depositRequest.args[4]).toBe(prepared.memo);
+      expect(prepared.depositRequest.args[3]).toBe(prepared.recordOpening.blinding_factor);
+      const decrypted = MemoKit.decryptMemo(owner.user_sk.address_sk, prepared.memo);
+      expect(CryptoToolkit.commitment(decrypted!, 'hex')).toBe(CryptoToolkit.commitment(prepared.recordOpening, 'hex'));
+    }
+    expect(new Set(batch.map((p) => CryptoToolkit.commitment(p.recordOpening, 'hex'))).size).toBe(3);
+
+    expect(batch.map((p) => p.approveNeeded)).toEqual([false, true, true]);
+    expect(batch.map((p) => p.approveRequest?.args[1])).toEqual([undefined, 50n, 25n]);
+    expect(reads.filter((name) => name === 'allowance')).toHaveLength(1);
+
+    await expect(ops.prepareDepositBatch({ chainId, outputs: [], ownerPublicKey: { user_pk: owner.user_pk }, account: '0x0000000000000000000000000000000000000003', publicClient })).rejects.toMatchObject({
+      code: 'CONFIG',
+    });
+  });
+
+  it('derives every blinding from a counter reserved in submission order', async () => {
+    const assets = {
+      getChain: () => ({ chainId: 1, ocashContractAddress: '0x0000000000000000000000000000000000000001', tokens: [] }),
+      getPoolInfo: () => ({ id: '1', symbol: 'T', decimals: 18, wrappedErc20: '0x0000000000000000000000000000000000000002', viewerPk: ['1', '2'], freezerPk: ['3', '4'], depositFeeBps: 0 }),
+    } as any;
+    const publicClient = { readContract: async ({ functionName }: any) => (functionName === 'allowance' ? 1000n : 0n) } as any;
+    const ops = new Ops(assets, {} as any, {} as any, {} as any, new TxBuilder(), { markSpent: async () => {} }, durableStore(), undefined);
+    const owner = KeyManager.getSecretKeyBySeed('opsDeposit-test-seed', '0');
+    const base = { chainId: 1, ownerPublicKey: { user_pk: owner.user_pk }, account: '0x0000000000000000000000000000000000000003' as const, publicClient };
+    const outputs = [100n, 50n, 25n].map((amount) => ({ assetId: '1', amount }));
+
+    const batch = await ops.prepareDepositBatch({ ...base, outputs, blindingDerivation: { ownerSecretKey: owner } });
+    expect(batch.map((p) => p.counter)).toEqual([0, 1, 2]);
+    batch.forEach((p, i) => expect(p.recordOpening.blinding_factor).toBe(KeyManager.deriveBlindingFactor(owner.user_sk.address_sk, [BigInt(i)])));
+    expect((await ops.prepareDepositBatch({ ...base, outputs }))[0]!.counter).toBeUndefined();
+
+    const other = KeyManager.getSecretKeyBySeed('opsDeposit-test-seed', '1');
+    await expect(ops.prepareDepositBatch({ ...base, outputs, blindingDerivation: { ownerSecretKey: other } })).rejects.toMatchObject({ code: 'CONFIG' });
+  });
+});
+
+describe('Ops.prepareDepositDeterministic', () => {
+  it('reserves a fresh counter per deposit and derives the blinding from it', async () => {
+    const chainId = 1;
+    const assets = {
+      getChain: () => ({ chainId, ocashContractAddress: '0x0000000000000000000000000000000000000001', tokens: [] }),
+      getPoolInfo: () => ({
+        id: '1',
+        symbol: 'T',
+        decimals: 18,
+        wrappedErc20: '0x0000000000000000000000000000000000000002',
+        viewerPk: ['1', '2'],
+        freezerPk: ['3', '4'],
+        depositFeeBps: 0,
+      }),
+    } as any;
+    const publicClient = {
+      readContract: async ({ functionName }: any) => {
+        if (functionName === 'depositRelayerFee') return 0n;
+        if (functionName === 'allowance') return 1000n;
+        throw new Error('unexpected');
+      },
+    } as any;
+
+    const store = durableStore();
+    const ops = new Ops(assets, {} as any, {} as any, {} as any, new TxBuilder(), { markSpent: async () => {} }, store, undefined);
+    const owner = KeyManager.getSecretKeyBySeed('opsDeposit-test-seed', '0');
+    const deposit = () => ops.prepareDepositDeterministic({ chainId, assetId: '1', amount: 100n, ownerSecretKey: owner, account: '0x0000000000000000000000000000000000000003', publicClient });
+
+    const [first, second] = await Promise.all([deposit(), deposit()]);
+    const third = await deposit();
+
+    expect([first.counter, second.counter, third.counter]).toEqual([0, 1, 2]);
+    expect(first.recordOpening.blinding_factor).toBe(KeyManager.deriveBlindingFactor(owner.user_sk.address_sk, [0n]));
+    expect(third.recordOpening.blinding_factor).toBe(KeyManager.deriveBlindingFactor(owner.user_sk.address_sk, [2n]));
+    expect(first.depositRequest.args[3]).toBe(first.recordOpening.blinding_factor);
+    const commitments = new Set([first, second, third].map((d) => CryptoToolkit.commitment(d.recordOpening, 'hex')));
+    expect(commitments.size).toBe(3);
+    expect(MemoKit.decryptMemo(owner.user_sk.address_sk, second.memo)?.blinding_factor).toBe(second.recordOpening.blinding_factor);
+  });
+
+  it('refuses to run without a store-managed counter', async () => {
+    const owner = KeyManager.getSecretKeyBySeed('opsDeposit-test-seed', '0');
+    for (const store of [undefined, new MemoryStore()]) {
+      const ops = new Ops({} as any, {} as any, {} as any, {} as any, new TxBuilder(), { markSpent: async () => {} }, store, undefined);
+      await expect(
+        ops.prepareDepositDeterministic({ chainId: 1, assetId: '1', amount: 100n, ownerSecretKey: owner, account: '0x0000000000000000000000000000000000000003', publicClient: {} as any }),
+      ).rejects.toMatchObject({ code: 'STORAGE' });
+    }
+  });
+});
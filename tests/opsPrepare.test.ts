@@ -395,4 +395,62 @@ describe('Ops.prepareTransfer / Ops.prepareWithdraw', () => {
     expect((res.request.body as any).gas_drop_value).toBe(gasDropValue.toString());
     expect((res.request.body as any).relayer_fee).toBe(relayerFee.toString());
   });
+
+  it('fetches input proofs from a configured proof source instead of the merkle engine', async () => {
+    const chainId = 1;
+    const utxo = { mkIndex: 10, commitment: '0x01', memo: '0x02', amount: 1000n };
+    const plan = {
+      action: 'withdraw' as const,
+      chainId,
+      assetId: '1',
+      token: { id: '1', wrappedErc20: '0x0000000000000000000000000000000000000002', viewerPk: ['1', '2'], freezerPk: ['3', '4'], withdrawMaxAmount: 0n },
+      requestedAmount: 100n,
+      protocolFee: 0n,
+      relayer: '0x00000000000000000000000000000000000000aa',
+      relayerUrl: 'https://relayer.example',
+      relayerFee: 7n,
+      burnAmount: 107n,
+      gasDropValue: 0n,
+      selectedInput: utxo,
+      outputRecordOpening: {},
+      extraData: '0x01',
+      proofBinding: 'binding',
+      recipient: '0x0000000000000000000000000000000000000006',
+    };
+    const remote = { merkle_root: '999', latest_cid: 63, proof: [{ leaf_index: 10, path: ['0x01'] }] };
+    const proofSource = { getProofByCids: vi.fn(async () => remote) };
+    const merkle = {
+      getProofByCids: vi.fn(async () => Promise.reject(new Error('local tree unavailable'))),
+      currentMerkleRootIndex: vi.fn(() => 1),
+      buildInputSecretsFromUtxos: vi.fn(async () => [{}]),
+    } as any;
+    const zkp = { proveWithdraw: vi.fn(async (_witness: any, context: any) => ({ ...dummyProofBase(), ...context })) } as any;
+    const publicClient = {
+      readContract: vi.fn(async ({ functionName, args }: any) => {
+        if (functionName === 'getArray') return [1n, 2n, 3n];
+        if (functionName === 'digest') return [0n, 123n];
+        if (functionName === 'totalElements') return 5n;
+        if (functionName === 'merkleRoots') return Number(args?.[0]) === 1 ? 999n : 0n;
+        throw new Error(`unexpected ${functionName}`);
+      }),
+    } as any;
+
+    const ops = new Ops(
+      { getChain: () => ({ chainId, ocashContractAddress: '0x0000000000000000000000000000000000000001', relayerUrl: plan.relayerUrl }) } as any,
+      { plan: vi.fn(async () => plan) } as any,
+      merkle,
+      zkp,
+      new TxBuilder(),
+      wallet,
+      undefined,
+      undefined,
+      { proofSource },
+    );
+    const res = await ops.prepareWithdraw({ chainId, assetId: '1', amount: 100n, recipient: plan.recipient as any, ownerKeyPair: {} as any, publicClient });
+
+    expect(proofSource.getProofByCids).toHaveBeenCalledWith({ chainId, cids: [10], totalElements: 5n });
+    expect(merkle.getProofByCids).not.toHaveBeenCalled();
+    expect(merkle.buildInputSecretsFromUtxos).toHaveBeenCalledWith(expect.objectContaining({ remote, utxos: [utxo] }));
+    expect(res.meta.merkleRootIndex).toBe(1);
+  });
 });
@@ -0,0 +1,35 @@
+import { describe, expect, it } from 'vitest';
+import { hydrateWalletState, serializeWalletState } from '../src/store/internal/persistedWalletState';
+
+describe('hydrateWalletState', () => {
+  const utxo = { chainId: 1, assetId: '7', amount: 5n, commitment: '0x01', nullifier: '0x02', mkIndex: 0, isFrozen: false, isSpent: true } as const;
+
+  it('round-trips serialized wallet state', () => {
+    const persisted = JSON.parse(
+      JSON.stringify(
+        serializeWalletState({
+          walletId: 'w',
+          cursors: new Map([[1, { memo: 3, nullifier: 2, merkle: 1 }]]),
+          utxos: new Map([['1:0x01', utxo]]),
+          prunedNullifiers: new Set(['1:0x09']),
+        }),
+      ),
+    );
+    const hydrated = hydrateWalletState(persisted);
+    expect(hydrated.cursors.get(1)).toEqual({ memo: 3, nullifier: 2, merkle: 1 });
+    expect(hydrated.utxos.get('1:0x01')).toEqual(utxo);
+    expect(hydrated.prunedNullifiers).toEqual(new Set(['1:0x09']));
+  });
+
+  it('resets the wallet state when a row does not match the current schema', () => {
+    const hydrated = hydrateWalletState({
+      cursors: { '1': { memo: 3, nullifier: 2, merkle: 1 } },
+      utxos: {
+        good: { ...utxo, amount: '5' },
+        legacy: { chainId: 1, assetId: '7', amount: '5', commitment: '0x03', nullifier: '0x04', mkIndex: 1 } as any,
+      },
+    });
+    expect(hydrated.utxos.size).toBe(0);
+    expect(hydrated.cursors.size).toBe(0);
+  });
+});
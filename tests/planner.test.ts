@@ -1,9 +1,12 @@
 import { describe, expect, it } from 'vitest';
-import { Planner } from '../src/planner/planner';
+import { Planner, minimizeSelectedInputs, planConsolidation, planSplit, requireInputsWithStrategy, selectInputsWithStrategy, selectWithdrawInput, sortOutputsByCommitment, sortUtxosByAmount } from '../src/planner/planner';
 import { MemoryStore } from '../src/store/memoryStore';
 import { WalletService } from '../src/wallet/walletService';
 import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
 import { KeyManager } from '../src/crypto/keyManager';
+import { Ops } from '../src/ops/ops';
+import { TxBuilder } from '../src/tx/txBuilder';
+import { createSeededRandomSource } from '../src/utils/random';
 
 const makeAssets = (input: {
   chainId: number;
@@ -123,6 +126,95 @@ describe('Planner.plan', () => {
     expect(plan.selectedInputs.length).toBeGreaterThan(0);
   });
 
+  it('pads unused transfer output slots with distinct zero-amount dummy records', async () => {
+    const chainId = 1;
+    const token = {
+      id: '1',
+      symbol: 'T',
+      decimals: 18,
+      wrappedErc20: '0x0000000000000000000000000000000000000002' as const,
+      viewerPk: ['1', '2'] as [string, string],
+      freezerPk: ['3', '4'] as [string, string],
+    };
+    const assets = makeAssets({ chainId, token, relayerFee: 0n });
+    const store = new MemoryStore();
+    const wallet = new WalletService(assets as any, store as any, () => undefined);
+    await wallet.open({ seed: 'planner-test-seed-key' });
+    const owner = KeyManager.getPublicKeyBySeed('planner-test-seed-key', '0').user_pk.user_address;
+    const dummies: any[] = [];
+    const bridge = {
+      createDummyRecordOpening: async () => {
+        const ro = CryptoToolkit.createRecordOpening({ asset_id: 1n, asset_amount: 0n, user_pk: { user_address: [owner[0], owner[1]] } });
+        dummies.push(ro);
+        return ro;
+      },
+    } as any;
+    await store.upsertUtxos([
+      { chainId, assetId: token.id, amount: 100n, commitment: '0x01' as any, nullifier: '0x02' as any, mkIndex: 1, isFrozen: false, isSpent: false, memo: '0x03' as any },
+    ]);
+
+    const planner = new Planner(assets as any, wallet as any, bridge);
+    const receiver = KeyManager.userPkToAddress(KeyManager.getPublicKeyBySeed('planner-test-seed-key', '1').user_pk as any);
+    const plan = (await planner.plan({ action: 'transfer', chainId, assetId: token.id, amount: 100n, to: receiver })) as any;
+
+    // No change: one real output, two padded slots.
+    expect(plan.outputs).toHaveLength(3);
+    expect(plan.outputs.slice(1)).toEqual(dummies);
+    expect(plan.outputs.slice(1).map((ro: any) => ro.asset_amount)).toEqual([0n, 0n]);
+    expect(CryptoToolkit.commitment(plan.outputs[1], 'hex')).not.toBe(CryptoToolkit.commitment(plan.outputs[2], 'hex'));
+  });
+
+  it('plans send-max as the largest inputs minus the relayer fee with no change', async () => {
+    const chainId = 1;
+    const token = {
+      id: '1',
+      symbol: 'T',
+      decimals: 18,
+      wrappedErc20: '0x0000000000000000000000000000000000000002' as const,
+      viewerPk: ['1', '2'] as [string, string],
+      freezerPk: ['3', '4'] as [string, string],
+    };
+    const assets = makeAssets({ chainId, token, relayerFee: 5n });
+    const store = new MemoryStore();
+    const wallet = new WalletService(assets as any, store as any, () => undefined);
+    await wallet.open({ seed: 'planner-test-seed-key' });
+    const validUserAddress = KeyManager.getPublicKeyBySeed('planner-test-seed-key', '0').user_pk.user_address;
+    const bridge = {
+      createDummyRecordOpening: async () => CryptoToolkit.createRecordOpening({ asset_id: 1n, asset_amount: 0n, user_pk: { user_address: [validUserAddress[0], validUserAddress[1]] } }),
+    } as any;
+    const utxo = (amount: bigint, i: number) => ({
+      chainId,
+      assetId: token.id,
+      amount,
+      commitment: `0x0${i}` as any,
+      nullifier: `0x1${i}` as any,
+      mkIndex: i,
+      isFrozen: false,
+      isSpent: false,
+      memo: '0x03' as any,
+    });
+    await store.upsertUtxos([utxo(30n, 1), utxo(100n, 2), utxo(10n, 3), utxo(50n, 4)]);
+
+    const planner = new Planner(assets as any, wallet as any, bridge);
+    const receiver = KeyManager.userPkToAddress(KeyManager.getPublicKeyBySeed('planner-test-seed-key', '1').user_pk as any);
+    const plan = await planner.planSendMax({ chainId, assetId: token.id, to: receiver });
+    expect(plan.selectedInputs.map((u) => u.amount)).toEqual([100n, 50n, 30n]);
+    expect(plan.selectedSum).toBe(180n);
+    expect(plan.sendAmount).toBe(180n - 5n);
+    expect(plan.required).toBe(plan.selectedSum);
+    expect(plan.outputs[0].asset_amount).toBe(175n);
+    expect(plan.outputs[1].asset_amount).toBe(0n);
+
+    const single = await planner.planSendMax({ chainId, assetId: token.id, to: receiver, maxInputs: 1 });
+    expect(single.sendAmount).toBe(95n);
+
+    const dust = new MemoryStore();
+    const dustWallet = new WalletService(assets as any, dust as any, () => undefined);
+    await dustWallet.open({ seed: 'planner-test-seed-key' });
+    await dust.upsertUtxos([utxo(5n, 1)]);
+    await expect(new Planner(assets as any, dustWallet as any, bridge).planSendMax({ chainId, assetId: token.id, to: receiver })).rejects.toMatchObject({ code: 'CONFIG' });
+  });
+
   it('rejects withdraw when no single utxo can cover burn amount', async () => {
     const chainId = 1;
     const token = {
@@ -179,4 +271,389 @@ describe('Planner.plan', () => {
       /no single utxo/i,
     );
   });
+
+  it('withdraw keeps input minus amount and fees as change, and reports an empty wallet as insufficient funds', async () => {
+    const chainId = 1;
+    const token = {
+      id: '1',
+      symbol: 'T',
+      decimals: 18,
+      wrappedErc20: '0x0000000000000000000000000000000000000002' as const,
+      viewerPk: ['1', '2'] as [string, string],
+      freezerPk: ['3', '4'] as [string, string],
+      withdrawFeeBps: 100,
+    };
+    const assets = makeAssets({ chainId, token, relayerFee: 3n });
+    const store = new MemoryStore();
+    const wallet = new WalletService(assets as any, store as any, () => undefined);
+    await wallet.open({ seed: 'planner-test-seed-key' });
+    const bridge = { createDummyRecordOpening: async () => ({ asset_amount: 0n }) } as any;
+    const planner = new Planner(assets as any, wallet as any, bridge);
+    const recipient = '0x0000000000000000000000000000000000000003';
+
+    await expect(planner.plan({ action: 'withdraw', chainId, assetId: token.id, amount: 500n, recipient })).rejects.toMatchObject({
+      code: 'CONFIG',
+      message: 'insufficient shielded balance',
+      detail: { reason: 'insufficient_funds', available: '0' },
+    });
+
+    await store.upsertUtxos([
+      { chainId, assetId: token.id, amount: 1000n, commitment: '0x01' as any, nullifier: '0x02' as any, mkIndex: 1, isFrozen: false, isSpent: false, memo: '0x03' as any },
+    ]);
+    const plan = (await planner.plan({ action: 'withdraw', chainId, assetId: token.id, amount: 500n, recipient })) as any;
+    expect(plan.protocolFee).toBeGreaterThan(0n);
+    expect(plan.burnAmount).toBe(500n + plan.relayerFee + plan.protocolFee);
+    expect(plan.outputRecordOpening.asset_amount).toBe(plan.selectedInput.amount - 500n - plan.relayerFee - plan.protocolFee);
+  });
+
+  it('applies the requested selection strategy and explains transfer shortfalls', async () => {
+    const chainId = 1;
+    const token = {
+      id: '1',
+      symbol: 'T',
+      decimals: 18,
+      wrappedErc20: '0x0000000000000000000000000000000000000002' as const,
+      viewerPk: ['1', '2'] as [string, string],
+      freezerPk: ['3', '4'] as [string, string],
+    };
+    const assets = makeAssets({ chainId, token, relayerFee: 0n });
+    const store = new MemoryStore();
+    const wallet = new WalletService(assets as any, store as any, () => undefined);
+    await wallet.open({ seed: 'planner-test-seed-key' });
+    const bridge = { createDummyRecordOpening: async () => ({ asset_amount: 0n }) } as any;
+    const planner = new Planner(assets as any, wallet as any, bridge);
+    const amounts = [120n, 60n, 25n, 40n, 10n];
+    await store.upsertUtxos(
+      amounts.map((amount, i) => ({ chainId, assetId: token.id, amount, commitment: `0x0${i + 1}` as any, nullifier: `0x1${i + 1}` as any, mkIndex: i, isFrozen: false, isSpent: false, memo: '0x03' as any })),
+    );
+
+    const greedy = (await planner.estimate({ chainId, assetId: token.id, action: 'transfer', amount: 100n })) as any;
+    expect(greedy.selectedInputs.map((u: any) => u.amount)).toEqual([120n]);
+    const optimal = (await planner.estimate({ chainId, assetId: token.id, action: 'transfer', amount: 100n, selection: 'optimal' })) as any;
+    expect(optimal.selectedInputs.map((u: any) => u.amount).sort()).toEqual([40n, 60n]);
+
+    const to = '0x0000000000000000000000000000000000000003';
+    await expect(planner.plan({ action: 'transfer', chainId, assetId: token.id, amount: 240n, to })).rejects.toMatchObject({ code: 'CONFIG', detail: { reason: 'too_many_inputs', maxInputs: 3 } });
+    await expect(planner.plan({ action: 'transfer', chainId, assetId: token.id, amount: 240n, to, autoMerge: true })).resolves.toMatchObject({ action: 'transfer-merge' });
+    await expect(planner.plan({ action: 'transfer', chainId, assetId: token.id, amount: 500n, to })).rejects.toMatchObject({ code: 'CONFIG', detail: { reason: 'insufficient_funds', available: '255' } });
+  });
+});
+
+describe('Planner fee config', () => {
+  const chainId = 1;
+  const makeToken = (id: string, withdrawFeeBps?: number) => ({
+    id,
+    symbol: `T${id}`,
+    decimals: 18,
+    wrappedErc20: '0x0000000000000000000000000000000000000002' as const,
+    viewerPk: ['1', '2'] as [string, string],
+    freezerPk: ['3', '4'] as [string, string],
+    withdrawFeeBps,
+  });
+  const tokens = [makeToken('1', 10), makeToken('2')];
+  const relayerConfig = {
+    config: { contract_address: '0x0000000000000000000000000000000000000002', chain_id: chainId, name: 'test', relayer_address: '0x0000000000000000000000000000000000000001' },
+    fee_configure: { valid_time: 0, transfer: {}, withdraw: {} },
+  };
+  const assets = {
+    getChain: () => ({ chainId, tokens }),
+    getPoolInfo: (_chainId: number, tokenId: string) => tokens.find((t) => t.id === tokenId),
+    getRelayerConfig: () => relayerConfig,
+  };
+  const wallet = {
+    getUtxos: async (query: { assetId: string }) => ({
+      total: 1,
+      rows: [{ chainId, assetId: query.assetId, amount: 1_000_000n, commitment: '0x01', nullifier: '0x02', mkIndex: 0, isFrozen: false, isSpent: false }],
+    }),
+  };
+  const fees = {
+    default: { transferRelayerFee: 7n, withdrawRelayerFee: 9n, withdrawFeeBps: 50 },
+    assets: { '1': { transferRelayerFee: 100n, withdrawRelayerFee: 200n } },
+  };
+
+  it('applies the per-asset schedule and falls back to the default for other assets', async () => {
+    const planner = new Planner(assets as any, wallet as any, {} as any, { fees });
+
+    const transfer1 = (await planner.estimate({ chainId, assetId: '1', action: 'transfer', amount: 1000n })) as any;
+    const transfer2 = (await planner.estimate({ chainId, assetId: '2', action: 'transfer', amount: 1000n })) as any;
+    expect(transfer1.relayerFee).toBe(100n);
+    expect(transfer1.required).toBe(1100n);
+    expect(transfer2.relayerFee).toBe(7n);
+    expect(transfer2.required).toBe(1007n);
+
+    const withdraw1 = (await planner.estimate({ chainId, assetId: '1', action: 'withdraw', amount: 10000n })) as any;
+    const withdraw2 = (await planner.estimate({ chainId, assetId: '2', action: 'withdraw', amount: 10000n })) as any;
+    expect(withdraw1.relayerFee).toBe(200n);
+    expect(withdraw1.protocolFee).toBe((10200n * 10n) / 10000n);
+    expect(withdraw2.relayerFee).toBe(9n);
+    expect(withdraw2.protocolFee).toBe((10009n * 50n) / 10000n);
+  });
+
+  it('prefers relayer config fees over the default schedule', async () => {
+    const key = `0x${'0'.repeat(63)}2`;
+    const withTable = {
+      ...relayerConfig,
+      fee_configure: { valid_time: 0, transfer: { [key]: { token_address: '0x0000000000000000000000000000000000000002', fee: 3n } }, withdraw: {} },
+    };
+    const planner = new Planner({ ...assets, getRelayerConfig: () => withTable } as any, wallet as any, {} as any, { fees });
+    const transfer2 = (await planner.estimate({ chainId, assetId: '2', action: 'transfer', amount: 1000n })) as any;
+    expect(transfer2.relayerFee).toBe(3n);
+  });
+
+  it('prefers relayer config fees over a per-asset override', async () => {
+    const key = `0x${'0'.repeat(63)}1`;
+    const withTable = {
+      ...relayerConfig,
+      fee_configure: {
+        valid_time: 0,
+        transfer: { [key]: { token_address: '0x0000000000000000000000000000000000000002', fee: 4n } },
+        withdraw: { [key]: { token_address: '0x0000000000000000000000000000000000000002', fee: 5n } },
+      },
+    };
+    const planner = new Planner({ ...assets, getRelayerConfig: () => withTable } as any, wallet as any, {} as any, { fees });
+    const transfer1 = (await planner.estimate({ chainId, assetId: '1', action: 'transfer', amount: 1000n })) as any;
+    const withdraw1 = (await planner.estimate({ chainId, assetId: '1', action: 'withdraw', amount: 10000n })) as any;
+    expect(transfer1.relayerFee).toBe(4n);
+    expect(withdraw1.relayerFee).toBe(5n);
+  });
+
+  it('prefers the contract withdraw fee bps over a per-asset override', async () => {
+    const overrides = { assets: { '1': { withdrawFeeBps: 99 }, '2': { withdrawFeeBps: 99 } } };
+    const planner = new Planner(assets as any, wallet as any, {} as any, { fees: overrides });
+    const withdraw1 = (await planner.estimate({ chainId, assetId: '1', action: 'withdraw', amount: 10000n })) as any;
+    const withdraw2 = (await planner.estimate({ chainId, assetId: '2', action: 'withdraw', amount: 10000n })) as any;
+    expect(withdraw1.protocolFee).toBe((10000n * 10n) / 10000n);
+    expect(withdraw2.protocolFee).toBe((10000n * 99n) / 10000n);
+  });
+
+  it('plans dust consolidation rounds within the input cap', () => {
+    const utxo = (commitment: string, amount: bigint, assetId = '1') => ({ commitment, amount, assetId, isSpent: false, isFrozen: false }) as any;
+    const dust = Array.from({ length: 10 }, (_, i) => utxo(`0x${(i + 1).toString(16)}`, BigInt(i + 1)));
+    const others = [utxo('0xaa', 1000n), utxo('0xbb', 1n, '2'), { ...utxo('0xcc', 1n), isFrozen: true }];
+
+    const first = planConsolidation([...others, ...dust], '1', { maxInputs: 3, dustThreshold: 100n });
+    expect(first.map((b) => b.selected.map((u) => u.amount))).toEqual([
+      [1n, 2n, 3n],
+      [4n, 5n, 6n],
+      [7n, 8n, 9n],
+    ]);
+    expect(first.map((b) => b.sum)).toEqual([6n, 15n, 24n]);
+
+    // Spend each round's batches as self-transfers until nothing is left to merge.
+    let wallet = [...others, ...dust];
+    const rounds: number[] = [];
+    for (let batches = first; batches.length; batches = planConsolidation(wallet, '1', { maxInputs: 3, dustThreshold: 100n })) {
+      rounds.push(batches.length);
+      const spent = new Set(batches.flatMap((b) => b.selected));
+      wallet = [...wallet.filter((u) => !spent.has(u)), ...batches.map((b, i) => utxo(`0xm${rounds.length}${i}`, b.sum))];
+    }
+    expect(rounds).toEqual([3, 1, 1]);
+    expect(sortUtxosByAmount(wallet.filter((u) => u.assetId === '1' && !u.isFrozen), 'asc').map((u) => u.amount)).toEqual([55n, 1000n]);
+
+    // Without a threshold the large coin joins, and batches removing more UTXOs come first.
+    const mixed = planConsolidation([utxo('0xa', 1n), utxo('0xb', 2n), utxo('0xc', 3n), utxo('0xd', 4n), utxo('0xe', 1000n)], '1');
+    expect(mixed.map((b) => b.selected.length)).toEqual([3, 2]);
+    expect(planConsolidation([utxo('0xa', 1n)], '1')).toEqual([]);
+  });
+
+  it('splits a UTXO into near-equal outputs that sum to the input minus the fee', () => {
+    const coin = { commitment: '0xa', amount: 1000n } as any;
+    const split = planSplit(coin, 3, 5n);
+    expect(split.outputAmounts).toEqual([332n, 332n, 331n]);
+    expect(split.outputAmounts.reduce((acc, cur) => acc + cur, 0n)).toBe(coin.amount - 5n);
+    expect(planSplit(coin, 2, 0n).outputAmounts).toEqual([500n, 500n]);
+
+    expect(() => planSplit(coin, 4, 5n)).toThrow('parts must be an integer between 2 and 3');
+    expect(() => planSplit(coin, 1, 5n)).toThrow('parts must be an integer between 2 and 3');
+    expect(() => planSplit({ ...coin, amount: 7n }, 3, 5n)).toThrow('UTXO is too small to split');
+    expect(() => planSplit(coin, 3, -1n)).toThrow('fee must be non-negative');
+  });
+
+  it('requireInputsWithStrategy reports why a selection cannot cover the amount', () => {
+    const utxo = (commitment: string, amount: bigint, isFrozen = false) => ({ commitment, amount, isFrozen }) as any;
+    const utxos = [utxo('0xa', 40n), utxo('0xb', 30n), utxo('0xc', 20n), utxo('0xd', 10n), utxo('0xe', 500n, true)];
+
+    expect(requireInputsWithStrategy(utxos, 60n)).toEqual(selectInputsWithStrategy(utxos, 60n));
+    expect(() => requireInputsWithStrategy(utxos, 150n)).toThrowError(
+      expect.objectContaining({ code: 'CONFIG', detail: { reason: 'insufficient_funds', available: '100', required: '150' } }),
+    );
+    expect(() => requireInputsWithStrategy(utxos, 95n)).toThrowError(
+      expect.objectContaining({ code: 'CONFIG', detail: { reason: 'too_many_inputs', maxInputs: 3, reachable: '90', available: '100', required: '95' } }),
+    );
+    // Frozen coins only count when they may be spent.
+    expect(requireInputsWithStrategy(utxos, 150n, 'greedy', 3, true).selected.map((u) => u.commitment)).toEqual(['0xe']);
+  });
+
+  it('produces byte-stable deposit and transfer memos with a seeded rng', async () => {
+    const chainId = 1;
+    const seed = 'planner-deterministic-seed';
+    const token = {
+      id: '1',
+      symbol: 'T',
+      decimals: 18,
+      wrappedErc20: '0x0000000000000000000000000000000000000002' as const,
+      viewerPk: ['1', '2'] as [string, string],
+      freezerPk: ['3', '4'] as [string, string],
+    };
+    const assets = makeAssets({ chainId, token, relayerFee: 0n });
+    const depositAssets = { ...assets, getChain: (id: number) => ({ ...assets.getChain(id), ocashContractAddress: '0x0000000000000000000000000000000000000001' }) };
+    const publicClient = {
+      readContract: async ({ functionName }: any) => (functionName === 'depositRelayerFee' ? 0n : 10n ** 30n),
+    } as any;
+    const owner = KeyManager.getPublicKeyBySeed(seed);
+    const bridge = {
+      createDummyRecordOpening: async () =>
+        CryptoToolkit.createRecordOpening({ asset_id: 1n, asset_amount: 0n, user_pk: { user_address: owner.user_pk.user_address }, blinding_factor: 9n }),
+    } as any;
+    const receiver = KeyManager.userPkToAddress(KeyManager.getPublicKeyBySeed(seed, '1').user_pk as any);
+
+    const runFlow = async () => {
+      const rng = createSeededRandomSource('deterministic-flow');
+      const wallet = new WalletService(assets as any, new MemoryStore(), () => undefined);
+      await wallet.open({ seed });
+      const ops = new Ops(depositAssets as any, {} as any, {} as any, {} as any, new TxBuilder(), { markSpent: async () => {} }, undefined, undefined, { rng });
+      const deposit = await ops.prepareDeposit({ chainId, assetId: token.id, amount: 100n, ownerPublicKey: owner, account: '0x0000000000000000000000000000000000000003', publicClient });
+      const applied = await wallet.applyMemos(chainId, [{ memo: deposit.memo, commitment: CryptoToolkit.commitment(deposit.recordOpening, 'hex'), cid: 0 }]);
+      expect(applied).toBe(1);
+
+      const planner = new Planner(assets as any, wallet, bridge, { rng });
+      const plan = (await planner.plan({ action: 'transfer', chainId, assetId: token.id, amount: 60n, to: receiver })) as any;
+      return {
+        depositMemo: deposit.memo,
+        outputs: plan.outputs.map((o: any) => CryptoToolkit.commitment(o, 'hex')),
+        extraData: plan.extraData,
+      };
+    };
+
+    const first = await runFlow();
+    const second = await runFlow();
+    expect(second).toEqual(first);
+    expect(new Set(first.extraData).size).toBe(3);
+  });
+
+  it('orders transfer outputs by commitment and keeps memos paired', async () => {
+    const chainId = 1;
+    const seed = 'planner-output-order-seed';
+    const token = {
+      id: '1',
+      symbol: 'T',
+      decimals: 18,
+      wrappedErc20: '0x0000000000000000000000000000000000000002' as const,
+      viewerPk: ['1', '2'] as [string, string],
+      freezerPk: ['3', '4'] as [string, string],
+    };
+    const assets = makeAssets({ chainId, token, relayerFee: 0n });
+    const owner = KeyManager.getPublicKeyBySeed(seed);
+    const bridge = {
+      createDummyRecordOpening: async () => CryptoToolkit.createRecordOpening({ asset_id: 1n, asset_amount: 0n, user_pk: { user_address: owner.user_pk.user_address }, blinding_factor: 9n }),
+    } as any;
+    const receiver = KeyManager.userPkToAddress(KeyManager.getPublicKeyBySeed(seed, '1').user_pk as any);
+
+    const planWith = async (outputOrder: 'fixed' | 'commitment') => {
+      const store = new MemoryStore();
+      const wallet = new WalletService(assets as any, store, () => undefined);
+      await wallet.open({ seed });
+      await store.upsertUtxos([{ chainId, assetId: token.id, amount: 100n, commitment: '0x01', nullifier: '0x02', mkIndex: 0, isFrozen: false, isSpent: false, memo: '0x03' }]);
+      const planner = new Planner(assets as any, wallet, bridge, { rng: createSeededRandomSource('output-order'), outputOrder });
+      return (await planner.plan({ action: 'transfer', chainId, assetId: token.id, amount: 60n, to: receiver })) as any;
+    };
+    const fixed = await planWith('fixed');
+    const sorted = await planWith('commitment');
+
+    const commitments = sorted.outputs.map((o: any) => CryptoToolkit.commitment(o, 'bigint'));
+    expect([...commitments].sort((a, b) => (a < b ? -1 : a > b ? 1 : 0))).toEqual(commitments);
+    const memoByCommitment = new Map(fixed.outputs.map((o: any, i: number) => [CryptoToolkit.commitment(o, 'hex'), fixed.extraData[i]]));
+    sorted.outputs.forEach((o: any, i: number) => expect(sorted.extraData[i]).toBe(memoByCommitment.get(CryptoToolkit.commitment(o, 'hex'))));
+    expect(new Set(sorted.extraData)).toEqual(new Set(fixed.extraData));
+
+    expect(() => sortOutputsByCommitment(fixed.outputs, fixed.extraData.slice(1))).toThrow(/same length/);
+  });
+});
+
+describe('sortUtxosByAmount', () => {
+  it('matches the legacy comparator and keeps ties stable', () => {
+    const utxos = Array.from({ length: 10_000 }, (_, i) => ({ commitment: `0x${i.toString(16)}`, amount: BigInt((i * 7919) % 257) }) as any);
+    const legacyDesc = [...utxos].sort((a, b) => (b.amount > a.amount ? 1 : b.amount < a.amount ? -1 : 0));
+    const legacyAsc = [...utxos].sort((a, b) => (a.amount > b.amount ? 1 : a.amount < b.amount ? -1 : 0));
+    expect(sortUtxosByAmount(utxos, 'desc')).toEqual(legacyDesc);
+    expect(sortUtxosByAmount(utxos, 'asc')).toEqual(legacyAsc);
+    expect(utxos[0].commitment).toBe('0x0');
+  });
+});
+
+describe('privacy coin selection', () => {
+  it('avoids merging unrelated coins that greedy would combine', () => {
+    const utxo = (commitment: string, amount: bigint, createdAt: number) => ({ commitment, amount, createdAt }) as any;
+    const utxos = [utxo('0xa', 60n, 100), utxo('0xb', 45n, 200), utxo('0xc', 45n, 200), utxo('0xd', 30n, 300)];
+
+    const greedy = selectInputsWithStrategy(utxos, 90n, 'greedy');
+    expect(greedy.selected.map((u) => u.commitment)).toEqual(['0xa', '0xb']);
+    expect(new Set(greedy.selected.map((u) => u.createdAt)).size).toBe(2);
+
+    const privacy = selectInputsWithStrategy(utxos, 90n, 'privacy');
+    expect(privacy.selected.map((u) => u.commitment)).toEqual(['0xb', '0xc']);
+    expect(privacy.sum).toBe(90n);
+
+    // A single covering input wins over a linked pair, despite the larger change.
+    const withLarge = [...utxos, utxo('0xe', 500n, 400)];
+    expect(selectInputsWithStrategy(withLarge, 90n, 'privacy').selected.map((u) => u.commitment)).toEqual(['0xe']);
+    // No single source covers the amount: fall back to greedy.
+    expect(selectInputsWithStrategy(utxos, 120n, 'privacy')).toEqual(selectInputsWithStrategy(utxos, 120n, 'greedy'));
+  });
+});
+
+describe('minimizeSelectedInputs', () => {
+  it('drops inputs the selection does not need', () => {
+    const utxo = (commitment: string, amount: bigint) => ({ commitment, amount }) as any;
+    const naive = [utxo('0xa', 30n), utxo('0xb', 100n)];
+    expect(minimizeSelectedInputs({ selected: naive, sum: 130n }, 90n)).toEqual({ selected: [naive[1]], sum: 100n });
+
+    // Smallest inputs go first, so the largest coin alone is kept.
+    const three = [utxo('0xa', 100n), utxo('0xb', 50n), utxo('0xc', 45n)];
+    expect(minimizeSelectedInputs({ selected: three, sum: 195n }, 90n).selected.map((u) => u.commitment)).toEqual(['0xa']);
+
+    // Already minimal or non-covering selections are left alone.
+    const minimal = { selected: [utxo('0xa', 60n), utxo('0xb', 45n)], sum: 105n };
+    expect(minimizeSelectedInputs(minimal, 90n)).toBe(minimal);
+    const short = { selected: [utxo('0xa', 60n)], sum: 60n };
+    expect(minimizeSelectedInputs(short, 90n)).toBe(short);
+  });
+});
+
+describe('optimal coin selection', () => {
+  it('minimizes change where greedy overshoots', () => {
+    const utxo = (commitment: string, amount: bigint) => ({ commitment, amount }) as any;
+    const utxos = [utxo('0xa', 120n), utxo('0xb', 60n), utxo('0xc', 25n), utxo('0xd', 40n)];
+
+    const greedy = selectInputsWithStrategy(utxos, 100n, 'greedy');
+    expect(greedy.selected.map((u) => u.commitment)).toEqual(['0xa']);
+    expect(greedy.sum - 100n).toBe(20n);
+
+    const optimal = selectInputsWithStrategy(utxos, 100n, 'optimal');
+    expect(optimal.selected.map((u) => u.commitment)).toEqual(['0xb', '0xd']);
+    expect(optimal.sum).toBe(100n);
+
+    // Equal change: fewer inputs win.
+    expect(selectInputsWithStrategy([...utxos, utxo('0xe', 100n)], 100n, 'optimal').selected.map((u) => u.commitment)).toEqual(['0xe']);
+    // Nothing within the input cap covers the amount: fall back to greedy.
+    expect(selectInputsWithStrategy(utxos, 230n, 'optimal', 2)).toEqual(selectInputsWithStrategy(utxos, 230n, 'greedy', 2));
+  });
+});
+
+describe('frozen utxos', () => {
+  const utxo = (amount: bigint, mkIndex: number, isFrozen: boolean) =>
+    ({ chainId: 1, assetId: '1', amount, commitment: '0x01', nullifier: '0x02', mkIndex, isFrozen, isSpent: false }) as any;
+  const utxos = [utxo(100n, 0, true), utxo(10n, 1, false), utxo(5n, 2, false)];
+
+  it('are never selected by default, even if the store returns them', () => {
+    expect(selectInputsWithStrategy(utxos, 12n).selected.map((u) => u.mkIndex)).toEqual([1, 2]);
+    expect(selectInputsWithStrategy(utxos, 50n).sum).toBe(15n);
+    expect(selectWithdrawInput(utxos, 8n)?.mkIndex).toBe(1);
+    expect(selectWithdrawInput(utxos, 50n)).toBeNull();
+  });
+
+  it('are selectable with includeFrozen', () => {
+    expect(selectInputsWithStrategy(utxos, 50n, 'greedy', 3, true).selected.map((u) => u.mkIndex)).toEqual([0]);
+    expect(selectWithdrawInput(utxos, 50n, true)?.mkIndex).toBe(0);
+  });
 });
@@ -0,0 +1,79 @@
+import { describe, expect, it } from 'vitest';
+import { Poseidon2, Poseidon2Domain, poseidon2DomainFromName } from '../src/crypto/poseidon2';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { BN254_FIELD_MODULUS, fieldToHex } from '../src/crypto/field';
+
+describe('Poseidon2', () => {
+  it('resolves domains by name', () => {
+    expect(poseidon2DomainFromName('Record')).toBe(Poseidon2Domain.Record);
+    expect(poseidon2DomainFromName('merkle')).toBe(Poseidon2Domain.Merkle);
+    expect(() => poseidon2DomainFromName('nope')).toThrow(/Unknown Poseidon2 domain/);
+  });
+
+  it('reproduces the record commitment sequence by domain name', () => {
+    const record = { asset_id: 7n, asset_amount: 100n, user_pk: { user_address: [1n, 2n] as [bigint, bigint] }, blinding_factor: 3n, is_frozen: false };
+    const byName = Poseidon2.hashSequenceNamed([1n, 2n, 3n, 7n, 100n], 'record');
+    expect(byName).toBe(CryptoToolkit.commitment(record, 'bigint'));
+  });
+
+  it('matches the seeded zero-length folding', () => {
+    expect(Poseidon2.hashSequenceNamed([], 'array', 5n)).toBe(5n);
+    expect(() => Poseidon2.hashSequenceNamed([], 'array')).toThrow();
+  });
+
+  it('hashes any number of fields with one canonical folding', () => {
+    const d = Poseidon2Domain.Record;
+    expect(Poseidon2.hashFields([9n], d)).toBe(Poseidon2.hashDomain(0n, 9n, d));
+    expect(Poseidon2.hashFields([1n, 2n], d)).toBe(Poseidon2.hashDomain(1n, 2n, d));
+    expect(Poseidon2.hashFields([1n, 2n, 3n, 7n], d)).toBe(Poseidon2.hashDomain(Poseidon2.hashDomain(Poseidon2.hashDomain(1n, 2n, d), 3n, d), 7n, d));
+    for (const inputs of [[9n], [1n, 2n], [1n, 2n, 3n, 7n]]) {
+      expect(Poseidon2.hashFields(inputs, d)).toBe(Poseidon2.hashSequenceWithDomain(inputs, d));
+    }
+    expect(Poseidon2.hashFields([1n, 2n], Poseidon2Domain.None)).toBe(Poseidon2.hash(1n, 2n));
+    expect(() => Poseidon2.hashFields([], d)).toThrow();
+  });
+
+  it('exposes the full permutation state and a 3-to-1 hash', () => {
+    for (const [a, b, d] of [
+      [1n, 2n, Poseidon2Domain.None],
+      [3n, 4n, Poseidon2Domain.Merkle],
+      [0n, 0n, Poseidon2Domain.Merkle],
+    ]) {
+      const state = Poseidon2.permute(a, b, d);
+      expect(state).toHaveLength(3);
+      expect(state[0]).toBe(Poseidon2.hashDomain(a, b, d));
+      expect(Poseidon2.hash3(a, b, d)).toBe(state[0]);
+    }
+    expect(fieldToHex(Poseidon2.hash3(0n, 0n, Poseidon2Domain.Merkle))).toBe('0x0c740b5d5661a25942f5aec3aa675b31c36714fa5026100b49af73236c2a265b');
+    expect(Poseidon2.permute(1n, 2n, 3n)).toEqual(Poseidon2.permute('1', 2, 3n + BN254_FIELD_MODULUS));
+  });
+
+  it('hashes a batch of pairs in input order, matching the per-pair hash', () => {
+    const pairs = [[0n, 0n], [1n, 2n], [2n, 1n], [BN254_FIELD_MODULUS - 1n, 5n], [BN254_FIELD_MODULUS + 1n, 7n], [123n, 456n]] as const;
+    const batch = Poseidon2.hashMany(pairs);
+    pairs.forEach(([a, b], i) => expect(batch[i]).toBe(Poseidon2.hash(a, b)));
+    expect(Poseidon2.hashMany(pairs, Poseidon2Domain.Merkle)).toEqual(pairs.map(([a, b]) => Poseidon2.hashDomain(a, b, Poseidon2Domain.Merkle)));
+    expect(Poseidon2.hashMany([])).toEqual([]);
+  });
+
+  it('absorbs inputs two at a time in the sponge', () => {
+    const d = Poseidon2Domain.Record;
+    // A single block is one permutation of (a, b, domain); the next block is added into the rate.
+    expect(Poseidon2.sponge([1n, 2n], d)).toBe(Poseidon2.hashDomain(1n, 2n, d));
+    expect(Poseidon2.sponge([9n], d)).toBe(Poseidon2.hashDomain(9n, 0n, d));
+    const [s0, s1, s2] = Poseidon2.permute(1n, 2n, d);
+    expect(Poseidon2.sponge([1n, 2n, 3n, 4n], d)).toBe(Poseidon2.permute(s0 + 3n, s1 + 4n, s2)[0]);
+
+    expect(Poseidon2.sponge([1n, 2n, 3n], d)).not.toBe(Poseidon2.hashFields([1n, 2n, 3n], d));
+    expect(Poseidon2.sponge([1n, 2n, 3n], d)).not.toBe(Poseidon2.sponge([1n, 2n, 3n]));
+    expect(() => Poseidon2.sponge([], d)).toThrow(/at least one input/);
+  });
+
+  it('traces every fold step of a sequence hash', () => {
+    expect(Poseidon2.hashSequenceTrace([], Poseidon2Domain.Array, 5n)).toEqual([5n]);
+    expect(Poseidon2.hashSequenceTrace([9n], Poseidon2Domain.Array)).toEqual([Poseidon2.hashSequenceWithDomain([9n], Poseidon2Domain.Array)]);
+    const seeded = Poseidon2.hashSequenceTrace([1n, 2n, 3n], Poseidon2Domain.Array, 0n);
+    expect(seeded).toHaveLength(3);
+    expect(seeded[2]).toBe(Poseidon2.hashSequenceWithDomain([1n, 2n, 3n], Poseidon2Domain.Array, 0n));
+  });
+});
@@ -0,0 +1,87 @@
+import { describe, expect, it } from 'vitest';
+import { bytesToHex } from '@noble/hashes/utils';
+import { RecordCodec } from '../src/crypto/recordCodec';
+import { BabyJubjub, BABYJUBJUB_SCALAR_FIELD } from '../src/crypto/babyJubjub';
+import { SdkError } from '../src/errors';
+import { createSeededRandomSource } from '../src/utils/random';
+
+const record = {
+  asset_id: 7n,
+  asset_amount: 1000n,
+  user_pk: { user_address: BabyJubjub.scalarMult(12345n) },
+  blinding_factor: 42n,
+  is_frozen: false,
+};
+const valid = RecordCodec.encode(record);
+
+const withSlot = (hex: string, slot: number, value: string) => `${hex.slice(0, 2 + slot * 64)}${value}${hex.slice(2 + (slot + 1) * 64)}`;
+
+// Compressed key slot: little-endian y with the x sign in the top bit of the last byte.
+const keySlot = (y: bigint, sign: boolean) => {
+  const bytes = new Uint8Array(32);
+  for (let i = 0; i < 32; i++) bytes[i] = Number((y >> BigInt(8 * i)) & 0xffn);
+  if (sign) bytes[31]! |= 0x80;
+  return bytesToHex(bytes);
+};
+
+const expectCleanRejection = (hex: string) => {
+  let error: unknown;
+  try {
+    RecordCodec.decode(hex);
+  } catch (e) {
+    error = e;
+  }
+  expect(error).toBeInstanceOf(SdkError);
+  expect((error as SdkError).code).toBe('CRYPTO');
+};
+
+describe('RecordCodec', () => {
+  it('round-trips a record opening', () => {
+    expect(RecordCodec.decode(valid)).toEqual(record);
+    expect(RecordCodec.decode(valid.slice(2).toUpperCase())).toEqual(record);
+  });
+
+  it('either rejects arbitrary 160-byte payloads cleanly or re-encodes them byte-for-byte', () => {
+    const rng = createSeededRandomSource('record-codec-fuzz');
+    let accepted = 0;
+    for (let i = 0; i < 300; i++) {
+      const mode = rng(1)[0]! % 3;
+      let hex: string;
+      if (mode === 0) hex = `0x${bytesToHex(rng(160))}`;
+      else if (mode === 1) hex = withSlot(valid, 2, bytesToHex(rng(32)));
+      else hex = withSlot(withSlot(valid, 2, bytesToHex(rng(32))), 4, `${'0'.repeat(62)}0${rng(1)[0]! % 3}`);
+      let decoded;
+      try {
+        decoded = RecordCodec.decode(hex);
+      } catch (error) {
+        expect(error).toBeInstanceOf(SdkError);
+        continue;
+      }
+      accepted++;
+      expect(RecordCodec.encode(decoded)).toBe(hex);
+    }
+    expect(accepted).toBeGreaterThan(0);
+  });
+
+  it('rejects payloads that are not exactly 160 bytes', () => {
+    expectCleanRejection(valid.slice(0, -2));
+    expectCleanRejection(`${valid}00`);
+  });
+
+  it('rejects a key slot whose y has no matching x', () => {
+    expectCleanRejection(withSlot(valid, 2, keySlot(2n, false)));
+  });
+
+  it('rejects a key slot with a non-canonical y', () => {
+    expectCleanRejection(withSlot(valid, 2, keySlot(BABYJUBJUB_SCALAR_FIELD, false)));
+  });
+
+  it('rejects the sign bit on x = 0', () => {
+    expect(RecordCodec.decode(withSlot(valid, 2, keySlot(1n, false))).user_pk.user_address).toEqual([0n, 1n]);
+    expectCleanRejection(withSlot(valid, 2, keySlot(1n, true)));
+  });
+
+  it('rejects bool slots other than 0 or 1', () => {
+    expectCleanRejection(withSlot(valid, 4, `${'0'.repeat(63)}2`));
+  });
+});
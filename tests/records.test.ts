@@ -0,0 +1,39 @@
+import { describe, expect, it } from 'vitest';
+import { toCommitmentData, toRecordOpeningJson } from '../src/crypto/records';
+import { KeyManager } from '../src/crypto/keyManager';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { BN254_FIELD_MODULUS } from '../src/crypto/field';
+
+describe('record opening conversions', () => {
+  const keyPair = KeyManager.deriveKeyPair('records-conversion-seed');
+  const ro = CryptoToolkit.createRecordOpening({
+    asset_id: 5n,
+    asset_amount: 10n ** 18n,
+    user_pk: { user_address: keyPair.user_pk.user_address },
+    blinding_factor: BN254_FIELD_MODULUS - 1n,
+    is_frozen: true,
+  });
+
+  it('round-trips through decimal and hex JSON representations', () => {
+    const decimal = toRecordOpeningJson(ro);
+    expect(decimal.asset_amount).toBe('1000000000000000000');
+    expect(toCommitmentData(decimal)).toEqual(ro);
+
+    const hex = toRecordOpeningJson(ro, { encoding: 'hex' });
+    expect(hex.asset_id).toBe(`0x${'0'.repeat(63)}5`);
+    expect(hex.blinding_factor).toHaveLength(66);
+    expect(toCommitmentData(hex)).toEqual(ro);
+    expect(CryptoToolkit.commitment(toCommitmentData(hex), 'hex')).toBe(CryptoToolkit.commitment(ro, 'hex'));
+  });
+
+  it('rejects malformed and out-of-range fields with SdkError(CRYPTO)', () => {
+    const json = toRecordOpeningJson(ro);
+    expect(() => toCommitmentData({ ...json, asset_amount: 'ten' })).toThrow(expect.objectContaining({ name: 'SdkError', code: 'CRYPTO', detail: { field: 'asset_amount', value: 'ten' } }));
+    expect(() => toCommitmentData({ ...json, blinding_factor: BN254_FIELD_MODULUS.toString() })).toThrow(expect.objectContaining({ code: 'CRYPTO', detail: expect.objectContaining({ field: 'blinding_factor' }) }));
+    expect(() => toCommitmentData({ ...json, asset_id: '-1' })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+    expect(() => toCommitmentData({ ...json, user_pk: { user_address: [json.user_pk.user_address[0], '0xzz'] } })).toThrow(
+      expect.objectContaining({ detail: expect.objectContaining({ field: 'user_pk.user_address[1]' }) }),
+    );
+    expect(() => toCommitmentData({ ...ro, asset_id: 1.5 })).toThrow(expect.objectContaining({ code: 'CRYPTO' }));
+  });
+});
@@ -92,4 +92,58 @@ describe('RelayerClient', () => {
     const client = new RelayerClient('https://relayer.example');
     await expect(client.getTxHash({ relayerTxHash: '0x01' })).resolves.toBe('0x0abc');
   });
+
+  it('getStatusByNullifier returns the status for a known nullifier and null for an unknown one', async () => {
+    const known = '0x0aaa';
+    const fetchMock = vi.fn(async (url: string) => {
+      const nullifier = new URL(url).searchParams.get('nullifier');
+      const data = nullifier === known ? { status: 'confirmed', relayer_txhash: '0x01', txhash: '0x0abc', block_number: 123 } : null;
+      return new Response(JSON.stringify({ code: 0, data }), { status: 200, headers: { 'content-type': 'application/json' } });
+    });
+    vi.stubGlobal('fetch', fetchMock);
+    const client = new RelayerClient('https://relayer.example');
+
+    await expect(client.getStatusByNullifier({ chainId: 1, nullifier: known, path: '/v2/status' })).resolves.toEqual({
+      nullifier: known,
+      status: 'confirmed',
+      relayerTxHash: '0x01',
+      txHash: '0x0abc',
+      blockNumber: 123,
+    });
+    expect(fetchMock.mock.calls[0]![0]).toBe('https://relayer.example/v2/status?chain_id=1&nullifier=0x0aaa');
+    await expect(client.getStatusByNullifier({ chainId: 1, nullifier: '0x0bbb', path: '/v2/status' })).resolves.toBeNull();
+  });
+
+  it('getStatusByNullifier rejects unknown states and malformed hashes', async () => {
+    let data: unknown = { status: 'lost' };
+    vi.stubGlobal(
+      'fetch',
+      vi.fn(async () => new Response(JSON.stringify({ data }), { status: 200, headers: { 'content-type': 'application/json' } })),
+    );
+    const client = new RelayerClient('https://relayer.example');
+    await expect(client.getStatusByNullifier({ chainId: 1, nullifier: '0x01', path: '/v2/status' })).rejects.toMatchObject({ code: 'RELAYER' });
+    data = { status: 'submitted', txhash: 'nope' };
+    await expect(client.getStatusByNullifier({ chainId: 1, nullifier: '0x01', path: '/v2/status' })).rejects.toMatchObject({ code: 'RELAYER' });
+    data = { status: 'pending' };
+    await expect(client.getStatusByNullifier({ chainId: 1, nullifier: '0x01', path: '/v2/status' })).resolves.toEqual({ nullifier: '0x01', status: 'pending' });
+  });
+
+  it('reports a request that outlives requestTimeoutMs as a timeout', async () => {
+    vi.stubGlobal(
+      'fetch',
+      vi.fn(
+        (_url: string, init?: RequestInit) =>
+          new Promise<Response>((_resolve, reject) => {
+            init?.signal?.addEventListener('abort', () => reject(init.signal!.reason), { once: true });
+          }),
+      ),
+    );
+    const client = new RelayerClient('https://relayer.example');
+    await expect(client.submit({ kind: 'relayer', method: 'POST', path: '/api/v1/transfer', body: {} }, { requestTimeoutMs: 20 })).rejects.toMatchObject({
+      name: 'SdkError',
+      code: 'RELAYER',
+      detail: { reason: 'timeout', url: 'https://relayer.example/api/v1/transfer', timeoutMs: 20 },
+    });
+    await expect(client.getTxHash({ relayerTxHash: '0x01', requestTimeoutMs: 20 })).rejects.toMatchObject({ code: 'RELAYER', detail: { reason: 'timeout' } });
+  });
 });
@@ -58,3 +58,17 @@ describe('SdkCore.ready', () => {
     expect(versionB).toBe(versionA);
   });
 });
+
+describe('SdkCore.emit', () => {
+  it('reports a rejecting onEvent as an error event and does not loop on error events', async () => {
+    const onEvent = vi.fn(async () => {
+      throw new Error('handler down');
+    });
+    const core = createCore(createProofBridgeMock() as unknown as ProofBridge, onEvent);
+    await core.emit({ type: 'core:ready', payload: { assetsVersion: 'none', durationMs: 1 } });
+    await new Promise((resolve) => setTimeout(resolve, 0));
+
+    expect(onEvent.mock.calls.map((call: any[]) => call[0].type)).toEqual(['core:ready', 'error']);
+    expect((onEvent.mock.calls[1] as any[])[0].payload).toMatchObject({ code: 'CONFIG', detail: { event: 'core:ready' } });
+  });
+});
@@ -0,0 +1,83 @@
+import { describe, expect, it } from 'vitest';
+import { mkdtemp, rm } from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { MemoryStore } from '../src/store/memoryStore';
+import { EncryptedStore } from '../src/store/encryptedStore';
+import { FileStore } from '../src/store/fileStore';
+import { KeyValueStore } from '../src/store/keyValueStore';
+import { getStoreBalances } from '../src/store/storeBalances';
+import type { UtxoRecord } from '../src/types';
+
+let next = 0;
+const utxo = (chainId: number, assetId: string, amount: bigint, flags: { isFrozen?: boolean; isSpent?: boolean } = {}): UtxoRecord => {
+  const n = next++;
+  return { chainId, assetId, amount, commitment: `0xc${n}`, nullifier: `0xa${n}`, mkIndex: n, isFrozen: false, isSpent: false, ...flags };
+};
+
+const records = [
+  utxo(1, '20', 5n),
+  utxo(1, '7', 10n),
+  utxo(1, '7', 3n, { isFrozen: true }),
+  utxo(1, '7', 100n, { isSpent: true }),
+  utxo(1, '20', 8n, { isFrozen: true }),
+  utxo(1, '20', 2n),
+  utxo(2, '7', 1n),
+];
+
+describe('getStoreBalances', () => {
+  it('sums unspent coins per asset, keeping frozen value out of spendable', async () => {
+    const store = new MemoryStore();
+    await store.upsertUtxos(records);
+
+    const expected = [
+      { chainId: 1, assetId: '20', spendable: 7n, frozen: 8n, total: 15n },
+      { chainId: 1, assetId: '7', spendable: 10n, frozen: 3n, total: 13n },
+      { chainId: 2, assetId: '7', spendable: 1n, frozen: 0n, total: 1n },
+    ];
+    await expect(getStoreBalances(store)).resolves.toEqual(expected);
+    await expect(getStoreBalances(store, { chainId: 2 })).resolves.toEqual([expected[2]]);
+  });
+
+  it('matches the in-memory sums in FileStore and KeyValueStore', async () => {
+    const reference = new MemoryStore();
+    await reference.upsertUtxos(records);
+
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-balances-'));
+    try {
+      const fileStore = new FileStore({ baseDir: dir });
+      await fileStore.init({ walletId: 'wallet_1' });
+      await fileStore.upsertUtxos(records);
+      await expect(fileStore.getBalances()).resolves.toEqual(await reference.getBalances());
+      await expect(fileStore.getBalances({ chainId: 1 })).resolves.toEqual(await reference.getBalances({ chainId: 1 }));
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+
+    const db = new Map<string, string>();
+    const kvStore = new KeyValueStore({
+      client: {
+        get: async (key: string) => db.get(key) ?? null,
+        set: async (key: string, value: string) => {
+          db.set(key, value);
+        },
+      },
+    });
+    await kvStore.init({ walletId: 'wallet_1' });
+    await kvStore.upsertUtxos(records);
+    await expect(kvStore.getBalances()).resolves.toEqual(await reference.getBalances());
+    await expect(kvStore.getBalances({ chainId: 2 })).resolves.toEqual(await reference.getBalances({ chainId: 2 }));
+  });
+
+  it('falls back to listUtxos for adapters without getBalances', async () => {
+    const reference = new MemoryStore();
+    await reference.upsertUtxos(records);
+    // EncryptedStore seals amounts in its inner store, so it must not forward the inner aggregate.
+    const encrypted = new EncryptedStore(new MemoryStore(), { key: EncryptedStore.deriveKey('store-balances-seed') });
+    await encrypted.upsertUtxos(records);
+
+    expect(encrypted.getBalances).toBeUndefined();
+    await expect(getStoreBalances(encrypted)).resolves.toEqual(await reference.getBalances());
+    await expect(getStoreBalances(encrypted, { chainId: 1 })).resolves.toEqual(await reference.getBalances({ chainId: 1 }));
+  });
+});
@@ -0,0 +1,41 @@
+import { describe, expect, it } from 'vitest';
+import { MemoryStore } from '../src/store/memoryStore';
+import { getStoreStats } from '../src/store/storeStats';
+import type { UtxoRecord } from '../src/types';
+
+const utxo = (chainId: number, mkIndex: number, isSpent: boolean): UtxoRecord => ({
+  chainId,
+  assetId: '1',
+  amount: 1n,
+  commitment: `0x${chainId}${mkIndex}`,
+  nullifier: `0x${chainId}${mkIndex}f`,
+  mkIndex,
+  isFrozen: false,
+  isSpent,
+});
+
+describe('getStoreStats', () => {
+  it('aggregates utxo, cursor and operation counts from the list methods', async () => {
+    const store = new MemoryStore();
+    await store.upsertUtxos([utxo(1, 0, false), utxo(1, 1, true), utxo(2, 0, false)]);
+    await store.setSyncCursor(1, { memo: 2, nullifier: 1, merkle: 0 });
+    await store.setSyncCursor(3, { memo: 5, nullifier: 0, merkle: 0 });
+    const op = store.createOperation({ type: 'deposit', chainId: 1, tokenId: 'T' });
+    store.createOperation({ type: 'transfer', chainId: 1, tokenId: 'T' });
+    store.updateOperation(op.id, { status: 'confirmed' });
+
+    const stats = await getStoreStats(store, { chainIds: [3] });
+    expect(stats).toEqual({
+      utxos: { total: 3, unspent: 2 },
+      cursors: { 1: { memo: 2, nullifier: 1, merkle: 0 }, 3: { memo: 5, nullifier: 0, merkle: 0 } },
+      operations: { total: 2, byStatus: { created: 1, confirmed: 1 } },
+    });
+  });
+
+  it('delegates to the adapter stats implementation when present', async () => {
+    const store = new MemoryStore();
+    const custom = { utxos: { total: 9, unspent: 9 }, cursors: {}, operations: { total: 0, byStatus: {} } };
+    (store as any).stats = async () => custom;
+    await expect(getStoreStats(store)).resolves.toBe(custom);
+  });
+});
@@ -34,4 +34,84 @@ describe('SyncEngine', () => {
     expect(status.memo.status).toBe('error');
     expect(events.some((e) => e.type === 'error')).toBe(true);
   });
+
+  it('clamps memo total when entry returns items with total 0', async () => {
+    (globalThis as any).fetch = async () => ({
+      ok: true,
+      json: async () => ({
+        code: 0,
+        data: { data: [{ commitment: '0x01', memo: '0x02', cid: 0, created_at: 1 }, { commitment: '0x03', memo: '0x04', cid: 1, created_at: 2 }], total: 0 },
+      }),
+    });
+
+    const assets = {
+      getChains: () => [{ chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' }],
+      getChain: () => ({ chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' }),
+    } as any;
+
+    const storage: StorageAdapter = {
+      getSyncCursor: async () => ({ memo: 0, nullifier: 0, merkle: 0 }),
+      setSyncCursor: async () => undefined,
+      upsertUtxos: async () => undefined,
+      listUtxos: async () => ({ total: 0, rows: [] }),
+      markSpent: async () => 0,
+    };
+
+    const wallet = {
+      getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+      applyMemos: async () => 0,
+      markSpent: async () => undefined,
+    } as any;
+
+    const events: any[] = [];
+    const engine = new SyncEngine(assets as any, storage, wallet, (evt) => events.push(evt), undefined);
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'], pageSize: 10, continueOnError: false });
+
+    const status = engine.getStatus()[1];
+    expect(status.memo.status).toBe('synced');
+    expect(status.memo.downloaded).toBe(2);
+    expect(status.memo.total).toBe(2);
+    const progress = events.filter((e) => e.type === 'sync:progress' && e.payload.resource === 'memo');
+    expect(progress.every((e) => e.payload.total >= e.payload.downloaded && e.payload.total > 0)).toBe(true);
+    expect(events.some((e) => e.type === 'debug' && e.payload.message === 'page:total_inconsistent')).toBe(true);
+  });
+
+  it('keeps fetching short memo pages while total indicates more data', async () => {
+    const fetchSpy = vi.fn(async (url: string) => {
+      const offset = Number(new URL(url).searchParams.get('offset'));
+      // Server caps pages at 2 items regardless of the requested limit.
+      const data = [offset, offset + 1].filter((cid) => cid < 5).map((cid) => ({ commitment: '0x01', memo: '0x02', cid, created_at: cid }));
+      return { ok: true, json: async () => ({ code: 0, data: { data, total: 5 } }) };
+    });
+    (globalThis as any).fetch = fetchSpy;
+
+    const assets = {
+      getChains: () => [{ chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' }],
+      getChain: () => ({ chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' }),
+    } as any;
+
+    let saved: any = null;
+    const storage: StorageAdapter = {
+      getSyncCursor: async () => ({ memo: 0, nullifier: 0, merkle: 0 }),
+      setSyncCursor: async (_chainId, cursor) => {
+        saved = cursor;
+      },
+      upsertUtxos: async () => undefined,
+      listUtxos: async () => ({ total: 0, rows: [] }),
+      markSpent: async () => 0,
+    };
+
+    const wallet = {
+      getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+      applyMemos: async () => 0,
+      markSpent: async () => undefined,
+    } as any;
+
+    const engine = new SyncEngine(assets as any, storage, wallet, () => undefined, undefined);
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'], pageSize: 10, continueOnError: false });
+
+    expect(engine.getStatus()[1].memo.status).toBe('synced');
+    expect(saved?.memo).toBe(5);
+    expect(fetchSpy).toHaveBeenCalledTimes(3);
+  });
 });
@@ -0,0 +1,41 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import type { StorageAdapter } from '../src/types';
+import { createSyncTestWallet, syncTestAssets } from './helpers';
+
+describe('SyncEngine.estimateBacklog', () => {
+  it('computes pending counts from entry totals without persisting', async () => {
+    const urls: string[] = [];
+    (globalThis as any).fetch = async (url: string) => {
+      urls.push(url);
+      const total = url.includes('/memos/list') ? 100 : 40;
+      return { ok: true, json: async () => ({ code: 0, data: { data: [], total } }) };
+    };
+
+    let writes = 0;
+    const storage: StorageAdapter = {
+      getSyncCursor: async () => ({ memo: 30, nullifier: 40, merkle: 0 }),
+      setSyncCursor: async () => {
+        writes++;
+      },
+      upsertUtxos: async () => {
+        writes++;
+      },
+      listUtxos: async () => ({ total: 0, rows: [] }),
+      markSpent: async () => 0,
+    };
+
+    const engine = new SyncEngine(syncTestAssets, storage, createSyncTestWallet(), () => undefined, undefined);
+    const backlog = await engine.estimateBacklog();
+
+    expect(backlog).toEqual([
+      {
+        chainId: 1,
+        memo: { cursor: 30, total: 100, pending: 70 },
+        nullifier: { cursor: 40, total: 40, pending: 0 },
+      },
+    ]);
+    expect(writes).toBe(0);
+    expect(urls.every((u) => u.includes('limit=1'))).toBe(true);
+  });
+});
@@ -0,0 +1,63 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MerkleEngine } from '../src/merkle/merkleEngine';
+import { computeMerkleRootFromLeaves } from '../src/merkle/merkleProof';
+import { MemoryStore } from '../src/store/memoryStore';
+import { fieldToHex } from '../src/crypto/field';
+import { createSyncTestWallet, syncTestAssets } from './helpers';
+
+const leafAt = (cid: number) => fieldToHex(BigInt(cid + 1));
+const leaves = Array.from({ length: 40 }, (_, cid) => leafAt(cid));
+const checkpoint = { memo: 40, nullifier: 5, merkleRoot: fieldToHex(computeMerkleRootFromLeaves(leaves)), leaves };
+
+const setup = () => {
+  const store = new MemoryStore();
+  const merkle = new MerkleEngine(() => ({}), {} as any, { mode: 'local' }, store);
+  return { store, engine: new SyncEngine(syncTestAssets, store, createSyncTestWallet(), () => undefined, merkle) };
+};
+
+describe('SyncEngine.applyCheckpoint', () => {
+  it('seeds the cursor and local tree so the next pass resumes from the checkpoint', async () => {
+    const requested: string[] = [];
+    (globalThis as any).fetch = async (url: string) => {
+      requested.push(url);
+      const offset = Number(new URL(url).searchParams.get('offset'));
+      if (url.includes('/memos/')) {
+        const data = [40, 41, 42, 43].filter((cid) => cid >= offset).map((cid) => ({ commitment: leafAt(cid), memo: '0x02', cid }));
+        return { ok: true, json: async () => ({ code: 0, data: { data, total: 44 } }) };
+      }
+      return { ok: true, json: async () => ({ code: 0, data: { data: [], total: 5 } }) };
+    };
+    const { store, engine } = setup();
+
+    await engine.applyCheckpoint(1, checkpoint);
+    expect(await store.getSyncCursor(1)).toEqual({ memo: 40, nullifier: 5, merkle: 1 });
+    expect((await store.getLatestChairmanMerkleVersion(1))?.version).toBe(32);
+    expect(await store.getMerkleLeaves(1)).toHaveLength(40);
+
+    await engine.syncOnce({ chainIds: [1], continueOnError: false });
+    expect(requested.filter((u) => u.includes('/memos/')).map((u) => new URL(u).searchParams.get('offset'))[0]).toBe('40');
+    expect(requested.filter((u) => u.includes('/nullifier/')).map((u) => new URL(u).searchParams.get('offset'))[0]).toBe('5');
+    expect((await store.getSyncCursor(1))?.memo).toBe(44);
+    expect(await store.getMerkleLeaves(1)).toHaveLength(44);
+
+    await expect(engine.applyCheckpoint(1, checkpoint)).rejects.toMatchObject({ code: 'SYNC' });
+  });
+
+  it('rejects a checkpoint whose leaves do not match its root', async () => {
+    const { store, engine } = setup();
+    await expect(engine.applyCheckpoint(1, { ...checkpoint, merkleRoot: fieldToHex(1n) })).rejects.toMatchObject({ code: 'MERKLE', message: 'Checkpoint merkle root mismatch' });
+    await expect(engine.applyCheckpoint(1, { ...checkpoint, memo: 41 })).rejects.toMatchObject({ code: 'CONFIG' });
+    expect(await store.getSyncCursor(1)).toBeUndefined();
+    expect(await store.getMerkleLeaves(1)).toBeUndefined();
+  });
+
+  it('holds the chain while seeding so a concurrent apply or reset is rejected', async () => {
+    const { store, engine } = setup();
+    const first = engine.applyCheckpoint(1, checkpoint);
+    await expect(engine.applyCheckpoint(1, checkpoint)).rejects.toMatchObject({ code: 'SYNC', message: 'Cannot apply checkpoint while chain is syncing' });
+    await expect(engine.forceResyncFrom(1, { memo: 0, nullifier: 0 })).rejects.toMatchObject({ code: 'SYNC' });
+    await first;
+    expect(await store.getSyncCursor(1)).toEqual({ memo: 40, nullifier: 5, merkle: 1 });
+  });
+});
@@ -0,0 +1,57 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+
+describe('SyncEngine combined updates', () => {
+  it('advances both cursors from the combined endpoint without separate nullifier requests', async () => {
+    const requested: string[] = [];
+    const nullifiers = ['0x0a', '0x0b', '0x0c'];
+    (globalThis as any).fetch = async (url: string) => {
+      requested.push(url);
+      const params = new URL(url).searchParams;
+      const memoOffset = Number(params.get('memo_offset'));
+      const nullifierOffset = Number(params.get('nullifier_offset'));
+      const limit = Number(params.get('limit'));
+      const memos = [0, 1, 2, 3, 4].filter((cid) => cid >= memoOffset).slice(0, limit).map((cid) => ({ commitment: '0x01', memo: '0x02', cid }));
+      const spent = nullifiers.slice(nullifierOffset, nullifierOffset + limit).map((nullifier) => ({ nullifier }));
+      return { ok: true, json: async () => ({ code: 0, data: { memos: { data: memos, total: 5 }, nullifiers: { data: spent, total: 3 } } }) };
+    };
+    const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+    const assets = { getChains: () => [chain], getChain: () => chain } as any;
+    const marked: string[] = [];
+    const wallet = {
+      getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+      applyMemos: async () => 0,
+      markSpent: async (input: { nullifiers: string[] }) => {
+        marked.push(...input.nullifiers);
+      },
+    } as any;
+    const store = new MemoryStore();
+    const engine = new SyncEngine(assets, store, wallet, () => undefined, undefined, { combinedUpdates: { path: '/v2/updates' } });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo', 'nullifier'], pageSize: 2, continueOnError: false });
+
+    expect(requested.every((url) => new URL(url).pathname === '/v2/updates')).toBe(true);
+    expect(requested).toHaveLength(3);
+    expect(await store.getSyncCursor(1)).toMatchObject({ memo: 5, nullifier: 3 });
+    expect(marked).toEqual(nullifiers);
+    expect(engine.getStatus()[1]).toMatchObject({ memo: { status: 'synced', downloaded: 5 }, nullifier: { status: 'synced', downloaded: 3 } });
+  });
+
+  it('keeps the published list endpoints unless combined updates are configured', async () => {
+    const requested: string[] = [];
+    (globalThis as any).fetch = async (url: string) => {
+      requested.push(url);
+      return { ok: true, json: async () => ({ code: 0, data: { data: [], total: 0 } }) };
+    };
+    const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+    const assets = { getChains: () => [chain], getChain: () => chain } as any;
+    const wallet = { getViewingAddress: () => '0x0000000000000000000000000000000000000001', applyMemos: async () => 0, markSpent: async () => undefined } as any;
+    const engine = new SyncEngine(assets, new MemoryStore(), wallet, () => undefined, undefined);
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo', 'nullifier'], continueOnError: false });
+
+    expect(requested.length).toBeGreaterThan(0);
+    expect(requested.some((url) => new URL(url).pathname.includes('updates'))).toBe(false);
+  });
+});
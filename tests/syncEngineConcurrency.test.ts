@@ -73,7 +73,7 @@ describe('SyncEngine (concurrency)', () => {
     expect(setCalls.some((c) => c.chainId === 1 && c.cursor.memo === 1 && c.cursor.merkle === 0)).toBe(true);
   });
 
-  it('skips re-entrant sync on the same chain but still syncs others', async () => {
+  it('joins an in-flight sync on the same chain but still syncs others', async () => {
     const m1 = deferred<any>();
     const fetchSpy = vi.fn(async (url: string) => {
       if (url.startsWith('https://e1')) return m1.promise;
@@ -123,12 +123,106 @@ describe('SyncEngine (concurrency)', () => {
     await new Promise((r) => setTimeout(r, 0));
     expect(fetchSpy.mock.calls.filter((c) => String(c[0]).startsWith('https://e1')).length).toBe(1);
     expect(fetchSpy.mock.calls.filter((c) => String(c[0]).startsWith('https://e2')).length).toBe(1);
-    expect(events.some((e) => e?.type === 'error' && e?.payload?.code === 'SYNC' && e?.payload?.detail?.skipped === true)).toBe(true);
+    expect(events.some((e) => e?.type === 'debug' && e?.payload?.message === 'syncChain:joined' && e?.payload?.detail?.chainId === 1)).toBe(true);
+
+    let t2Done = false;
+    void t2.then(() => {
+      t2Done = true;
+    });
+    await new Promise((r) => setTimeout(r, 0));
+    expect(t2Done).toBe(false);
 
     m1.resolve({
       ok: true,
       json: async () => ({ data: { data: [{ commitment: '0x00', memo: '0x00', cid: 0 }], total: 1 } }),
     });
     await Promise.all([t1, t2]);
+    expect(t2Done).toBe(true);
+    expect(fetchSpy.mock.calls.filter((c) => String(c[0]).startsWith('https://e1')).length).toBe(1);
+  });
+
+  it('caps chains in flight at maxConcurrency and keeps syncing past a failing chain', async () => {
+    const m2 = deferred<any>();
+    const page = { ok: true, json: async () => ({ data: { data: [{ commitment: '0x00', memo: '0x00', cid: 0 }], total: 1 } }) };
+    const fetchSpy = vi.fn(async (url: string) => (url.startsWith('https://e2') ? m2.promise : page));
+    (globalThis as any).fetch = fetchSpy;
+
+    const chains = [2, 3].map((chainId) => ({ chainId, entryUrl: `https://e${chainId}`, ocashContractAddress: '0x0000000000000000000000000000000000000002' }));
+    const assets = {
+      getChains: () => chains,
+      getChain: (id: number) => {
+        const chain = chains.find((c) => c.chainId === id);
+        if (!chain) throw new Error(`unknown chain ${id}`);
+        return chain;
+      },
+    } as any;
+    const cursors = new Map<number, any>();
+    const storage: StorageAdapter = {
+      getSyncCursor: async () => ({ memo: 0, nullifier: 0, merkle: 0 }),
+      setSyncCursor: async (chainId, cursor) => {
+        cursors.set(chainId, cursor);
+      },
+      upsertUtxos: async () => undefined,
+      listUtxos: async () => ({ total: 0, rows: [] }),
+      markSpent: async () => 0,
+    };
+    const wallet = {
+      getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+      applyMemos: async () => 0,
+      markSpent: async () => undefined,
+    } as any;
+
+    const engine = new SyncEngine(assets, storage, wallet, () => undefined, undefined, { maxConcurrency: 1 });
+    const task = engine.syncOnce({ chainIds: [1, 2, 3], resources: ['memo'], continueOnError: false });
+
+    await new Promise((r) => setTimeout(r, 0));
+    expect(fetchSpy.mock.calls.map((c) => String(c[0]).slice(0, 10))).toEqual(['https://e2']);
+
+    m2.resolve(page);
+    await expect(task).rejects.toThrow('unknown chain 1');
+    expect(cursors.get(2)?.memo).toBe(1);
+    expect(cursors.get(3)?.memo).toBe(1);
+  });
+
+  it('lets a joiner abort its own wait and queues callers that need other resources', async () => {
+    const m1 = deferred<any>();
+    const page = { ok: true, json: async () => ({ data: { data: [], total: 0 } }) };
+    const fetchSpy = vi.fn(async (url: string) => (url.includes('/memos/') ? m1.promise : page));
+    (globalThis as any).fetch = fetchSpy;
+
+    const chain = { chainId: 1, entryUrl: 'https://e1', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+    const assets = { getChains: () => [chain], getChain: () => chain } as any;
+    const storage: StorageAdapter = {
+      getSyncCursor: async () => ({ memo: 0, nullifier: 0, merkle: 0 }),
+      setSyncCursor: async () => undefined,
+      upsertUtxos: async () => undefined,
+      listUtxos: async () => ({ total: 0, rows: [] }),
+      markSpent: async () => 0,
+    };
+    const wallet = {
+      getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+      applyMemos: async () => 0,
+      markSpent: async () => undefined,
+    } as any;
+
+    const events: any[] = [];
+    const engine = new SyncEngine(assets, storage, wallet, (evt) => events.push(evt), undefined);
+    const owner = engine.syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: false });
+    const joinerAbort = new AbortController();
+    const joiner = engine.syncOnce({ chainIds: [1], resources: ['memo'], signal: joinerAbort.signal, continueOnError: false });
+    const queued = engine.syncOnce({ chainIds: [1], resources: ['nullifier'], continueOnError: false });
+
+    await new Promise((r) => setTimeout(r, 0));
+    expect(events.some((e) => e?.type === 'debug' && e?.payload?.message === 'syncChain:queued')).toBe(true);
+    expect(fetchSpy.mock.calls.some((c) => String(c[0]).includes('/nullifier'))).toBe(false);
+
+    joinerAbort.abort(new Error('joiner gave up'));
+    await expect(joiner).rejects.toThrow('joiner gave up');
+
+    m1.resolve({ ok: true, json: async () => ({ data: { data: [{ commitment: '0x00', memo: '0x00', cid: 0 }], total: 1 } }) });
+    await expect(owner).resolves.toBeUndefined();
+    await expect(queued).resolves.toBeUndefined();
+    expect(fetchSpy.mock.calls.filter((c) => String(c[0]).includes('/memos/')).length).toBe(1);
+    expect(fetchSpy.mock.calls.some((c) => String(c[0]).includes('/nullifier'))).toBe(true);
   });
 });
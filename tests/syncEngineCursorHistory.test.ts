@@ -0,0 +1,56 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+import { fieldToHex } from '../src/crypto/field';
+
+const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+const assets = { getChains: () => [chain], getChain: () => chain } as any;
+const wallet = {
+  getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+  applyMemos: async () => 0,
+  markSpent: async () => undefined,
+} as any;
+
+const stubEntry = (total: number) => {
+  (globalThis as any).fetch = async (url: string) => {
+    const params = new URL(url).searchParams;
+    const offset = Number(params.get('offset'));
+    const end = Math.min(total, offset + Number(params.get('limit')));
+    const data = Array.from({ length: Math.max(0, end - offset) }, (_, i) => ({ commitment: fieldToHex(BigInt(offset + i + 1)), memo: '0x02', cid: offset + i }));
+    return { ok: true, json: async () => ({ code: 0, data: { data, total } }) };
+  };
+};
+
+describe('SyncEngine cursor history', () => {
+  it('records each cursor advance across passes in order', async () => {
+    const store = new MemoryStore();
+    const engine = new SyncEngine(assets, store, wallet, () => undefined, undefined, { pageSize: 100 });
+
+    stubEntry(20);
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: false });
+    // A pass with nothing new moves nothing and records nothing.
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: false });
+    stubEntry(50);
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: false });
+
+    const history = await store.getCursorHistory(1);
+    expect(history.map(({ previous, next }) => ({ previous, next }))).toEqual([
+      { previous: { memo: 0, nullifier: 0, merkle: 0 }, next: { memo: 20, nullifier: 0, merkle: 0 } },
+      { previous: { memo: 20, nullifier: 0, merkle: 0 }, next: { memo: 50, nullifier: 0, merkle: 1 } },
+    ]);
+    expect(history[1]!.timestamp).toBeGreaterThanOrEqual(history[0]!.timestamp);
+    expect(await store.getCursorHistory(1, 1)).toEqual([history[1]]);
+    expect(await store.getCursorHistory(2)).toEqual([]);
+
+    await engine.forceResyncFrom(1, { memo: 0, nullifier: 0 });
+    expect((await store.getCursorHistory(1, 1))[0]).toMatchObject({ previous: { memo: 50 }, next: { memo: 0, merkle: 0 } });
+  });
+
+  it('keeps only the newest entries in MemoryStore', async () => {
+    const store = new MemoryStore({ maxCursorHistory: 2 });
+    for (let memo = 1; memo <= 4; memo++) {
+      await store.appendCursorHistory(1, { memo: memo - 1, nullifier: 0, merkle: 0 }, { memo, nullifier: 0, merkle: 0 }, memo);
+    }
+    expect((await store.getCursorHistory(1)).map((entry) => entry.next.memo)).toEqual([3, 4]);
+  });
+});
@@ -0,0 +1,124 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+import { fieldToHex } from '../src/crypto/field';
+import { createSdk } from '../src/index';
+import type { SdkEvent } from '../src/types';
+
+const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+const assets = { getChains: () => [chain], getChain: () => chain } as any;
+const wallet = {
+  getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+  applyMemos: async () => 0,
+  markSpent: async () => undefined,
+} as any;
+
+const stubEntry = (total: number) => {
+  (globalThis as any).fetch = async (url: string) => {
+    const params = new URL(url).searchParams;
+    const offset = Number(params.get('offset'));
+    const end = Math.min(total, offset + Number(params.get('limit')));
+    const data = Array.from({ length: Math.max(0, end - offset) }, (_, i) => ({ commitment: fieldToHex(BigInt(offset + i + 1)), memo: '0x02', cid: offset + i }));
+    return { ok: true, json: async () => ({ code: 0, data: { data, total } }) };
+  };
+};
+
+// A handler that records each event, then stalls until released.
+const slowHandler = () => {
+  const delivered: SdkEvent['type'][] = [];
+  let release!: () => void;
+  const gate = new Promise<void>((resolve) => (release = resolve));
+  return { delivered, release, handler: async (evt: SdkEvent) => (delivered.push(evt.type), gate) };
+};
+
+const syncOptions = { chainIds: [1], resources: ['memo' as const], continueOnError: false };
+
+describe('SyncEngine event queue', () => {
+  it('keeps syncing past a stalled handler and drops the oldest events when full', async () => {
+    stubEntry(30);
+    const store = new MemoryStore();
+    const { delivered, release, handler } = slowHandler();
+    const engine = new SyncEngine(assets, store, wallet, handler, undefined, { pageSize: 10, eventQueue: { capacity: 3 } });
+
+    await engine.syncOnce(syncOptions);
+    expect((await store.getSyncCursor(1))?.memo).toBe(30);
+    // Only the first event reached the handler; the rest are queued behind it.
+    expect(delivered).toHaveLength(1);
+
+    release();
+    await engine.flushEvents();
+    expect(delivered).toHaveLength(4);
+    expect(delivered[3]).toBe('sync:done');
+  });
+
+  it('pauses between pages under the block policy and delivers every event', async () => {
+    stubEntry(30);
+    const inline: SdkEvent['type'][] = [];
+    const direct = new SyncEngine(assets, new MemoryStore(), wallet, (evt) => inline.push(evt.type), undefined, { pageSize: 10 });
+    await direct.syncOnce(syncOptions);
+    await expect(direct.flushEvents()).resolves.toBeUndefined();
+
+    const store = new MemoryStore();
+    const { delivered, release, handler } = slowHandler();
+    const engine = new SyncEngine(assets, store, wallet, handler, undefined, { pageSize: 10, eventQueue: { capacity: 2, overflow: 'block' } });
+    let finished = false;
+    const pass = engine.syncOnce(syncOptions).then(() => (finished = true));
+
+    await new Promise((resolve) => setTimeout(resolve, 20));
+    expect(finished).toBe(false);
+    expect((await store.getSyncCursor(1))?.memo ?? 0).toBeLessThan(30);
+
+    release();
+    await pass;
+    await engine.flushEvents();
+    expect((await store.getSyncCursor(1))?.memo).toBe(30);
+    expect(delivered).toEqual(inline);
+  });
+
+  it('never drops error or sync:done events under the drop policy', async () => {
+    (globalThis as any).fetch = async () => ({ ok: false, status: 400 });
+    const { delivered, release, handler } = slowHandler();
+    const engine = new SyncEngine(assets, new MemoryStore(), wallet, handler, undefined, { eventQueue: { capacity: 1 } });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo', 'nullifier'], continueOnError: true });
+    release();
+    await engine.flushEvents();
+
+    expect(delivered.filter((type) => type === 'error').length).toBeGreaterThanOrEqual(2);
+    expect(delivered[delivered.length - 1]).toBe('sync:done');
+  });
+
+  it('reports a rejecting handler as an error event, inline and queued', async () => {
+    for (const eventQueue of [undefined, { capacity: 4 }]) {
+      stubEntry(0);
+      const seen: SdkEvent[] = [];
+      const handler = async (evt: SdkEvent) => {
+        seen.push(evt);
+        if (evt.type === 'sync:start') throw new Error('handler down');
+      };
+      const engine = new SyncEngine(assets, new MemoryStore(), wallet, handler, undefined, { eventQueue });
+      await engine.syncOnce(syncOptions);
+      await engine.flushEvents();
+      await new Promise((resolve) => setTimeout(resolve, 0));
+
+      const failure = seen.find((evt) => evt.type === 'error');
+      expect(failure?.payload).toMatchObject({ code: 'CONFIG', message: 'onEvent handler failed', detail: { event: 'sync:start' } });
+    }
+  });
+
+  it('awaits an async onEvent passed to createSdk', async () => {
+    stubEntry(0);
+    const { release, handler } = slowHandler();
+    const sdk = createSdk({ chains: [chain as any], onEvent: handler, sync: { eventQueue: { capacity: 4 } } });
+
+    await sdk.sync.syncOnce({ chainIds: [1], resources: ['nullifier'], continueOnError: true });
+    let flushed = false;
+    const flush = sdk.sync.flushEvents().then(() => (flushed = true));
+    await new Promise((resolve) => setTimeout(resolve, 20));
+    expect(flushed).toBe(false);
+
+    release();
+    await flush;
+    expect(flushed).toBe(true);
+  });
+});
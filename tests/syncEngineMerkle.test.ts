@@ -1,5 +1,9 @@
 import { describe, expect, it, vi } from 'vitest';
 import { SyncEngine } from '../src/sync/syncEngine';
+import { MerkleEngine } from '../src/merkle/merkleEngine';
+import { computeMerkleRootFromLeaves } from '../src/merkle/merkleProof';
+import { MemoryStore } from '../src/store/memoryStore';
+import { fieldToHex } from '../src/crypto/field';
 import type { StorageAdapter } from '../src/types';
 
 describe('SyncEngine (merkle)', () => {
@@ -72,4 +76,52 @@ describe('SyncEngine (merkle)', () => {
 
     expect(saved?.merkle).toBe(0);
   });
+
+  describe('local tree from memo commitments', () => {
+    const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+    const assets = { getChains: () => [chain], getChain: () => chain } as any;
+    const wallet = {
+      getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+      applyMemos: async () => 0,
+      markSpent: async () => undefined,
+    } as any;
+    const commitments = Array.from({ length: 70 }, (_, cid) => fieldToHex(BigInt(cid + 1)));
+
+    const run = async (buildMerkle?: boolean, mode: 'local' | 'hybrid' = 'local') => {
+      (globalThis as any).fetch = async (url: string) => {
+        const params = new URL(url).searchParams;
+        const offset = Number(params.get('offset'));
+        if (url.includes('/memos/')) {
+          const data = commitments.slice(offset, offset + Number(params.get('limit'))).map((commitment, i) => ({ commitment, memo: '0x02', cid: offset + i }));
+          return { ok: true, json: async () => ({ code: 0, data: { data, total: commitments.length } }) };
+        }
+        return { ok: true, json: async () => ({ code: 0, data: { data: [], total: 0 } }) };
+      };
+      const store = new MemoryStore();
+      const merkle = new MerkleEngine(() => ({}), {} as any, { mode }, store);
+      const engine = new SyncEngine(assets, store, wallet, () => undefined, merkle, { pageSize: 16, buildMerkle });
+      await engine.syncOnce({ chainIds: [1], continueOnError: false });
+      return store;
+    };
+
+    it('persists leaves and tree versions whose root matches a tree built over the same commitments', async () => {
+      const store = await run();
+      expect(await store.getSyncCursor(1)).toMatchObject({ memo: 70, merkle: 2 });
+      expect((await store.getMerkleLeaves(1))?.map((l) => l.commitment)).toEqual(commitments);
+      const latest = await store.getLatestChairmanMerkleVersion(1);
+      expect(latest?.version).toBe(64);
+      expect(BigInt(latest!.rootHash)).toBe(computeMerkleRootFromLeaves(commitments.slice(0, 64)));
+    });
+
+    it('leaves the local tree and merkle cursor untouched when buildMerkle is false', async () => {
+      const store = await run(false, 'hybrid');
+      expect(await store.getSyncCursor(1)).toMatchObject({ memo: 70, merkle: 0 });
+      expect(await store.getMerkleLeaves(1)).toBeUndefined();
+      expect(await store.getLatestChairmanMerkleVersion(1)).toBeUndefined();
+    });
+
+    it('rejects buildMerkle false for a local-mode merkle engine', async () => {
+      await expect(run(false, 'local')).rejects.toMatchObject({ name: 'SdkError', code: 'CONFIG' });
+    });
+  });
 });
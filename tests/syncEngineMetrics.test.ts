@@ -0,0 +1,52 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+import { createSdk } from '../src/index';
+import { createSyncTestWallet, syncTestAssets } from './helpers';
+
+afterEach(() => {
+  vi.restoreAllMocks();
+});
+
+describe('SyncEngine metrics', () => {
+  it('counts memos, owned memos, nullifiers, errors and the last sync time', async () => {
+    let failEntry = false;
+    (globalThis as any).fetch = async (url: string) => {
+      if (failEntry) return { ok: false, status: 500 };
+      const offset = Number(new URL(url).searchParams.get('offset'));
+      if (url.includes('/memos/')) {
+        const data = [0, 1, 2].filter((cid) => cid >= offset).map((cid) => ({ commitment: '0x01', memo: '0x02', cid }));
+        return { ok: true, json: async () => ({ code: 0, data: { data, total: 3 } }) };
+      }
+      const data = ['0x0a', '0x0b'].slice(offset).map((nullifier) => ({ nullifier }));
+      return { ok: true, json: async () => ({ code: 0, data: { data, total: 2 } }) };
+    };
+    const wallet = createSyncTestWallet({ applyMemos: async (_chainId, memos) => memos.length - 1 });
+
+    vi.spyOn(Date, 'now').mockReturnValue(1_700_000_000_000);
+    const engine = new SyncEngine(syncTestAssets, new MemoryStore(), wallet, () => undefined, undefined);
+    expect(engine.metricsSnapshot()).toEqual({ memosDownloaded: 0, ownedMemosFound: 0, nullifiersProcessed: 0, syncErrors: 0, lastSyncAt: {} });
+
+    await engine.syncOnce({ chainIds: [1], continueOnError: false });
+    expect(engine.metricsSnapshot()).toEqual({ memosDownloaded: 3, ownedMemosFound: 2, nullifiersProcessed: 2, syncErrors: 0, lastSyncAt: { 1: 1_700_000_000_000 } });
+
+    failEntry = true;
+    await engine.syncOnce({ chainIds: [1], continueOnError: true });
+    const snapshot = engine.metricsSnapshot();
+    expect(snapshot).toMatchObject({ memosDownloaded: 3, nullifiersProcessed: 2, syncErrors: 1, lastSyncAt: { 1: 1_700_000_000_000 } });
+    expect(JSON.parse(JSON.stringify(snapshot))).toEqual(snapshot);
+
+    snapshot.lastSyncAt[1] = 0;
+    expect(engine.metricsSnapshot().lastSyncAt[1]).toBe(1_700_000_000_000);
+  });
+
+  it('is exposed on sdk.sync', async () => {
+    (globalThis as any).fetch = async () => ({ ok: true, json: async () => ({ code: 0, data: { data: [], total: 0 } }) });
+    vi.spyOn(Date, 'now').mockReturnValue(1_700_000_000_000);
+    const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+    const sdk = createSdk({ chains: [chain as any] });
+
+    await sdk.sync.syncOnce({ chainIds: [1], resources: ['memo', 'nullifier'], continueOnError: false });
+    expect(sdk.sync.metricsSnapshot()).toEqual({ memosDownloaded: 0, ownedMemosFound: 0, nullifiersProcessed: 0, syncErrors: 0, lastSyncAt: { 1: 1_700_000_000_000 } });
+  });
+});
@@ -0,0 +1,64 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+
+const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+const assets = { getChains: () => [chain], getChain: () => chain } as any;
+const wallet = {
+  getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+  applyMemos: async () => 0,
+  markSpent: async () => undefined,
+} as any;
+
+const stubEntry = () => {
+  const limits = { memo: [] as number[], nullifier: [] as number[] };
+  (globalThis as any).fetch = async (url: string) => {
+    const params = new URL(url).searchParams;
+    const offset = Number(params.get('offset'));
+    const limit = Number(params.get('limit'));
+    if (url.includes('/memos/')) {
+      limits.memo.push(limit);
+      const data = Array.from({ length: 10 }, (_, cid) => cid)
+        .filter((cid) => cid >= offset)
+        .slice(0, limit)
+        .map((cid) => ({ commitment: '0x01', memo: '0x02', cid }));
+      return { ok: true, json: async () => ({ code: 0, data: { data, total: 10 } }) };
+    }
+    limits.nullifier.push(limit);
+    const data = Array.from({ length: 10 }, (_, i) => ({ nullifier: `0x${(i + 1).toString(16)}` })).slice(offset, offset + limit);
+    return { ok: true, json: async () => ({ code: 0, data: { data, total: 10 } }) };
+  };
+  return limits;
+};
+
+describe('SyncEngine page sizes', () => {
+  it('requests each resource with its configured page size', async () => {
+    const limits = stubEntry();
+    const store = new MemoryStore();
+    const engine = new SyncEngine(assets, store, wallet, () => undefined, undefined, { pageSize: 100, memoPageSize: 3, nullifierPageSize: 4 });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo', 'nullifier'], continueOnError: false });
+
+    expect(limits.memo).toEqual([3, 3, 3, 3]);
+    expect(limits.nullifier).toEqual([4, 4, 4]);
+    expect(await store.getSyncCursor(1)).toMatchObject({ memo: 10, nullifier: 10 });
+  });
+
+  it('lets pageSize set both and per-call sizes override the config', async () => {
+    const limits = stubEntry();
+    const engine = new SyncEngine(assets, new MemoryStore(), wallet, () => undefined, undefined, { pageSize: 5 });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo', 'nullifier'], continueOnError: false });
+    expect(limits).toEqual({ memo: [5, 5, 5], nullifier: [5, 5, 5] });
+
+    const perCall = stubEntry();
+    await new SyncEngine(assets, new MemoryStore(), wallet, () => undefined, undefined, { pageSize: 5 }).syncOnce({
+      chainIds: [1],
+      resources: ['memo', 'nullifier'],
+      pageSize: 6,
+      nullifierPageSize: 10,
+      continueOnError: false,
+    });
+    expect(perCall).toEqual({ memo: [6, 6], nullifier: [10, 10] });
+  });
+});
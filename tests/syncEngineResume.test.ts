@@ -0,0 +1,66 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MerkleEngine } from '../src/merkle/merkleEngine';
+import { computeMerkleRootFromLeaves } from '../src/merkle/merkleProof';
+import { MemoryStore } from '../src/store/memoryStore';
+import { fieldToHex } from '../src/crypto/field';
+
+const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+const assets = { getChains: () => [chain], getChain: () => chain } as any;
+const leaves = Array.from({ length: 64 }, (_, cid) => fieldToHex(BigInt(cid + 1)));
+
+const stubEntry = () => {
+  const offsets: number[] = [];
+  (globalThis as any).fetch = async (url: string) => {
+    const params = new URL(url).searchParams;
+    const offset = Number(params.get('offset'));
+    offsets.push(offset);
+    const data = leaves.slice(offset, offset + Number(params.get('limit'))).map((commitment, i) => ({ commitment, memo: '0x02', cid: offset + i }));
+    return { ok: true, json: async () => ({ code: 0, data: { data, total: leaves.length } }) };
+  };
+  return offsets;
+};
+
+// A fresh SyncEngine + MerkleEngine over the same store stands in for a restarted process.
+const boot = (store: MemoryStore, applied: number[]) => {
+  const wallet = {
+    getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+    applyMemos: async (_chainId: number, memos: Array<{ cid: number | null }>) => {
+      applied.push(...memos.map((m) => m.cid!));
+      return 0;
+    },
+    markSpent: async () => undefined,
+  } as any;
+  const merkle = new MerkleEngine(() => ({}), {} as any, { mode: 'local' }, store);
+  return new SyncEngine(assets, store, wallet, () => undefined, merkle, { pageSize: 16 });
+};
+
+describe('SyncEngine resume after a crash', () => {
+  it('persists the cursor per page and resumes from the first page that did not complete', async () => {
+    const store = new MemoryStore();
+    const setSyncCursor = store.setSyncCursor.bind(store);
+    // Crash while committing page 3: its leaves reached the tree and wallet, but the cursor never advanced past page 2.
+    store.setSyncCursor = async (chainId, cursor) => {
+      if (cursor.memo === 48) throw new Error('process killed');
+      return setSyncCursor(chainId, cursor);
+    };
+    const applied: number[] = [];
+    const first = stubEntry();
+    await boot(store, applied).syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: true });
+    expect(first).toEqual([0, 16, 32]);
+    expect((await store.getSyncCursor(1))?.memo).toBe(32);
+
+    store.setSyncCursor = setSyncCursor;
+    const resumed = stubEntry();
+    await boot(store, applied).syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: false });
+
+    expect(resumed[0]).toBe(32);
+    expect((await store.getSyncCursor(1))?.memo).toBe(64);
+    // Page 3 is replayed once; the tree skips the leaves it already holds.
+    expect(applied.filter((cid) => cid === 32)).toHaveLength(2);
+    expect(await store.getMerkleLeaves(1)).toHaveLength(64);
+    const latest = await store.getLatestChairmanMerkleVersion(1);
+    expect(latest?.version).toBe(64);
+    expect(BigInt(latest!.rootHash)).toBe(computeMerkleRootFromLeaves(leaves));
+  });
+});
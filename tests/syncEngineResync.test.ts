@@ -0,0 +1,63 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+import type { UtxoRecord } from '../src/types';
+import { createSyncTestWallet, syncTestAssets } from './helpers';
+
+const utxoAt = (mkIndex: number): UtxoRecord => ({
+  chainId: 1,
+  assetId: '1',
+  amount: 1n,
+  commitment: `0x0${mkIndex}`,
+  nullifier: `0x1${mkIndex}`,
+  mkIndex,
+  isFrozen: false,
+  isSpent: false,
+});
+
+describe('SyncEngine.forceResyncFrom', () => {
+  it('rewinds the cursor, prunes downstream utxos and re-downloads from the target cid', async () => {
+    const memoOffsets: number[] = [];
+    (globalThis as any).fetch = async (url: string) => {
+      const offset = Number(new URL(url).searchParams.get('offset'));
+      if (url.includes('/memos/')) {
+        memoOffsets.push(offset);
+        const data = [0, 1, 2, 3].filter((cid) => cid >= offset).map((cid) => ({ commitment: '0x01', memo: '0x02', cid, created_at: cid }));
+        return { ok: true, json: async () => ({ code: 0, data: { data, total: 4 } }) };
+      }
+      return { ok: true, json: async () => ({ code: 0, data: { data: [], total: 0 } }) };
+    };
+
+    const store = new MemoryStore();
+    await store.upsertUtxos([utxoAt(1), utxoAt(3)]);
+    const engine = new SyncEngine(syncTestAssets, store, createSyncTestWallet(), () => undefined, undefined);
+    await engine.syncOnce({ chainIds: [1], continueOnError: false });
+    expect((await store.getSyncCursor(1))?.memo).toBe(4);
+
+    await expect(engine.forceResyncFrom(1, { memo: 5, nullifier: 0 })).rejects.toMatchObject({ code: 'CONFIG' });
+
+    await engine.forceResyncFrom(1, { memo: 2, nullifier: 0, clearDownstream: true });
+    expect(await store.getSyncCursor(1)).toEqual({ memo: 2, nullifier: 0, merkle: 0 });
+    expect((await store.listUtxos({ chainId: 1 })).rows.map((u) => u.mkIndex)).toEqual([1]);
+
+    memoOffsets.length = 0;
+    await engine.syncOnce({ chainIds: [1], continueOnError: false });
+    expect(memoOffsets[0]).toBe(2);
+    expect((await store.getSyncCursor(1))?.memo).toBe(4);
+  });
+
+  it('restarts the nullifier cursor with clearDownstream and holds the chain while resetting', async () => {
+    const store = new MemoryStore();
+    await store.setSyncCursor(1, { memo: 4, nullifier: 3, merkle: 0 });
+    await store.upsertUtxos([utxoAt(1), utxoAt(3)]);
+    const engine = new SyncEngine(syncTestAssets, store, createSyncTestWallet(), () => undefined, undefined);
+
+    const reset = engine.forceResyncFrom(1, { memo: 2, nullifier: 3, clearDownstream: true });
+    await expect(engine.forceResyncFrom(1, { memo: 0, nullifier: 0 })).rejects.toMatchObject({ code: 'SYNC' });
+    await reset;
+    expect(await store.getSyncCursor(1)).toEqual({ memo: 2, nullifier: 0, merkle: 0 });
+
+    await engine.forceResyncFrom(1, { memo: 1, nullifier: 0 });
+    expect((await store.getSyncCursor(1))?.memo).toBe(1);
+  });
+});
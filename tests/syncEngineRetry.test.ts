@@ -0,0 +1,54 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+import type { SdkEvent } from '../src/types';
+
+const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+const assets = { getChains: () => [chain], getChain: () => chain } as any;
+const wallet = {
+  getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+  applyMemos: async () => 0,
+  markSpent: async () => undefined,
+} as any;
+
+// Entry stub serving 2 memos after answering the first `failures` requests with 503.
+const stubEntry = (failures: number) => {
+  let calls = 0;
+  const fetch = vi.fn(async (url: string) => {
+    if (calls++ < failures) return new Response('unavailable', { status: 503 });
+    const offset = Number(new URL(url).searchParams.get('offset'));
+    const data = [0, 1].filter((cid) => cid >= offset).map((cid) => ({ commitment: '0x01', memo: '0x02', cid }));
+    return { ok: true, json: async () => ({ code: 0, data: { data, total: 2 } }) };
+  });
+  vi.stubGlobal('fetch', fetch);
+  return fetch;
+};
+
+afterEach(() => {
+  vi.unstubAllGlobals();
+});
+
+describe('SyncEngine entry retries', () => {
+  it('retries 5xx responses inside the entry client with the sync retry policy', async () => {
+    const fetch = stubEntry(2);
+    const store = new MemoryStore();
+    const engine = new SyncEngine(assets, store, wallet, () => undefined, undefined, { retry: { attempts: 3, baseDelayMs: 0 } });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: false });
+
+    expect(fetch).toHaveBeenCalledTimes(3);
+    expect((await store.getSyncCursor(1))?.memo).toBe(2);
+  });
+
+  it('does not retry again once the entry client has exhausted its attempts', async () => {
+    const fetch = stubEntry(Number.POSITIVE_INFINITY);
+    const events: SdkEvent[] = [];
+    const engine = new SyncEngine(assets, new MemoryStore(), wallet, (evt) => events.push(evt), undefined, { retry: { attempts: 2, baseDelayMs: 0 } });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'], continueOnError: true });
+
+    expect(fetch).toHaveBeenCalledTimes(2);
+    expect(events.some((e) => e.type === 'error' && e.payload.message === 'Sync request failed, retrying')).toBe(false);
+    expect(engine.getStatus()[1]?.memo.status).toBe('error');
+  });
+});
@@ -39,7 +39,7 @@ describe('SyncEngine.start', () => {
     expect(syncOnceSpy).toHaveBeenCalledTimes(1);
 
     // simulate a chain currently syncing -> interval tick should be skipped (no extra call)
-    (engine as any).runningChains.add(1);
+    (engine as any).runningChains.set(1, { task: Promise.resolve(), resources: new Set(['memo', 'nullifier', 'merkle']) });
     await vi.advanceTimersByTimeAsync(15_000);
     expect(syncOnceSpy).toHaveBeenCalledTimes(1);
 
@@ -0,0 +1,65 @@
+import { describe, expect, it } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+import type { SyncStreamEvent } from '../src/types';
+import { createSyncTestWallet, syncTestAssets } from './helpers';
+
+const wallet = createSyncTestWallet();
+
+// Entry stub serving 6 memos; pages at or past `blockFrom` hang until the request is aborted.
+const stubEntry = (blockFrom = Number.POSITIVE_INFINITY) => {
+  const offsets: number[] = [];
+  (globalThis as any).fetch = async (url: string, init?: { signal?: AbortSignal }) => {
+    const params = new URL(url).searchParams;
+    const offset = Number(params.get('offset'));
+    const limit = Number(params.get('limit'));
+    offsets.push(offset);
+    if (offset >= blockFrom) {
+      await new Promise((_, reject) => init?.signal?.addEventListener('abort', () => reject(init.signal!.reason), { once: true }));
+    }
+    const data = [0, 1, 2, 3, 4, 5]
+      .filter((cid) => cid >= offset)
+      .slice(0, limit)
+      .map((cid) => ({ commitment: '0x01', memo: '0x02', cid }));
+    return { ok: true, json: async () => ({ code: 0, data: { data, total: 6 } }) };
+  };
+  return offsets;
+};
+
+describe('SyncEngine.syncStream', () => {
+  it('yields start, per-page progress and done events for one pass', async () => {
+    stubEntry();
+    const store = new MemoryStore();
+    const engine = new SyncEngine(syncTestAssets, store, wallet, () => undefined, undefined, { pageSize: 2 });
+
+    const events: SyncStreamEvent[] = [];
+    for await (const event of engine.syncStream({ chainIds: [1], resources: ['memo'] })) events.push(event);
+
+    expect(events[0]).toEqual({ type: 'sync:start', payload: { chainId: 1, source: 'entry' } });
+    expect(events.filter((e) => e.type === 'sync:progress').map((e) => (e.type === 'sync:progress' ? e.payload.downloaded : -1))).toEqual([0, 2, 4, 6]);
+    expect(events[events.length - 1]).toMatchObject({ type: 'sync:done', payload: { chainId: 1, cursor: { memo: 6 } } });
+    expect(events.some((e) => e.type === 'error')).toBe(false);
+    expect((await store.getSyncCursor(1))?.memo).toBe(6);
+  });
+
+  it('cancels when the consumer stops early, keeping the cursor of every applied page', async () => {
+    const offsets = stubEntry(4);
+    const store = new MemoryStore();
+    const seen: string[] = [];
+    const engine = new SyncEngine(syncTestAssets, store, wallet, (evt) => seen.push(evt.type), undefined, { pageSize: 2 });
+
+    for await (const event of engine.syncStream({ chainIds: [1], resources: ['memo'] })) {
+      if (event.type === 'sync:progress' && event.payload.downloaded === 2) break;
+    }
+
+    expect(offsets.slice(0, 2)).toEqual([0, 2]);
+    expect((await store.getSyncCursor(1))?.memo).toBe(4);
+    expect(engine.getStatus()[1]?.memo.status).toBe('error');
+    expect(seen).toContain('sync:done');
+
+    // The stream detached its listener: later passes only reach the SDK handler.
+    stubEntry();
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'] });
+    expect((await store.getSyncCursor(1))?.memo).toBe(6);
+  });
+});
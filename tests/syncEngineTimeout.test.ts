@@ -0,0 +1,63 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { SyncEngine } from '../src/sync/syncEngine';
+import { MemoryStore } from '../src/store/memoryStore';
+import { SdkError } from '../src/errors';
+import type { SdkEvent } from '../src/types';
+
+const chain = { chainId: 1, entryUrl: 'https://entry.test', ocashContractAddress: '0x0000000000000000000000000000000000000002' };
+const assets = { getChains: () => [chain], getChain: () => chain } as any;
+const wallet = {
+  getViewingAddress: () => '0x0000000000000000000000000000000000000001',
+  applyMemos: async () => 0,
+  markSpent: async () => undefined,
+} as any;
+
+// Entry stub serving 2 memos; the first `stalls` requests hang until their signal aborts, like a dead connection.
+const stubEntry = (stalls: number) => {
+  let calls = 0;
+  const fetch = vi.fn(async (url: string, init?: { signal?: AbortSignal }) => {
+    if (calls++ < stalls) {
+      return new Promise<never>((_, reject) => init?.signal?.addEventListener('abort', () => reject(init.signal!.reason), { once: true }));
+    }
+    const offset = Number(new URL(url).searchParams.get('offset'));
+    const data = [0, 1].filter((cid) => cid >= offset).map((cid) => ({ commitment: '0x01', memo: '0x02', cid }));
+    return { ok: true, json: async () => ({ code: 0, data: { data, total: 2 } }) };
+  });
+  vi.stubGlobal('fetch', fetch);
+  return fetch;
+};
+
+afterEach(() => {
+  vi.unstubAllGlobals();
+});
+
+describe('SyncEngine request timeouts', () => {
+  it('retries a timed-out page with a fresh deadline', async () => {
+    const fetch = stubEntry(1);
+    const events: SdkEvent[] = [];
+    const store = new MemoryStore();
+    const engine = new SyncEngine(assets, store, wallet, (evt) => events.push(evt), undefined, { requestTimeoutMs: 1000, retry: { attempts: 2, baseDelayMs: 0 } });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'] });
+
+    expect(fetch.mock.calls.length).toBeGreaterThanOrEqual(2);
+    expect((await store.getSyncCursor(1))?.memo).toBe(2);
+    const retry = events.find((e) => e.type === 'error' && e.payload.message === 'Sync request failed, retrying');
+    expect(retry?.type === 'error' && retry.payload.cause).toMatchObject({ code: 'SYNC', detail: { reason: 'timeout' } });
+  }, 10_000);
+
+  it('reports SdkError(SYNC) once every attempt has timed out', async () => {
+    const fetch = stubEntry(Number.POSITIVE_INFINITY);
+    const events: SdkEvent[] = [];
+    const engine = new SyncEngine(assets, new MemoryStore(), wallet, (evt) => events.push(evt), undefined, { requestTimeoutMs: 1000, retry: { attempts: 2, baseDelayMs: 0 } });
+
+    await engine.syncOnce({ chainIds: [1], resources: ['memo'] });
+
+    expect(fetch).toHaveBeenCalledTimes(2);
+    const failure = events.find((e) => e.type === 'error' && e.payload.message !== 'Sync request failed, retrying');
+    const cause = failure?.type === 'error' ? failure.payload.cause : undefined;
+    expect(cause).toBeInstanceOf(SdkError);
+    expect(cause).toMatchObject({ code: 'SYNC', detail: { reason: 'timeout' } });
+    expect(engine.getStatus()[1]?.memo.status).toBe('error');
+  }, 10_000);
+});
@@ -1,5 +1,7 @@
 import { describe, expect, it } from 'vitest';
 import { TxBuilder } from '../src/tx/txBuilder';
+import { canonicalRelayerRequestBytes, relayerRequestDigest } from '../src/tx/canonicalRequest';
+import { RelayerRequestBuilder } from '../src/tx/relayerRequest';
 
 const dummyProof = (): any => ({
   proof: Array.from({ length: 8 }, () => '0') as any,
@@ -41,5 +43,40 @@ describe('TxBuilder', () => {
     expect(req.path).toBe('/api/v1/burn');
     expect(req.body.burn_amount).toBe('123');
   });
+
+  it('encodes structurally-equal relayer requests to identical canonical bytes', () => {
+    const a = {
+      kind: 'relayer' as const,
+      method: 'POST' as const,
+      path: '/api/v1/transfer',
+      body: { proof: ['1', '2'], extra_data: ['0x01', '0x02', '0x03'], merkle_root_index: 3, nested: { b: 2n, a: 1 }, skipped: undefined },
+    };
+    const b = {
+      path: '/api/v1/transfer',
+      body: { nested: { a: 1, b: 2n }, merkle_root_index: 3, extra_data: ['0x01', '0x02', '0x03'], proof: ['1', '2'] },
+      method: 'POST' as const,
+      kind: 'relayer' as const,
+    };
+    expect(canonicalRelayerRequestBytes(a)).toEqual(canonicalRelayerRequestBytes(b));
+    expect(relayerRequestDigest(a)).toBe(relayerRequestDigest(b));
+
+    const reordered = { ...a, body: { ...a.body, extra_data: ['0x02', '0x01', '0x03'] } };
+    expect(relayerRequestDigest(reordered)).not.toBe(relayerRequestDigest(a));
+    expect(relayerRequestDigest({ ...a, path: '/api/v1/burn' })).not.toBe(relayerRequestDigest(a));
+  });
 });
 
+describe('RelayerRequestBuilder', () => {
+  it('serializes actions to the existing relayer paths', () => {
+    const body = { proof: ['1'], relayer: '0x0000000000000000000000000000000000000001' };
+    expect(new RelayerRequestBuilder().action('transfer').body(body).build()).toEqual({ kind: 'relayer', method: 'POST', path: '/api/v1/transfer', body });
+    expect(new RelayerRequestBuilder().action('withdraw').body(body).build()).toEqual({ kind: 'relayer', method: 'POST', path: '/api/v1/burn', body });
+  });
+
+  it('rejects unknown actions and incomplete requests', () => {
+    expect(() => new RelayerRequestBuilder().action('tranfser' as any)).toThrow('Unknown relayer action');
+    expect(() => new RelayerRequestBuilder().action('toString' as any)).toThrow('Unknown relayer action');
+    expect(() => new RelayerRequestBuilder().body({}).build()).toThrow('Relayer request action is required');
+    expect(() => new RelayerRequestBuilder().action('transfer').build()).toThrow('Relayer request body is required');
+  });
+});
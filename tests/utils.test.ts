@@ -1,5 +1,7 @@
-import { afterEach, describe, expect, it, vi } from 'vitest';
+import { afterEach, describe, expect, expectTypeOf, it, vi } from 'vitest';
+import type { Address } from 'viem';
 import { Utils } from '../src/utils';
+import type { ChainId, ContractAddress } from '../src/types';
 import * as randomModule from '../src/utils/random';
 
 const BABYJUB_ORDER = 21888242871839275222246405745257275088548364400416034343698204186575808495617n;
@@ -20,6 +22,66 @@ describe('Utils.calcDepositFee', () => {
   });
 });
 
+describe('Utils.toChainId / Utils.toContractAddress', () => {
+  it('validates and brands chain ids', () => {
+    expect(Utils.toChainId(11155111)).toBe(11155111);
+    for (const value of [0, -1, 1.5, Number.NaN, 2 ** 60]) {
+      expect(() => Utils.toChainId(value)).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    }
+  });
+
+  it('validates and checksums contract addresses', () => {
+    expect(Utils.toContractAddress('0x52908400098527886e0f7030069857d2e4169ee7')).toBe('0x52908400098527886E0F7030069857D2E4169EE7');
+    for (const value of ['', '0x1234', '52908400098527886e0f7030069857d2e4169ee7', `0x${'g'.repeat(40)}`]) {
+      expect(() => Utils.toContractAddress(value)).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    }
+  });
+
+  it('does not accept raw values where a branded type is expected', () => {
+    expectTypeOf<number>().not.toMatchTypeOf<ChainId>();
+    expectTypeOf<Address>().not.toMatchTypeOf<ContractAddress>();
+    expectTypeOf<ChainId>().not.toMatchTypeOf<ContractAddress>();
+    expectTypeOf<ChainId>().toMatchTypeOf<number>();
+    expectTypeOf<ContractAddress>().toMatchTypeOf<Address>();
+  });
+});
+
+describe('Utils.formatAmountAs / Utils.parseAmountAs', () => {
+  it('formats one amount in each representation', () => {
+    const amount = 1_500_000_000_000_000_000n;
+    expect(Utils.formatAmountAs(amount, 'hex')).toBe('0x14d1120d7b160000');
+    expect(Utils.formatAmountAs(amount, 'base-units')).toBe('1500000000000000000');
+    expect(Utils.formatAmountAs(amount, 'decimal', 18)).toBe('1.5');
+    expect(Utils.formatAmountAs(0n, 'hex')).toBe('0x0');
+    expect(Utils.formatAmountAs(5n, 'decimal', 6)).toBe('0.000005');
+  });
+
+  it('round-trips between every pair of formats', () => {
+    const formats = ['hex', 'base-units', 'decimal'] as const;
+    for (const amount of [0n, 1n, 999_999n, 1_500_000n, 123_456_789_012n]) {
+      for (const from of formats) {
+        const parsed = Utils.parseAmountAs(Utils.formatAmountAs(amount, from, 6), from, 6);
+        expect(parsed).toBe(amount);
+        for (const to of formats) {
+          expect(Utils.parseAmountAs(Utils.formatAmountAs(parsed, to, 6), to, 6)).toBe(amount);
+        }
+      }
+    }
+    expect(Utils.parseAmountAs('0xABC', 'hex')).toBe(0xabcn);
+    expect(Utils.parseAmountAs('2', 'decimal', 6)).toBe(2_000_000n);
+  });
+
+  it('rejects malformed, negative or over-precise input', () => {
+    expect(() => Utils.formatAmountAs(-1n, 'base-units')).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    expect(() => Utils.formatAmountAs(1n, 'decimal')).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    expect(() => Utils.parseAmountAs('12', 'hex')).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    expect(() => Utils.parseAmountAs('-1', 'base-units')).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    expect(() => Utils.parseAmountAs('1.5', 'base-units')).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    expect(() => Utils.parseAmountAs('1.0000001', 'decimal', 6)).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+    expect(() => Utils.parseAmountAs('.5', 'decimal', 6)).toThrow(expect.objectContaining({ code: 'CONFIG' }));
+  });
+});
+
 describe('Utils.serializeBigInt', () => {
   it('stringifies bigint fields recursively', () => {
     const serialized = Utils.serializeBigInt({
@@ -0,0 +1,55 @@
+import { describe, expect, it } from 'vitest';
+import { validateUtxo } from '../src/ops/validateUtxo';
+import { MemoKit } from '../src/memo/memoKit';
+import { KeyManager } from '../src/crypto/keyManager';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import type { UtxoRecord } from '../src/types';
+
+const owner = KeyManager.deriveKeyPair('validate-utxo-owner-seed');
+const other = KeyManager.deriveKeyPair('validate-utxo-other-seed');
+
+const utxoFor = (amount: bigint, mkIndex: number): UtxoRecord => {
+  const ro = CryptoToolkit.createRecordOpening({ asset_id: 7n, asset_amount: amount, user_pk: { user_address: owner.user_pk.user_address } });
+  return {
+    chainId: 1,
+    assetId: '7',
+    amount,
+    commitment: CryptoToolkit.commitment(ro, 'hex'),
+    nullifier: '0x01',
+    mkIndex,
+    isFrozen: false,
+    isSpent: false,
+    memo: MemoKit.createMemo(ro),
+  };
+};
+
+const thrown = (fn: () => void): unknown => {
+  try {
+    fn();
+  } catch (error) {
+    return error;
+  }
+  throw new Error('expected validateUtxo to throw');
+};
+
+describe('validateUtxo', () => {
+  it('accepts a UTXO whose memo opens to its commitment, or that has no memo', () => {
+    const utxo = utxoFor(100n, 0);
+    expect(() => validateUtxo(utxo, owner.user_sk.address_sk)).not.toThrow();
+    expect(() => validateUtxo({ ...utxo, memo: undefined }, other.user_sk.address_sk)).not.toThrow();
+  });
+
+  it('flags a UTXO carrying the memo of a different commitment', () => {
+    const a = utxoFor(100n, 0);
+    const b = utxoFor(100n, 1);
+    const swapped = { ...a, memo: b.memo };
+    expect(thrown(() => validateUtxo(swapped, owner.user_sk.address_sk))).toMatchObject({
+      code: 'CRYPTO',
+      detail: { reason: 'commitment_mismatch', commitment: a.commitment, memoCommitment: b.commitment },
+    });
+  });
+
+  it('flags a memo the key cannot open', () => {
+    expect(thrown(() => validateUtxo(utxoFor(5n, 2), other.user_sk.address_sk))).toMatchObject({ code: 'CRYPTO', detail: { reason: 'memo_unreadable' } });
+  });
+});
@@ -0,0 +1,128 @@
+import { describe, expect, it } from 'vitest';
+import { mkdtemp, readFile, rm } from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { generateMerkleVectors, generateNullifierVectors, generatePolicyNullifierVectors, merkleVector, writeVectors, type CommitmentVector } from '../scripts/genVectors';
+import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
+import { BabyJubjub } from '../src/crypto/babyJubjub';
+import { fieldToHex } from '../src/crypto/field';
+import { KeyManager } from '../src/crypto/keyManager';
+import { Poseidon2, Poseidon2Domain } from '../src/crypto/poseidon2';
+import { verifyMerkleProof } from '../src/merkle/merkleProof';
+
+describe('genVectors', () => {
+  it('writes commitment vectors that re-load and match CryptoToolkit', async () => {
+    const dir = await mkdtemp(path.join(os.tmpdir(), 'ocash-sdk-vectors-'));
+    try {
+      const written = await writeVectors(dir);
+      expect(written).toEqual(['commitment.json', 'nullifier.json', 'nullifier_policy.json', 'merkle.json']);
+
+      const vectors = JSON.parse(await readFile(path.join(dir, 'commitment.json'), 'utf8')) as CommitmentVector[];
+      expect(vectors.length).toBeGreaterThan(0);
+      for (const v of vectors) {
+        const commitment = CryptoToolkit.commitment(
+          {
+            asset_id: BigInt(v.asset_id),
+            asset_amount: BigInt(v.asset_amount),
+            user_pk: { user_address: [BigInt(v.user_pk[0]), BigInt(v.user_pk[1])] },
+            blinding_factor: BigInt(v.blinding_factor),
+            is_frozen: v.is_frozen,
+          },
+          'hex',
+        );
+        expect(commitment).toBe(v.commitment);
+      }
+    } finally {
+      await rm(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('produces nullifier vectors for default and custom freezers', () => {
+    const vectors = generateNullifierVectors();
+    expect(vectors.some((v) => v.freezer_pk === null)).toBe(true);
+    expect(vectors.some((v) => v.freezer_pk !== null)).toBe(true);
+    for (const v of vectors) {
+      const freezer = v.freezer_pk ? ([BigInt(v.freezer_pk[0]), BigInt(v.freezer_pk[1])] as [bigint, bigint]) : undefined;
+      expect(CryptoToolkit.nullifier(BigInt(v.secret_key), v.commitment, freezer)).toBe(v.nullifier);
+    }
+  });
+
+  it('produces policy nullifier vectors for identity, single and set freezers', () => {
+    const vectors = generatePolicyNullifierVectors();
+    expect(new Set(vectors.map((v) => v.policy.kind))).toEqual(new Set(['none', 'single', 'set']));
+    for (const v of vectors) {
+      const freezer = [BigInt(v.freezer_pk[0]), BigInt(v.freezer_pk[1])] as [bigint, bigint];
+      expect(CryptoToolkit.nullifier(BigInt(v.secret_key), v.commitment, freezer)).toBe(v.nullifier);
+      if (v.policy.kind === 'none') {
+        expect(v.freezer_pk).toEqual(['0', '1']);
+        expect(v.nullifier).toBe(CryptoToolkit.nullifier(BigInt(v.secret_key), v.commitment));
+      }
+      if (v.policy.kind === 'set') {
+        expect(v.freezer_pk).toEqual(v.policy.freezer_pk);
+        expect(v.policy.freezers).toContainEqual(v.freezer_pk);
+      }
+    }
+  });
+
+  it('produces merkle proofs that verify against the vector root', () => {
+    for (const v of generateMerkleVectors()) {
+      for (const p of v.proofs) {
+        expect(p.path).toHaveLength(v.depth + 1);
+        expect(verifyMerkleProof({ path: p.path, leafIndex: p.leaf_index }, v.root)).toBe(true);
+      }
+    }
+  });
+});
+
+// Known answers computed by scripts/refVectors.py, which shares no hashing or curve code with the SDK.
+describe('reference vectors', () => {
+  const owner: [bigint, bigint] = [
+    19649055493706707054227543952293108083531865249967651845006107589639068627074n,
+    12112450042127193446189577552007703839818242727902437791835414514847797088033n,
+  ];
+  const freezer: [bigint, bigint] = [
+    20116430324039290829419172776146199614469768477688294126172321412365539407642n,
+    18856460861531942120859708048677603751294231190189224157283439874962410808705n,
+  ];
+  const record = { asset_id: 1n, asset_amount: 10n ** 18n, user_pk: { user_address: owner }, blinding_factor: 42n, is_frozen: false };
+  const commitment = '0x06a8fa52138c426679fd9a4b0533a4a11ff7218bafe6852862b35ab2b76752e0';
+  const frozenCommitment = '0x2f06427ab70cc90a3167f0436a8edf26f79feac414363db329110dc4cfb422d4';
+
+  it('matches the reference curve arithmetic', () => {
+    expect(BabyJubjub.scalarMult(7n)).toEqual(owner);
+    expect(BabyJubjub.scalarMult(11n)).toEqual(freezer);
+  });
+
+  it('matches reference commitments and nullifiers', () => {
+    expect(CryptoToolkit.commitment(record, 'hex')).toBe(commitment);
+    expect(CryptoToolkit.commitment({ ...record, is_frozen: true }, 'hex')).toBe(frozenCommitment);
+    expect(CryptoToolkit.nullifier(5n, commitment)).toBe('0x26d22bb116d6ac206e83c0bfdacc5279541153a04ccd771dff123b254462094e');
+    expect(CryptoToolkit.nullifier(5n, commitment, freezer)).toBe('0x150b0f64add97c2a255e09597cc269a9029ef75f61bd09c792c4e60dec52df5d');
+  });
+
+  it('builds merkle vectors with the reference roots', () => {
+    expect(merkleVector([commitment]).root).toBe('0x1cd7491decc4b08bcce48f386d5de5aeca8c7de653cb7c7f7f526d6c4751a7c3');
+    const three = merkleVector([commitment, frozenCommitment, `0x${'3'.padStart(64, '0')}`]);
+    expect(three.root).toBe('0x05d72e49e45041eca7fdbd8678fe3126cf85b27aa817f2d45183564de52b3090');
+    for (const p of three.proofs) expect(verifyMerkleProof({ path: p.path, leafIndex: p.leaf_index }, three.root)).toBe(true);
+  });
+
+  it('matches the reference blinding derivation', () => {
+    expect(fieldToHex(KeyManager.deriveBlindingFactor(5n, [0n]))).toBe('0x1c0f7c176f26e9a9232d901c481b93334eb1bd085d698c881125265936b0af04');
+    expect(fieldToHex(KeyManager.deriveBlindingFactor(5n, [3n]))).toBe('0x101d67ccdaa121d04f137e553d482504953fc810d1cc8e130be20cad11de228b');
+  });
+
+  it('matches the reference sponge for inputs 1..n under the Record domain', () => {
+    const expected: Record<number, string> = {
+      1: '0x15864f5fc9adcc508d08abe5e6d5b73f48a0f58d55aa142b799e098286671410',
+      2: '0x3048d4e7ac8b75e96fa5e9f1d683d0e87ccfbeb2a99edc32e30ceee98c769278',
+      3: '0x2f9c4906b8d2d648f952179c168e190cb6aff95b017a57b032b8e9325dc7b029',
+      4: '0x07e6411ae67083e3fb6abf287cfd9c7109f246620485caf56639e775802f3c3f',
+      5: '0x29cadffd2e2030d75633fbd2e47146a6772ae24d743f7be21c72bd0a112496cc',
+    };
+    for (const [n, hash] of Object.entries(expected)) {
+      const inputs = Array.from({ length: Number(n) }, (_, i) => BigInt(i + 1));
+      expect(fieldToHex(Poseidon2.sponge(inputs, Poseidon2Domain.Record))).toBe(hash);
+    }
+  });
+});
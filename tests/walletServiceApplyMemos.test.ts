@@ -1,9 +1,9 @@
-import { describe, expect, it } from 'vitest';
+import { describe, expect, it, vi } from 'vitest';
 import { WalletService } from '../src/wallet/walletService';
 import { MemoryStore } from '../src/store/memoryStore';
 import { KeyManager } from '../src/crypto/keyManager';
 import { CryptoToolkit } from '../src/crypto/cryptoToolkit';
-import { MemoKit } from '../src/memo/memoKit';
+import { MAX_MEMO_BYTES_DEFAULT, MemoKit } from '../src/memo/memoKit';
 import { BabyJubjub } from '../src/crypto/babyJubjub';
 
 describe('WalletService.applyMemos', () => {
@@ -28,6 +28,15 @@ describe('WalletService.applyMemos', () => {
     await expect(wallet.getUtxos({ chainId: 1 })).resolves.toEqual({ total: 0, rows: [] });
   });
 
+  it('skips oversized memos instead of failing the scan', async () => {
+    const wallet = new WalletService({ getChains: () => [] } as any, new MemoryStore(), () => undefined);
+    await wallet.open({ seed: 'wallet-seed-test-key' });
+    const oversized = `0x${'ab'.repeat(MAX_MEMO_BYTES_DEFAULT + 1)}` as const;
+
+    await expect(wallet.applyMemos(1, [{ memo: oversized, commitment: '0x01', cid: 0 }])).resolves.toBe(0);
+    await expect(wallet.applyMemos(1, [{ memo: oversized, commitment: '0x01', cid: 1, is_transparent: true }])).resolves.toBe(0);
+  });
+
   it('uses chain-scoped asset lookup and refreshes lookup when chains update', async () => {
     const seed = 'wallet-seed-test-key';
     const keyPair = KeyManager.deriveKeyPair(seed);
@@ -68,3 +77,46 @@ describe('WalletService.applyMemos', () => {
     expect(utxos.rows[0]!.nullifier).toBe(CryptoToolkit.nullifier(keyPair.user_sk.address_sk, commitment, freezerPoint));
   });
 });
+
+describe('WalletService foreign memo cache', () => {
+  const foreignEntry = (cid: number) => {
+    const other = KeyManager.deriveKeyPair('someone-else-seed');
+    const ro = CryptoToolkit.createRecordOpening({ asset_id: 1n, asset_amount: 10n, user_pk: { user_address: other.user_pk.user_address } });
+    return { memo: MemoKit.createMemo(ro), commitment: CryptoToolkit.commitment(ro, 'hex'), cid };
+  };
+
+  it('skips trial decryption of memos already found to be foreign', async () => {
+    const decode = vi.spyOn(MemoKit, 'decodeMemoForOwner');
+    const wallet = new WalletService({ getChains: () => [] } as any, new MemoryStore(), () => undefined);
+    await wallet.open({ seed: 'wallet-seed-test-key' });
+    const entries = [foreignEntry(0), foreignEntry(1)];
+
+    await expect(wallet.applyMemos(1, entries)).resolves.toBe(0);
+    expect(decode).toHaveBeenCalledTimes(2);
+    await expect(wallet.applyMemos(1, entries)).resolves.toBe(0);
+    expect(decode).toHaveBeenCalledTimes(2);
+
+    // Opening another account invalidates the cache.
+    await wallet.open({ seed: 'wallet-seed-test-key', accountNonce: 1 });
+    await wallet.applyMemos(1, entries);
+    expect(decode).toHaveBeenCalledTimes(4);
+  });
+
+  it('evicts the least recently seen memo and can be disabled', async () => {
+    const decode = vi.spyOn(MemoKit, 'decodeMemoForOwner');
+    const [a, b, c] = [foreignEntry(0), foreignEntry(1), foreignEntry(2)];
+    const wallet = new WalletService({ getChains: () => [] } as any, new MemoryStore(), () => undefined, { foreignMemoCacheSize: 2 });
+    await wallet.open({ seed: 'wallet-seed-test-key' });
+    await wallet.applyMemos(1, [a, b]);
+    await wallet.applyMemos(1, [a, c]);
+    expect(decode).toHaveBeenCalledTimes(3);
+    await wallet.applyMemos(1, [b]);
+    expect(decode).toHaveBeenCalledTimes(4);
+
+    const uncached = new WalletService({ getChains: () => [] } as any, new MemoryStore(), () => undefined, { foreignMemoCacheSize: 0 });
+    await uncached.open({ seed: 'wallet-seed-test-key' });
+    await uncached.applyMemos(1, [a]);
+    await uncached.applyMemos(1, [a]);
+    expect(decode).toHaveBeenCalledTimes(6);
+  });
+});
@@ -6,6 +6,7 @@ export default defineConfig([
       index: 'src/index.ts',
       browser: 'src/index.browser.ts',
       node: 'src/index.node.ts',
+      crypto: 'src/index.crypto.ts',
     },
     format: ['esm', 'cjs'],
     splitting: false,
@@ -15,6 +16,7 @@ export default defineConfig([
         index: 'src/index.ts',
         browser: 'src/index.browser.ts',
         node: 'src/index.node.ts',
+        crypto: 'src/index.crypto.ts',
       },
     },
     clean: true,